@@ -1,13 +1,17 @@
 use crate::config::{ENV_CONFIG_DIR, override_runtime_config_dir, resolve_config_dir_with};
 use crate::config_schema::ensure_global_sample_config;
+use crate::prompts::command::PromptsCommand;
 use crate::sdd::command::SddArgs;
+use crate::secrets::command::SecretsArgs;
 use crate::self_command::SelfArgs;
 use crate::skills::cli::command::SkillsArgs;
 use crate::skills::cli::interactive::is_interactive;
 use crate::tool::command::{ToolArgs, ToolCommands};
+use crate::x::agents::command::AgentsArgs;
 use crate::x::claude_code::command::ClaudeCodeArgs;
 use crate::x::codex::command::CodexArgs;
 use crate::x::cursor::command::CursorArgs;
+use crate::x::mcp::command::McpArgs;
 use anyhow::{Result, anyhow};
 use clap::{CommandFactory, Parser, Subcommand};
 use std::env;
@@ -46,7 +50,7 @@ pub struct Cli {
 
 #[derive(Subcommand)]
 pub enum Commands {
-    /// Prompt orchestrator (interactive-only)
+    /// Prompt orchestrator and app-agnostic shared templates
     #[command(name = "prompts", aliases = ["prompt", "rule"])]
     Prompts(PromptsArgs),
     /// Manage skills
@@ -64,7 +68,11 @@ pub enum Commands {
 }
 
 #[derive(Parser)]
+#[command(args_conflicts_with_subcommands = true)]
 pub struct PromptsArgs {
+    #[command(subcommand)]
+    pub command: Option<PromptsCommand>,
+
     /// Print guidance and exit (use app-specific `llman x <app> prompts` commands for non-interactive usage)
     #[arg(long = "no-interactive")]
     pub no_interactive: bool,
@@ -85,6 +93,12 @@ pub enum XCommands {
     ClaudeCode(ClaudeCodeArgs),
     /// Commands for managing Codex configurations
     Codex(CodexArgs),
+    /// Manage the encrypted secret store used by account groups
+    Secrets(SecretsArgs),
+    /// Manage a shared MCP server registry synced to each agent tool
+    Mcp(McpArgs),
+    /// Convert agent definitions between agent tools
+    Agents(AgentsArgs),
 }
 
 pub fn run() -> Result<()> {
@@ -176,6 +190,10 @@ fn is_llman_dev_project_at(current_dir: &Path) -> bool {
 }
 
 fn handle_prompts_command(args: &PromptsArgs) -> Result<()> {
+    if let Some(command) = &args.command {
+        return crate::prompts::command::run(command, is_interactive());
+    }
+
     if args.no_interactive {
         print_prompts_delegation_guidance();
         return Ok(());
@@ -228,6 +246,10 @@ fn print_prompts_delegation_guidance() {
     println!("  - llman x cursor prompts");
     println!("  - llman x codex prompts");
     println!("  - llman x claude-code prompts");
+    println!("Shared templates for every app:");
+    println!("  - llman prompts gen --all-apps");
+    println!("Check generated targets for drift:");
+    println!("  - llman prompts status");
 }
 
 fn handle_x_command(args: &XArgs) -> Result<()> {
@@ -237,6 +259,9 @@ fn handle_x_command(args: &XArgs) -> Result<()> {
             crate::x::claude_code::command::run(claude_code_args)
         }
         XCommands::Codex(codex_args) => crate::x::codex::command::run(codex_args),
+        XCommands::Secrets(secrets_args) => crate::secrets::command::run(secrets_args),
+        XCommands::Mcp(mcp_args) => crate::x::mcp::command::run(mcp_args),
+        XCommands::Agents(agents_args) => crate::x::agents::command::run(agents_args),
     }
 }
 
@@ -317,6 +342,7 @@ version = "0.1.0"
     fn test_requires_global_config_other_returns_true() {
         use super::{Commands, PromptsArgs, RequiresGlobalConfig};
         let prompts_args = PromptsArgs {
+            command: None,
             no_interactive: true,
         };
         let command = Commands::Prompts(prompts_args);
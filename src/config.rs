@@ -11,8 +11,10 @@ pub const APP_NAME: &str = "llman";
 pub const CURSOR_APP: &str = "cursor";
 pub const CODEX_APP: &str = "codex";
 pub const CLAUDE_CODE_APP: &str = "claude-code";
+pub const SHARED_APP: &str = "shared";
 pub const CURSOR_EXTENSION: &str = "mdc";
 pub const CODEX_EXTENSION: &str = "md";
+pub const SHARED_EXTENSION: &str = "md";
 pub const DEFAULT_EXTENSION: &str = "txt";
 pub const PROMPT_DIR: &str = "prompt";
 pub const TARGET_CURSOR_RULES_DIR: &str = ".cursor/rules";
@@ -47,6 +49,7 @@ fn rule_extension_for_app(app: &str) -> &'static str {
     match app {
         CURSOR_APP => CURSOR_EXTENSION,
         CODEX_APP => CODEX_EXTENSION,
+        SHARED_APP => SHARED_EXTENSION,
         _ => DEFAULT_EXTENSION,
     }
 }
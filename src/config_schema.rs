@@ -8,6 +8,7 @@ use jsonschema::validator_for;
 use schemars::JsonSchema;
 use schemars::generate::SchemaSettings;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -46,6 +47,53 @@ pub struct ProjectConfig {
     pub version: String,
     #[schemars(description = "Tool configuration.")]
     pub tools: tool_config::ToolsConfig,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Account groups pinned for this project.")]
+    pub accounts: Option<ProjectAccountsConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Variables for prompt templates rendered in this project.")]
+    pub prompts: Option<ProjectPromptsConfig>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema, PartialEq)]
+#[schemars(description = "Account groups pinned for this project, per wrapper.")]
+pub struct ProjectAccountsConfig {
+    #[serde(
+        rename = "claude-code",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[schemars(description = "Pin for `llman x claude-code` groups.")]
+    pub claude_code: Option<AccountPin>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Pin for `llman x codex` providers.")]
+    pub codex: Option<AccountPin>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema, PartialEq)]
+#[schemars(description = "Variables exposed to prompt templates (MiniJinja).")]
+pub struct ProjectPromptsConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Primary language, available to templates as `language`.")]
+    pub language: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    #[schemars(description = "Extra template variables; they override the built-in ones.")]
+    pub vars: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema, PartialEq)]
+#[schemars(description = "Default account group and allow-list for one wrapper.")]
+pub struct AccountPin {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        description = "Group selected automatically when no group is given. A per-user `.llman/config.local.yaml` may override it."
+    )]
+    pub group: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[schemars(
+        description = "Groups allowed in this project. Empty allows any group; the local override file cannot extend it."
+    )]
+    pub allowed: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
@@ -78,6 +126,8 @@ impl Default for ProjectConfig {
         Self {
             version: tool_defaults.version,
             tools: tool_defaults.tools,
+            accounts: None,
+            prompts: None,
         }
     }
 }
@@ -356,7 +406,7 @@ pub fn global_config_path() -> Result<PathBuf> {
     Ok(resolve_config_dir(None)?.join("config.yaml"))
 }
 
-fn find_config_root(start: &Path) -> Option<PathBuf> {
+pub(crate) fn find_config_root(start: &Path) -> Option<PathBuf> {
     let mut current = start.to_path_buf();
     loop {
         if is_config_root(&current) {
@@ -0,0 +1,443 @@
+//! Minimal JSONC (JSON with comments) editing that keeps the rest of the document intact.
+//!
+//! Agent settings files are hand-edited and often contain comments, so llman patches the one
+//! member it owns instead of re-serializing the whole document.
+
+use serde_json::Value;
+
+pub fn line_indent(text: &str, idx: usize) -> String {
+    let line_start = text[..idx].rfind('\n').map(|pos| pos + 1).unwrap_or(0);
+    let slice = &text[line_start..idx];
+    slice.chars().take_while(|ch| ch.is_whitespace()).collect()
+}
+
+pub fn skip_ws_and_comments(text: &str, mut idx: usize) -> usize {
+    let bytes = text.as_bytes();
+    loop {
+        while idx < bytes.len() && bytes[idx].is_ascii_whitespace() {
+            idx += 1;
+        }
+
+        if idx + 1 < bytes.len() && bytes[idx] == b'/' && bytes[idx + 1] == b'/' {
+            idx += 2;
+            while idx < bytes.len() && bytes[idx] != b'\n' {
+                idx += 1;
+            }
+            continue;
+        }
+
+        if idx + 1 < bytes.len() && bytes[idx] == b'/' && bytes[idx + 1] == b'*' {
+            idx += 2;
+            while idx + 1 < bytes.len() {
+                if bytes[idx] == b'*' && bytes[idx + 1] == b'/' {
+                    idx += 2;
+                    break;
+                }
+                idx += 1;
+            }
+            continue;
+        }
+
+        break;
+    }
+    idx
+}
+
+pub fn parse_json_string(text: &str, start_quote: usize) -> Option<(String, usize)> {
+    let bytes = text.as_bytes();
+    if bytes.get(start_quote)? != &b'"' {
+        return None;
+    }
+
+    let mut out = String::new();
+    let mut idx = start_quote + 1;
+    while idx < bytes.len() {
+        match bytes[idx] {
+            b'\\' => {
+                idx += 1;
+                let esc = *bytes.get(idx)?;
+                match esc {
+                    b'"' => out.push('"'),
+                    b'\\' => out.push('\\'),
+                    b'/' => out.push('/'),
+                    b'b' => out.push('\u{0008}'),
+                    b'f' => out.push('\u{000c}'),
+                    b'n' => out.push('\n'),
+                    b'r' => out.push('\r'),
+                    b't' => out.push('\t'),
+                    b'u' => {
+                        let hex = text.get(idx + 1..idx + 5)?;
+                        let code = u16::from_str_radix(hex, 16).ok()?;
+                        out.push(char::from_u32(code as u32)?);
+                        idx += 4;
+                    }
+                    _ => return None,
+                }
+                idx += 1;
+            }
+            b'"' => return Some((out, idx + 1)),
+            other => {
+                out.push(other as char);
+                idx += 1;
+            }
+        }
+    }
+
+    None
+}
+
+pub fn find_object_member_value_start(
+    text: &str,
+    start: usize,
+    end: usize,
+    key: &str,
+) -> Option<usize> {
+    let bytes = text.as_bytes();
+    let mut idx = start;
+    let mut depth_obj = 0usize;
+    let mut depth_arr = 0usize;
+
+    while idx < end {
+        idx = skip_ws_and_comments(text, idx);
+        if idx >= end {
+            break;
+        }
+
+        match bytes[idx] {
+            b'"' => {
+                let (s, next) = parse_json_string(text, idx)?;
+                let after = skip_ws_and_comments(text, next);
+                if depth_obj == 0 && depth_arr == 0 && s == key && bytes.get(after) == Some(&b':') {
+                    return Some(skip_ws_and_comments(text, after + 1));
+                }
+                idx = next;
+            }
+            b'{' => {
+                depth_obj += 1;
+                idx += 1;
+            }
+            b'}' => {
+                depth_obj = depth_obj.saturating_sub(1);
+                idx += 1;
+            }
+            b'[' => {
+                depth_arr += 1;
+                idx += 1;
+            }
+            b']' => {
+                depth_arr = depth_arr.saturating_sub(1);
+                idx += 1;
+            }
+            _ => idx += 1,
+        }
+    }
+
+    None
+}
+
+pub fn find_matching_bracket(text: &str, start: usize, open: u8, close: u8) -> Option<usize> {
+    let bytes = text.as_bytes();
+    if bytes.get(start)? != &open {
+        return None;
+    }
+
+    let mut idx = start;
+    let mut depth = 0usize;
+    while idx < bytes.len() {
+        idx = skip_ws_and_comments(text, idx);
+        if idx >= bytes.len() {
+            break;
+        }
+
+        match bytes[idx] {
+            b'"' => {
+                let (_, next) = parse_json_string(text, idx)?;
+                idx = next;
+            }
+            ch if ch == open => {
+                depth += 1;
+                idx += 1;
+            }
+            ch if ch == close => {
+                depth = depth.saturating_sub(1);
+                idx += 1;
+                if depth == 0 {
+                    return Some(idx - 1);
+                }
+            }
+            _ => idx += 1,
+        }
+    }
+
+    None
+}
+
+/// Exclusive end offset of the JSON value starting at `start`.
+pub fn value_end(text: &str, start: usize) -> Option<usize> {
+    let bytes = text.as_bytes();
+    match *bytes.get(start)? {
+        b'{' => find_matching_bracket(text, start, b'{', b'}').map(|end| end + 1),
+        b'[' => find_matching_bracket(text, start, b'[', b']').map(|end| end + 1),
+        b'"' => parse_json_string(text, start).map(|(_, next)| next),
+        _ => {
+            let mut idx = start;
+            while idx < bytes.len()
+                && !matches!(bytes[idx], b',' | b'}' | b']' | b'/')
+                && !bytes[idx].is_ascii_whitespace()
+            {
+                idx += 1;
+            }
+            (idx > start).then_some(idx)
+        }
+    }
+}
+
+/// A member of a JSONC object, as byte offsets into the source text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemberSpan {
+    pub key: String,
+    pub key_start: usize,
+    pub value_start: usize,
+    pub value_end: usize,
+}
+
+/// Members of the object whose `{` is at `open`.
+pub fn object_members(text: &str, open: usize) -> Option<Vec<MemberSpan>> {
+    let close = find_matching_bracket(text, open, b'{', b'}')?;
+    let bytes = text.as_bytes();
+    let mut members = Vec::new();
+    let mut idx = open + 1;
+
+    loop {
+        idx = skip_ws_and_comments(text, idx);
+        if idx >= close {
+            break;
+        }
+        if bytes[idx] == b',' {
+            idx += 1;
+            continue;
+        }
+        let (key, next) = parse_json_string(text, idx)?;
+        let colon = skip_ws_and_comments(text, next);
+        if bytes.get(colon) != Some(&b':') {
+            return None;
+        }
+        let value_start = skip_ws_and_comments(text, colon + 1);
+        let end = value_end(text, value_start)?;
+        members.push(MemberSpan {
+            key,
+            key_start: idx,
+            value_start,
+            value_end: end,
+        });
+        idx = end;
+    }
+
+    Some(members)
+}
+
+/// Set the member at `path` to `value`, creating missing parent objects.
+///
+/// Returns `None` when the document shape cannot be patched in place (e.g. a parent on the
+/// path is not an object); callers then fall back to re-serializing the parsed value.
+pub fn set_member(original: &str, path: &[&str], value: &Value) -> Option<String> {
+    let (last, parents) = path.split_last()?;
+    let mut open = skip_ws_and_comments(original, 0);
+    if original.as_bytes().get(open) != Some(&b'{') {
+        return None;
+    }
+
+    for (depth, key) in parents.iter().enumerate() {
+        let members = object_members(original, open)?;
+        match members.iter().find(|member| member.key == *key) {
+            Some(member) if original.as_bytes().get(member.value_start) == Some(&b'{') => {
+                open = member.value_start;
+            }
+            Some(_) => return None,
+            None => {
+                let nested = nest_value(&path[depth + 1..], value);
+                return Some(insert_member(original, open, &members, key, &nested));
+            }
+        }
+    }
+
+    let members = object_members(original, open)?;
+    match members.iter().find(|member| member.key == *last) {
+        Some(member) => {
+            let indent = line_indent(original, member.key_start);
+            let rendered = render_value(value, &indent);
+            let mut out = String::with_capacity(original.len() + rendered.len());
+            out.push_str(&original[..member.value_start]);
+            out.push_str(&rendered);
+            out.push_str(&original[member.value_end..]);
+            Some(out)
+        }
+        None => Some(insert_member(original, open, &members, last, value)),
+    }
+}
+
+/// Remove the member at `path`, including its separating comma.
+///
+/// Returns the original text unchanged when the member does not exist.
+pub fn remove_member(original: &str, path: &[&str]) -> Option<String> {
+    let (last, parents) = path.split_last()?;
+    let mut open = skip_ws_and_comments(original, 0);
+    if original.as_bytes().get(open) != Some(&b'{') {
+        return None;
+    }
+    for key in parents {
+        let members = object_members(original, open)?;
+        match members.iter().find(|member| member.key == *key) {
+            Some(member) if original.as_bytes().get(member.value_start) == Some(&b'{') => {
+                open = member.value_start;
+            }
+            _ => return Some(original.to_string()),
+        }
+    }
+
+    let members = object_members(original, open)?;
+    let Some(index) = members.iter().position(|member| member.key == *last) else {
+        return Some(original.to_string());
+    };
+    let member = &members[index];
+
+    // Remove from the end of the previous member (or the opening brace) so the
+    // preceding comma and the member's own line go away together.
+    let (start, end) = if index > 0 {
+        (members[index - 1].value_end, member.value_end)
+    } else {
+        let after = skip_ws_and_comments(original, member.value_end);
+        let end = if original.as_bytes().get(after) == Some(&b',') {
+            after + 1
+        } else {
+            member.value_end
+        };
+        (open + 1, end)
+    };
+
+    let mut out = String::with_capacity(original.len());
+    out.push_str(&original[..start]);
+    out.push_str(&original[end..]);
+    Some(out)
+}
+
+fn nest_value(path: &[&str], value: &Value) -> Value {
+    path.iter().rev().fold(value.clone(), |inner, key| {
+        let mut map = serde_json::Map::new();
+        map.insert((*key).to_string(), inner);
+        Value::Object(map)
+    })
+}
+
+fn insert_member(
+    original: &str,
+    open: usize,
+    members: &[MemberSpan],
+    key: &str,
+    value: &Value,
+) -> String {
+    let key_json = serde_json::to_string(key).unwrap_or_else(|_| "\"\"".to_string());
+    let mut out = String::with_capacity(original.len() + 64);
+
+    match members.last() {
+        Some(last) => {
+            let indent = line_indent(original, last.key_start);
+            out.push_str(&original[..last.value_end]);
+            out.push_str(",\n");
+            out.push_str(&indent);
+            out.push_str(&key_json);
+            out.push_str(": ");
+            out.push_str(&render_value(value, &indent));
+            out.push_str(&original[last.value_end..]);
+        }
+        None => {
+            let outer = line_indent(original, open);
+            let indent = format!("{outer}  ");
+            let close = find_matching_bracket(original, open, b'{', b'}').unwrap_or(open + 1);
+            out.push_str(&original[..open + 1]);
+            out.push('\n');
+            out.push_str(&indent);
+            out.push_str(&key_json);
+            out.push_str(": ");
+            out.push_str(&render_value(value, &indent));
+            out.push('\n');
+            out.push_str(&outer);
+            out.push_str(&original[close..]);
+        }
+    }
+    out
+}
+
+/// Pretty-print `value` so that continuation lines line up under `line_indent`.
+pub fn render_value(value: &Value, line_indent: &str) -> String {
+    let pretty = serde_json::to_string_pretty(value).unwrap_or_else(|_| "null".to_string());
+    pretty
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| {
+            if idx == 0 {
+                line.to_string()
+            } else {
+                format!("{line_indent}{line}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const SETTINGS: &str = r#"{
+  // keep me
+  "model": "opus",
+  "mcpServers": {
+    "docs": { "command": "npx" }
+  }
+}
+"#;
+
+    #[test]
+    fn set_member_replaces_existing_value_and_keeps_comments() {
+        let out = set_member(
+            SETTINGS,
+            &["mcpServers", "docs"],
+            &json!({"command": "uvx"}),
+        )
+        .unwrap();
+        assert!(out.contains("// keep me"));
+        let parsed: Value = llm_json::loads(&out, &Default::default()).unwrap();
+        assert_eq!(parsed["mcpServers"]["docs"]["command"], "uvx");
+        assert_eq!(parsed["model"], "opus");
+    }
+
+    #[test]
+    fn set_member_inserts_missing_members_and_parents() {
+        let out = set_member(SETTINGS, &["mcpServers", "fetch"], &json!({"command": "x"})).unwrap();
+        let out = set_member(&out, &["hooks", "Stop"], &json!([])).unwrap();
+        let parsed: Value = llm_json::loads(&out, &Default::default()).unwrap();
+        assert_eq!(parsed["mcpServers"]["fetch"]["command"], "x");
+        assert_eq!(parsed["mcpServers"]["docs"]["command"], "npx");
+        assert_eq!(parsed["hooks"]["Stop"], json!([]));
+        assert!(out.contains("// keep me"));
+
+        let empty = set_member("{}", &["a"], &json!(1)).unwrap();
+        assert_eq!(empty, "{\n  \"a\": 1\n}");
+    }
+
+    #[test]
+    fn remove_member_drops_member_and_comma() {
+        let out = remove_member(SETTINGS, &["model"]).unwrap();
+        let parsed: Value = llm_json::loads(&out, &Default::default()).unwrap();
+        assert!(parsed.get("model").is_none());
+        assert!(parsed.get("mcpServers").is_some());
+
+        let out = remove_member(SETTINGS, &["mcpServers", "docs"]).unwrap();
+        let parsed: Value = serde_json::from_str(&out.replace("// keep me", "")).unwrap();
+        assert_eq!(parsed["mcpServers"], json!({}));
+
+        assert_eq!(remove_member(SETTINGS, &["missing"]).unwrap(), SETTINGS);
+    }
+}
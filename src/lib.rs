@@ -13,10 +13,12 @@ pub mod editor;
 pub mod env_safety;
 pub mod error;
 pub(crate) mod fs_utils;
+pub mod jsonc;
 pub mod managed_block;
 pub mod path_utils;
 pub mod prompts;
 pub mod sdd;
+pub mod secrets;
 pub mod self_command;
 pub mod skills;
 pub mod tool;
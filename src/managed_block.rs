@@ -1,10 +1,13 @@
 use crate::fs_utils::atomic_write_with_mode;
 use anyhow::{Result, anyhow};
 use std::fs;
+use std::ops::Range;
 use std::path::Path;
 
 pub const LLMAN_PROMPTS_MARKER_START: &str = "<!-- LLMAN-PROMPTS:START -->";
 pub const LLMAN_PROMPTS_MARKER_END: &str = "<!-- LLMAN-PROMPTS:END -->";
+pub const LLMAN_IGNORE_MARKER_START: &str = "<!-- LLMAN-IGNORE:START -->";
+pub const LLMAN_IGNORE_MARKER_END: &str = "<!-- LLMAN-IGNORE:END -->";
 
 pub fn has_markers(content: &str, start_marker: &str, end_marker: &str) -> bool {
     content.lines().any(|line| line.trim() == start_marker)
@@ -179,3 +182,60 @@ pub fn update_text_with_markers(
         _ => existing.to_string(),
     }
 }
+
+/// Byte ranges of the first marked region: the whole region (marker lines included) and the
+/// body between the marker lines. Markers only count on their own (trimmed) line.
+fn find_marked_region(
+    existing: &str,
+    start_marker: &str,
+    end_marker: &str,
+) -> Option<(Range<usize>, Range<usize>)> {
+    let mut start: Option<(usize, usize)> = None;
+
+    let mut cursor = 0usize;
+    for line in existing.split_inclusive('\n') {
+        let line_start = cursor;
+        let line_end = cursor + line.len();
+        let trimmed = line.trim();
+        if start.is_none() && trimmed == start_marker {
+            start = Some((line_start, line_end));
+        } else if let Some((region_start, body_start)) = start
+            && trimmed == end_marker
+        {
+            return Some((region_start..line_end, body_start..line_start));
+        }
+        cursor = line_end;
+    }
+    None
+}
+
+/// Text between the marker lines of the first marked region, if one exists.
+pub fn text_between_markers<'a>(
+    existing: &'a str,
+    start_marker: &str,
+    end_marker: &str,
+) -> Option<&'a str> {
+    let (_, body) = find_marked_region(existing, start_marker, end_marker)?;
+    Some(&existing[body])
+}
+
+/// `existing` with the marked region (markers included) removed; `None` when no region exists.
+///
+/// A blank line left between the region and the surrounding text is removed with it.
+pub fn remove_text_with_markers(
+    existing: &str,
+    start_marker: &str,
+    end_marker: &str,
+) -> Option<String> {
+    let (region, _) = find_marked_region(existing, start_marker, end_marker)?;
+    let (start, end) = (region.start, region.end);
+    let before = &existing[..start];
+    let mut after = &existing[end..];
+    if before.is_empty() || before.ends_with("\n\n") {
+        after = after
+            .strip_prefix("\r\n")
+            .or_else(|| after.strip_prefix('\n'))
+            .unwrap_or(after);
+    }
+    Some(format!("{before}{after}"))
+}
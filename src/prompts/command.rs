@@ -0,0 +1,568 @@
+use crate::config::{
+    CLAUDE_CODE_APP, CODEX_APP, CURSOR_APP, Config, SHARED_APP, TARGET_CURSOR_RULES_DIR,
+};
+use crate::fs_utils::atomic_write_with_mode;
+use crate::path_utils::safe_parent_for_creation;
+use crate::prompts::confirm::confirm_overwrite;
+use crate::prompts::managed_file::write_generated_block;
+use crate::prompts::paths::{claude_home_dir, codex_home_dir, cwd, project_root};
+use crate::prompts::provenance::stamp;
+use crate::prompts::render::{GLOBAL_SCOPE, PROJECT_SCOPE, PromptRenderer};
+use crate::prompts::shared::{SharedTemplate, build_managed_body};
+use crate::prompts::store as prompt_store;
+use crate::prompts::{drift, history, regen};
+use anyhow::{Result, anyhow};
+use clap::{Args, Subcommand, ValueEnum};
+use inquire::Confirm;
+use similar::TextDiff;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const CODEX_AGENTS_FILE: &str = "AGENTS.md";
+pub const CODEX_AGENTS_OVERRIDE_FILE: &str = "AGENTS.override.md";
+pub const CLAUDE_MEMORY_FILE: &str = "CLAUDE.md";
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum PromptsCommand {
+    /// Render shared templates into Cursor rules, AGENTS.md and CLAUDE.md
+    Gen(GenArgs),
+    /// Show whether generated targets still match their templates
+    Status(TargetArgs),
+    /// Like `status`, but exit non-zero when any target drifted (for CI)
+    Check(TargetArgs),
+    /// Re-render managed blocks from the templates recorded in their provenance
+    Regen(TargetArgs),
+    /// Remove llman managed blocks from AGENTS.md / CLAUDE.md
+    Ungen {
+        #[command(flatten)]
+        targets: TargetArgs,
+        /// Skip confirmation prompts (required for non-interactive removal)
+        #[arg(long)]
+        yes: bool,
+    },
+    /// Show the revision history of a template
+    Log {
+        #[arg(value_enum)]
+        app: TemplateStore,
+        name: String,
+    },
+    /// Diff two revisions of a template (default: previous revision against the current file)
+    Diff {
+        #[arg(value_enum)]
+        app: TemplateStore,
+        name: String,
+        /// Older revision (default: the one before the latest)
+        #[arg(long)]
+        from: Option<u32>,
+        /// Newer revision (default: the current template file)
+        #[arg(long)]
+        to: Option<u32>,
+    },
+    /// Restore a template, including a removed one, from history
+    Restore {
+        #[arg(value_enum)]
+        app: TemplateStore,
+        /// `<name>@<rev>` (see `llman prompts log`)
+        spec: String,
+        /// Overwrite the current template without confirmation
+        #[arg(long)]
+        force: bool,
+    },
+    /// List shared templates (prompt/shared/*.md)
+    List,
+    /// Create or update a shared template
+    Upsert {
+        #[arg(long)]
+        name: String,
+        #[command(flatten)]
+        content: TemplateContentSource,
+    },
+    /// Remove a shared template
+    Rm {
+        #[arg(long)]
+        name: String,
+        /// Skip confirmation prompts (required for non-interactive deletes)
+        #[arg(long)]
+        yes: bool,
+    },
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct GenArgs {
+    /// Shared template name(s) (repeatable; default: all shared templates)
+    #[arg(long, value_delimiter = ',', action = clap::ArgAction::Append)]
+    pub template: Vec<String>,
+
+    /// Target app(s) (repeatable, or comma-separated)
+    #[arg(long, value_enum, value_delimiter = ',', action = clap::ArgAction::Append, required_unless_present = "all_apps")]
+    pub app: Vec<PromptApp>,
+
+    /// Render into every supported app in one run
+    #[arg(long = "all-apps", conflicts_with = "app")]
+    pub all_apps: bool,
+
+    /// Target scope(s) for injection (Cursor only supports `project`)
+    #[arg(long, value_enum, value_delimiter = ',', action = clap::ArgAction::Append, default_value = "project")]
+    pub scope: Vec<PromptScopeArg>,
+
+    /// Force generation, skip repository detection / overwrite checks
+    ///
+    /// Also replaces same-named sections that `x codex|cc prompts gen` wrote into the block.
+    #[arg(long)]
+    pub force: bool,
+}
+
+/// Generated targets selected by `status`, `check`, `regen` and `ungen`.
+#[derive(Args, Debug, Clone)]
+pub struct TargetArgs {
+    /// App(s) to inspect (default: all)
+    #[arg(long, value_enum, value_delimiter = ',', action = clap::ArgAction::Append)]
+    pub app: Vec<PromptApp>,
+
+    /// Scope(s) to inspect (default: global and project)
+    #[arg(long, value_enum, value_delimiter = ',', action = clap::ArgAction::Append)]
+    pub scope: Vec<PromptScopeArg>,
+}
+
+impl TargetArgs {
+    pub fn apps(&self) -> Vec<PromptApp> {
+        let mut apps = if self.app.is_empty() {
+            PromptApp::ALL.to_vec()
+        } else {
+            self.app.clone()
+        };
+        apps.sort();
+        apps.dedup();
+        apps
+    }
+
+    pub fn scopes(&self) -> Vec<PromptScopeArg> {
+        status_scopes(&self.scope)
+    }
+}
+
+/// `status` / `check` arguments for a single app (`llman x <app> prompts status`).
+#[derive(Args, Debug, Clone)]
+pub struct AppStatusArgs {
+    /// Scope(s) to inspect (default: global and project)
+    #[arg(long, value_enum, value_delimiter = ',', action = clap::ArgAction::Append)]
+    pub scope: Vec<PromptScopeArg>,
+}
+
+impl AppStatusArgs {
+    pub fn run(&self, app: PromptApp, check: bool) -> Result<()> {
+        drift::run(&[app], &status_scopes(&self.scope), check)
+    }
+}
+
+/// Scopes to inspect; empty means both.
+pub fn status_scopes(scopes: &[PromptScopeArg]) -> Vec<PromptScopeArg> {
+    if scopes.is_empty() {
+        vec![PromptScopeArg::Global, PromptScopeArg::Project]
+    } else {
+        scopes.to_vec()
+    }
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PromptApp {
+    Cursor,
+    Codex,
+    #[value(name = "claude-code")]
+    ClaudeCode,
+}
+
+impl PromptApp {
+    pub const ALL: [PromptApp; 3] = [PromptApp::Cursor, PromptApp::Codex, PromptApp::ClaudeCode];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            PromptApp::Cursor => CURSOR_APP,
+            PromptApp::Codex => CODEX_APP,
+            PromptApp::ClaudeCode => CLAUDE_CODE_APP,
+        }
+    }
+}
+
+/// Template stores under `prompt/`.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TemplateStore {
+    Cursor,
+    Codex,
+    #[value(name = "claude-code")]
+    ClaudeCode,
+    Shared,
+}
+
+impl TemplateStore {
+    pub fn name(self) -> &'static str {
+        match self {
+            TemplateStore::Cursor => CURSOR_APP,
+            TemplateStore::Codex => CODEX_APP,
+            TemplateStore::ClaudeCode => CLAUDE_CODE_APP,
+            TemplateStore::Shared => SHARED_APP,
+        }
+    }
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PromptScopeArg {
+    Global,
+    Project,
+}
+
+impl PromptScopeArg {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PromptScopeArg::Global => GLOBAL_SCOPE,
+            PromptScopeArg::Project => PROJECT_SCOPE,
+        }
+    }
+}
+
+#[derive(Args, Debug, Clone)]
+#[group(required = true, multiple = false)]
+pub struct TemplateContentSource {
+    #[arg(long)]
+    pub content: Option<String>,
+    #[arg(long)]
+    pub file: Option<PathBuf>,
+}
+
+pub fn run(command: &PromptsCommand, interactive: bool) -> Result<()> {
+    match command {
+        PromptsCommand::Gen(args) => run_gen(args, interactive),
+        PromptsCommand::Status(args) => drift::run(&args.apps(), &args.scopes(), false),
+        PromptsCommand::Check(args) => drift::run(&args.apps(), &args.scopes(), true),
+        PromptsCommand::Regen(args) => regen::run_regen(&args.apps(), &args.scopes()),
+        PromptsCommand::Ungen { targets, yes } => {
+            regen::run_ungen(&targets.apps(), &targets.scopes(), *yes, interactive)
+        }
+        PromptsCommand::Log { app, name } => run_log(app.name(), name),
+        PromptsCommand::Diff {
+            app,
+            name,
+            from,
+            to,
+        } => run_diff(app.name(), name, *from, *to),
+        PromptsCommand::Restore { app, spec, force } => {
+            run_restore(app.name(), spec, *force, interactive)
+        }
+        PromptsCommand::List => run_list(),
+        PromptsCommand::Upsert { name, content } => {
+            run_upsert(name, content.content.as_deref(), content.file.as_deref())
+        }
+        PromptsCommand::Rm { name, yes } => run_rm(name, *yes, interactive),
+    }
+}
+
+fn run_list() -> Result<()> {
+    let config = Config::new()?;
+    let templates = prompt_store::list_templates(&config, SHARED_APP)?;
+    if templates.is_empty() {
+        println!("  {}", t!("errors.no_rules_found"));
+        return Ok(());
+    }
+    for name in templates {
+        let content = prompt_store::read_template(&config, SHARED_APP, &name)?;
+        let apps = match SharedTemplate::parse(&name, &content) {
+            Ok(template) if template.meta.apps.is_empty() => "all".to_string(),
+            Ok(template) => template.meta.apps.join(","),
+            Err(_) => "invalid".to_string(),
+        };
+        println!("  {} ({apps})", t!("prompt.list.rule_item", name = name));
+    }
+    Ok(())
+}
+
+fn run_upsert(name: &str, content: Option<&str>, file: Option<&Path>) -> Result<()> {
+    let content = if let Some(content) = content {
+        content.to_string()
+    } else if let Some(file_path) = file {
+        fs::read_to_string(file_path)?
+    } else {
+        return Err(anyhow!(t!("messages.content_or_file_required")));
+    };
+    // Reject broken sections before they reach every app.
+    SharedTemplate::parse(name, &content)?;
+
+    let config = Config::new()?;
+    let path = prompt_store::upsert_template(&config, SHARED_APP, name, &content)?;
+    println!("{}", t!("messages.rule_saved", path = path.display()));
+    Ok(())
+}
+
+fn run_rm(name: &str, yes: bool, interactive: bool) -> Result<()> {
+    if !yes {
+        if !interactive {
+            return Err(anyhow!(t!(
+                "errors.non_interactive_delete_requires_yes",
+                name = name
+            )));
+        }
+        let confirm = Confirm::new(&t!("messages.confirm_delete", name = name))
+            .with_default(false)
+            .prompt()?;
+        if !confirm {
+            println!("{}", t!("messages.operation_cancelled"));
+            return Ok(());
+        }
+    }
+
+    let config = Config::new()?;
+    prompt_store::remove_template(&config, SHARED_APP, name)?;
+    println!("{}", t!("messages.rule_deleted", name = name));
+    Ok(())
+}
+
+fn run_log(app: &str, name: &str) -> Result<()> {
+    let config = Config::new()?;
+    let entries = history::entries(&config, app, name)?;
+    if entries.is_empty() {
+        println!("{}", t!("prompt.history.empty", app = app, name = name));
+        return Ok(());
+    }
+    for entry in entries.iter().rev() {
+        let detail = match (entry.restored_from, &entry.digest) {
+            (Some(rev), _) => format!("from @{rev}"),
+            (None, Some(digest)) => digest.chars().take(12).collect(),
+            (None, None) => String::new(),
+        };
+        println!(
+            "  {name}@{:<4} {}  {:<8}  {detail}",
+            entry.rev,
+            entry.timestamp,
+            entry.action.label()
+        );
+    }
+    Ok(())
+}
+
+fn run_diff(app: &str, name: &str, from: Option<u32>, to: Option<u32>) -> Result<()> {
+    let config = Config::new()?;
+    let entries = history::entries(&config, app, name)?;
+    let from = match from {
+        Some(rev) => rev,
+        None => match entries.len() {
+            0 => return Err(anyhow!(t!("prompt.history.empty", app = app, name = name))),
+            1 => entries[0].rev,
+            n => entries[n - 2].rev,
+        },
+    };
+    let before =
+        history::read_revision(&config, &history::find_revision(&config, app, name, from)?)?
+            .unwrap_or_default();
+    let (after, after_label) = match to {
+        Some(rev) => (
+            history::read_revision(&config, &history::find_revision(&config, app, name, rev)?)?
+                .unwrap_or_default(),
+            format!("{name}@{rev}"),
+        ),
+        None => (
+            fs::read_to_string(config.rule_file_path(app, name)).unwrap_or_default(),
+            format!("{name} (current)"),
+        ),
+    };
+
+    if before == after {
+        println!("{}", t!("prompt.history.no_changes"));
+        return Ok(());
+    }
+    let diff = TextDiff::from_lines(&before, &after);
+    print!(
+        "{}",
+        diff.unified_diff()
+            .context_radius(3)
+            .header(&format!("{name}@{from}"), &after_label)
+    );
+    Ok(())
+}
+
+fn run_restore(app: &str, spec: &str, force: bool, interactive: bool) -> Result<()> {
+    let (name, rev) = history::parse_revision_spec(spec)?;
+    let config = Config::new()?;
+    let path = config.rule_file_path(app, &name);
+    if path.exists() && !force && !confirm_overwrite(&path, interactive)? {
+        println!("{}", t!("messages.operation_cancelled"));
+        return Ok(());
+    }
+    let path = prompt_store::restore_template(&config, app, &name, rev)?;
+    println!(
+        "{}",
+        t!(
+            "prompt.history.restored",
+            spec = spec,
+            path = path.display()
+        )
+    );
+    Ok(())
+}
+
+pub fn load_shared_templates(config: &Config, names: &[String]) -> Result<Vec<SharedTemplate>> {
+    let names = if names.is_empty() {
+        prompt_store::list_templates(config, SHARED_APP)?
+    } else {
+        names.to_vec()
+    };
+    if names.is_empty() {
+        return Err(anyhow!(t!("prompt.shared.no_templates")));
+    }
+
+    names
+        .iter()
+        .map(|name| {
+            let content = prompt_store::read_template(config, SHARED_APP, name)?;
+            SharedTemplate::parse(name, &content)
+        })
+        .collect()
+}
+
+fn run_gen(args: &GenArgs, interactive: bool) -> Result<()> {
+    let config = Config::new()?;
+    let templates = load_shared_templates(&config, &args.template)?;
+
+    let mut apps = if args.all_apps {
+        PromptApp::ALL.to_vec()
+    } else {
+        args.app.clone()
+    };
+    apps.sort();
+    apps.dedup();
+
+    let cwd = cwd()?;
+    let root = if args.scope.contains(&PromptScopeArg::Project) {
+        project_root(&cwd, args.force, interactive)?
+    } else {
+        None
+    };
+    let scopes: Vec<PromptScopeArg> = [PromptScopeArg::Global, PromptScopeArg::Project]
+        .into_iter()
+        .filter(|s| args.scope.contains(s))
+        .collect();
+
+    let renderer = match root.as_deref() {
+        Some(root) => PromptRenderer::new(&config, Some(root))?,
+        None => PromptRenderer::for_dir(&config, &cwd)?,
+    };
+
+    let mut first_error: Option<anyhow::Error> = None;
+    for app in apps {
+        let result = match app {
+            PromptApp::Cursor => {
+                if scopes.contains(&PromptScopeArg::Global) {
+                    println!("{}", t!("prompt.shared.cursor_global_skipped"));
+                }
+                match root.as_deref() {
+                    Some(root) => {
+                        write_cursor_rules(root, &templates, &renderer, args.force, interactive)
+                    }
+                    None => Ok(()),
+                }
+            }
+            PromptApp::Codex | PromptApp::ClaudeCode => write_managed_blocks(
+                app,
+                &scopes,
+                root.as_deref(),
+                &templates,
+                &renderer,
+                args,
+                interactive,
+            ),
+        };
+        if let Err(e) = result
+            && first_error.is_none()
+        {
+            first_error = Some(e);
+        }
+    }
+
+    if let Some(err) = first_error {
+        return Err(err);
+    }
+    Ok(())
+}
+
+fn write_cursor_rules(
+    root: &Path,
+    templates: &[SharedTemplate],
+    renderer: &PromptRenderer,
+    force: bool,
+    interactive: bool,
+) -> Result<()> {
+    for template in templates {
+        let Some(content) = template.render_cursor_rule(renderer)? else {
+            continue;
+        };
+        let path = cursor_rule_path(root, &template.name);
+        if path.exists() {
+            if fs::read_to_string(&path).is_ok_and(|existing| existing == content) {
+                continue;
+            }
+            if !force && !confirm_overwrite(&path, interactive)? {
+                println!("{}", t!("messages.operation_cancelled"));
+                continue;
+            }
+        }
+        if let Some(parent) = safe_parent_for_creation(&path) {
+            fs::create_dir_all(parent)?;
+        }
+        atomic_write_with_mode(&path, content.as_bytes(), None)?;
+        println!("{}", t!("messages.rule_generated", path = path.display()));
+    }
+    Ok(())
+}
+
+fn write_managed_blocks(
+    app: PromptApp,
+    scopes: &[PromptScopeArg],
+    root: Option<&Path>,
+    templates: &[SharedTemplate],
+    renderer: &PromptRenderer,
+    args: &GenArgs,
+    interactive: bool,
+) -> Result<()> {
+    for scope in scopes {
+        let Some(path) = managed_block_path(app, *scope, root)? else {
+            continue;
+        };
+        let body = build_managed_body(templates, app.name(), renderer, scope.as_str())?;
+        if body.is_empty() {
+            continue;
+        }
+        let records = templates
+            .iter()
+            .filter(|t| t.applies_to(app.name()))
+            .map(SharedTemplate::record)
+            .collect();
+        let body = stamp(app.name(), scope.as_str(), records, &body);
+        if write_generated_block(&path, &body, args.force, interactive)? {
+            println!("{}", t!("messages.rule_generated", path = path.display()));
+        }
+    }
+    Ok(())
+}
+
+pub fn cursor_rule_path(root: &Path, name: &str) -> PathBuf {
+    root.join(TARGET_CURSOR_RULES_DIR)
+        .join(format!("{name}.mdc"))
+}
+
+/// `AGENTS.md` / `CLAUDE.md` for `scope`; `None` for Cursor or an unresolved project root.
+pub fn managed_block_path(
+    app: PromptApp,
+    scope: PromptScopeArg,
+    root: Option<&Path>,
+) -> Result<Option<PathBuf>> {
+    let file_name = match app {
+        PromptApp::Codex => CODEX_AGENTS_FILE,
+        PromptApp::ClaudeCode => CLAUDE_MEMORY_FILE,
+        PromptApp::Cursor => return Ok(None),
+    };
+    Ok(match scope {
+        PromptScopeArg::Global => Some(match app {
+            PromptApp::Codex => codex_home_dir()?.join(file_name),
+            _ => claude_home_dir()?.join(file_name),
+        }),
+        PromptScopeArg::Project => root.map(|root| root.join(file_name)),
+    })
+}
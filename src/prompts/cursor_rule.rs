@@ -0,0 +1,370 @@
+//! Cursor `.mdc` rule frontmatter: parsing, authoring and lint.
+//!
+//! Cursor writes `globs: *.ts,*.tsx` unquoted, which is not valid YAML, so frontmatter is
+//! read line by line as `key: value` instead of through serde_yaml.
+
+use crate::sdd::spec::frontmatter::split_frontmatter;
+use anyhow::{Result, bail};
+use ignore::WalkBuilder;
+use std::path::Path;
+
+const KNOWN_KEYS: [&str; 3] = ["description", "globs", "alwaysApply"];
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CursorRuleFrontmatter {
+    pub description: Option<String>,
+    pub globs: Option<String>,
+    pub always_apply: Option<bool>,
+}
+
+/// A parsed rule: frontmatter (if present), body, and problems found while parsing.
+#[derive(Debug, Clone, Default)]
+pub struct CursorRule {
+    pub frontmatter: Option<CursorRuleFrontmatter>,
+    pub body: String,
+    pub problems: Vec<String>,
+}
+
+impl CursorRuleFrontmatter {
+    /// Replace the fields that `overrides` sets.
+    pub fn merge(&mut self, overrides: &CursorRuleFrontmatter) {
+        if overrides.description.is_some() {
+            self.description = overrides.description.clone();
+        }
+        if overrides.globs.is_some() {
+            self.globs = overrides.globs.clone();
+        }
+        if overrides.always_apply.is_some() {
+            self.always_apply = overrides.always_apply;
+        }
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if let Some(description) = &self.description
+            && description.contains('\n')
+        {
+            bail!(t!("prompt.cursor.rule.multiline_description"));
+        }
+        if let Some(globs) = &self.globs {
+            for pattern in split_globs(globs) {
+                for expanded in expand_braces(pattern) {
+                    if let Err(e) = glob::Pattern::new(&expanded) {
+                        bail!(t!(
+                            "prompt.cursor.rule.invalid_glob",
+                            pattern = pattern,
+                            error = e
+                        ));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// `---` delimited frontmatter block; `alwaysApply` defaults to true when no globs are set.
+    pub fn render(&self) -> String {
+        let always_apply = self.always_apply.unwrap_or(self.globs.is_none());
+        format!(
+            "---\ndescription: {}\nglobs: {}\nalwaysApply: {always_apply}\n---\n",
+            self.description.as_deref().unwrap_or_default(),
+            self.globs.as_deref().unwrap_or_default()
+        )
+    }
+}
+
+impl CursorRule {
+    pub fn parse(content: &str) -> Self {
+        let (frontmatter, body) = split_frontmatter(content);
+        let Some(frontmatter) = frontmatter else {
+            return Self {
+                frontmatter: None,
+                body,
+                problems: Vec::new(),
+            };
+        };
+
+        let mut parsed = CursorRuleFrontmatter::default();
+        let mut problems = Vec::new();
+        for (idx, line) in frontmatter.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let Some((key, value)) = line.split_once(':') else {
+                problems.push(t!("prompt.cursor.rule.malformed_line", line = idx + 1).to_string());
+                continue;
+            };
+            let key = key.trim();
+            let value = unquote(value.trim());
+            let value = (!value.is_empty()).then(|| value.to_string());
+            match key {
+                "description" => parsed.description = value,
+                "globs" => parsed.globs = value,
+                "alwaysApply" => match value.as_deref() {
+                    None => {}
+                    Some("true") => parsed.always_apply = Some(true),
+                    Some("false") => parsed.always_apply = Some(false),
+                    Some(other) => problems.push(
+                        t!("prompt.cursor.rule.invalid_always_apply", value = other).to_string(),
+                    ),
+                },
+                _ => problems.push(
+                    t!(
+                        "prompt.cursor.rule.unknown_key",
+                        key = key,
+                        expected = KNOWN_KEYS.join(", ")
+                    )
+                    .to_string(),
+                ),
+            }
+        }
+        if let Err(e) = parsed.validate() {
+            problems.push(e.to_string());
+        }
+        Self {
+            frontmatter: Some(parsed),
+            body,
+            problems,
+        }
+    }
+
+    /// Rule text with `overrides` applied to its frontmatter (added if missing).
+    pub fn with_frontmatter(&self, overrides: &CursorRuleFrontmatter) -> String {
+        let mut frontmatter = self.frontmatter.clone().unwrap_or_default();
+        frontmatter.merge(overrides);
+        format!(
+            "{}\n{}\n",
+            frontmatter.render(),
+            self.body.trim_matches('\n')
+        )
+    }
+}
+
+/// Comma-separated globs; commas inside `{...}` alternatives belong to the pattern.
+pub fn split_globs(globs: &str) -> Vec<&str> {
+    let mut patterns = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (i, ch) in globs.char_indices() {
+        match ch {
+            '{' => depth += 1,
+            '}' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                patterns.push(&globs[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    patterns.push(&globs[start..]);
+    patterns
+        .into_iter()
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .collect()
+}
+
+/// Expand `{a,b}` alternatives, which `glob::Pattern` does not support.
+fn expand_braces(pattern: &str) -> Vec<String> {
+    let Some(open) = pattern.find('{') else {
+        return vec![pattern.to_string()];
+    };
+    let mut depth = 0usize;
+    let mut close = None;
+    let mut alternatives = Vec::new();
+    let mut start = open + 1;
+    for (i, ch) in pattern[open..].char_indices().map(|(i, ch)| (open + i, ch)) {
+        match ch {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    alternatives.push(&pattern[start..i]);
+                    close = Some(i);
+                    break;
+                }
+            }
+            ',' if depth == 1 => {
+                alternatives.push(&pattern[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    let Some(close) = close else {
+        // Unbalanced: leave the pattern for `glob::Pattern` to judge.
+        return vec![pattern.to_string()];
+    };
+
+    let (prefix, suffix) = (&pattern[..open], &pattern[close + 1..]);
+    alternatives
+        .into_iter()
+        .flat_map(|alt| expand_braces(&format!("{prefix}{alt}{suffix}")))
+        .collect()
+}
+
+fn unquote(value: &str) -> &str {
+    for quote in ['"', '\''] {
+        if let Some(inner) = value
+            .strip_prefix(quote)
+            .and_then(|v| v.strip_suffix(quote))
+        {
+            return inner;
+        }
+    }
+    value
+}
+
+/// Repository files (relative, `/`-separated), honoring `.gitignore`.
+pub fn repo_files(root: &Path) -> Vec<String> {
+    let mut files = Vec::new();
+    let walker = WalkBuilder::new(root)
+        .hidden(false)
+        .filter_entry(|entry| entry.file_name() != ".git")
+        .build();
+    for entry in walker.flatten() {
+        if !entry.file_type().is_some_and(|t| t.is_file()) {
+            continue;
+        }
+        if let Ok(relative) = entry.path().strip_prefix(root) {
+            files.push(relative.to_string_lossy().replace('\\', "/"));
+        }
+    }
+    files
+}
+
+/// Lint warnings for one rule; `files` come from [`repo_files`].
+pub fn lint_rule(content: &str, files: &[String]) -> Vec<String> {
+    let rule = CursorRule::parse(content);
+    let mut warnings = rule.problems.clone();
+    let Some(frontmatter) = rule.frontmatter else {
+        warnings.push(t!("prompt.cursor.rule.missing_frontmatter").to_string());
+        return warnings;
+    };
+
+    if frontmatter.always_apply != Some(true)
+        && frontmatter.globs.is_none()
+        && frontmatter.description.is_none()
+    {
+        warnings.push(t!("prompt.cursor.rule.never_applied").to_string());
+    }
+    if let Some(globs) = &frontmatter.globs {
+        for pattern in split_globs(globs) {
+            let mut compiled = Vec::new();
+            for expanded in expand_braces(pattern) {
+                let Ok(exact) = glob::Pattern::new(&expanded) else {
+                    continue;
+                };
+                compiled.push(exact);
+                // Cursor matches slash-less globs such as `*.ts` at any depth.
+                if !expanded.contains('/')
+                    && let Ok(anywhere) = glob::Pattern::new(&format!("**/{expanded}"))
+                {
+                    compiled.push(anywhere);
+                }
+            }
+            if compiled.is_empty() {
+                continue;
+            }
+            let matched = files
+                .iter()
+                .any(|file| compiled.iter().any(|p| p.matches(file)));
+            if !matched {
+                warnings.push(
+                    t!("prompt.cursor.rule.glob_matches_nothing", pattern = pattern).to_string(),
+                );
+            }
+        }
+    }
+    if rule.body.trim().is_empty() {
+        warnings.push(t!("prompt.cursor.rule.empty_body").to_string());
+    }
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_unquoted_globs_and_applies_overrides() {
+        let rule = CursorRule::parse(
+            "---\ndescription: TS rules\nglobs: *.ts,*.tsx\nalwaysApply: false\n---\nUse strict mode.\n",
+        );
+        assert!(rule.problems.is_empty(), "{:?}", rule.problems);
+        let frontmatter = rule.frontmatter.clone().unwrap();
+        assert_eq!(frontmatter.globs.as_deref(), Some("*.ts,*.tsx"));
+        assert_eq!(frontmatter.always_apply, Some(false));
+
+        let overridden = rule.with_frontmatter(&CursorRuleFrontmatter {
+            description: Some("Strict TS".to_string()),
+            ..Default::default()
+        });
+        assert_eq!(
+            overridden,
+            "---\ndescription: Strict TS\nglobs: *.ts,*.tsx\nalwaysApply: false\n---\n\nUse strict mode.\n"
+        );
+
+        let plain = CursorRule::parse("Just text.\n");
+        assert_eq!(
+            plain.with_frontmatter(&CursorRuleFrontmatter::default()),
+            "---\ndescription: \nglobs: \nalwaysApply: true\n---\n\nJust text.\n"
+        );
+    }
+
+    #[test]
+    fn split_globs_keeps_brace_alternatives_together() {
+        assert_eq!(
+            split_globs("**/*.{ts,tsx}, src/{a,b}/*.rs,*.md"),
+            vec!["**/*.{ts,tsx}", "src/{a,b}/*.rs", "*.md"]
+        );
+        assert_eq!(
+            expand_braces("src/{a,b{1,2}}/*.rs"),
+            vec!["src/a/*.rs", "src/b1/*.rs", "src/b2/*.rs"]
+        );
+        assert_eq!(expand_braces("*.{ts"), vec!["*.{ts"]);
+
+        let frontmatter = CursorRuleFrontmatter {
+            globs: Some("**/*.{ts,tsx},*.md".to_string()),
+            ..Default::default()
+        };
+        assert!(frontmatter.validate().is_ok());
+    }
+
+    #[test]
+    fn lint_reports_malformed_frontmatter_and_unmatched_globs() {
+        let files = vec!["src/main.rs".to_string(), "web/app.ts".to_string()];
+        let warnings = lint_rule(
+            "---\nglobs: *.ts, docs/**/*.md\nalwaysApply: maybe\nauthor: me\n---\nBody\n",
+            &files,
+        );
+        assert!(
+            warnings.iter().any(|w| w.contains("alwaysApply")),
+            "{warnings:?}"
+        );
+        assert!(
+            warnings
+                .iter()
+                .any(|w| w.contains("unknown frontmatter key 'author'"))
+        );
+        assert!(
+            warnings
+                .iter()
+                .any(|w| w.contains("'docs/**/*.md' matches no files"))
+        );
+        assert!(
+            !warnings.iter().any(|w| w.contains("'*.ts'")),
+            "{warnings:?}"
+        );
+
+        assert!(lint_rule("---\nalwaysApply: true\n---\nBody\n", &files).is_empty());
+        assert!(
+            lint_rule("---\nglobs: **/*.{ts,tsx}\n---\nBody\n", &files).is_empty(),
+            "brace alternatives match"
+        );
+        assert!(
+            lint_rule("No frontmatter\n", &files)
+                .iter()
+                .any(|w| w.contains("missing frontmatter"))
+        );
+    }
+}
@@ -0,0 +1,459 @@
+//! Drift detection for generated prompt targets.
+//!
+//! Managed blocks (`AGENTS.md`, `AGENTS.override.md`, `CLAUDE.md`) list their templates as
+//! `## llman prompts: <name>` sections; Cursor rules are matched to a template by file stem.
+//! Each target is re-rendered from the current store and classified:
+//! - `up-to-date`: matches the current rendering
+//! - `stale`: a template changed after the target was written
+//! - `modified`: the target was edited by hand after generation
+//! - `orphaned`: a section's template no longer exists
+//!
+//! Blocks carrying a provenance line tell hand edits from template changes by digest; older
+//! blocks fall back to comparing modification times.
+
+use crate::config::{CURSOR_APP, Config, SHARED_APP, TARGET_CURSOR_RULES_DIR};
+use crate::managed_block::{LLMAN_PROMPTS_MARKER_END, LLMAN_PROMPTS_MARKER_START};
+use crate::prompts::command::{
+    CODEX_AGENTS_OVERRIDE_FILE, PromptApp, PromptScopeArg, managed_block_path,
+};
+use crate::prompts::cursor_rule::CursorRule;
+use crate::prompts::paths::cwd;
+use crate::prompts::provenance::split_provenance;
+use crate::prompts::render::{PROJECT_SCOPE, PromptRenderer};
+use crate::prompts::shared::SharedTemplate;
+use crate::prompts::store as prompt_store;
+use crate::skills::shared::git::find_git_root;
+use anyhow::{Result, anyhow};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+pub const SECTION_PREFIX: &str = "## llman prompts: ";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DriftState {
+    UpToDate,
+    Stale,
+    Modified,
+    Orphaned,
+}
+
+impl DriftState {
+    pub fn label(self) -> &'static str {
+        match self {
+            DriftState::UpToDate => "up-to-date",
+            DriftState::Stale => "stale",
+            DriftState::Modified => "modified",
+            DriftState::Orphaned => "orphaned",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DriftEntry {
+    pub app: PromptApp,
+    pub path: PathBuf,
+    pub templates: Vec<String>,
+    pub state: DriftState,
+}
+
+/// One `## llman prompts: <name>` section of a managed block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockSection {
+    pub name: String,
+    pub body: String,
+}
+
+/// A current rendering of a template and the store file it came from.
+struct Rendering {
+    text: String,
+    source: PathBuf,
+}
+
+/// Print drift for `apps` and, when `check` is set, fail if anything drifted.
+pub fn run(apps: &[PromptApp], scopes: &[PromptScopeArg], check: bool) -> Result<()> {
+    let config = Config::new()?;
+    let cwd = cwd()?;
+    let root = find_git_root(&cwd);
+    if root.is_none() && scopes.contains(&PromptScopeArg::Project) {
+        println!("{}", t!("prompt.status.project_skipped"));
+    }
+    let renderer = PromptRenderer::new(&config, root.as_deref())?;
+
+    let mut entries = Vec::new();
+    for app in apps {
+        entries.extend(scan_app(&config, *app, &renderer, root.as_deref(), scopes)?);
+    }
+
+    if entries.is_empty() {
+        println!("{}", t!("prompt.status.none"));
+        return Ok(());
+    }
+    for entry in &entries {
+        println!(
+            "{:<10}  {:<11}  {} ({})",
+            entry.state.label(),
+            entry.app.name(),
+            entry.path.display(),
+            entry.templates.join(", ")
+        );
+    }
+    let drifted = entries
+        .iter()
+        .filter(|e| e.state != DriftState::UpToDate)
+        .count();
+    println!(
+        "{}",
+        t!(
+            "prompt.status.summary",
+            total = entries.len(),
+            up_to_date = entries.len() - drifted,
+            drifted = drifted
+        )
+    );
+
+    if check && drifted > 0 {
+        return Err(anyhow!(t!("prompt.status.check_failed", count = drifted)));
+    }
+    Ok(())
+}
+
+/// Drift entries for every generated target of `app` in `scopes`.
+pub fn scan_app(
+    config: &Config,
+    app: PromptApp,
+    renderer: &PromptRenderer,
+    root: Option<&Path>,
+    scopes: &[PromptScopeArg],
+) -> Result<Vec<DriftEntry>> {
+    let mut entries = Vec::new();
+    if app == PromptApp::Cursor {
+        if let Some(root) = root
+            && scopes.contains(&PromptScopeArg::Project)
+        {
+            entries.extend(scan_cursor_rules(config, renderer, root)?);
+        }
+        return Ok(entries);
+    }
+
+    for (scope, path) in managed_block_targets(app, scopes, root)? {
+        if let Some(entry) = check_managed_block(config, app, renderer, scope, &path)? {
+            entries.push(entry);
+        }
+    }
+    Ok(entries)
+}
+
+/// Files that may hold a managed block for `app` (`AGENTS.md`, `AGENTS.override.md`,
+/// `CLAUDE.md`), whether or not they exist.
+pub fn managed_block_targets(
+    app: PromptApp,
+    scopes: &[PromptScopeArg],
+    root: Option<&Path>,
+) -> Result<Vec<(PromptScopeArg, PathBuf)>> {
+    let mut targets = Vec::new();
+    for scope in [PromptScopeArg::Global, PromptScopeArg::Project] {
+        if !scopes.contains(&scope) {
+            continue;
+        }
+        let Some(path) = managed_block_path(app, scope, root)? else {
+            continue;
+        };
+        if app == PromptApp::Codex {
+            targets.push((scope, path.with_file_name(CODEX_AGENTS_OVERRIDE_FILE)));
+        }
+        targets.push((scope, path));
+    }
+    targets.sort_by(|a, b| a.1.cmp(&b.1));
+    Ok(targets)
+}
+
+fn check_managed_block(
+    config: &Config,
+    app: PromptApp,
+    renderer: &PromptRenderer,
+    scope: PromptScopeArg,
+    path: &Path,
+) -> Result<Option<DriftEntry>> {
+    let Ok(content) = fs::read_to_string(path) else {
+        return Ok(None);
+    };
+    let Some(block) = extract_managed_block(&content) else {
+        return Ok(None);
+    };
+    let (provenance, body) = split_provenance(&block)?;
+    let (preamble, sections) = split_sections(&body);
+    let templates = sections.iter().map(|s| s.name.clone()).collect();
+    let edited = provenance.as_ref().map(|p| p.body_edited(&body));
+
+    let state = if edited == Some(true) || sections.is_empty() || !preamble.trim().is_empty() {
+        DriftState::Modified
+    } else {
+        let mut orphaned = false;
+        let mut sources = Vec::new();
+        for section in &sections {
+            let renderings = section_renderings(config, app, renderer, scope, &section.name)?;
+            if renderings.is_empty() {
+                orphaned = true;
+            } else if !renderings
+                .iter()
+                .any(|r| r.text.trim() == section.body.trim())
+            {
+                sources.extend(renderings.into_iter().map(|r| r.source));
+            }
+        }
+        if orphaned {
+            DriftState::Orphaned
+        } else if edited.is_some() && !sources.is_empty() {
+            // The body is exactly what `gen` wrote, so the templates moved on.
+            DriftState::Stale
+        } else {
+            classify_difference(path, &sources)
+        }
+    };
+
+    Ok(Some(DriftEntry {
+        app,
+        path: path.to_path_buf(),
+        templates,
+        state,
+    }))
+}
+
+/// Renderings of `name` from the app store and the shared store, whichever exist.
+fn section_renderings(
+    config: &Config,
+    app: PromptApp,
+    renderer: &PromptRenderer,
+    scope: PromptScopeArg,
+    name: &str,
+) -> Result<Vec<Rendering>> {
+    let mut out = Vec::new();
+    let app_source = config.rule_file_path(app.name(), name);
+    if app_source.exists() {
+        let raw = prompt_store::read_template(config, app.name(), name)?;
+        out.push(Rendering {
+            text: renderer.render_app_template(name, &raw, app.name(), scope.as_str())?,
+            source: app_source,
+        });
+    }
+    let shared_source = config.rule_file_path(SHARED_APP, name);
+    if shared_source.exists() {
+        let raw = prompt_store::read_template(config, SHARED_APP, name)?;
+        let template = SharedTemplate::parse(name, &raw)?;
+        // A template that no longer targets this app would be dropped by `gen`.
+        let text = template
+            .render_for(app.name(), renderer, scope.as_str())?
+            .unwrap_or_default();
+        out.push(Rendering {
+            text,
+            source: shared_source,
+        });
+    }
+    Ok(out)
+}
+
+/// `.cursor/rules/*.mdc` files whose stem names a Cursor or shared template.
+fn scan_cursor_rules(
+    config: &Config,
+    renderer: &PromptRenderer,
+    root: &Path,
+) -> Result<Vec<DriftEntry>> {
+    let mut entries = Vec::new();
+    let rules_dir = root.join(TARGET_CURSOR_RULES_DIR);
+    let Ok(read_dir) = fs::read_dir(&rules_dir) else {
+        return Ok(entries);
+    };
+    let mut paths: Vec<PathBuf> = read_dir
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.is_file() && p.extension().and_then(|e| e.to_str()) == Some("mdc"))
+        .collect();
+    paths.sort();
+
+    for path in paths {
+        let Some(name) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        let mut renderings = Vec::new();
+        let cursor_source = config.rule_file_path(CURSOR_APP, name);
+        if cursor_source.exists() {
+            let raw = prompt_store::read_template(config, CURSOR_APP, name)?;
+            renderings.push(Rendering {
+                text: renderer.render_app_template(name, &raw, CURSOR_APP, PROJECT_SCOPE)?,
+                source: cursor_source,
+            });
+        }
+        let shared_source = config.rule_file_path(SHARED_APP, name);
+        if shared_source.exists() {
+            let raw = prompt_store::read_template(config, SHARED_APP, name)?;
+            if let Some(text) = SharedTemplate::parse(name, &raw)?.render_cursor_rule(renderer)? {
+                renderings.push(Rendering {
+                    text,
+                    source: shared_source,
+                });
+            }
+        }
+        if renderings.is_empty() {
+            // Not generated by llman.
+            continue;
+        }
+
+        let content = fs::read_to_string(&path)?;
+        // Frontmatter set by `gen --description/--globs/--always-apply` is not drift.
+        let body = CursorRule::parse(&content).body;
+        let state = if renderings.iter().any(|r| {
+            r.text.trim_end() == content.trim_end()
+                || CursorRule::parse(&r.text).body.trim() == body.trim()
+        }) {
+            DriftState::UpToDate
+        } else {
+            let sources: Vec<PathBuf> = renderings.into_iter().map(|r| r.source).collect();
+            classify_difference(&path, &sources)
+        };
+        entries.push(DriftEntry {
+            app: PromptApp::Cursor,
+            path: path.clone(),
+            templates: vec![name.to_string()],
+            state,
+        });
+    }
+    Ok(entries)
+}
+
+/// Without a provenance line, the newer side decides: a template edited after the target
+/// was written means stale, otherwise the target itself was changed.
+fn classify_difference(target: &Path, sources: &[PathBuf]) -> DriftState {
+    if sources.is_empty() {
+        return DriftState::UpToDate;
+    }
+    let target_time = modified_time(target);
+    let newest_source = sources.iter().filter_map(|s| modified_time(s)).max();
+    match (newest_source, target_time) {
+        (Some(source), Some(target)) if source > target => DriftState::Stale,
+        _ => DriftState::Modified,
+    }
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Text between the llman prompt markers, if both are present.
+pub fn extract_managed_block(content: &str) -> Option<String> {
+    let mut inside = false;
+    let mut lines = Vec::new();
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if !inside && trimmed == LLMAN_PROMPTS_MARKER_START {
+            inside = true;
+            continue;
+        }
+        if inside && trimmed == LLMAN_PROMPTS_MARKER_END {
+            return Some(lines.join("\n"));
+        }
+        if inside {
+            lines.push(line);
+        }
+    }
+    None
+}
+
+/// Split a managed block into text before the first section and its sections.
+pub fn split_sections(block: &str) -> (String, Vec<BlockSection>) {
+    let mut preamble = Vec::new();
+    let mut sections: Vec<BlockSection> = Vec::new();
+    for line in block.lines() {
+        if let Some(name) = line.strip_prefix(SECTION_PREFIX) {
+            sections.push(BlockSection {
+                name: name.trim().to_string(),
+                body: String::new(),
+            });
+            continue;
+        }
+        match sections.last_mut() {
+            Some(section) => {
+                section.body.push_str(line);
+                section.body.push('\n');
+            }
+            None => preamble.push(line),
+        }
+    }
+    (preamble.join("\n"), sections)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{CODEX_APP, override_runtime_config_dir};
+    use crate::test_utils::TestProcess;
+    use tempfile::TempDir;
+
+    #[test]
+    fn split_sections_reads_names_and_bodies() {
+        let block = "## llman prompts: a\n\nFirst.\n\n## llman prompts: b\n\nSecond.";
+        let (preamble, sections) = split_sections(block);
+        assert!(preamble.is_empty());
+        assert_eq!(sections.len(), 2);
+        assert_eq!(sections[0].name, "a");
+        assert_eq!(sections[0].body.trim(), "First.");
+        assert_eq!(sections[1].body.trim(), "Second.");
+
+        let content = "intro\n<!-- LLMAN-PROMPTS:START -->\nx\n<!-- LLMAN-PROMPTS:END -->\n";
+        assert_eq!(extract_managed_block(content).as_deref(), Some("x"));
+        assert_eq!(extract_managed_block("no markers"), None);
+    }
+
+    #[test]
+    fn managed_block_states() {
+        let _proc = TestProcess::new();
+        let temp = TempDir::new().unwrap();
+        let _guard = override_runtime_config_dir(temp.path().join("cfg"));
+        let config = Config::new().unwrap();
+        let renderer = PromptRenderer::default();
+        prompt_store::upsert_template(&config, CODEX_APP, "rules", "Be brief.\n").unwrap();
+
+        let root = temp.path().join("repo");
+        fs::create_dir_all(&root).unwrap();
+        let agents = root.join("AGENTS.md");
+        let write_block = |body: &str| {
+            fs::write(
+                &agents,
+                format!(
+                    "# Notes\n\n<!-- LLMAN-PROMPTS:START -->\n{body}\n<!-- LLMAN-PROMPTS:END -->\n"
+                ),
+            )
+            .unwrap();
+        };
+        let state = || {
+            check_managed_block(
+                &config,
+                PromptApp::Codex,
+                &renderer,
+                PromptScopeArg::Project,
+                &agents,
+            )
+            .unwrap()
+            .unwrap()
+            .state
+        };
+
+        write_block("## llman prompts: rules\n\nBe brief.");
+        assert_eq!(state(), DriftState::UpToDate);
+
+        write_block("## llman prompts: rules\n\nBe very brief.");
+        assert_eq!(state(), DriftState::Modified);
+
+        // Template edited after the target was written.
+        let past = SystemTime::now() - std::time::Duration::from_secs(60);
+        fs::File::options()
+            .write(true)
+            .open(&agents)
+            .unwrap()
+            .set_modified(past)
+            .unwrap();
+        assert_eq!(state(), DriftState::Stale);
+
+        write_block("## llman prompts: gone\n\nOld text.");
+        assert_eq!(state(), DriftState::Orphaned);
+    }
+}
@@ -0,0 +1,318 @@
+//! Append-only history for the prompt template store.
+//!
+//! Every write through `prompts::store` appends to `prompt/.history/journal.jsonl` and keeps
+//! the content under `prompt/.history/objects/<sha256>`. Revisions are numbered per template
+//! from 1; a template that predates the history gets its current content recorded as a
+//! `baseline` revision before its first tracked change, so deletes are always recoverable.
+
+use crate::config::Config;
+use crate::fs_utils::atomic_write_with_mode;
+use anyhow::{Context, Result, anyhow, bail};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+
+pub const HISTORY_DIR: &str = ".history";
+const JOURNAL_FILE: &str = "journal.jsonl";
+const OBJECTS_DIR: &str = "objects";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HistoryAction {
+    Baseline,
+    Upsert,
+    Remove,
+    Restore,
+}
+
+impl HistoryAction {
+    pub fn label(self) -> &'static str {
+        match self {
+            HistoryAction::Baseline => "baseline",
+            HistoryAction::Upsert => "upsert",
+            HistoryAction::Remove => "remove",
+            HistoryAction::Restore => "restore",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub app: String,
+    pub name: String,
+    pub rev: u32,
+    pub action: HistoryAction,
+    /// Content digest; `None` for removals.
+    pub digest: Option<String>,
+    pub timestamp: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub restored_from: Option<u32>,
+}
+
+fn history_dir(config: &Config) -> PathBuf {
+    config.prompt_dir().join(HISTORY_DIR)
+}
+
+fn content_digest(content: &str) -> String {
+    Sha256::digest(content.as_bytes())
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// Journal entries for one template, oldest first.
+pub fn entries(config: &Config, app: &str, name: &str) -> Result<Vec<HistoryEntry>> {
+    let path = history_dir(config).join(JOURNAL_FILE);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let journal = fs::read_to_string(&path)
+        .with_context(|| t!("prompt.history.error.read_failed", path = path.display()))?;
+    let mut out = Vec::new();
+    for (idx, line) in journal.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let entry: HistoryEntry = serde_json::from_str(line).map_err(|e| {
+            anyhow!(t!(
+                "prompt.history.error.invalid_entry",
+                path = path.display(),
+                line = idx + 1,
+                error = e
+            ))
+        })?;
+        if entry.app == app && entry.name == name {
+            out.push(entry);
+        }
+    }
+    Ok(out)
+}
+
+/// Content recorded for `entry`; `None` for removals.
+pub fn read_revision(config: &Config, entry: &HistoryEntry) -> Result<Option<String>> {
+    let Some(digest) = &entry.digest else {
+        return Ok(None);
+    };
+    let path = history_dir(config).join(OBJECTS_DIR).join(digest);
+    fs::read_to_string(&path).map(Some).with_context(|| {
+        t!(
+            "prompt.history.error.read_object_failed",
+            path = path.display()
+        )
+    })
+}
+
+/// Look up revision `rev` of a template.
+pub fn find_revision(config: &Config, app: &str, name: &str, rev: u32) -> Result<HistoryEntry> {
+    entries(config, app, name)?
+        .into_iter()
+        .find(|entry| entry.rev == rev)
+        .ok_or_else(|| {
+            anyhow!(t!(
+                "prompt.history.error.unknown_revision",
+                rev = rev,
+                app = app,
+                name = name
+            ))
+        })
+}
+
+/// Record `previous` as a baseline revision unless it is already the latest recorded content.
+///
+/// Call it before overwriting or deleting a template, so the old content is saved even when
+/// the write succeeds and recording the write itself fails.
+pub fn record_baseline(
+    config: &Config,
+    app: &str,
+    name: &str,
+    previous: Option<&str>,
+) -> Result<()> {
+    let Some(previous) = previous else {
+        return Ok(());
+    };
+    let existing = entries(config, app, name)?;
+    let last = existing.last();
+    if last.and_then(|entry| entry.digest.as_deref()) == Some(content_digest(previous).as_str()) {
+        return Ok(());
+    }
+    // Content that was never recorded (pre-history file, or edited by hand).
+    let rev = last.map_or(1, |entry| entry.rev + 1);
+    append(
+        config,
+        app,
+        name,
+        rev,
+        HistoryAction::Baseline,
+        Some(previous),
+        None,
+    )
+}
+
+/// Record a store write. `previous` is the content before the write (if the file existed),
+/// `content` the content after it (`None` for removals). Unchanged upserts are not recorded.
+pub fn record(
+    config: &Config,
+    app: &str,
+    name: &str,
+    previous: Option<&str>,
+    action: HistoryAction,
+    content: Option<&str>,
+    restored_from: Option<u32>,
+) -> Result<()> {
+    record_baseline(config, app, name, previous)?;
+    if action == HistoryAction::Upsert && previous.is_some_and(|previous| Some(previous) == content)
+    {
+        return Ok(());
+    }
+    let next_rev = entries(config, app, name)?
+        .last()
+        .map_or(1, |entry| entry.rev + 1);
+    append(config, app, name, next_rev, action, content, restored_from)
+}
+
+fn append(
+    config: &Config,
+    app: &str,
+    name: &str,
+    rev: u32,
+    action: HistoryAction,
+    content: Option<&str>,
+    restored_from: Option<u32>,
+) -> Result<()> {
+    let dir = history_dir(config);
+    fs::create_dir_all(&dir).with_context(|| {
+        t!(
+            "prompt.history.error.create_dir_failed",
+            path = dir.display()
+        )
+    })?;
+    let digest = match content {
+        Some(content) => {
+            let digest = content_digest(content);
+            let objects = dir.join(OBJECTS_DIR);
+            fs::create_dir_all(&objects).with_context(|| {
+                t!(
+                    "prompt.history.error.create_dir_failed",
+                    path = objects.display()
+                )
+            })?;
+            let object = objects.join(&digest);
+            if !object.exists() {
+                atomic_write_with_mode(&object, content.as_bytes(), None).with_context(|| {
+                    t!("prompt.history.error.write_failed", path = object.display())
+                })?;
+            }
+            Some(digest)
+        }
+        None => None,
+    };
+
+    let entry = HistoryEntry {
+        app: app.to_string(),
+        name: name.to_string(),
+        rev,
+        action,
+        digest,
+        timestamp: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+        restored_from,
+    };
+    let mut line = serde_json::to_string(&entry)?;
+    line.push('\n');
+    let journal_path = dir.join(JOURNAL_FILE);
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&journal_path)
+        .and_then(|mut journal| journal.write_all(line.as_bytes()))
+        .with_context(|| {
+            t!(
+                "prompt.history.error.write_failed",
+                path = journal_path.display()
+            )
+        })?;
+    Ok(())
+}
+
+/// Parse `<name>@<rev>`.
+pub fn parse_revision_spec(spec: &str) -> Result<(String, u32)> {
+    let Some((name, rev)) = spec.rsplit_once('@') else {
+        bail!(t!("prompt.history.error.invalid_spec", spec = spec));
+    };
+    let rev = rev.parse::<u32>().map_err(|_| {
+        anyhow!(t!(
+            "prompt.history.error.invalid_revision",
+            rev = rev,
+            spec = spec
+        ))
+    })?;
+    if name.is_empty() {
+        bail!(t!("prompt.history.error.invalid_spec", spec = spec));
+    }
+    Ok((name.to_string(), rev))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{CODEX_APP, override_runtime_config_dir};
+    use crate::prompts::store as prompt_store;
+    use crate::test_utils::TestProcess;
+    use tempfile::TempDir;
+
+    #[test]
+    fn store_writes_are_journaled_and_restorable() {
+        let _proc = TestProcess::new();
+        let temp = TempDir::new().unwrap();
+        let _guard = override_runtime_config_dir(temp.path().join("cfg"));
+        let config = Config::new().unwrap();
+
+        // A template written before history existed becomes a baseline revision.
+        let path = config.rule_file_path(CODEX_APP, "rules");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "v0\n").unwrap();
+
+        prompt_store::upsert_template(&config, CODEX_APP, "rules", "v1\n").unwrap();
+        prompt_store::upsert_template(&config, CODEX_APP, "rules", "v1\n").unwrap();
+        prompt_store::remove_template(&config, CODEX_APP, "rules").unwrap();
+
+        let log = entries(&config, CODEX_APP, "rules").unwrap();
+        let actions: Vec<_> = log.iter().map(|e| (e.rev, e.action)).collect();
+        assert_eq!(
+            actions,
+            vec![
+                (1, HistoryAction::Baseline),
+                (2, HistoryAction::Upsert),
+                (3, HistoryAction::Remove)
+            ]
+        );
+
+        assert!(prompt_store::restore_template(&config, CODEX_APP, "rules", 3).is_err());
+        prompt_store::restore_template(&config, CODEX_APP, "rules", 1).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "v0\n");
+        let last = entries(&config, CODEX_APP, "rules").unwrap().pop().unwrap();
+        assert_eq!(last.action, HistoryAction::Restore);
+        assert_eq!(last.restored_from, Some(1));
+
+        // Hand edits are saved as a baseline once, before the store touches the file.
+        record_baseline(&config, CODEX_APP, "rules", Some("hand edit\n")).unwrap();
+        record_baseline(&config, CODEX_APP, "rules", Some("hand edit\n")).unwrap();
+        let log = entries(&config, CODEX_APP, "rules").unwrap();
+        let baseline = log.last().unwrap();
+        assert_eq!(
+            (baseline.rev, baseline.action),
+            (5, HistoryAction::Baseline)
+        );
+        assert_eq!(
+            read_revision(&config, baseline).unwrap().as_deref(),
+            Some("hand edit\n")
+        );
+
+        assert_eq!(
+            parse_revision_spec("rules@2").unwrap(),
+            ("rules".to_string(), 2)
+        );
+        assert!(parse_revision_spec("rules").is_err());
+    }
+}
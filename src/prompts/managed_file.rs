@@ -4,7 +4,9 @@ use crate::managed_block::{
 };
 use crate::path_utils::safe_parent_for_creation;
 use crate::prompts::confirm::confirm_inject_twice;
-use anyhow::{Result, anyhow};
+use crate::prompts::drift::{SECTION_PREFIX, extract_managed_block, split_sections};
+use crate::prompts::provenance::{split_provenance, stamp};
+use anyhow::{Result, anyhow, bail};
 use std::fs;
 use std::path::Path;
 
@@ -43,3 +45,150 @@ pub fn write_llman_managed_block(
 
     Ok(true)
 }
+
+/// Write a block produced by `gen`, keeping the sections other stores put in the existing one.
+///
+/// `llman prompts gen` (shared templates) and `x codex|cc prompts gen` (per-app templates)
+/// share one managed block; the provenance records which store each section came from.
+pub fn write_generated_block(
+    path: &Path,
+    block: &str,
+    force: bool,
+    interactive: bool,
+) -> Result<bool> {
+    let existing = fs::read_to_string(path).unwrap_or_default();
+    let block = merge_foreign_sections(path, &existing, block, force)?;
+    write_llman_managed_block(path, &block, force, interactive)
+}
+
+/// `block` plus the sections of `existing`'s managed block that came from another store.
+///
+/// A foreign section whose name `block` also writes is only dropped with `force`.
+fn merge_foreign_sections(path: &Path, existing: &str, block: &str, force: bool) -> Result<String> {
+    let Some(old_block) = extract_managed_block(existing) else {
+        return Ok(block.to_string());
+    };
+    let (Some(old), old_body) = split_provenance(&old_block)? else {
+        return Ok(block.to_string());
+    };
+    let (Some(new), new_body) = split_provenance(block)? else {
+        return Ok(block.to_string());
+    };
+
+    let own_source = |source: &str| new.templates.iter().any(|r| r.source == source);
+    let (_, old_sections) = split_sections(&old_body);
+    let mut kept_records = Vec::new();
+    let mut kept_sections = Vec::new();
+    let mut replaced = Vec::new();
+    for record in old.templates.iter().filter(|r| !own_source(&r.source)) {
+        let Some(section) = old_sections.iter().find(|s| s.name == record.name) else {
+            continue;
+        };
+        if new.templates.iter().any(|r| r.name == record.name) {
+            replaced.push(format!("{}/{}", record.source, record.name));
+            continue;
+        }
+        kept_records.push(record.clone());
+        kept_sections.push(format!(
+            "{SECTION_PREFIX}{}\n{}",
+            section.name,
+            section.body.trim_end()
+        ));
+    }
+    if !replaced.is_empty() && !force {
+        bail!(t!(
+            "prompt.gen.foreign_sections",
+            path = path.display(),
+            sections = replaced.join(", ")
+        ));
+    }
+    if kept_records.is_empty() {
+        return Ok(block.to_string());
+    }
+
+    // Per-app sections come first, shared ones after, whichever `gen` ran last.
+    let new_first = new
+        .templates
+        .iter()
+        .all(|r| r.source != crate::config::SHARED_APP);
+    let mut records = new.templates.clone();
+    let mut sections = vec![new_body.trim().to_string()];
+    if new_first {
+        records.extend(kept_records);
+        sections.extend(kept_sections);
+    } else {
+        records.splice(0..0, kept_records);
+        sections.splice(0..0, kept_sections);
+    }
+    Ok(stamp(&new.app, &new.scope, records, &sections.join("\n\n")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prompts::provenance::TemplateRecord;
+
+    fn file_with(block: &str) -> String {
+        format!("# Notes\n\n{LLMAN_PROMPTS_MARKER_START}\n{block}\n{LLMAN_PROMPTS_MARKER_END}\n")
+    }
+
+    #[test]
+    fn generated_blocks_keep_sections_from_other_stores() {
+        let path = Path::new("AGENTS.md");
+        let codex = stamp(
+            "codex",
+            "project",
+            vec![TemplateRecord::new("ci", "codex", "CI rules.")],
+            "## llman prompts: ci\n\nCI rules.",
+        );
+        let shared = stamp(
+            "codex",
+            "project",
+            vec![TemplateRecord::new("house", "shared", "House rules.")],
+            "## llman prompts: house\n\nHouse rules.",
+        );
+
+        let merged = merge_foreign_sections(path, &file_with(&codex), &shared, false).unwrap();
+        let (provenance, body) = split_provenance(&merged).unwrap();
+        let provenance = provenance.unwrap();
+        let sources: Vec<&str> = provenance
+            .templates
+            .iter()
+            .map(|r| r.source.as_str())
+            .collect();
+        assert_eq!(sources, vec!["codex", "shared"]);
+        assert_eq!(
+            body,
+            "## llman prompts: ci\n\nCI rules.\n\n## llman prompts: house\n\nHouse rules."
+        );
+        assert!(!provenance.body_edited(&body));
+
+        // Re-running the per-app generator keeps the shared section and the order.
+        let again = merge_foreign_sections(path, &file_with(&merged), &codex, false).unwrap();
+        assert_eq!(again, merged);
+    }
+
+    #[test]
+    fn generated_block_needs_force_to_replace_another_stores_section() {
+        let path = Path::new("AGENTS.md");
+        let codex = stamp(
+            "codex",
+            "project",
+            vec![TemplateRecord::new("house", "codex", "Codex rules.")],
+            "## llman prompts: house\n\nCodex rules.",
+        );
+        let shared = stamp(
+            "codex",
+            "project",
+            vec![TemplateRecord::new("house", "shared", "House rules.")],
+            "## llman prompts: house\n\nHouse rules.",
+        );
+
+        let err = merge_foreign_sections(path, &file_with(&codex), &shared, false).unwrap_err();
+        assert!(err.to_string().contains("codex/house"), "{err}");
+        assert_eq!(
+            merge_foreign_sections(path, &file_with(&codex), &shared, true).unwrap(),
+            shared
+        );
+    }
+}
@@ -1,4 +1,12 @@
+pub mod command;
 pub mod confirm;
+pub mod cursor_rule;
+pub mod drift;
+pub mod history;
 pub mod managed_file;
 pub mod paths;
+pub mod provenance;
+pub mod regen;
+pub mod render;
+pub mod shared;
 pub mod store;
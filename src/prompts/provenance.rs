@@ -0,0 +1,117 @@
+//! Provenance line at the top of generated managed blocks.
+//!
+//! `gen` writes one HTML comment recording the llman version, app, scope, each template's
+//! store (`codex`, `claude-code` or `shared`) and content digest, and a digest of the body
+//! it wrote. `status` uses it to tell hand edits from template changes, `regen` re-renders
+//! exactly the recorded templates.
+
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+const PROVENANCE_PREFIX: &str = "<!-- llman:provenance ";
+const PROVENANCE_SUFFIX: &str = " -->";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Provenance {
+    pub llman: String,
+    pub app: String,
+    pub scope: String,
+    pub templates: Vec<TemplateRecord>,
+    /// Digest of the block body below the provenance line.
+    pub body: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TemplateRecord {
+    pub name: String,
+    /// Store the template was read from (`prompt/<source>/`).
+    pub source: String,
+    pub digest: String,
+}
+
+impl TemplateRecord {
+    pub fn new(name: &str, source: &str, raw: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            source: source.to_string(),
+            digest: digest(raw),
+        }
+    }
+}
+
+/// Short SHA-256 hex digest of `content`.
+pub fn digest(content: &str) -> String {
+    let hash = Sha256::digest(content.as_bytes());
+    hash.iter()
+        .take(8)
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// Prefix `body` with a provenance line for `templates`.
+pub fn stamp(app: &str, scope: &str, templates: Vec<TemplateRecord>, body: &str) -> String {
+    let body = body.trim();
+    let provenance = Provenance {
+        llman: env!("CARGO_PKG_VERSION").to_string(),
+        app: app.to_string(),
+        scope: scope.to_string(),
+        templates,
+        body: digest(body),
+    };
+    let json = serde_json::to_string(&provenance).expect("provenance serializes");
+    format!("{PROVENANCE_PREFIX}{json}{PROVENANCE_SUFFIX}\n{body}")
+}
+
+/// Split a managed block into its provenance (if any) and the body below it.
+pub fn split_provenance(block: &str) -> Result<(Option<Provenance>, String)> {
+    let trimmed = block.trim_start();
+    let Some(rest) = trimmed.strip_prefix(PROVENANCE_PREFIX) else {
+        return Ok((None, block.to_string()));
+    };
+    let (line, body) = rest.split_once('\n').unwrap_or((rest, ""));
+    let json = line
+        .trim_end()
+        .strip_suffix(PROVENANCE_SUFFIX.trim_start())
+        .ok_or_else(|| anyhow!(t!("prompt.provenance.unterminated")))?;
+    let provenance = serde_json::from_str(json.trim())
+        .map_err(|e| anyhow!(t!("prompt.provenance.invalid", error = e)))?;
+    Ok((Some(provenance), body.to_string()))
+}
+
+impl Provenance {
+    /// True when the body differs from what `gen` wrote.
+    pub fn body_edited(&self, body: &str) -> bool {
+        digest(body.trim()) != self.body
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stamp_round_trips_and_detects_edits() {
+        let records = vec![TemplateRecord::new("house", "shared", "Use anyhow.\n")];
+        let block = stamp(
+            "codex",
+            "project",
+            records.clone(),
+            "## llman prompts: house\n\nUse anyhow.\n",
+        );
+        assert!(block.starts_with("<!-- llman:provenance {"));
+
+        let (provenance, body) = split_provenance(&block).unwrap();
+        let provenance = provenance.expect("provenance");
+        assert_eq!(provenance.templates, records);
+        assert_eq!(provenance.scope, "project");
+        assert_eq!(provenance.llman, env!("CARGO_PKG_VERSION"));
+        assert!(!provenance.body_edited(&body));
+        assert!(provenance.body_edited(&body.replace("anyhow", "thiserror")));
+
+        let (none, plain) = split_provenance("## llman prompts: x\n\ny").unwrap();
+        assert!(none.is_none());
+        assert_eq!(plain, "## llman prompts: x\n\ny");
+        assert!(split_provenance("<!-- llman:provenance {bad} -->\nx").is_err());
+    }
+}
@@ -0,0 +1,187 @@
+//! `prompts regen` / `prompts ungen`: maintain managed blocks that `gen` already wrote.
+
+use crate::config::{Config, SHARED_APP};
+use crate::fs_utils::atomic_write_with_mode;
+use crate::managed_block::{
+    LLMAN_PROMPTS_MARKER_END, LLMAN_PROMPTS_MARKER_START, remove_text_with_markers,
+};
+use crate::prompts::command::{PromptApp, PromptScopeArg};
+use crate::prompts::drift::{extract_managed_block, managed_block_targets};
+use crate::prompts::managed_file::write_llman_managed_block;
+use crate::prompts::paths::cwd;
+use crate::prompts::provenance::{Provenance, TemplateRecord, split_provenance, stamp};
+use crate::prompts::render::PromptRenderer;
+use crate::prompts::shared::{SharedTemplate, build_managed_body};
+use crate::prompts::store as prompt_store;
+use crate::skills::shared::git::find_git_root;
+use anyhow::{Result, anyhow};
+use inquire::Confirm;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+enum RegenOutcome {
+    Missing,
+    NoProvenance,
+    Unchanged,
+    Updated,
+}
+
+/// Re-render every managed block from the templates recorded in its provenance.
+pub fn run_regen(apps: &[PromptApp], scopes: &[PromptScopeArg]) -> Result<()> {
+    let config = Config::new()?;
+    let root = find_git_root(&cwd()?);
+    let renderer = PromptRenderer::new(&config, root.as_deref())?;
+
+    let mut first_error: Option<anyhow::Error> = None;
+    for app in apps {
+        for (_, path) in managed_block_targets(*app, scopes, root.as_deref())? {
+            match regen_file(&config, *app, &renderer, &path) {
+                Ok(RegenOutcome::Missing) => {}
+                Ok(RegenOutcome::NoProvenance) => println!(
+                    "{}",
+                    t!("prompt.regen.no_provenance", path = path.display())
+                ),
+                Ok(RegenOutcome::Unchanged) => {
+                    println!("{}", t!("prompt.regen.unchanged", path = path.display()))
+                }
+                Ok(RegenOutcome::Updated) => {
+                    println!("{}", t!("messages.rule_generated", path = path.display()))
+                }
+                Err(e) => {
+                    if first_error.is_none() {
+                        first_error = Some(e);
+                    }
+                }
+            }
+        }
+    }
+
+    if let Some(err) = first_error {
+        return Err(err);
+    }
+    Ok(())
+}
+
+fn regen_file(
+    config: &Config,
+    app: PromptApp,
+    renderer: &PromptRenderer,
+    path: &Path,
+) -> Result<RegenOutcome> {
+    let Ok(content) = fs::read_to_string(path) else {
+        return Ok(RegenOutcome::Missing);
+    };
+    let Some(block) = extract_managed_block(&content) else {
+        return Ok(RegenOutcome::Missing);
+    };
+    let (Some(provenance), _) = split_provenance(&block)? else {
+        return Ok(RegenOutcome::NoProvenance);
+    };
+
+    let body = render_recorded(config, app, renderer, &provenance).map_err(|e| {
+        anyhow!(t!(
+            "prompt.regen.failed",
+            path = path.display(),
+            error = format!("{e:#}")
+        ))
+    })?;
+    if body.trim() == block.trim() {
+        return Ok(RegenOutcome::Unchanged);
+    }
+    write_llman_managed_block(path, &body, true, false)?;
+    Ok(RegenOutcome::Updated)
+}
+
+/// Stamped block body for the templates `provenance` records, rendered from the current store.
+fn render_recorded(
+    config: &Config,
+    app: PromptApp,
+    renderer: &PromptRenderer,
+    provenance: &Provenance,
+) -> Result<String> {
+    let scope = provenance.scope.as_str();
+    let mut sections = Vec::new();
+    let mut records = Vec::new();
+    for record in &provenance.templates {
+        let raw = prompt_store::read_template(config, &record.source, &record.name)?;
+        if record.source == SHARED_APP {
+            let template = SharedTemplate::parse(&record.name, &raw)?;
+            let section =
+                build_managed_body(std::slice::from_ref(&template), app.name(), renderer, scope)?;
+            if section.is_empty() {
+                // The template no longer targets this app.
+                continue;
+            }
+            sections.push(section);
+            records.push(template.record());
+        } else {
+            sections.push(prompt_store::build_llman_prompts_body(
+                config,
+                &record.source,
+                std::slice::from_ref(&record.name),
+                renderer,
+                scope,
+            )?);
+            records.push(TemplateRecord::new(&record.name, &record.source, &raw));
+        }
+    }
+    Ok(stamp(app.name(), scope, records, &sections.join("\n\n")))
+}
+
+/// Strip llman managed blocks; files left empty are deleted.
+pub fn run_ungen(
+    apps: &[PromptApp],
+    scopes: &[PromptScopeArg],
+    yes: bool,
+    interactive: bool,
+) -> Result<()> {
+    let root = find_git_root(&cwd()?);
+
+    let mut targets: Vec<(PathBuf, String)> = Vec::new();
+    for app in apps {
+        for (_, path) in managed_block_targets(*app, scopes, root.as_deref())? {
+            let Ok(content) = fs::read_to_string(&path) else {
+                continue;
+            };
+            if let Some(updated) = remove_text_with_markers(
+                &content,
+                LLMAN_PROMPTS_MARKER_START,
+                LLMAN_PROMPTS_MARKER_END,
+            ) {
+                targets.push((path, updated));
+            }
+        }
+    }
+    if targets.is_empty() {
+        println!("{}", t!("prompt.ungen.none"));
+        return Ok(());
+    }
+
+    for (path, _) in &targets {
+        println!("  - {}", path.display());
+    }
+    if !yes {
+        if !interactive {
+            return Err(anyhow!(t!("prompt.ungen.requires_yes")));
+        }
+        let confirmed = Confirm::new(&t!("prompt.ungen.confirm", count = targets.len()))
+            .with_default(false)
+            .prompt()
+            .map_err(|e| anyhow!(t!("errors.interactive_prompt_error", error = e)))?;
+        if !confirmed {
+            println!("{}", t!("messages.operation_cancelled"));
+            return Ok(());
+        }
+    }
+
+    for (path, updated) in targets {
+        if updated.trim().is_empty() {
+            fs::remove_file(&path)?;
+            println!("{}", t!("prompt.ungen.deleted", path = path.display()));
+        } else {
+            atomic_write_with_mode(&path, updated.as_bytes(), None)?;
+            println!("{}", t!("prompt.ungen.removed", path = path.display()));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,282 @@
+//! MiniJinja rendering for prompt templates.
+//!
+//! Templates see these variables:
+//! - `app`: `cursor`, `codex` or `claude-code`
+//! - `scope`: `project` or `global`
+//! - `repo_name`: project root directory name (empty outside a project)
+//! - `language`: `prompts.language` from `.llman/config.yaml` (empty when unset)
+//! - `toolchain`: detected toolchains, comma-separated (e.g. `rust,node`)
+//! - every entry of `prompts.vars` in `.llman/config.yaml`
+//!
+//! Fragments in `prompt/fragments/*.md` can be pulled in with `{% include "<name>" %}`.
+//! Printing an undefined variable fails rendering, so typos surface at `gen` time.
+//!
+//! Shared templates always render. Per-app templates often quote `{{ ... }}` literally (CI
+//! expressions, other template languages), so they only render when their first line is
+//! `{# llman:template #}`; the rest are copied verbatim.
+
+use crate::config::{Config, PROMPT_DIR};
+use crate::config_schema::ProjectPromptsConfig;
+use crate::sdd::project::templates::render_with_units_strict;
+use crate::skills::shared::git::find_git_root;
+use anyhow::{Result, anyhow};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+pub const FRAGMENTS_DIR: &str = "fragments";
+pub const PROJECT_SCOPE: &str = "project";
+pub const GLOBAL_SCOPE: &str = "global";
+/// First line that opts a per-app template into MiniJinja rendering.
+pub const TEMPLATE_MARKER: &str = "{# llman:template #}";
+
+/// Marker files used to detect a project's toolchains.
+const TOOLCHAIN_MARKERS: &[(&str, &str)] = &[
+    ("Cargo.toml", "rust"),
+    ("package.json", "node"),
+    ("pyproject.toml", "python"),
+    ("setup.py", "python"),
+    ("go.mod", "go"),
+    ("pom.xml", "java"),
+    ("build.gradle", "java"),
+    ("build.gradle.kts", "java"),
+    ("Gemfile", "ruby"),
+    ("composer.json", "php"),
+    ("mix.exs", "elixir"),
+];
+
+/// Only the `prompts` section of the project config matters here.
+#[derive(Debug, Default, Deserialize)]
+struct PromptsSection {
+    #[serde(default)]
+    prompts: Option<ProjectPromptsConfig>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PromptRenderer {
+    fragments: BTreeMap<String, String>,
+    vars: BTreeMap<String, String>,
+}
+
+impl PromptRenderer {
+    /// Renderer for templates generated into `project_root` (or outside any project).
+    pub fn new(config: &Config, project_root: Option<&Path>) -> Result<Self> {
+        let mut vars = BTreeMap::new();
+        let repo_name = project_root
+            .and_then(|root| root.file_name())
+            .and_then(|name| name.to_str())
+            .unwrap_or_default();
+        vars.insert("repo_name".to_string(), repo_name.to_string());
+        vars.insert(
+            "toolchain".to_string(),
+            project_root
+                .map(|root| detect_toolchains(root).join(","))
+                .unwrap_or_default(),
+        );
+
+        let prompts = match project_root {
+            Some(root) => read_project_prompts(root)?,
+            None => ProjectPromptsConfig::default(),
+        };
+        vars.insert("language".to_string(), prompts.language.unwrap_or_default());
+        vars.extend(prompts.vars);
+
+        Ok(Self {
+            fragments: load_fragments(config)?,
+            vars,
+        })
+    }
+
+    /// Renderer for the repository containing `dir`, if any.
+    pub fn for_dir(config: &Config, dir: &Path) -> Result<Self> {
+        Self::new(config, find_git_root(dir).as_deref())
+    }
+
+    /// Render template `name` for one app and scope.
+    pub fn render(&self, name: &str, raw: &str, app: &str, scope: &str) -> Result<String> {
+        let mut vars = self.vars.clone();
+        vars.insert("app".to_string(), app.to_string());
+        vars.insert("scope".to_string(), scope.to_string());
+        render_with_units_strict(name, raw, &self.fragments, &vars).map_err(|e| {
+            anyhow!(t!(
+                "prompt.render.failed",
+                name = name,
+                app = app,
+                scope = scope,
+                error = format!("{e:#}")
+            ))
+        })
+    }
+
+    /// Render a per-app template when it starts with `TEMPLATE_MARKER`, else return it as-is.
+    pub fn render_app_template(
+        &self,
+        name: &str,
+        raw: &str,
+        app: &str,
+        scope: &str,
+    ) -> Result<String> {
+        let (first, rest) = raw.split_once('\n').unwrap_or((raw, ""));
+        if first.trim() != TEMPLATE_MARKER {
+            return Ok(raw.to_string());
+        }
+        self.render(name, rest, app, scope)
+    }
+}
+
+pub fn detect_toolchains(root: &Path) -> Vec<&'static str> {
+    let mut found: Vec<&'static str> = Vec::new();
+    for (marker, toolchain) in TOOLCHAIN_MARKERS {
+        if root.join(marker).is_file() && !found.contains(toolchain) {
+            found.push(toolchain);
+        }
+    }
+    found
+}
+
+fn read_project_prompts(root: &Path) -> Result<ProjectPromptsConfig> {
+    let path = root.join(".llman").join("config.yaml");
+    if !path.exists() {
+        return Ok(ProjectPromptsConfig::default());
+    }
+    let content = fs::read_to_string(&path).map_err(|e| {
+        anyhow!(t!(
+            "prompt.render.project_config_read_failed",
+            path = path.display(),
+            error = e
+        ))
+    })?;
+    let section: PromptsSection = serde_yaml::from_str(&content).map_err(|e| {
+        anyhow!(t!(
+            "prompt.render.project_config_parse_failed",
+            path = path.display(),
+            error = e
+        ))
+    })?;
+    Ok(section.prompts.unwrap_or_default())
+}
+
+/// Fragments keyed by file stem (and by file name, so `include "x.md"` works too).
+fn load_fragments(config: &Config) -> Result<BTreeMap<String, String>> {
+    let dir = config.config_dir().join(PROMPT_DIR).join(FRAGMENTS_DIR);
+    let mut fragments = BTreeMap::new();
+    if !dir.exists() {
+        return Ok(fragments);
+    }
+    let entries = fs::read_dir(&dir).map_err(|e| {
+        anyhow!(t!(
+            "prompt.render.fragment_read_failed",
+            path = dir.display(),
+            error = e
+        ))
+    })?;
+    for entry in entries {
+        let path = entry?.path();
+        if !path.is_file() || path.extension().and_then(|e| e.to_str()) != Some("md") {
+            continue;
+        }
+        let (Some(stem), Some(file_name)) = (
+            path.file_stem().and_then(|s| s.to_str()),
+            path.file_name().and_then(|s| s.to_str()),
+        ) else {
+            continue;
+        };
+        let content = fs::read_to_string(&path).map_err(|e| {
+            anyhow!(t!(
+                "prompt.render.fragment_read_failed",
+                path = path.display(),
+                error = e
+            ))
+        })?;
+        fragments.insert(file_name.to_string(), content.clone());
+        fragments.insert(stem.to_string(), content);
+    }
+    Ok(fragments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn renderer(temp: &TempDir) -> PromptRenderer {
+        let config = Config::with_config_dir(Some(temp.path().join("cfg").to_str().unwrap()))
+            .expect("config");
+        let fragments = config.config_dir().join(PROMPT_DIR).join(FRAGMENTS_DIR);
+        fs::create_dir_all(&fragments).unwrap();
+        fs::write(
+            fragments.join("testing.md"),
+            "Run tests for {{ repo_name }}.",
+        )
+        .unwrap();
+
+        let root = temp.path().join("demo");
+        fs::create_dir_all(root.join(".llman")).unwrap();
+        fs::write(root.join("Cargo.toml"), "").unwrap();
+        fs::write(
+            root.join(".llman").join("config.yaml"),
+            "version: \"0.1\"\nprompts:\n  language: English\n  vars:\n    team: core\n",
+        )
+        .unwrap();
+        PromptRenderer::new(&config, Some(&root)).expect("renderer")
+    }
+
+    #[test]
+    fn renders_vars_includes_and_scope_conditionals() {
+        let temp = TempDir::new().unwrap();
+        let renderer = renderer(&temp);
+        let raw = "{{ language }}/{{ team }}/{{ toolchain }}\n{% include \"testing\" %}\n{% if scope == \"project\" %}project-only{% endif %}";
+
+        let project = renderer.render("rules", raw, "codex", "project").unwrap();
+        assert_eq!(
+            project,
+            "English/core/rust\nRun tests for demo.\nproject-only"
+        );
+
+        let global = renderer.render("rules", raw, "codex", "global").unwrap();
+        assert!(!global.contains("project-only"));
+    }
+
+    #[test]
+    fn app_templates_render_only_when_marked() {
+        let temp = TempDir::new().unwrap();
+        let renderer = renderer(&temp);
+        let literal = "Use ${{ secrets.TOKEN }} in workflows.\n";
+        assert_eq!(
+            renderer
+                .render_app_template("ci", literal, "codex", "project")
+                .unwrap(),
+            literal
+        );
+
+        let marked = format!("{TEMPLATE_MARKER}\nRepo: {{{{ repo_name }}}}");
+        assert_eq!(
+            renderer
+                .render_app_template("ci", &marked, "codex", "project")
+                .unwrap(),
+            "Repo: demo"
+        );
+    }
+
+    #[test]
+    fn undefined_variables_are_errors() {
+        let temp = TempDir::new().unwrap();
+        let renderer = renderer(&temp);
+        let err = renderer
+            .render("rules", "Hi {{ tema }}", "cursor", "project")
+            .unwrap_err();
+        assert!(err.to_string().contains("rules"), "{err}");
+        assert!(format!("{err:#}").contains("undefined"), "{err:#}");
+
+        let ok = renderer
+            .render(
+                "rules",
+                "{% if tema is defined %}x{% endif %}ok",
+                "cursor",
+                "project",
+            )
+            .unwrap();
+        assert_eq!(ok, "ok");
+    }
+}
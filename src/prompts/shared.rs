@@ -0,0 +1,256 @@
+//! App-agnostic prompt templates (`prompt/shared/*.md`).
+//!
+//! One source renders into every tool: Cursor gets a `.mdc` rule, Codex and Claude Code get
+//! a section of the managed block in `AGENTS.md` / `CLAUDE.md`. Optional frontmatter holds
+//! Cursor rule options and an `apps` restriction; app-specific text goes between
+//! `<!-- llman:only <app>[,<app>...] -->` and `<!-- llman:end -->`.
+
+use crate::config::{CLAUDE_CODE_APP, CODEX_APP, CURSOR_APP, SHARED_APP};
+use crate::prompts::cursor_rule::CursorRuleFrontmatter;
+use crate::prompts::provenance::{TemplateRecord, digest};
+use crate::prompts::render::{PROJECT_SCOPE, PromptRenderer};
+use crate::sdd::spec::frontmatter::split_frontmatter;
+use anyhow::{Result, anyhow, bail};
+use serde::Deserialize;
+
+pub const SHARED_APPS: [&str; 3] = [CURSOR_APP, CODEX_APP, CLAUDE_CODE_APP];
+
+const ONLY_PREFIX: &str = "<!-- llman:only ";
+const END_MARKER: &str = "<!-- llman:end -->";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SharedTemplateMeta {
+    /// Cursor rule description
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Cursor rule globs (comma-separated)
+    #[serde(default)]
+    pub globs: Option<String>,
+    /// Cursor `alwaysApply` (default: true when no globs are set)
+    #[serde(default, rename = "alwaysApply")]
+    pub always_apply: Option<bool>,
+    /// Apps this template renders into (default: all)
+    #[serde(default)]
+    pub apps: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SharedTemplate {
+    pub name: String,
+    pub meta: SharedTemplateMeta,
+    pub body: String,
+    /// Digest of the raw template file, recorded in managed-block provenance.
+    pub digest: String,
+}
+
+impl SharedTemplate {
+    pub fn parse(name: &str, content: &str) -> Result<Self> {
+        let (frontmatter, body) = split_frontmatter(content);
+        let meta = match frontmatter {
+            Some(yaml) if !yaml.trim().is_empty() => serde_yaml::from_str(&yaml).map_err(|e| {
+                anyhow!(t!(
+                    "prompt.shared.error.invalid_frontmatter",
+                    name = name,
+                    error = e
+                ))
+            })?,
+            _ => SharedTemplateMeta::default(),
+        };
+        for app in &meta.apps {
+            if !SHARED_APPS.contains(&app.as_str()) {
+                bail!(t!(
+                    "prompt.shared.error.unknown_app",
+                    name = name,
+                    app = app
+                ));
+            }
+        }
+        // Validate sections up front so a typo fails for every app, not just one.
+        for app in SHARED_APPS {
+            select_sections(name, &body, app)?;
+        }
+        Ok(Self {
+            name: name.to_string(),
+            meta,
+            body,
+            digest: digest(content),
+        })
+    }
+
+    pub fn record(&self) -> TemplateRecord {
+        TemplateRecord {
+            name: self.name.clone(),
+            source: SHARED_APP.to_string(),
+            digest: self.digest.clone(),
+        }
+    }
+
+    pub fn applies_to(&self, app: &str) -> bool {
+        self.meta.apps.is_empty() || self.meta.apps.iter().any(|a| a == app)
+    }
+
+    /// Body with sections for other apps removed; `None` when the template skips `app`.
+    pub fn body_for(&self, app: &str) -> Result<Option<String>> {
+        if !self.applies_to(app) {
+            return Ok(None);
+        }
+        select_sections(&self.name, &self.body, app).map(Some)
+    }
+
+    /// Section-selected body rendered through MiniJinja for `app` and `scope`.
+    pub fn render_for(
+        &self,
+        app: &str,
+        renderer: &PromptRenderer,
+        scope: &str,
+    ) -> Result<Option<String>> {
+        let Some(body) = self.body_for(app)? else {
+            return Ok(None);
+        };
+        renderer.render(&self.name, &body, app, scope).map(Some)
+    }
+
+    /// Full `.mdc` rule file for Cursor.
+    pub fn render_cursor_rule(&self, renderer: &PromptRenderer) -> Result<Option<String>> {
+        let Some(body) = self.render_for(CURSOR_APP, renderer, PROJECT_SCOPE)? else {
+            return Ok(None);
+        };
+        let frontmatter = CursorRuleFrontmatter {
+            description: self.meta.description.clone(),
+            globs: self.meta.globs.clone(),
+            always_apply: self.meta.always_apply,
+        };
+        let mut out = frontmatter.render();
+        out.push('\n');
+        out.push_str(body.trim());
+        out.push('\n');
+        Ok(Some(out))
+    }
+}
+
+/// Managed-block body for Codex / Claude Code, one section per applicable template.
+pub fn build_managed_body(
+    templates: &[SharedTemplate],
+    app: &str,
+    renderer: &PromptRenderer,
+    scope: &str,
+) -> Result<String> {
+    let mut parts = Vec::new();
+    for template in templates {
+        if let Some(body) = template.render_for(app, renderer, scope)? {
+            parts.push(format!(
+                "## llman prompts: {}\n\n{}",
+                template.name,
+                body.trim()
+            ));
+        }
+    }
+    Ok(parts.join("\n\n"))
+}
+
+fn select_sections(name: &str, body: &str, app: &str) -> Result<String> {
+    let mut out = Vec::new();
+    let mut section: Option<bool> = None;
+    for (idx, line) in body.lines().enumerate() {
+        let trimmed = line.trim();
+        if let Some(rest) = trimmed.strip_prefix(ONLY_PREFIX) {
+            if section.is_some() {
+                bail!(t!(
+                    "prompt.shared.error.nested_section",
+                    name = name,
+                    line = idx + 1
+                ));
+            }
+            let Some(apps) = rest.strip_suffix("-->") else {
+                bail!(t!(
+                    "prompt.shared.error.unterminated_marker",
+                    name = name,
+                    line = idx + 1
+                ));
+            };
+            let apps: Vec<&str> = apps.split(',').map(str::trim).collect();
+            if let Some(unknown) = apps.iter().find(|a| !SHARED_APPS.contains(a)) {
+                bail!(t!(
+                    "prompt.shared.error.unknown_section_app",
+                    name = name,
+                    line = idx + 1,
+                    app = unknown
+                ));
+            }
+            section = Some(apps.contains(&app));
+            continue;
+        }
+        if trimmed == END_MARKER {
+            if section.take().is_none() {
+                bail!(t!(
+                    "prompt.shared.error.unmatched_end",
+                    name = name,
+                    line = idx + 1
+                ));
+            }
+            continue;
+        }
+        if section != Some(false) {
+            out.push(line);
+        }
+    }
+    if section.is_some() {
+        bail!(t!("prompt.shared.error.unclosed_section", name = name));
+    }
+
+    let mut text = out.join("\n");
+    // Collapse blank runs left behind by removed sections.
+    while text.contains("\n\n\n") {
+        text = text.replace("\n\n\n", "\n\n");
+    }
+    Ok(text.trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUSE_RULES: &str = "---\ndescription: House rules\nglobs: \"**/*.rs\"\n---\n\nUse anyhow for errors.\n\n<!-- llman:only cursor -->\nPrefer the Composer.\n<!-- llman:end -->\n\n<!-- llman:only codex, claude-code -->\nRun cargo test before finishing.\n<!-- llman:end -->\n\nKeep diffs small.\n";
+
+    #[test]
+    fn per_app_sections_render_only_for_their_apps() {
+        let template = SharedTemplate::parse("house", HOUSE_RULES).unwrap();
+
+        let renderer = PromptRenderer::default();
+        let rule = template.render_cursor_rule(&renderer).unwrap().unwrap();
+        assert_eq!(
+            rule,
+            "---\ndescription: House rules\nglobs: **/*.rs\nalwaysApply: false\n---\n\nUse anyhow for errors.\n\nPrefer the Composer.\n\nKeep diffs small.\n"
+        );
+
+        let body = build_managed_body(&[template], CODEX_APP, &renderer, PROJECT_SCOPE).unwrap();
+        assert_eq!(
+            body,
+            "## llman prompts: house\n\nUse anyhow for errors.\n\nRun cargo test before finishing.\n\nKeep diffs small."
+        );
+    }
+
+    #[test]
+    fn apps_restriction_and_malformed_sections() {
+        let template =
+            SharedTemplate::parse("claude-only", "---\napps: [claude-code]\n---\nHi\n").unwrap();
+        assert!(template.body_for(CURSOR_APP).unwrap().is_none());
+        assert_eq!(
+            template.body_for(CLAUDE_CODE_APP).unwrap().as_deref(),
+            Some("Hi")
+        );
+        assert_eq!(
+            template
+                .render_cursor_rule(&PromptRenderer::default())
+                .unwrap(),
+            None,
+            "cursor is not listed"
+        );
+
+        let err = SharedTemplate::parse("bad", "<!-- llman:only vim -->\nx\n<!-- llman:end -->\n")
+            .unwrap_err();
+        assert!(err.to_string().contains("unknown app 'vim'"), "{err}");
+        assert!(SharedTemplate::parse("open", "<!-- llman:only codex -->\nx\n").is_err());
+    }
+}
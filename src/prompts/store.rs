@@ -1,13 +1,16 @@
-use crate::config::{CLAUDE_CODE_APP, CODEX_APP, CURSOR_APP, Config};
+use crate::config::{CLAUDE_CODE_APP, CODEX_APP, CURSOR_APP, Config, SHARED_APP};
 use crate::fs_utils::atomic_write_with_mode;
 use crate::path_utils::validate_path_segment;
+use crate::prompts::history::{self, HistoryAction};
+use crate::prompts::provenance::{TemplateRecord, stamp};
+use crate::prompts::render::PromptRenderer;
 use anyhow::{Result, anyhow};
 use std::fs;
 use std::path::PathBuf;
 
-fn validate_app(app: &str) -> Result<()> {
+pub fn validate_app(app: &str) -> Result<()> {
     match app {
-        CURSOR_APP | CODEX_APP | CLAUDE_CODE_APP => Ok(()),
+        CURSOR_APP | CODEX_APP | CLAUDE_CODE_APP | SHARED_APP => Ok(()),
         _ => Err(anyhow!(t!("errors.invalid_app", app = app))),
     }
 }
@@ -39,12 +42,59 @@ pub fn upsert_template(config: &Config, app: &str, name: &str, content: &str) ->
     let name = validate_path_segment(name, "template name")
         .map_err(|e| anyhow!("invalid template name: {e}"))?;
 
+    write_tracked(config, app, &name, content, HistoryAction::Upsert, None)
+}
+
+/// Write a template and record it in the store history.
+fn write_tracked(
+    config: &Config,
+    app: &str,
+    name: &str,
+    content: &str,
+    action: HistoryAction,
+    restored_from: Option<u32>,
+) -> Result<PathBuf> {
     config.ensure_app_dir(app)?;
-    let template_path = config.rule_file_path(app, &name);
+    let template_path = config.rule_file_path(app, name);
+    let previous = fs::read_to_string(&template_path).ok();
+    history::record_baseline(config, app, name, previous.as_deref())?;
     atomic_write_with_mode(&template_path, content.as_bytes(), None)?;
+    history::record(
+        config,
+        app,
+        name,
+        previous.as_deref(),
+        action,
+        Some(content),
+        restored_from,
+    )?;
     Ok(template_path)
 }
 
+/// Restore revision `rev` of a template from the store history.
+pub fn restore_template(config: &Config, app: &str, name: &str, rev: u32) -> Result<PathBuf> {
+    validate_app(app)?;
+    let name = validate_path_segment(name, "template name")
+        .map_err(|e| anyhow!("invalid template name: {e}"))?;
+    let entry = history::find_revision(config, app, &name, rev)?;
+    let Some(content) = history::read_revision(config, &entry)? else {
+        return Err(anyhow!(t!(
+            "prompt.history.error.removal_revision",
+            rev = rev,
+            app = app,
+            name = name
+        )));
+    };
+    write_tracked(
+        config,
+        app,
+        &name,
+        &content,
+        HistoryAction::Restore,
+        Some(rev),
+    )
+}
+
 pub fn remove_template(config: &Config, app: &str, name: &str) -> Result<()> {
     validate_app(app)?;
     let name = validate_path_segment(name, "template name")
@@ -55,14 +105,28 @@ pub fn remove_template(config: &Config, app: &str, name: &str) -> Result<()> {
         return Err(anyhow!(t!("errors.rule_not_found", name = name)));
     }
 
+    let previous = fs::read_to_string(&template_path).ok();
+    history::record_baseline(config, app, &name, previous.as_deref())?;
     fs::remove_file(&template_path)?;
+    history::record(
+        config,
+        app,
+        &name,
+        previous.as_deref(),
+        HistoryAction::Remove,
+        None,
+        None,
+    )?;
     Ok(())
 }
 
+/// Managed-block body for `app`, each template rendered for `scope` (`project` / `global`).
 pub fn build_llman_prompts_body(
     config: &Config,
     app: &str,
     templates: &[String],
+    renderer: &PromptRenderer,
+    scope: &str,
 ) -> Result<String> {
     validate_app(app)?;
     if templates.is_empty() {
@@ -73,6 +137,7 @@ pub fn build_llman_prompts_body(
     for name in templates {
         let name = validate_path_segment(name, "template name")?;
         let content = read_template(config, app, &name)?;
+        let content = renderer.render_app_template(&name, &content, app, scope)?;
         parts.push(format!(
             "## llman prompts: {name}\n\n{}",
             content.trim_end()
@@ -80,3 +145,22 @@ pub fn build_llman_prompts_body(
     }
     Ok(parts.join("\n\n"))
 }
+
+/// `build_llman_prompts_body` with a provenance line, for `AGENTS.md` / `CLAUDE.md` targets.
+pub fn build_stamped_prompts_body(
+    config: &Config,
+    app: &str,
+    templates: &[String],
+    renderer: &PromptRenderer,
+    scope: &str,
+) -> Result<String> {
+    let body = build_llman_prompts_body(config, app, templates, renderer, scope)?;
+    let records = templates
+        .iter()
+        .map(|name| {
+            let raw = read_template(config, app, name)?;
+            Ok(TemplateRecord::new(name, app, &raw))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(stamp(app, scope, records, &body))
+}
@@ -239,11 +239,40 @@ fn render_template(
 ///
 /// `unit()` returns **already-rendered** content (same vars), so MiniJinja
 /// conditionals inside unit files are evaluated — not pasted as raw source.
-fn render_with_units(
+pub(crate) fn render_with_units(
     raw: &str,
     unit_map: &BTreeMap<String, String>,
     vars: &BTreeMap<String, String>,
     depth: usize,
+) -> std::result::Result<String, minijinja::Error> {
+    render_units(
+        "sdd_template",
+        raw,
+        unit_map,
+        vars,
+        depth,
+        UndefinedBehavior::Lenient,
+    )
+}
+
+/// Like [`render_with_units`], but printing or iterating an undefined variable is an error
+/// (`{% if name is defined %}` still works). Units are also reachable via `{% include %}`.
+pub(crate) fn render_with_units_strict(
+    name: &str,
+    raw: &str,
+    unit_map: &BTreeMap<String, String>,
+    vars: &BTreeMap<String, String>,
+) -> std::result::Result<String, minijinja::Error> {
+    render_units(name, raw, unit_map, vars, 0, UndefinedBehavior::SemiStrict)
+}
+
+fn render_units(
+    name: &str,
+    raw: &str,
+    unit_map: &BTreeMap<String, String>,
+    vars: &BTreeMap<String, String>,
+    depth: usize,
+    undefined: UndefinedBehavior,
 ) -> std::result::Result<String, minijinja::Error> {
     if depth > MAX_UNIT_NESTING_DEPTH {
         return Err(minijinja::Error::new(
@@ -253,14 +282,18 @@ fn render_with_units(
     }
 
     let mut env = Environment::new();
-    env.set_undefined_behavior(UndefinedBehavior::Lenient);
+    env.set_undefined_behavior(undefined);
 
     for (key, value) in vars {
         env.add_global(key.clone(), value.clone());
     }
 
+    let loader_units = unit_map.clone();
+    env.set_loader(move |id| Ok(loader_units.get(id).cloned()));
+
     let unit_map = unit_map.clone();
     let vars = vars.clone();
+    let unit_name = name.to_string();
     env.add_function(
         "unit",
         move |id: String| -> std::result::Result<String, minijinja::Error> {
@@ -270,12 +303,12 @@ fn render_with_units(
                     format!("missing template unit '{}'", id),
                 )
             })?;
-            render_with_units(&content, &unit_map, &vars, depth + 1)
+            render_units(&unit_name, &content, &unit_map, &vars, depth + 1, undefined)
         },
     );
 
-    env.add_template("sdd_template", raw)?;
-    env.get_template("sdd_template")?.render(())
+    env.add_template_owned(name.to_string(), raw.to_string())?;
+    env.get_template(name)?.render(())
 }
 
 fn embedded_template(path: &str) -> Option<&'static str> {
@@ -0,0 +1,102 @@
+use crate::secrets::store::{SecretStore, read_passphrase};
+use anyhow::{Result, anyhow, bail};
+use clap::{Args, Subcommand};
+use std::io::{IsTerminal, Read};
+
+#[derive(Args)]
+#[command(
+    about = "Manage the passphrase-encrypted secret store",
+    long_about = "Manage the passphrase-encrypted secret store referenced as { store = \"name\" } from account groups"
+)]
+pub struct SecretsArgs {
+    #[command(subcommand)]
+    pub command: SecretsCommands,
+}
+
+#[derive(Subcommand)]
+pub enum SecretsCommands {
+    /// List secret names (values are never printed)
+    List,
+    /// Add or replace a secret
+    Set {
+        /// Secret name, referenced as { store = "<name>" }
+        name: String,
+        /// Read the value from stdin instead of a hidden prompt
+        #[arg(long)]
+        stdin: bool,
+    },
+    /// Remove a secret
+    #[command(alias = "rm")]
+    Remove {
+        /// Secret name
+        name: String,
+    },
+}
+
+pub fn run(args: &SecretsArgs) -> Result<()> {
+    let path = SecretStore::default_path()?;
+    match &args.command {
+        SecretsCommands::List => {
+            if !path.exists() {
+                println!("{}", t!("secrets.command.empty", path = path.display()));
+                return Ok(());
+            }
+            let store = SecretStore::open(&path, read_passphrase(false)?)?;
+            let names = store.names();
+            if names.is_empty() {
+                println!("{}", t!("secrets.command.empty", path = path.display()));
+            }
+            for name in names {
+                println!("{name}");
+            }
+        }
+        SecretsCommands::Set { name, stdin } => {
+            validate_name(name)?;
+            let mut store = SecretStore::open(&path, read_passphrase(!path.exists())?)?;
+            let value = read_secret_value(name, *stdin)?;
+            store.set(name, value);
+            store.save()?;
+            println!("{}", t!("secrets.command.saved", name = name));
+        }
+        SecretsCommands::Remove { name } => {
+            if !path.exists() {
+                bail!(t!("secrets.store.missing", path = path.display()));
+            }
+            let mut store = SecretStore::open(&path, read_passphrase(false)?)?;
+            if !store.remove(name) {
+                bail!(t!("secrets.resolve.store_missing", name = name));
+            }
+            store.save()?;
+            println!("{}", t!("secrets.command.removed", name = name));
+        }
+    }
+    Ok(())
+}
+
+fn validate_name(name: &str) -> Result<()> {
+    if name.is_empty() || name.chars().any(char::is_whitespace) {
+        bail!(t!("secrets.command.invalid_name", name = name));
+    }
+    Ok(())
+}
+
+fn read_secret_value(name: &str, from_stdin: bool) -> Result<String> {
+    let value = if from_stdin || !std::io::stdin().is_terminal() {
+        let mut raw = String::new();
+        std::io::stdin().read_to_string(&mut raw)?;
+        raw.strip_suffix("\r\n")
+            .or_else(|| raw.strip_suffix('\n'))
+            .unwrap_or(&raw)
+            .to_string()
+    } else {
+        inquire::Password::new(&t!("secrets.command.value_prompt", name = name))
+            .with_display_mode(inquire::PasswordDisplayMode::Hidden)
+            .without_confirmation()
+            .prompt()
+            .map_err(|e| anyhow!(t!("secrets.store.prompt_failed", error = e)))?
+    };
+    if value.is_empty() {
+        bail!(t!("secrets.command.empty_value", name = name));
+    }
+    Ok(value)
+}
@@ -0,0 +1,262 @@
+//! Secret references for account env values.
+//!
+//! A value is either a plain string or a reference resolved when a command actually needs it:
+//! `{ cmd = "pass show anthropic" }`, `{ file = "~/.secrets/key" }`, `{ env = "OTHER_VAR" }`
+//! or `{ store = "name" }` for the passphrase-encrypted local store.
+
+pub mod command;
+pub mod store;
+
+use crate::secrets::store::SecretStore;
+use anyhow::{Context, Result, anyhow, bail};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::env;
+use std::fmt;
+use std::fs;
+use std::process::Command;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum SecretValue {
+    Plain(String),
+    Ref(SecretRef),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SecretRef {
+    /// Shell command whose stdout is the value.
+    Cmd(String),
+    /// File whose contents are the value (`~` expands to the home directory).
+    File(String),
+    /// Another environment variable of the llman process.
+    Env(String),
+    /// Entry in the encrypted store managed by `llman x secrets`.
+    Store(String),
+}
+
+impl From<String> for SecretValue {
+    fn from(value: String) -> Self {
+        Self::Plain(value)
+    }
+}
+
+impl From<&str> for SecretValue {
+    fn from(value: &str) -> Self {
+        Self::Plain(value.to_string())
+    }
+}
+
+impl fmt::Display for SecretRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Cmd(cmd) => write!(f, "<cmd: {cmd}>"),
+            Self::File(path) => write!(f, "<file: {path}>"),
+            Self::Env(key) => write!(f, "<env: {key}>"),
+            Self::Store(name) => write!(f, "<store: {name}>"),
+        }
+    }
+}
+
+impl SecretValue {
+    /// Display form that never resolves references; plain values go through `mask`.
+    pub fn display_with(&self, mask: impl FnOnce(&str) -> String) -> String {
+        match self {
+            Self::Plain(value) => mask(value),
+            Self::Ref(reference) => reference.to_string(),
+        }
+    }
+}
+
+/// Resolves secret values, unlocking the encrypted store at most once and only when a
+/// `store` reference is actually used.
+#[derive(Default)]
+pub struct SecretResolver {
+    store: Option<SecretStore>,
+}
+
+impl SecretResolver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn resolve(&mut self, value: &SecretValue) -> Result<String> {
+        match value {
+            SecretValue::Plain(value) => Ok(value.clone()),
+            SecretValue::Ref(reference) => self.resolve_ref(reference),
+        }
+    }
+
+    /// Resolve every value of an env map, keeping its keys.
+    pub fn resolve_map(
+        &mut self,
+        values: &HashMap<String, SecretValue>,
+    ) -> Result<HashMap<String, String>> {
+        values
+            .iter()
+            .map(|(key, value)| {
+                let resolved = self
+                    .resolve(value)
+                    .with_context(|| t!("secrets.resolve.key_failed", key = key))?;
+                Ok((key.clone(), resolved))
+            })
+            .collect()
+    }
+
+    fn resolve_ref(&mut self, reference: &SecretRef) -> Result<String> {
+        match reference {
+            SecretRef::Cmd(cmd) => run_secret_command(cmd),
+            SecretRef::File(raw) => {
+                let path = expand_home(raw)?;
+                let content = fs::read_to_string(&path)
+                    .with_context(|| t!("secrets.resolve.file_failed", path = path.display()))?;
+                Ok(trim_line_ending(&content).to_string())
+            }
+            SecretRef::Env(key) => {
+                env::var(key).map_err(|_| anyhow!(t!("secrets.resolve.env_missing", key = key)))
+            }
+            SecretRef::Store(name) => {
+                if self.store.is_none() {
+                    self.store = Some(SecretStore::unlock_default()?);
+                }
+                let store = self.store.as_ref().expect("store unlocked above");
+                store
+                    .get(name)
+                    .map(str::to_string)
+                    .ok_or_else(|| anyhow!(t!("secrets.resolve.store_missing", name = name)))
+            }
+        }
+    }
+}
+
+fn run_secret_command(cmd: &str) -> Result<String> {
+    let mut command = if cfg!(windows) {
+        let mut command = Command::new("cmd");
+        command.arg("/C");
+        command
+    } else {
+        let mut command = Command::new("sh");
+        command.arg("-c");
+        command
+    };
+    let output = command
+        .arg(cmd)
+        .stderr(std::process::Stdio::inherit())
+        .output()
+        .with_context(|| t!("secrets.resolve.cmd_spawn_failed", cmd = cmd))?;
+    if !output.status.success() {
+        bail!(t!(
+            "secrets.resolve.cmd_failed",
+            cmd = cmd,
+            status = output.status
+        ));
+    }
+    let stdout = String::from_utf8(output.stdout)
+        .map_err(|_| anyhow!(t!("secrets.resolve.cmd_not_utf8", cmd = cmd)))?;
+    Ok(trim_line_ending(&stdout).to_string())
+}
+
+fn expand_home(raw: &str) -> Result<std::path::PathBuf> {
+    if raw == "~" {
+        return crate::config::home_dir();
+    }
+    if let Some(rest) = raw.strip_prefix("~/") {
+        return Ok(crate::config::home_dir()?.join(rest));
+    }
+    Ok(std::path::PathBuf::from(raw))
+}
+
+/// Strip the trailing newline tools like `pass` or `echo` add, but keep other whitespace.
+fn trim_line_ending(value: &str) -> &str {
+    value
+        .strip_suffix("\r\n")
+        .or_else(|| value.strip_suffix('\n'))
+        .unwrap_or(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TestProcess;
+    use tempfile::TempDir;
+
+    #[derive(Debug, Deserialize)]
+    struct Group {
+        vars: HashMap<String, SecretValue>,
+    }
+
+    #[test]
+    fn secret_values_parse_from_plain_strings_and_inline_tables() {
+        let group: Group = toml::from_str(
+            r#"
+[vars]
+PLAIN = "value"
+FROM_CMD = { cmd = "pass show anthropic" }
+FROM_FILE = { file = "~/.secrets/key" }
+FROM_ENV = { env = "OTHER_VAR" }
+FROM_STORE = { store = "anthropic" }
+"#,
+        )
+        .expect("parse");
+        assert_eq!(group.vars["PLAIN"], SecretValue::from("value"));
+        assert_eq!(
+            group.vars["FROM_CMD"],
+            SecretValue::Ref(SecretRef::Cmd("pass show anthropic".to_string()))
+        );
+        assert_eq!(
+            group.vars["FROM_STORE"].display_with(|_| unreachable!()),
+            "<store: anthropic>"
+        );
+        assert!(toml::from_str::<Group>("[vars]\nBAD = { nope = \"x\" }\n").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn resolver_reads_cmd_file_and_env_references() {
+        let temp = TempDir::new().expect("temp dir");
+        let key_file = temp.path().join("key");
+        fs::write(&key_file, "from-file\n").expect("write key");
+        let mut proc = TestProcess::new();
+        proc.set_var("LLMAN_TEST_SECRET_SOURCE", "from-env");
+
+        let mut resolver = SecretResolver::new();
+        let values: HashMap<String, SecretValue> = [
+            (
+                "A".to_string(),
+                SecretValue::Ref(SecretRef::Cmd("printf 'from-cmd\\n'".to_string())),
+            ),
+            (
+                "B".to_string(),
+                SecretValue::Ref(SecretRef::File(key_file.display().to_string())),
+            ),
+            (
+                "C".to_string(),
+                SecretValue::Ref(SecretRef::Env("LLMAN_TEST_SECRET_SOURCE".to_string())),
+            ),
+            ("D".to_string(), SecretValue::from("plain")),
+        ]
+        .into_iter()
+        .collect();
+
+        let resolved = resolver.resolve_map(&values).expect("resolve");
+        assert_eq!(resolved["A"], "from-cmd");
+        assert_eq!(resolved["B"], "from-file");
+        assert_eq!(resolved["C"], "from-env");
+        assert_eq!(resolved["D"], "plain");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn resolver_reports_failing_command_with_key() {
+        let mut resolver = SecretResolver::new();
+        let values: HashMap<String, SecretValue> = [(
+            "API_KEY".to_string(),
+            SecretValue::Ref(SecretRef::Cmd("exit 3".to_string())),
+        )]
+        .into_iter()
+        .collect();
+        let err = resolver.resolve_map(&values).expect_err("should fail");
+        assert!(format!("{err:#}").contains("API_KEY"), "{err:#}");
+    }
+}
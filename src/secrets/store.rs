@@ -0,0 +1,277 @@
+use anyhow::{Context, Result, anyhow, bail};
+use ring::aead::{Aad, CHACHA20_POLY1305, LessSafeKey, NONCE_LEN, Nonce, UnboundKey};
+use ring::pbkdf2;
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::io::IsTerminal;
+use std::num::NonZeroU32;
+use std::path::{Path, PathBuf};
+
+/// Store file name inside the llman config directory.
+pub const STORE_FILE: &str = "secrets.enc.json";
+/// Passphrase source for non-interactive use.
+pub const PASSPHRASE_ENV: &str = "LLMAN_SECRETS_PASSPHRASE";
+
+const FORMAT_VERSION: u32 = 1;
+const KDF_NAME: &str = "pbkdf2-hmac-sha256";
+const DEFAULT_ITERATIONS: u32 = 600_000;
+const SALT_LEN: usize = 16;
+const KEY_LEN: usize = 32;
+const AAD: &[u8] = b"llman-secrets-v1";
+
+/// On-disk envelope; everything except the KDF parameters is encrypted.
+#[derive(Debug, Serialize, Deserialize)]
+struct StoreFile {
+    version: u32,
+    kdf: String,
+    iterations: u32,
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+/// Decrypted view of the local secret store.
+pub struct SecretStore {
+    path: PathBuf,
+    passphrase: String,
+    iterations: u32,
+    entries: BTreeMap<String, String>,
+}
+
+impl SecretStore {
+    pub fn default_path() -> Result<PathBuf> {
+        Ok(crate::config::resolve_config_dir(None)?.join(STORE_FILE))
+    }
+
+    /// Open the default store, asking for the passphrase when needed.
+    pub fn unlock_default() -> Result<Self> {
+        let path = Self::default_path()?;
+        if !path.exists() {
+            bail!(t!("secrets.store.missing", path = path.display()));
+        }
+        let passphrase = read_passphrase(false)?;
+        Self::open(&path, passphrase)
+    }
+
+    /// Open `path` with `passphrase`; a missing file yields an empty store.
+    pub fn open(path: &Path, passphrase: String) -> Result<Self> {
+        let mut store = Self {
+            path: path.to_path_buf(),
+            passphrase,
+            iterations: DEFAULT_ITERATIONS,
+            entries: BTreeMap::new(),
+        };
+        if !path.exists() {
+            return Ok(store);
+        }
+
+        let content = fs::read_to_string(path)
+            .with_context(|| t!("secrets.store.read_failed", path = path.display()))?;
+        let file: StoreFile = serde_json::from_str(&content)
+            .with_context(|| t!("secrets.store.invalid", path = path.display()))?;
+        if file.version != FORMAT_VERSION || file.kdf != KDF_NAME {
+            bail!(t!("secrets.store.unsupported", path = path.display()));
+        }
+
+        let salt = decode_hex(&file.salt)?;
+        let nonce: [u8; NONCE_LEN] = decode_hex(&file.nonce)?
+            .try_into()
+            .map_err(|_| anyhow!(t!("secrets.store.invalid", path = path.display())))?;
+        let mut data = decode_hex(&file.ciphertext)?;
+        let key = derive_key(&store.passphrase, &salt, file.iterations)?;
+        let plaintext = key
+            .open_in_place(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::from(AAD),
+                &mut data,
+            )
+            .map_err(|_| anyhow!(t!("secrets.store.wrong_passphrase")))?;
+        store.entries = serde_json::from_slice(plaintext)
+            .with_context(|| t!("secrets.store.invalid", path = path.display()))?;
+        store.iterations = file.iterations;
+        Ok(store)
+    }
+
+    pub fn exists(&self) -> bool {
+        self.path.exists()
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.entries.get(name).map(String::as_str)
+    }
+
+    pub fn set(&mut self, name: &str, value: String) {
+        self.entries.insert(name.to_string(), value);
+    }
+
+    pub fn remove(&mut self, name: &str) -> bool {
+        self.entries.remove(name).is_some()
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.entries.keys().map(String::as_str).collect()
+    }
+
+    /// Re-encrypt with a fresh salt and nonce and write the store with user-only permissions.
+    pub fn save(&self) -> Result<()> {
+        let rng = SystemRandom::new();
+        let mut salt = [0u8; SALT_LEN];
+        let mut nonce = [0u8; NONCE_LEN];
+        rng.fill(&mut salt)
+            .and_then(|_| rng.fill(&mut nonce))
+            .map_err(|_| anyhow!(t!("secrets.store.random_failed")))?;
+
+        let key = derive_key(&self.passphrase, &salt, self.iterations)?;
+        let mut data = serde_json::to_vec(&self.entries)?;
+        key.seal_in_place_append_tag(
+            Nonce::assume_unique_for_key(nonce),
+            Aad::from(AAD),
+            &mut data,
+        )
+        .map_err(|_| anyhow!(t!("secrets.store.encrypt_failed")))?;
+
+        let file = StoreFile {
+            version: FORMAT_VERSION,
+            kdf: KDF_NAME.to_string(),
+            iterations: self.iterations,
+            salt: encode_hex(&salt),
+            nonce: encode_hex(&nonce),
+            ciphertext: encode_hex(&data),
+        };
+        if let Some(parent) = crate::path_utils::safe_parent_for_creation(&self.path) {
+            fs::create_dir_all(parent)?;
+        }
+        let content = serde_json::to_string_pretty(&file)?;
+        crate::fs_utils::atomic_write_with_mode(
+            &self.path,
+            format!("{content}\n").as_bytes(),
+            Some(0o600),
+        )
+        .with_context(|| t!("secrets.store.write_failed", path = self.path.display()))
+    }
+
+    #[cfg(test)]
+    fn with_iterations(mut self, iterations: u32) -> Self {
+        self.iterations = iterations;
+        self
+    }
+}
+
+/// Read the store passphrase from `LLMAN_SECRETS_PASSPHRASE` or, on a terminal, a hidden prompt.
+/// `confirm` asks twice, for creating a new store.
+pub fn read_passphrase(confirm: bool) -> Result<String> {
+    if let Ok(passphrase) = env::var(PASSPHRASE_ENV)
+        && !passphrase.is_empty()
+    {
+        return Ok(passphrase);
+    }
+    if !std::io::stdin().is_terminal() {
+        bail!(t!(
+            "secrets.store.passphrase_required",
+            env = PASSPHRASE_ENV
+        ));
+    }
+    let message = t!("secrets.store.passphrase_prompt");
+    let mut prompt =
+        inquire::Password::new(&message).with_display_mode(inquire::PasswordDisplayMode::Hidden);
+    if !confirm {
+        prompt = prompt.without_confirmation();
+    }
+    let passphrase = prompt
+        .prompt()
+        .map_err(|e| anyhow!(t!("secrets.store.prompt_failed", error = e)))?;
+    if passphrase.is_empty() {
+        bail!(t!("secrets.store.passphrase_empty"));
+    }
+    Ok(passphrase)
+}
+
+fn derive_key(passphrase: &str, salt: &[u8], iterations: u32) -> Result<LessSafeKey> {
+    let iterations =
+        NonZeroU32::new(iterations).ok_or_else(|| anyhow!(t!("secrets.store.encrypt_failed")))?;
+    let mut key = [0u8; KEY_LEN];
+    pbkdf2::derive(
+        pbkdf2::PBKDF2_HMAC_SHA256,
+        iterations,
+        salt,
+        passphrase.as_bytes(),
+        &mut key,
+    );
+    let unbound = UnboundKey::new(&CHACHA20_POLY1305, &key)
+        .map_err(|_| anyhow!(t!("secrets.store.encrypt_failed")))?;
+    Ok(LessSafeKey::new(unbound))
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn decode_hex(raw: &str) -> Result<Vec<u8>> {
+    if !raw.is_ascii() || !raw.len().is_multiple_of(2) {
+        bail!(t!("secrets.store.invalid_hex"));
+    }
+    (0..raw.len())
+        .step_by(2)
+        .map(|idx| {
+            u8::from_str_radix(&raw[idx..idx + 2], 16)
+                .map_err(|_| anyhow!(t!("secrets.store.invalid_hex")))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn store_roundtrips_and_rejects_wrong_passphrase() {
+        let temp = TempDir::new().expect("temp dir");
+        let path = temp.path().join(STORE_FILE);
+
+        let mut store = SecretStore::open(&path, "correct horse".to_string())
+            .expect("open empty")
+            .with_iterations(1_000);
+        assert!(!store.exists());
+        store.set("anthropic", "sk-ant-secret".to_string());
+        store.save().expect("save");
+
+        let raw = fs::read_to_string(&path).expect("read store");
+        assert!(!raw.contains("sk-ant-secret"));
+        assert!(!raw.contains("anthropic"));
+
+        let reopened = SecretStore::open(&path, "correct horse".to_string()).expect("reopen");
+        assert_eq!(reopened.get("anthropic"), Some("sk-ant-secret"));
+        assert_eq!(reopened.names(), vec!["anthropic"]);
+
+        let err = SecretStore::open(&path, "wrong".to_string())
+            .err()
+            .expect("wrong passphrase fails");
+        assert!(err.to_string().contains("passphrase"), "{err}");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn store_file_uses_user_only_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp = TempDir::new().expect("temp dir");
+        let path = temp.path().join(STORE_FILE);
+        let store = SecretStore::open(&path, "pw".to_string())
+            .expect("open")
+            .with_iterations(1_000);
+        store.save().expect("save");
+        let mode = fs::metadata(&path).expect("meta").permissions().mode() & 0o777;
+        assert_eq!(mode, 0o600);
+    }
+
+    #[test]
+    fn hex_roundtrip() {
+        let bytes = [0u8, 1, 127, 255];
+        assert_eq!(decode_hex(&encode_hex(&bytes)).expect("decode"), bytes);
+        assert!(decode_hex("abc").is_err());
+    }
+}
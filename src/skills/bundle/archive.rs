@@ -0,0 +1,543 @@
+use crate::skills::catalog::scan::{SKILL_FILE, SKILL_TEMPLATE_FILE, read_frontmatter_version};
+use crate::skills::catalog::types::{SkillCandidate, SkillsPaths, TargetConflictStrategy};
+use crate::skills::targets::sync::{
+    compute_dir_digest, copy_dir_all_follow_links, remove_existing_entry_for_copy,
+    resolve_entry_conflict,
+};
+use anyhow::{Context, Result, anyhow};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// Manifest stored at the root of every bundle.
+pub const BUNDLE_MANIFEST_FILE: &str = "llman-bundle.json";
+const BUNDLE_SKILLS_DIR: &str = "skills";
+const BUNDLE_FORMAT: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleManifest {
+    pub format: u32,
+    pub llman_version: String,
+    pub skills: Vec<BundleSkill>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleSkill {
+    pub id: String,
+    /// Directory name under `skills/` in the bundle and under the skills root on unpack.
+    pub dir: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// Same directory digest used for copy-mode targets.
+    pub digest: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct PackArgs {
+    pub skill_ids: Vec<String>,
+    pub out: PathBuf,
+    pub force: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct UnpackArgs {
+    pub bundle: PathBuf,
+    pub dry_run: bool,
+}
+
+pub fn run_pack(skills: &[SkillCandidate], args: &PackArgs) -> Result<()> {
+    if args.out.exists() && !args.force {
+        return Err(anyhow!(t!(
+            "skills.bundle.out_exists",
+            path = args.out.display()
+        )));
+    }
+
+    let mut selected = Vec::new();
+    for id in &args.skill_ids {
+        let skill = skills
+            .iter()
+            .find(|skill| &skill.skill_id == id)
+            .ok_or_else(|| anyhow!(t!("skills.bundle.unknown_skill", skill = id)))?;
+        if !selected
+            .iter()
+            .any(|existing: &&SkillCandidate| existing.skill_id == skill.skill_id)
+        {
+            selected.push(skill);
+        }
+    }
+
+    let tempdir = tempfile::tempdir().context("create tempdir for bundle staging")?;
+    let staging_root = tempdir.path().join("staging");
+    let staging_skills = staging_root.join(BUNDLE_SKILLS_DIR);
+    fs::create_dir_all(&staging_skills).context("create bundle staging root")?;
+
+    let mut manifest = BundleManifest {
+        format: BUNDLE_FORMAT,
+        llman_version: env!("CARGO_PKG_VERSION").to_string(),
+        skills: Vec::new(),
+    };
+    for skill in selected {
+        let dir = bundle_dir_name(skill);
+        let staged = staging_skills.join(&dir);
+        if staged.exists() {
+            return Err(anyhow!(t!(
+                "skills.bundle.duplicate_dir",
+                dir = dir,
+                skill = skill.skill_id
+            )));
+        }
+        copy_dir_all_follow_links(&skill.skill_dir, &staged)?;
+        manifest.skills.push(BundleSkill {
+            id: skill.skill_id.clone(),
+            version: skill_version(&staged),
+            digest: compute_dir_digest(&staged)?,
+            dir,
+        });
+    }
+
+    let manifest_json = serde_json::to_string_pretty(&manifest)?;
+    fs::write(
+        staging_root.join(BUNDLE_MANIFEST_FILE),
+        format!("{manifest_json}\n"),
+    )?;
+
+    if let Some(parent) = args.out.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        fs::create_dir_all(parent)?;
+    }
+    if args.out.exists() {
+        fs::remove_file(&args.out)
+            .with_context(|| format!("remove existing bundle {}", args.out.display()))?;
+    }
+    sevenz_rust2::compress_to_path(&staging_root, &args.out)
+        .map_err(|e| anyhow!(t!("skills.bundle.write_failed", error = e)))?;
+
+    println!(
+        "{}",
+        t!(
+            "skills.bundle.packed",
+            count = manifest.skills.len(),
+            path = args.out.display()
+        )
+    );
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum UnpackAction {
+    Install,
+    Replace,
+    UpToDate,
+    Skip,
+    /// Dry run without `--target-conflict`: reported instead of prompting.
+    Conflict,
+}
+
+pub fn run_unpack(
+    paths: &SkillsPaths,
+    args: &UnpackArgs,
+    interactive: bool,
+    target_conflict: Option<TargetConflictStrategy>,
+) -> Result<()> {
+    let tempdir = tempfile::tempdir().context("create tempdir for bundle extraction")?;
+    let extracted = tempdir.path().join("bundle");
+    extract_bundle(&args.bundle, &extracted)?;
+    let manifest = read_manifest(&extracted)?;
+
+    // Phase 1: verify every skill before touching the skills root.
+    let skills_dir = extracted.join(BUNDLE_SKILLS_DIR);
+    for skill in &manifest.skills {
+        validate_bundle_dir_name(&skill.dir)?;
+        let source = skills_dir.join(&skill.dir);
+        if !source.is_dir() {
+            return Err(anyhow!(t!(
+                "skills.bundle.skill_missing",
+                skill = skill.id,
+                dir = skill.dir
+            )));
+        }
+        let digest = compute_dir_digest(&source)?;
+        if digest != skill.digest {
+            return Err(anyhow!(t!(
+                "skills.bundle.digest_mismatch",
+                skill = skill.id,
+                expected = skill.digest,
+                actual = digest
+            )));
+        }
+    }
+
+    // Phase 2: plan and resolve conflicts so that a cancel leaves the skills root untouched.
+    let root_label = paths.root.display().to_string();
+    let mut planned = Vec::new();
+    for skill in &manifest.skills {
+        let dest = paths.root.join(&skill.dir);
+        let action = match fs::symlink_metadata(&dest) {
+            Err(_) => UnpackAction::Install,
+            Ok(meta)
+                if meta.is_dir()
+                    && !meta.file_type().is_symlink()
+                    && compute_dir_digest(&dest)? == skill.digest =>
+            {
+                UnpackAction::UpToDate
+            }
+            Ok(_) if args.dry_run && target_conflict.is_none() => UnpackAction::Conflict,
+            Ok(_) => {
+                match resolve_entry_conflict(&skill.id, &root_label, interactive, target_conflict)?
+                {
+                    TargetConflictStrategy::Overwrite => UnpackAction::Replace,
+                    TargetConflictStrategy::Skip => UnpackAction::Skip,
+                }
+            }
+        };
+        planned.push((skill, action));
+    }
+
+    // Phase 3: apply.
+    for (skill, action) in planned {
+        let dest = paths.root.join(&skill.dir);
+        let status = match action {
+            UnpackAction::UpToDate => t!("skills.bundle.status_up_to_date"),
+            UnpackAction::Skip => t!("skills.bundle.status_skipped"),
+            UnpackAction::Conflict => t!("skills.bundle.status_conflict"),
+            UnpackAction::Install | UnpackAction::Replace => {
+                if !args.dry_run {
+                    if action == UnpackAction::Replace {
+                        remove_existing_entry_for_copy(&dest)?;
+                    }
+                    copy_dir_all_follow_links(&skills_dir.join(&skill.dir), &dest)?;
+                }
+                if action == UnpackAction::Replace {
+                    t!("skills.bundle.status_replaced")
+                } else {
+                    t!("skills.bundle.status_installed")
+                }
+            }
+        };
+        println!(
+            "{}",
+            t!(
+                "skills.bundle.unpacked_skill",
+                skill = skill.id,
+                status = status,
+                path = dest.display()
+            )
+        );
+    }
+    if args.dry_run {
+        println!("{}", t!("skills.bundle.dry_run"));
+    }
+    Ok(())
+}
+
+/// Extract `bundle` into `dest`, refusing any entry outside `skills/` and the manifest so a
+/// crafted archive cannot write beyond the staging directory.
+fn extract_bundle(bundle: &Path, dest: &Path) -> Result<()> {
+    let mut rejected = None;
+    sevenz_rust2::decompress_file_with_extract_fn(bundle, dest, |entry, reader, path| {
+        // The staging root itself is stored as an unnamed directory entry.
+        if entry.name().is_empty() && entry.is_directory() {
+            return Ok(true);
+        }
+        if !is_allowed_entry(entry.name()) {
+            rejected = Some(entry.name().to_string());
+            return Ok(false);
+        }
+        sevenz_rust2::default_entry_extract_fn(entry, reader, path)
+    })
+    .map_err(|e| anyhow!(t!("skills.bundle.read_failed", error = e)))?;
+    match rejected {
+        Some(entry) => Err(anyhow!(t!("skills.bundle.unsafe_entry", entry = entry))),
+        None => Ok(()),
+    }
+}
+
+fn is_allowed_entry(name: &str) -> bool {
+    let normalized = name.replace('\\', "/");
+    let path = Path::new(&normalized);
+    if !path
+        .components()
+        .all(|component| matches!(component, Component::Normal(_)))
+    {
+        return false;
+    }
+    path == Path::new(BUNDLE_MANIFEST_FILE)
+        || path
+            .components()
+            .next()
+            .is_some_and(|first| first.as_os_str() == BUNDLE_SKILLS_DIR)
+}
+
+fn read_manifest(extracted: &Path) -> Result<BundleManifest> {
+    let manifest_path = extracted.join(BUNDLE_MANIFEST_FILE);
+    let content = fs::read_to_string(&manifest_path)
+        .map_err(|e| anyhow!(t!("skills.bundle.manifest_missing", error = e)))?;
+    let manifest: BundleManifest = serde_json::from_str(&content)
+        .map_err(|e| anyhow!(t!("skills.bundle.manifest_invalid", error = e)))?;
+    if manifest.format != BUNDLE_FORMAT {
+        return Err(anyhow!(t!(
+            "skills.bundle.unsupported_format",
+            format = manifest.format
+        )));
+    }
+    Ok(manifest)
+}
+
+/// Bundle directories must be a single plain path component so unpack cannot escape the root.
+fn validate_bundle_dir_name(dir: &str) -> Result<()> {
+    let mut components = Path::new(dir).components();
+    match (components.next(), components.next()) {
+        (Some(Component::Normal(_)), None) if dir != "store" => Ok(()),
+        _ => Err(anyhow!(t!("skills.bundle.invalid_dir", dir = dir))),
+    }
+}
+
+fn bundle_dir_name(skill: &SkillCandidate) -> String {
+    skill
+        .skill_dir
+        .file_name()
+        .and_then(|name| name.to_str())
+        .map(str::to_string)
+        .unwrap_or_else(|| skill.skill_id.clone())
+}
+
+fn skill_version(skill_dir: &Path) -> Option<String> {
+    [SKILL_FILE, SKILL_TEMPLATE_FILE]
+        .iter()
+        .find_map(|name| read_frontmatter_version(&skill_dir.join(name)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_skill(root: &Path, dir: &str, body: &str) -> SkillCandidate {
+        let skill_dir = root.join(dir);
+        fs::create_dir_all(&skill_dir).expect("create skill dir");
+        fs::write(
+            skill_dir.join(SKILL_FILE),
+            format!("---\nname: {dir}\nmetadata:\n  version: \"1.2.0\"\n---\n{body}"),
+        )
+        .expect("write skill");
+        SkillCandidate {
+            skill_id: dir.to_string(),
+            skill_dir,
+        }
+    }
+
+    fn skills_paths(root: &Path) -> SkillsPaths {
+        SkillsPaths {
+            root: root.to_path_buf(),
+            config_path: root.join("config.toml"),
+        }
+    }
+
+    #[test]
+    fn test_pack_and_unpack_round_trip() {
+        let temp = TempDir::new().expect("temp dir");
+        let source_root = temp.path().join("source");
+        let alpha = write_skill(&source_root, "alpha", "# alpha");
+        let beta = write_skill(&source_root, "beta", "# beta");
+        let bundle = temp.path().join("out").join("bundle.7z");
+
+        run_pack(
+            &[alpha, beta],
+            &PackArgs {
+                skill_ids: vec!["alpha".to_string()],
+                out: bundle.clone(),
+                force: false,
+            },
+        )
+        .expect("pack");
+
+        let dest_root = temp.path().join("dest");
+        fs::create_dir_all(&dest_root).expect("create dest");
+        let args = UnpackArgs {
+            bundle: bundle.clone(),
+            dry_run: false,
+        };
+        run_unpack(&skills_paths(&dest_root), &args, false, None).expect("unpack");
+        assert_eq!(
+            fs::read_to_string(dest_root.join("alpha").join(SKILL_FILE)).expect("read"),
+            fs::read_to_string(source_root.join("alpha").join(SKILL_FILE)).expect("read")
+        );
+        assert!(!dest_root.join("beta").exists());
+
+        // Unpacking again is a no-op: identical content is not a conflict.
+        run_unpack(&skills_paths(&dest_root), &args, false, None).expect("unpack again");
+    }
+
+    #[test]
+    fn test_unpack_conflict_requires_flag_and_honours_skip() {
+        let temp = TempDir::new().expect("temp dir");
+        let source_root = temp.path().join("source");
+        let alpha = write_skill(&source_root, "alpha", "# alpha");
+        let bundle = temp.path().join("bundle.7z");
+        run_pack(
+            &[alpha],
+            &PackArgs {
+                skill_ids: vec!["alpha".to_string()],
+                out: bundle.clone(),
+                force: false,
+            },
+        )
+        .expect("pack");
+
+        let dest_root = temp.path().join("dest");
+        write_skill(&dest_root, "alpha", "# local edits");
+        let args = UnpackArgs {
+            bundle,
+            dry_run: false,
+        };
+        let err = run_unpack(&skills_paths(&dest_root), &args, false, None)
+            .expect_err("conflict requires flag");
+        assert!(err.to_string().contains("--target-conflict"));
+
+        run_unpack(
+            &skills_paths(&dest_root),
+            &args,
+            false,
+            Some(TargetConflictStrategy::Skip),
+        )
+        .expect("skip");
+        assert!(
+            fs::read_to_string(dest_root.join("alpha").join(SKILL_FILE))
+                .expect("read")
+                .contains("# local edits")
+        );
+
+        run_unpack(
+            &skills_paths(&dest_root),
+            &args,
+            false,
+            Some(TargetConflictStrategy::Overwrite),
+        )
+        .expect("overwrite");
+        assert!(
+            fs::read_to_string(dest_root.join("alpha").join(SKILL_FILE))
+                .expect("read")
+                .ends_with("# alpha")
+        );
+    }
+
+    #[test]
+    fn test_unpack_rejects_tampered_bundle() {
+        let temp = TempDir::new().expect("temp dir");
+        let staging = temp.path().join("staging");
+        let skill_dir = staging.join(BUNDLE_SKILLS_DIR).join("alpha");
+        fs::create_dir_all(&skill_dir).expect("create staged skill");
+        fs::write(skill_dir.join(SKILL_FILE), "# tampered").expect("write skill");
+        let manifest = BundleManifest {
+            format: BUNDLE_FORMAT,
+            llman_version: "0.0.0".to_string(),
+            skills: vec![BundleSkill {
+                id: "alpha".to_string(),
+                dir: "alpha".to_string(),
+                version: None,
+                digest: "0".repeat(64),
+            }],
+        };
+        fs::write(
+            staging.join(BUNDLE_MANIFEST_FILE),
+            serde_json::to_string(&manifest).expect("json"),
+        )
+        .expect("write manifest");
+        let bundle = temp.path().join("bundle.7z");
+        sevenz_rust2::compress_to_path(&staging, &bundle).expect("compress");
+
+        let dest_root = temp.path().join("dest");
+        fs::create_dir_all(&dest_root).expect("create dest");
+        let err = run_unpack(
+            &skills_paths(&dest_root),
+            &UnpackArgs {
+                bundle,
+                dry_run: false,
+            },
+            false,
+            None,
+        )
+        .expect_err("digest mismatch");
+        assert!(err.to_string().contains("digest"));
+        assert!(!dest_root.join("alpha").exists());
+    }
+
+    #[test]
+    fn test_unpack_rejects_entries_outside_the_bundle_layout() {
+        let temp = TempDir::new().expect("temp dir");
+        let bundle = temp.path().join("evil.7z");
+        let mut writer = sevenz_rust2::ArchiveWriter::create(&bundle).expect("create archive");
+        writer
+            .push_archive_entry(
+                sevenz_rust2::ArchiveEntry::new_file("../escaped.txt"),
+                Some("pwned".as_bytes()),
+            )
+            .expect("push entry");
+        writer.finish().expect("finish archive");
+
+        let dest_root = temp.path().join("dest");
+        fs::create_dir_all(&dest_root).expect("create dest");
+        let err = run_unpack(
+            &skills_paths(&dest_root),
+            &UnpackArgs {
+                bundle,
+                dry_run: true,
+            },
+            false,
+            None,
+        )
+        .expect_err("traversal entry");
+        assert!(err.to_string().contains("../escaped.txt"), "{err}");
+
+        assert!(is_allowed_entry("skills/alpha/SKILL.md"));
+        assert!(is_allowed_entry(BUNDLE_MANIFEST_FILE));
+        assert!(!is_allowed_entry("/etc/passwd"));
+        assert!(!is_allowed_entry("skills/../../x"));
+        assert!(!is_allowed_entry(".bashrc"));
+    }
+
+    #[test]
+    fn test_unpack_dry_run_reports_conflict_without_prompting() {
+        let temp = TempDir::new().expect("temp dir");
+        let alpha = write_skill(&temp.path().join("source"), "alpha", "# alpha");
+        let bundle = temp.path().join("bundle.7z");
+        run_pack(
+            &[alpha],
+            &PackArgs {
+                skill_ids: vec!["alpha".to_string()],
+                out: bundle.clone(),
+                force: false,
+            },
+        )
+        .expect("pack");
+
+        let dest_root = temp.path().join("dest");
+        write_skill(&dest_root, "alpha", "# local edits");
+        run_unpack(
+            &skills_paths(&dest_root),
+            &UnpackArgs {
+                bundle,
+                dry_run: true,
+            },
+            false,
+            None,
+        )
+        .expect("dry run reports the conflict");
+        assert!(
+            fs::read_to_string(dest_root.join("alpha").join(SKILL_FILE))
+                .expect("read")
+                .contains("# local edits")
+        );
+    }
+
+    #[test]
+    fn test_validate_bundle_dir_name_rejects_traversal() {
+        assert!(validate_bundle_dir_name("alpha").is_ok());
+        assert!(validate_bundle_dir_name("../alpha").is_err());
+        assert!(validate_bundle_dir_name("a/b").is_err());
+        assert!(validate_bundle_dir_name("/abs").is_err());
+        assert!(validate_bundle_dir_name("store").is_err());
+    }
+}
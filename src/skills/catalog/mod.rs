@@ -1,2 +1,3 @@
+pub mod relations;
 pub mod scan;
 pub mod types;
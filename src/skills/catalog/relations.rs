@@ -0,0 +1,304 @@
+use crate::skills::catalog::scan::{SKILL_FILE, SKILL_TEMPLATE_FILE, read_frontmatter_id_list};
+use crate::skills::catalog::types::SkillCandidate;
+use anyhow::{Result, anyhow};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+/// Relationships a skill declares in its frontmatter under `metadata.requires` and
+/// `metadata.conflicts`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SkillRelations {
+    pub requires: Vec<String>,
+    pub conflicts: Vec<String>,
+}
+
+/// Declared relationships for every skill in the catalog, keyed by skill id.
+#[derive(Debug, Clone, Default)]
+pub struct SkillGraph {
+    relations: BTreeMap<String, SkillRelations>,
+}
+
+/// Outcome of resolving a desired skill set against the declared relationships.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SelectionResolution {
+    pub selected: HashSet<String>,
+    /// Requirements added on top of the caller's selection.
+    pub auto_selected: BTreeSet<String>,
+    /// Conflicting pairs that are both selected, each ordered `(a, b)` with `a < b`.
+    pub conflicts: BTreeSet<(String, String)>,
+    /// `(skill, requirement)` pairs whose requirement is not in the catalog.
+    pub missing: BTreeSet<(String, String)>,
+}
+
+impl SkillGraph {
+    pub fn from_skills(skills: &[SkillCandidate]) -> Self {
+        let relations = skills
+            .iter()
+            .map(|skill| {
+                let relations = [SKILL_FILE, SKILL_TEMPLATE_FILE]
+                    .iter()
+                    .map(|name| skill.skill_dir.join(name))
+                    .find(|path| path.is_file())
+                    .map(|path| SkillRelations {
+                        requires: read_frontmatter_id_list(&path, "requires"),
+                        conflicts: read_frontmatter_id_list(&path, "conflicts"),
+                    })
+                    .unwrap_or_default();
+                (skill.skill_id.clone(), relations)
+            })
+            .collect();
+        Self { relations }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn from_relations(relations: BTreeMap<String, SkillRelations>) -> Self {
+        Self { relations }
+    }
+
+    fn requires(&self, skill_id: &str) -> &[String] {
+        self.relations
+            .get(skill_id)
+            .map(|relations| relations.requires.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Transitive requirements of `skill_id` that exist in the catalog, excluding the skill itself.
+    pub fn requirement_closure(&self, skill_id: &str) -> BTreeSet<String> {
+        let mut closure = BTreeSet::new();
+        let mut stack: Vec<&str> = self.requires(skill_id).iter().map(String::as_str).collect();
+        while let Some(next) = stack.pop() {
+            if next == skill_id
+                || !self.relations.contains_key(next)
+                || !closure.insert(next.to_string())
+            {
+                continue;
+            }
+            stack.extend(self.requires(next).iter().map(String::as_str));
+        }
+        closure
+    }
+
+    /// Selected skills that directly require `skill_id`, sorted.
+    pub fn required_by(&self, skill_id: &str, selected: &HashSet<String>) -> Vec<String> {
+        let mut dependents: Vec<String> = selected
+            .iter()
+            .filter(|candidate| candidate.as_str() != skill_id)
+            .filter(|candidate| self.requires(candidate).iter().any(|req| req == skill_id))
+            .cloned()
+            .collect();
+        dependents.sort();
+        dependents
+    }
+
+    /// Conflicting pairs within `selected`. A conflict declared by either side counts.
+    pub fn conflicts_within(&self, selected: &HashSet<String>) -> BTreeSet<(String, String)> {
+        let mut pairs = BTreeSet::new();
+        for skill_id in selected {
+            let Some(relations) = self.relations.get(skill_id) else {
+                continue;
+            };
+            for other in &relations.conflicts {
+                if other != skill_id && selected.contains(other) {
+                    let pair = if skill_id < other {
+                        (skill_id.clone(), other.clone())
+                    } else {
+                        (other.clone(), skill_id.clone())
+                    };
+                    pairs.insert(pair);
+                }
+            }
+        }
+        pairs
+    }
+
+    /// Resolve `desired` for a target whose currently installed skills are `present`.
+    ///
+    /// Requirements of desired skills are added automatically. Dropping a present skill that a
+    /// desired skill still requires is refused unless `force` is set, in which case the skill
+    /// stays disabled and is not re-added as a requirement.
+    pub fn resolve(
+        &self,
+        desired: &HashSet<String>,
+        present: &HashSet<String>,
+        force: bool,
+    ) -> Result<SelectionResolution> {
+        let mut kept_disabled = HashSet::new();
+        let mut removed: Vec<&String> = present
+            .iter()
+            .filter(|skill_id| !desired.contains(*skill_id))
+            .collect();
+        removed.sort();
+        for skill_id in removed {
+            let dependents = self.required_by(skill_id, desired);
+            if dependents.is_empty() {
+                continue;
+            }
+            if !force {
+                return Err(anyhow!(t!(
+                    "skills.relations.disable_required",
+                    skill = skill_id,
+                    dependents = dependents.join(", ")
+                )));
+            }
+            kept_disabled.insert(skill_id.clone());
+        }
+
+        let mut resolution = SelectionResolution {
+            selected: desired.clone(),
+            ..Default::default()
+        };
+        let mut ordered: Vec<&String> = desired.iter().collect();
+        ordered.sort();
+        for skill_id in ordered {
+            for requirement in self.requires(skill_id) {
+                if !self.relations.contains_key(requirement) {
+                    resolution
+                        .missing
+                        .insert((skill_id.clone(), requirement.clone()));
+                }
+            }
+            for requirement in self.requirement_closure(skill_id) {
+                if kept_disabled.contains(&requirement) || desired.contains(&requirement) {
+                    continue;
+                }
+                resolution.selected.insert(requirement.clone());
+                resolution.auto_selected.insert(requirement);
+            }
+        }
+        resolution.conflicts = self.conflicts_within(&resolution.selected);
+        Ok(resolution)
+    }
+}
+
+impl SelectionResolution {
+    /// Print auto-selections and warnings for `target_id` to stderr.
+    pub fn report(&self, target_id: &str) {
+        if !self.auto_selected.is_empty() {
+            let skills: Vec<&str> = self.auto_selected.iter().map(String::as_str).collect();
+            eprintln!(
+                "{}",
+                t!(
+                    "skills.relations.auto_selected",
+                    target = target_id,
+                    skills = skills.join(", ")
+                )
+            );
+        }
+        for (skill, requirement) in &self.missing {
+            eprintln!(
+                "{}",
+                t!(
+                    "skills.relations.missing_requirement",
+                    skill = skill,
+                    requirement = requirement
+                )
+            );
+        }
+        for (left, right) in &self.conflicts {
+            eprintln!(
+                "{}",
+                t!(
+                    "skills.relations.conflict",
+                    target = target_id,
+                    left = left,
+                    right = right
+                )
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn graph(entries: &[(&str, &[&str], &[&str])]) -> SkillGraph {
+        SkillGraph::from_relations(
+            entries
+                .iter()
+                .map(|(id, requires, conflicts)| {
+                    (
+                        id.to_string(),
+                        SkillRelations {
+                            requires: requires.iter().map(|s| s.to_string()).collect(),
+                            conflicts: conflicts.iter().map(|s| s.to_string()).collect(),
+                        },
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    fn set(ids: &[&str]) -> HashSet<String> {
+        ids.iter().map(|id| id.to_string()).collect()
+    }
+
+    #[test]
+    fn test_from_skills_reads_frontmatter_relations() {
+        let temp = TempDir::new().expect("temp dir");
+        let skill_dir = temp.path().join("workflow");
+        fs::create_dir_all(&skill_dir).expect("create skill dir");
+        fs::write(
+            skill_dir.join(SKILL_FILE),
+            "---\nname: workflow\nmetadata:\n  requires: [helper]\n  conflicts: [legacy]\n---\n",
+        )
+        .expect("write skill");
+
+        let graph = SkillGraph::from_skills(&[SkillCandidate {
+            skill_id: "workflow".to_string(),
+            skill_dir,
+        }]);
+        assert_eq!(graph.requires("workflow"), ["helper".to_string()]);
+    }
+
+    #[test]
+    fn test_resolve_auto_selects_transitive_requirements() {
+        let graph = graph(&[
+            ("workflow", &["helper", "ghost"], &[]),
+            ("helper", &["base"], &[]),
+            ("base", &["workflow"], &[]),
+        ]);
+        let resolution = graph
+            .resolve(&set(&["workflow"]), &HashSet::new(), false)
+            .expect("resolve");
+        assert_eq!(resolution.selected, set(&["workflow", "helper", "base"]));
+        assert_eq!(
+            resolution.auto_selected,
+            ["base".to_string(), "helper".to_string()].into()
+        );
+        assert_eq!(
+            resolution.missing,
+            [("workflow".to_string(), "ghost".to_string())].into()
+        );
+    }
+
+    #[test]
+    fn test_resolve_reports_conflicts_declared_by_either_side() {
+        let graph = graph(&[("new", &[], &["old"]), ("old", &[], &[])]);
+        let resolution = graph
+            .resolve(&set(&["new", "old"]), &HashSet::new(), false)
+            .expect("resolve");
+        assert_eq!(
+            resolution.conflicts,
+            [("new".to_string(), "old".to_string())].into()
+        );
+    }
+
+    #[test]
+    fn test_resolve_refuses_disabling_required_skill_unless_forced() {
+        let graph = graph(&[("workflow", &["helper"], &[]), ("helper", &[], &[])]);
+        let present = set(&["workflow", "helper"]);
+
+        let err = graph
+            .resolve(&set(&["workflow"]), &present, false)
+            .expect_err("should refuse");
+        assert!(err.to_string().contains("helper"));
+
+        let resolution = graph
+            .resolve(&set(&["workflow"]), &present, true)
+            .expect("forced");
+        assert_eq!(resolution.selected, set(&["workflow"]));
+        assert!(resolution.auto_selected.is_empty());
+    }
+}
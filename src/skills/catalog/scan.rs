@@ -6,6 +6,11 @@ use std::collections::HashSet;
 use std::fs;
 use std::path::{Path, PathBuf};
 
+/// Skill entry file linked or copied verbatim into targets.
+pub const SKILL_FILE: &str = "SKILL.md";
+/// MiniJinja skill entry file rendered per target into `SKILL.md`.
+pub const SKILL_TEMPLATE_FILE: &str = "SKILL.md.j2";
+
 pub fn discover_skills(root: &Path) -> Result<Vec<SkillCandidate>> {
     discover_skills_with_global_ignore(root, None)
 }
@@ -42,7 +47,10 @@ fn discover_skills_with_global_ignore(
     for entry in walker {
         let entry = entry?;
         let path = entry.path();
-        if path.file_name().is_some_and(|name| name == "SKILL.md") {
+        if path
+            .file_name()
+            .is_some_and(|name| name == SKILL_FILE || name == SKILL_TEMPLATE_FILE)
+        {
             if !skill_file_exists(path) {
                 continue;
             }
@@ -99,32 +107,14 @@ fn resolve_skill_id(skill_dir: &Path, skill_file: &Path) -> String {
 }
 
 fn read_frontmatter_name(path: &Path) -> Option<String> {
-    let content = fs::read_to_string(path).ok()?;
-    let mut lines = content.lines();
-    if lines.next()? != "---" {
-        return None;
-    }
-    let mut yaml = String::new();
-    for line in lines {
-        if line.trim() == "---" {
-            break;
-        }
-        yaml.push_str(line);
-        yaml.push('\n');
-    }
-    if yaml.trim().is_empty() {
-        return None;
-    }
-    let parsed: Value = serde_yaml::from_str(&yaml).ok()?;
-    parsed
+    read_frontmatter(path)?
         .get("name")
         .and_then(|value| value.as_str())
         .map(|value| value.to_string())
 }
 
-/// Read the `metadata.version` field from a SKILL.md frontmatter.
-/// Returns None if the field is missing or cannot be parsed.
-pub fn read_frontmatter_version(path: &Path) -> Option<String> {
+/// Parse the YAML frontmatter block at the top of a SKILL.md file.
+fn read_frontmatter(path: &Path) -> Option<Value> {
     let content = fs::read_to_string(path).ok()?;
     let mut lines = content.lines();
     if lines.next()? != "---" {
@@ -141,14 +131,42 @@ pub fn read_frontmatter_version(path: &Path) -> Option<String> {
     if yaml.trim().is_empty() {
         return None;
     }
-    let parsed: Value = serde_yaml::from_str(&yaml).ok()?;
-    parsed
+    serde_yaml::from_str(&yaml).ok()
+}
+
+/// Read the `metadata.version` field from a SKILL.md frontmatter.
+/// Returns None if the field is missing or cannot be parsed.
+pub fn read_frontmatter_version(path: &Path) -> Option<String> {
+    read_frontmatter(path)?
         .get("metadata")
         .and_then(|m| m.get("version"))
         .and_then(|v| v.as_str())
         .map(|v| v.to_string())
 }
 
+/// Read a `metadata.<key>` list of skill ids from a SKILL.md frontmatter.
+/// A single string is accepted as a one-element list; missing fields yield an empty list.
+pub fn read_frontmatter_id_list(path: &Path, key: &str) -> Vec<String> {
+    let Some(value) = read_frontmatter(path).and_then(|parsed| {
+        parsed
+            .get("metadata")
+            .and_then(|metadata| metadata.get(key))
+            .cloned()
+    }) else {
+        return Vec::new();
+    };
+    let ids: Vec<&str> = match &value {
+        Value::String(id) => vec![id.as_str()],
+        Value::Sequence(items) => items.iter().filter_map(|item| item.as_str()).collect(),
+        _ => Vec::new(),
+    };
+    ids.into_iter()
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
 /// Extract major.minor version from a semver string.
 /// For example, "0.0.50" -> "0.0"
 fn extract_major_minor(version: &str) -> Option<String> {
@@ -195,12 +213,16 @@ fn resolve_symlink_skill_file(path: &Path) -> Option<PathBuf> {
     if !meta.is_dir() {
         return None;
     }
-    let skill_file = path.join("SKILL.md");
-    if skill_file_exists(&skill_file) {
-        Some(skill_file)
-    } else {
-        None
-    }
+    [SKILL_FILE, SKILL_TEMPLATE_FILE]
+        .iter()
+        .map(|name| path.join(name))
+        .find(|skill_file| skill_file_exists(skill_file))
+}
+
+/// Returns `true` when the skill directory ships a `SKILL.md.j2` template, meaning it must be
+/// rendered per target instead of linked.
+pub fn is_template_skill_dir(skill_dir: &Path) -> bool {
+    skill_file_exists(&skill_dir.join(SKILL_TEMPLATE_FILE))
 }
 
 fn skill_file_exists(path: &Path) -> bool {
@@ -317,6 +339,24 @@ mod tests {
         assert_eq!(discovered[0].skill_id, "kept-skill");
     }
 
+    #[test]
+    fn test_discover_includes_template_only_skill() {
+        let temp = TempDir::new().expect("temp dir");
+        let root = temp.path().join("source");
+        let templated = root.join("templated");
+        fs::create_dir_all(&templated).expect("create templated skill");
+        fs::write(
+            templated.join(SKILL_TEMPLATE_FILE),
+            "---\nname: Templated Skill\n---\nUse {{ agent }} tools.\n",
+        )
+        .expect("write template");
+
+        let discovered = discover_skills(&root).expect("discover skills");
+        assert_eq!(discovered.len(), 1);
+        assert_eq!(discovered[0].skill_id, "templated-skill");
+        assert!(is_template_skill_dir(&discovered[0].skill_dir));
+    }
+
     #[test]
     fn test_read_frontmatter_version_with_metadata() {
         let temp = TempDir::new().expect("temp dir");
@@ -351,6 +391,27 @@ mod tests {
         assert_eq!(version, None);
     }
 
+    #[test]
+    fn test_read_frontmatter_id_list_accepts_list_and_single_string() {
+        let temp = TempDir::new().expect("temp dir");
+        let skill_file = temp.path().join("SKILL.md");
+        fs::write(
+            &skill_file,
+            "---\nname: workflow\nmetadata:\n  requires: [git-helper, \" reviewer \"]\n  conflicts: legacy-workflow\n---\n",
+        )
+        .expect("write file");
+
+        assert_eq!(
+            read_frontmatter_id_list(&skill_file, "requires"),
+            vec!["git-helper".to_string(), "reviewer".to_string()]
+        );
+        assert_eq!(
+            read_frontmatter_id_list(&skill_file, "conflicts"),
+            vec!["legacy-workflow".to_string()]
+        );
+        assert!(read_frontmatter_id_list(&skill_file, "missing").is_empty());
+    }
+
     #[test]
     fn test_extract_major_minor() {
         assert_eq!(extract_major_minor("0.0.50"), Some("0.0".to_string()));
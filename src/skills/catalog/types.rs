@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 #[derive(Clone, Debug)]
@@ -8,6 +9,8 @@ pub struct ConfigEntry {
     pub path: PathBuf,
     pub enabled: bool,
     pub mode: TargetMode,
+    /// User-defined template variables for rendered (`SKILL.md.j2`) skills.
+    pub vars: BTreeMap<String, String>,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -26,6 +29,23 @@ pub enum TargetConflictStrategy {
 #[derive(Clone, Debug)]
 pub struct SkillsConfig {
     pub targets: Vec<ConfigEntry>,
+    /// Known agent profiles (built-in plus `[[profile]]` entries), in menu order.
+    pub profiles: Vec<AgentProfile>,
+}
+
+/// Reusable description of an agent tool's skills directories.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AgentProfile {
+    pub agent: String,
+    pub label: String,
+    /// User scope directory (may use `~` and `$VAR`).
+    pub user_path: Option<String>,
+    /// Project scope directory, relative to the repository root.
+    pub project_path: Option<String>,
+    pub user_mode: TargetMode,
+    pub project_mode: TargetMode,
+    /// Directory whose presence marks the tool as installed (optional built-ins only).
+    pub detect_path: Option<String>,
 }
 
 #[derive(Clone, Debug)]
@@ -1,16 +1,20 @@
-use crate::skills::catalog::scan::discover_skills;
+use crate::skills::bundle::archive::{PackArgs, UnpackArgs, run_pack, run_unpack};
+use crate::skills::catalog::relations::SkillGraph;
+use crate::skills::catalog::scan::{SKILL_FILE, SKILL_TEMPLATE_FILE, discover_skills};
 use crate::skills::catalog::types::{
-    ConfigEntry, SkillCandidate, SkillsConfig, SkillsPaths, TargetConflictStrategy, TargetMode,
+    AgentProfile, ConfigEntry, SkillCandidate, SkillsConfig, SkillsPaths, TargetConflictStrategy,
+    TargetMode,
 };
 use crate::skills::cli::interactive::is_interactive;
 use crate::skills::cli::tui_picker;
 use crate::skills::cli::tui_picker::{TuiEntry, TuiEntryKind};
-use crate::skills::config::load_config;
+use crate::skills::config::{builtin_profiles, load_config};
+use crate::skills::report::usage::{ReportArgs, run_report};
 use crate::skills::shared::git::find_git_root;
 use crate::skills::targets::sync::SkillSyncCancelled;
 use crate::skills::targets::sync::{apply_target_diff, is_skill_present};
 use anyhow::Result;
-use clap::{Args, ValueEnum};
+use clap::{Args, Subcommand, ValueEnum};
 use inquire::error::InquireError;
 use inquire::{Confirm, Select};
 use std::collections::{HashMap, HashSet};
@@ -23,12 +27,55 @@ use std::path::{Path, PathBuf};
 #[command(about = "Manage skills", long_about = "Interactive skills manager")]
 pub struct SkillsArgs {
     /// Conflict policy when a target entry already exists (overwrite or skip)
-    #[arg(long = "target-conflict", value_enum)]
+    #[arg(long = "target-conflict", value_enum, global = true)]
     pub target_conflict: Option<TargetConflictArg>,
 
     /// Override skills root directory (env: LLMAN_SKILLS_DIR)
-    #[arg(long = "skills-dir")]
+    #[arg(long = "skills-dir", global = true)]
     pub skills_dir: Option<std::path::PathBuf>,
+
+    /// Disable skills even when an enabled skill declares them in `metadata.requires`
+    #[arg(long)]
+    pub force: bool,
+
+    #[command(subcommand)]
+    pub command: Option<SkillsCommands>,
+}
+
+#[derive(Subcommand)]
+pub enum SkillsCommands {
+    /// Pack skills into a shareable 7z bundle with a digest manifest
+    Pack {
+        /// Skill ids to include
+        #[arg(required = true)]
+        ids: Vec<String>,
+        /// Output bundle path
+        #[arg(long, short = 'o')]
+        out: PathBuf,
+        /// Overwrite an existing bundle file
+        #[arg(long)]
+        force: bool,
+    },
+    /// Verify and unpack a skills bundle into the skills root
+    Unpack {
+        /// Bundle path
+        bundle: PathBuf,
+        /// Show what would be installed without writing
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Report skill usage from local Claude Code / Codex sessions and flag stale skills
+    Report {
+        /// Usage window in days
+        #[arg(long, default_value_t = 30)]
+        days: u32,
+        /// Flag skills whose source has not changed for this many months
+        #[arg(long = "stale-months", default_value_t = 6)]
+        stale_months: u32,
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 #[derive(ValueEnum, Debug, Clone, Copy)]
@@ -87,11 +134,55 @@ pub fn run(args: &SkillsArgs) -> Result<()> {
             )
         );
     }
-    let config = load_config(&paths)?;
     let target_conflict = args.target_conflict.map(TargetConflictStrategy::from);
+    match &args.command {
+        Some(SkillsCommands::Pack { ids, out, force }) => {
+            let skills = dedupe_skills(discover_skills(&paths.root)?);
+            return run_pack(
+                &skills,
+                &PackArgs {
+                    skill_ids: ids.clone(),
+                    out: out.clone(),
+                    force: *force,
+                },
+            );
+        }
+        Some(SkillsCommands::Unpack { bundle, dry_run }) => {
+            return run_unpack(
+                &paths,
+                &UnpackArgs {
+                    bundle: bundle.clone(),
+                    dry_run: *dry_run,
+                },
+                interactive,
+                target_conflict,
+            );
+        }
+        Some(SkillsCommands::Report { .. }) | None => {}
+    }
+
+    let config = load_config(&paths)?;
     let skills = dedupe_skills(discover_skills(&paths.root)?);
     let skill_dir_catalog = build_skill_dir_catalog(&paths.root, &skills)?;
     let runtime_presets = infer_runtime_presets_from_catalog(&skill_dir_catalog);
+    let relations = SkillGraph::from_skills(&skills);
+
+    if let Some(SkillsCommands::Report {
+        days,
+        stale_months,
+        json,
+    }) = &args.command
+    {
+        return run_report(
+            &skills,
+            &config,
+            &ReportArgs {
+                days: *days,
+                stale_months: *stale_months,
+                json: *json,
+            },
+        );
+    }
 
     if skills.is_empty() {
         println!("{}", t!("skills.manager.no_skills"));
@@ -99,21 +190,27 @@ pub fn run(args: &SkillsArgs) -> Result<()> {
     }
 
     if interactive {
-        let Some(interactive_selection) =
-            run_interactive_selection(&skills, &config, &runtime_presets, &skill_dir_catalog)?
+        let Some(interactive_selection) = run_interactive_selection(
+            &skills,
+            &config,
+            &runtime_presets,
+            &skill_dir_catalog,
+            &relations,
+        )?
         else {
             return Ok(());
         };
-        if !confirm_apply(&interactive_selection.target)? {
+        let target = &interactive_selection.target;
+        let resolution = relations.resolve(
+            &interactive_selection.selected,
+            &present_skill_ids(&skills, target),
+            args.force,
+        )?;
+        resolution.report(&target.id);
+        if !confirm_apply(target)? {
             return Ok(());
         }
-        match apply_target_diff(
-            &skills,
-            &interactive_selection.target,
-            &interactive_selection.selected,
-            true,
-            target_conflict,
-        ) {
+        match apply_target_diff(&skills, target, &resolution.selected, true, target_conflict) {
             Ok(()) => {}
             Err(e) if e.is::<SkillSyncCancelled>() => {
                 println!("{}", t!("messages.operation_cancelled"));
@@ -133,7 +230,16 @@ pub fn run(args: &SkillsArgs) -> Result<()> {
                     }
                 })
                 .collect();
-            apply_target_diff(&skills, target, &desired, false, target_conflict)?;
+            let resolution =
+                relations.resolve(&desired, &present_skill_ids(&skills, target), args.force)?;
+            resolution.report(&target.id);
+            apply_target_diff(
+                &skills,
+                target,
+                &resolution.selected,
+                false,
+                target_conflict,
+            )?;
         }
     }
     Ok(())
@@ -144,12 +250,19 @@ fn run_interactive_selection(
     config: &SkillsConfig,
     runtime_presets: &HashMap<String, RuntimePreset>,
     skill_dir_catalog: &SkillDirCatalog,
+    relations: &SkillGraph,
 ) -> Result<Option<InteractiveSelection>> {
     let Some(target) = select_target(config)? else {
         return Ok(None);
     };
-    let Some(selected) =
-        select_skills_for_target(skills, &target, config, skill_dir_catalog, runtime_presets)?
+    let Some(selected) = select_skills_for_target(
+        skills,
+        &target,
+        config,
+        skill_dir_catalog,
+        runtime_presets,
+        relations,
+    )?
     else {
         return Ok(None);
     };
@@ -282,11 +395,12 @@ fn discover_root_skill_dirs(root: &Path) -> Result<HashMap<String, PathBuf>> {
             continue;
         }
 
-        let skill_file = path.join("SKILL.md");
-        if !fs::metadata(&skill_file)
-            .map(|m| m.is_file())
-            .unwrap_or(false)
-        {
+        let has_skill_file = [SKILL_FILE, SKILL_TEMPLATE_FILE].iter().any(|name| {
+            fs::metadata(path.join(name))
+                .map(|m| m.is_file())
+                .unwrap_or(false)
+        });
+        if !has_skill_file {
             continue;
         }
 
@@ -419,11 +533,15 @@ fn selectable_agents(config: &SkillsConfig) -> Vec<AgentChoice> {
         unique.insert(target.agent.clone());
     }
     let mut agents = unique.into_iter().collect::<Vec<_>>();
-    agents.sort_by(|a, b| agent_order(a).cmp(&agent_order(b)).then_with(|| a.cmp(b)));
+    agents.sort_by(|a, b| {
+        agent_order(config, a)
+            .cmp(&agent_order(config, b))
+            .then_with(|| a.cmp(b))
+    });
     agents
         .into_iter()
         .map(|agent| AgentChoice {
-            label: display_agent_label(&agent),
+            label: display_agent_label(config, &agent),
             agent,
         })
         .collect()
@@ -471,25 +589,40 @@ fn scopes_for_agent(config: &SkillsConfig, agent: &str) -> Vec<ScopeChoice> {
     scopes
 }
 
-fn display_agent_label(agent: &str) -> String {
-    agent.to_string()
+/// Configured profiles first (custom ones may relabel built-ins), then built-ins.
+fn find_profile(config: &SkillsConfig, agent: &str) -> Option<(usize, AgentProfile)> {
+    config
+        .profiles
+        .iter()
+        .position(|profile| profile.agent == agent)
+        .map(|index| (index, config.profiles[index].clone()))
+        .or_else(|| {
+            builtin_profiles()
+                .into_iter()
+                .enumerate()
+                .find(|(_, profile)| profile.agent == agent)
+        })
+}
+
+fn display_agent_label(config: &SkillsConfig, agent: &str) -> String {
+    find_profile(config, agent)
+        .map(|(_, profile)| profile.label)
+        .unwrap_or_else(|| agent.to_string())
 }
 
 fn display_scope_label(agent: &str, scope: &str) -> String {
     match (agent, scope) {
-        ("claude", "user") => "Personal (All your projects)".to_string(),
-        ("claude", "project") => "Project (This project only)".to_string(),
         ("agents", "project") => ".agents (This project only)".to_string(),
+        (_, "user") => "Personal (All your projects)".to_string(),
+        (_, "project") => "Project (This project only)".to_string(),
         _ => scope.to_string(),
     }
 }
 
-fn agent_order(agent: &str) -> u8 {
-    match agent {
-        "claude" => 0,
-        "agents" => 1,
-        _ => 2,
-    }
+fn agent_order(config: &SkillsConfig, agent: &str) -> usize {
+    find_profile(config, agent)
+        .map(|(index, _)| index)
+        .unwrap_or(usize::MAX)
 }
 
 /// Returns `true` when the current directory is inside a git repository and
@@ -513,21 +646,21 @@ fn should_prefer_project_scope() -> bool {
 
 fn scope_order(agent: &str, scope: &str, prefer_project_first: bool) -> u8 {
     match (agent, scope) {
-        ("claude", "user") => {
+        ("agents", "project") => 2,
+        (_, "user") => {
             if prefer_project_first {
                 1
             } else {
                 0
             }
         }
-        ("claude", "project") => {
+        (_, "project") => {
             if prefer_project_first {
                 0
             } else {
                 1
             }
         }
-        ("agents", "project") => 2,
         _ => 10,
     }
 }
@@ -538,6 +671,7 @@ fn select_skills_for_target(
     config: &SkillsConfig,
     skill_dir_catalog: &SkillDirCatalog,
     runtime_presets: &HashMap<String, RuntimePreset>,
+    relations: &SkillGraph,
 ) -> Result<Option<HashSet<String>>> {
     let visible_skills = visible_skills_for_target(skills, target, config);
     let hidden_count = skills.len().saturating_sub(visible_skills.len());
@@ -560,6 +694,7 @@ fn select_skills_for_target(
         &t!("skills.manager.select_skills"),
         &tui_entries,
         &default_selected_skills,
+        relations,
     )
 }
 
@@ -846,6 +981,14 @@ fn linked_skill_indexes(skills: &[SkillCandidate], target: &ConfigEntry) -> Vec<
     indexes
 }
 
+fn present_skill_ids(skills: &[SkillCandidate], target: &ConfigEntry) -> HashSet<String> {
+    skills
+        .iter()
+        .filter(|skill| is_skill_present(skill, target))
+        .map(|skill| skill.skill_id.clone())
+        .collect()
+}
+
 fn confirm_apply(target: &crate::skills::catalog::types::ConfigEntry) -> Result<bool> {
     let prompt = t!("skills.manager.confirm_apply", target = target.id);
     let confirmation = match Confirm::new(&prompt).with_default(true).prompt() {
@@ -941,6 +1084,7 @@ mod tests {
                     path: PathBuf::from("/tmp/agents-project"),
                     enabled: true,
                     mode: TargetMode::Link,
+                    vars: Default::default(),
                 },
                 ConfigEntry {
                     id: "claude_user".to_string(),
@@ -949,8 +1093,10 @@ mod tests {
                     path: PathBuf::from("/tmp/claude-user"),
                     enabled: true,
                     mode: TargetMode::Link,
+                    vars: Default::default(),
                 },
             ],
+            profiles: Vec::new(),
         };
 
         let agents = selectable_agents(&config);
@@ -958,6 +1104,44 @@ mod tests {
         assert_eq!(labels, vec!["claude", "agents"]);
     }
 
+    #[test]
+    fn test_selectable_agents_orders_builtin_then_custom_profiles() {
+        let target = |agent: &str| ConfigEntry {
+            id: format!("{agent}_user"),
+            agent: agent.to_string(),
+            scope: "user".to_string(),
+            path: PathBuf::from(format!("/tmp/{agent}")),
+            enabled: true,
+            mode: TargetMode::Link,
+            vars: Default::default(),
+        };
+        let mut profiles = builtin_profiles();
+        profiles.push(AgentProfile {
+            agent: "windsurf".to_string(),
+            label: "Windsurf".to_string(),
+            user_path: Some("~/.windsurf/skills".to_string()),
+            project_path: None,
+            user_mode: TargetMode::Link,
+            project_mode: TargetMode::Copy,
+            detect_path: None,
+        });
+        let config = SkillsConfig {
+            targets: vec![
+                target("zzz"),
+                target("windsurf"),
+                target("gemini"),
+                target("claude"),
+            ],
+            profiles,
+        };
+
+        let labels: Vec<String> = selectable_agents(&config)
+            .into_iter()
+            .map(|choice| choice.label)
+            .collect();
+        assert_eq!(labels, vec!["claude", "gemini", "Windsurf", "zzz"]);
+    }
+
     #[test]
     fn test_scope_label_for_known_agent_and_scope() {
         assert_eq!(
@@ -991,6 +1175,7 @@ mod tests {
                     path: PathBuf::from("/tmp/claude-project"),
                     enabled: true,
                     mode: TargetMode::Link,
+                    vars: Default::default(),
                 },
                 ConfigEntry {
                     id: "claude_user".to_string(),
@@ -999,6 +1184,7 @@ mod tests {
                     path: PathBuf::from("/tmp/claude-user"),
                     enabled: true,
                     mode: TargetMode::Link,
+                    vars: Default::default(),
                 },
                 ConfigEntry {
                     id: "claude_team".to_string(),
@@ -1007,8 +1193,10 @@ mod tests {
                     path: PathBuf::from("/tmp/claude-team"),
                     enabled: true,
                     mode: TargetMode::Link,
+                    vars: Default::default(),
                 },
             ],
+            profiles: Vec::new(),
         };
 
         let scopes = scopes_for_agent(&config, "claude");
@@ -1061,6 +1249,7 @@ mod tests {
                     path: PathBuf::from("/tmp/claude-user"),
                     enabled: true,
                     mode: TargetMode::Link,
+                    vars: Default::default(),
                 },
                 ConfigEntry {
                     id: "claude_project".to_string(),
@@ -1069,8 +1258,10 @@ mod tests {
                     path: PathBuf::from("/tmp/claude-project"),
                     enabled: true,
                     mode: TargetMode::Link,
+                    vars: Default::default(),
                 },
             ],
+            profiles: Vec::new(),
         };
 
         let target = config
@@ -1126,6 +1317,7 @@ mod tests {
                     path: claude_user,
                     enabled: true,
                     mode: TargetMode::Link,
+                    vars: Default::default(),
                 },
                 ConfigEntry {
                     id: "claude_project".to_string(),
@@ -1134,8 +1326,10 @@ mod tests {
                     path: claude_project,
                     enabled: true,
                     mode: TargetMode::Link,
+                    vars: Default::default(),
                 },
             ],
+            profiles: Vec::new(),
         };
 
         let target = config
@@ -1648,6 +1842,7 @@ mod tests {
                     path: claude_user,
                     enabled: true,
                     mode: TargetMode::Link,
+                    vars: Default::default(),
                 },
                 ConfigEntry {
                     id: "claude_project".to_string(),
@@ -1656,8 +1851,10 @@ mod tests {
                     path: claude_project,
                     enabled: true,
                     mode: TargetMode::Link,
+                    vars: Default::default(),
                 },
             ],
+            profiles: Vec::new(),
         };
 
         let target = config
@@ -1712,6 +1909,7 @@ mod tests {
                     path: claude_user,
                     enabled: true,
                     mode: TargetMode::Link,
+                    vars: Default::default(),
                 },
                 ConfigEntry {
                     id: "claude_project".to_string(),
@@ -1720,8 +1918,10 @@ mod tests {
                     path: claude_project,
                     enabled: true,
                     mode: TargetMode::Link,
+                    vars: Default::default(),
                 },
             ],
+            profiles: Vec::new(),
         };
 
         let target = config
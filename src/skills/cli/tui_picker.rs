@@ -1,3 +1,4 @@
+use crate::skills::catalog::relations::SkillGraph;
 use anyhow::Result;
 use crossterm::event::{self, Event, KeyCode, KeyEventKind};
 use ratatui::layout::{Constraint, Layout};
@@ -60,6 +61,7 @@ pub fn pick(
     prompt: &str,
     entries: &[TuiEntry],
     default_selected_skills: &HashSet<String>,
+    relations: &SkillGraph,
 ) -> Result<Option<HashSet<String>>> {
     if entries.is_empty() {
         return Ok(Some(HashSet::new()));
@@ -68,7 +70,8 @@ pub fn pick(
     let mut terminal = ratatui::try_init()?;
     let _restore = TerminalRestoreGuard;
 
-    let mut app = PickerState::new(entries.to_vec(), default_selected_skills);
+    let mut app =
+        PickerState::new(entries.to_vec(), default_selected_skills).with_relations(relations);
 
     loop {
         terminal.draw(|frame| app.draw(frame, prompt))?;
@@ -124,6 +127,8 @@ struct PickerState {
     selected_skills: HashSet<String>,
     filter_query: String,
     search_mode: bool,
+    relations: SkillGraph,
+    notice: Option<String>,
 }
 
 impl PickerState {
@@ -145,9 +150,16 @@ impl PickerState {
             selected_skills,
             filter_query: String::new(),
             search_mode: false,
+            relations: SkillGraph::default(),
+            notice: None,
         }
     }
 
+    fn with_relations(mut self, relations: &SkillGraph) -> Self {
+        self.relations = relations.clone();
+        self
+    }
+
     fn selected_skills(&self) -> HashSet<String> {
         self.selected_skills.clone()
     }
@@ -185,6 +197,7 @@ impl PickerState {
             Constraint::Min(1),
             Constraint::Length(1),
             Constraint::Length(1),
+            Constraint::Length(1),
         ])
         .split(frame.area());
 
@@ -216,11 +229,50 @@ impl PickerState {
             Paragraph::new(Line::from(filter_line)).style(Style::default().fg(Color::DarkGray));
         frame.render_widget(filter, chunks[1]);
 
+        let status = Paragraph::new(Line::from(self.status_line()))
+            .style(Style::default().fg(Color::Yellow));
+        frame.render_widget(status, chunks[2]);
+
         let help = Paragraph::new(Line::from(
             "↑↓ move · space toggle · →/l expand · ←/h collapse · / search · a all · n none · Enter confirm · Esc cancel",
         ))
         .style(Style::default().fg(Color::DarkGray));
-        frame.render_widget(help, chunks[2]);
+        frame.render_widget(help, chunks[3]);
+    }
+
+    /// Latest toggle notice, otherwise any conflicts among the current selection.
+    fn status_line(&self) -> String {
+        if let Some(notice) = &self.notice {
+            return notice.clone();
+        }
+        let conflicts = self.relations.conflicts_within(&self.selected_skills);
+        if conflicts.is_empty() {
+            return String::new();
+        }
+        let pairs: Vec<String> = conflicts
+            .iter()
+            .map(|(left, right)| format!("{left} ↔ {right}"))
+            .collect();
+        format!("Conflicts: {}", pairs.join(", "))
+    }
+
+    /// Select `skill_id` together with its declared requirements.
+    fn select_with_requirements(&mut self, skill_id: &str) -> Vec<String> {
+        self.selected_skills.insert(skill_id.to_string());
+        let mut added = Vec::new();
+        for requirement in self.relations.requirement_closure(skill_id) {
+            if self.is_known_skill(&requirement) && self.selected_skills.insert(requirement.clone())
+            {
+                added.push(requirement);
+            }
+        }
+        added
+    }
+
+    fn is_known_skill(&self, skill_id: &str) -> bool {
+        self.presets
+            .iter()
+            .any(|preset| preset.skill_ids.iter().any(|id| id == skill_id))
     }
 
     fn visible_rows(&self) -> Vec<RowRef> {
@@ -408,16 +460,21 @@ impl PickerState {
             return;
         };
 
+        let skill_ids = preset.skill_ids.clone();
         match self.preset_state(preset_idx) {
             SelectionState::Checked => {
-                for skill_id in &preset.skill_ids {
+                for skill_id in &skill_ids {
                     self.selected_skills.remove(skill_id);
                 }
+                self.notice = None;
             }
             SelectionState::Unchecked | SelectionState::Partial => {
-                for skill_id in &preset.skill_ids {
-                    self.selected_skills.insert(skill_id.clone());
+                let mut added = Vec::new();
+                for skill_id in &skill_ids {
+                    added.extend(self.select_with_requirements(skill_id));
                 }
+                added.retain(|skill_id| !skill_ids.contains(skill_id));
+                self.notice = requirements_notice(&added);
             }
         }
     }
@@ -431,10 +488,14 @@ impl PickerState {
             return;
         };
 
-        if self.selected_skills.contains(skill.skill_id.as_str()) {
-            self.selected_skills.remove(skill.skill_id.as_str());
+        let skill_id = skill.skill_id.clone();
+        if self.selected_skills.remove(skill_id.as_str()) {
+            let dependents = self.relations.required_by(&skill_id, &self.selected_skills);
+            self.notice = (!dependents.is_empty())
+                .then(|| format!("{skill_id} is required by {}", dependents.join(", ")));
         } else {
-            self.selected_skills.insert(skill.skill_id.clone());
+            let added = self.select_with_requirements(&skill_id);
+            self.notice = requirements_notice(&added);
         }
     }
 
@@ -444,10 +505,12 @@ impl PickerState {
                 self.selected_skills.insert(skill_id.clone());
             }
         }
+        self.notice = None;
     }
 
     fn clear_all(&mut self) {
         self.selected_skills.clear();
+        self.notice = None;
     }
 
     fn preset_state(&self, preset_idx: usize) -> SelectionState {
@@ -571,6 +634,10 @@ fn build_presets(entries: Vec<TuiEntry>) -> Vec<PresetNode> {
     presets
 }
 
+fn requirements_notice(added: &[String]) -> Option<String> {
+    (!added.is_empty()).then(|| format!("Also selected required: {}", added.join(", ")))
+}
+
 fn selection_marker(state: SelectionState) -> &'static str {
     match state {
         SelectionState::Unchecked => "[ ]",
@@ -742,4 +809,38 @@ mod tests {
         assert_eq!(state.filter_query, "");
         assert_eq!(state.cursor, 1);
     }
+
+    #[test]
+    fn toggle_skill_auto_selects_requirements_and_warns_on_deselect() {
+        use crate::skills::catalog::relations::SkillRelations;
+
+        let relations = SkillGraph::from_relations(
+            [
+                (
+                    "marimo-editor".to_string(),
+                    SkillRelations {
+                        requires: vec!["marimo-inspect".to_string()],
+                        conflicts: vec!["ruff".to_string()],
+                    },
+                ),
+                ("marimo-inspect".to_string(), SkillRelations::default()),
+            ]
+            .into(),
+        );
+        let mut app =
+            PickerState::new(sample_entries(), &HashSet::new()).with_relations(&relations);
+
+        app.toggle_skill(0, 0);
+        assert!(app.selected_skills.contains("marimo-inspect"));
+        assert_eq!(app.status_line(), "Also selected required: marimo-inspect");
+
+        app.toggle_skill(0, 1);
+        assert_eq!(
+            app.status_line(),
+            "marimo-inspect is required by marimo-editor"
+        );
+
+        app.select_all_skills();
+        assert_eq!(app.status_line(), "Conflicts: marimo-editor ↔ ruff");
+    }
 }
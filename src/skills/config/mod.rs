@@ -1,15 +1,22 @@
 use crate::config::{ENV_CONFIG_DIR, resolve_config_dir};
 use crate::config_schema::{ConfigSchemaKind, validate_yaml_value};
 use crate::path_utils::validate_path_str;
-use crate::skills::catalog::types::{ConfigEntry, SkillsConfig, SkillsPaths, TargetMode};
+use crate::skills::catalog::types::{
+    AgentProfile, ConfigEntry, SkillsConfig, SkillsPaths, TargetMode,
+};
 use crate::skills::shared::git::find_git_root;
 use anyhow::{Result, anyhow};
 use regex::Regex;
 use serde::Deserialize;
+use std::collections::BTreeMap;
 use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
 
+mod profiles;
+
+pub use profiles::{ProfileEnv, builtin_profiles};
+
 const ENV_SKILLS_DIR: &str = "LLMAN_SKILLS_DIR";
 const LLMAN_CONFIG_FILE: &str = "config.yaml";
 const SKILLS_DIR: &str = "skills";
@@ -22,17 +29,35 @@ struct TomlConfig {
     target: Vec<TomlEntry>,
     #[serde(default)]
     source: Vec<TomlEntry>,
+    #[serde(default)]
+    vars: BTreeMap<String, String>,
+    #[serde(default)]
+    profile: Vec<TomlProfile>,
 }
 
 #[derive(Deserialize, Debug)]
 struct TomlEntry {
     id: String,
-    agent: String,
+    agent: Option<String>,
     scope: String,
-    path: String,
+    path: Option<String>,
+    /// Agent profile providing `agent`, `path` and `mode` defaults (defaults to `agent`).
+    profile: Option<String>,
     mode: Option<String>,
     #[serde(default = "default_true")]
     enabled: bool,
+    #[serde(default)]
+    vars: BTreeMap<String, String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct TomlProfile {
+    agent: String,
+    label: Option<String>,
+    user_path: Option<String>,
+    project_path: Option<String>,
+    user_mode: Option<String>,
+    project_mode: Option<String>,
 }
 
 #[derive(Deserialize, Debug)]
@@ -155,6 +180,10 @@ fn load_skills_root_from_config_path(path: &Path, config_dir: &Path) -> Result<O
 }
 
 pub fn load_config(paths: &SkillsPaths) -> Result<SkillsConfig> {
+    load_config_with(paths, &ProfileEnv::current())
+}
+
+fn load_config_with(paths: &SkillsPaths, profile_env: &ProfileEnv) -> Result<SkillsConfig> {
     if paths.config_path.exists() {
         let content = fs::read_to_string(&paths.config_path)
             .map_err(|e| anyhow!(t!("skills.config.read_failed", error = e)))?;
@@ -170,27 +199,106 @@ pub fn load_config(paths: &SkillsPaths) -> Result<SkillsConfig> {
         if !parsed.source.is_empty() {
             return Err(anyhow!(t!("skills.config.sources_removed")));
         }
-        let targets = resolve_target_entries(parsed.target)?;
-        Ok(SkillsConfig { targets })
+        let profiles = profiles::merge_profiles(resolve_profiles(parsed.profile)?);
+        let targets = resolve_target_entries(parsed.target, &parsed.vars, &profiles, profile_env)?;
+        Ok(SkillsConfig { targets, profiles })
     } else {
         Ok(SkillsConfig {
-            targets: default_targets()?,
+            targets: default_targets_from(profile_env)?,
+            profiles: builtin_profiles(),
         })
     }
 }
 
-fn resolve_target_entries(entries: Vec<TomlEntry>) -> Result<Vec<ConfigEntry>> {
+fn resolve_profiles(entries: Vec<TomlProfile>) -> Result<Vec<AgentProfile>> {
     let mut resolved = Vec::new();
     for entry in entries {
-        let mode = parse_target_mode(entry.mode.as_deref())?;
-        let path = expand_path(&entry.path)?;
+        let user_mode = match entry.user_mode.as_deref() {
+            Some(raw) => parse_target_mode(Some(raw))?,
+            None => TargetMode::Link,
+        };
+        let project_mode = match entry.project_mode.as_deref() {
+            Some(raw) => parse_target_mode(Some(raw))?,
+            None => TargetMode::Copy,
+        };
+        resolved.push(AgentProfile {
+            label: entry.label.unwrap_or_else(|| entry.agent.clone()),
+            agent: entry.agent,
+            user_path: entry.user_path,
+            project_path: entry.project_path,
+            user_mode,
+            project_mode,
+            detect_path: None,
+        });
+    }
+    Ok(resolved)
+}
+
+fn resolve_target_entries(
+    entries: Vec<TomlEntry>,
+    global_vars: &BTreeMap<String, String>,
+    profiles: &[AgentProfile],
+    profile_env: &ProfileEnv,
+) -> Result<Vec<ConfigEntry>> {
+    let mut resolved = Vec::new();
+    for entry in entries {
+        let profile_name = entry.profile.as_deref().or(entry.agent.as_deref());
+        let profile = profile_name.and_then(|name| profiles.iter().find(|p| p.agent == name));
+        if let Some(name) = entry.profile.as_deref()
+            && profile.is_none()
+        {
+            return Err(anyhow!(t!(
+                "skills.config.unknown_profile",
+                id = entry.id,
+                profile = name
+            )));
+        }
+
+        let (path, mode) = match entry.path.as_deref() {
+            Some(raw) => (expand_path(raw)?, parse_target_mode(entry.mode.as_deref())?),
+            None => {
+                let Some((path, preferred)) = profile
+                    .map(|profile| {
+                        profiles::resolve_profile_scope(profile, &entry.scope, profile_env)
+                    })
+                    .transpose()?
+                    .flatten()
+                else {
+                    return Err(anyhow!(t!(
+                        "skills.config.target_path_missing",
+                        id = entry.id,
+                        scope = entry.scope
+                    )));
+                };
+                // Read-only project scopes stay read-only even with an explicit mode.
+                let mode = match entry.mode.as_deref() {
+                    Some(raw) if preferred != TargetMode::Skip => parse_target_mode(Some(raw))?,
+                    _ => preferred,
+                };
+                (path, mode)
+            }
+        };
+        let Some(agent) = entry
+            .agent
+            .or_else(|| profile.map(|profile| profile.agent.clone()))
+        else {
+            return Err(anyhow!(t!(
+                "skills.config.target_agent_missing",
+                id = entry.id
+            )));
+        };
+
+        // Target-level vars override the top-level `[vars]` table.
+        let mut vars = global_vars.clone();
+        vars.extend(entry.vars);
         resolved.push(ConfigEntry {
             id: entry.id,
-            agent: entry.agent,
+            agent,
             scope: entry.scope,
             path,
             enabled: entry.enabled,
             mode,
+            vars,
         });
     }
     Ok(resolved)
@@ -208,11 +316,12 @@ fn parse_target_mode(raw: Option<&str>) -> Result<TargetMode> {
     }
 }
 
-fn default_targets() -> Result<Vec<ConfigEntry>> {
-    let cwd = env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
-    let home_dir = crate::config::try_home_dir();
-    let claude_home = env::var("CLAUDE_HOME").ok().map(PathBuf::from);
-    default_targets_with(&cwd, home_dir.as_deref(), claude_home.as_deref())
+fn default_targets_from(profile_env: &ProfileEnv) -> Result<Vec<ConfigEntry>> {
+    default_targets_with(
+        &profile_env.cwd,
+        profile_env.home_dir.as_deref(),
+        profile_env.claude_home.as_deref(),
+    )
 }
 
 fn default_targets_with(
@@ -220,35 +329,38 @@ fn default_targets_with(
     home_dir: Option<&Path>,
     claude_home: Option<&Path>,
 ) -> Result<Vec<ConfigEntry>> {
-    let (claude_project_path, claude_project_mode) = default_repo_scope_dir(cwd, ".claude/skills");
-    let (agents_project_path, agents_project_mode) = default_repo_scope_dir(cwd, ".agents/skills");
-
-    Ok(vec![
-        ConfigEntry {
-            id: "claude_user".to_string(),
-            agent: "claude".to_string(),
-            scope: "user".to_string(),
-            path: default_claude_user_dir_with(claude_home, home_dir)?,
-            enabled: true,
-            mode: TargetMode::Link,
-        },
-        ConfigEntry {
-            id: "claude_project".to_string(),
-            agent: "claude".to_string(),
-            scope: "project".to_string(),
-            path: claude_project_path,
-            enabled: true,
-            mode: claude_project_mode,
-        },
-        ConfigEntry {
-            id: "agents_project".to_string(),
-            agent: "agents".to_string(),
-            scope: "project".to_string(),
-            path: agents_project_path,
-            enabled: true,
-            mode: agents_project_mode,
-        },
-    ])
+    let profile_env = ProfileEnv {
+        cwd: cwd.to_path_buf(),
+        home_dir: home_dir.map(Path::to_path_buf),
+        claude_home: claude_home.map(Path::to_path_buf),
+    };
+
+    let mut targets = Vec::new();
+    for profile in builtin_profiles() {
+        // Optional agents are offered only when installed, and stay opt-in so a plain
+        // `llman skills` run never starts populating directories of tools the user hasn't picked.
+        let always_on = profile.detect_path.is_none();
+        if !always_on && !profiles::is_profile_detected(&profile, home_dir) {
+            continue;
+        }
+        for scope in ["user", "project"] {
+            let Some((path, mode)) =
+                profiles::resolve_profile_scope(&profile, scope, &profile_env)?
+            else {
+                continue;
+            };
+            targets.push(ConfigEntry {
+                id: format!("{}_{}", profile.agent, scope),
+                agent: profile.agent.clone(),
+                scope: scope.to_string(),
+                path,
+                enabled: always_on,
+                mode,
+                vars: Default::default(),
+            });
+        }
+    }
+    Ok(targets)
 }
 
 fn default_claude_user_dir_with(
@@ -487,6 +599,130 @@ mod tests {
         assert_eq!(config.targets[0].mode, TargetMode::Copy);
     }
 
+    #[test]
+    fn test_target_vars_override_global_vars() {
+        let temp = TempDir::new().expect("temp dir");
+        let skills_root = temp.path().join("skills");
+        fs::create_dir_all(&skills_root).expect("create skills root");
+        fs::write(
+            skills_root.join("config.toml"),
+            "version = 2\n\n[vars]\nteam = \"core\"\nshell = \"bash\"\n\n[[target]]\nid = \"claude_user\"\nagent = \"claude\"\nscope = \"user\"\npath = \"~/.claude/skills\"\nvars = { shell = \"zsh\" }\n",
+        )
+        .expect("write config");
+        let paths = SkillsPaths {
+            root: skills_root.clone(),
+            config_path: skills_root.join("config.toml"),
+        };
+        let config = load_config(&paths).expect("config");
+        let vars = &config.targets[0].vars;
+        assert_eq!(vars.get("team").map(String::as_str), Some("core"));
+        assert_eq!(vars.get("shell").map(String::as_str), Some("zsh"));
+    }
+
+    fn write_skills_config(content: &str) -> (TempDir, SkillsPaths) {
+        let temp = TempDir::new().expect("temp dir");
+        let skills_root = temp.path().join("skills");
+        fs::create_dir_all(&skills_root).expect("create skills root");
+        fs::write(skills_root.join("config.toml"), content).expect("write config");
+        let paths = SkillsPaths {
+            root: skills_root.clone(),
+            config_path: skills_root.join("config.toml"),
+        };
+        (temp, paths)
+    }
+
+    #[test]
+    fn test_target_resolves_path_and_mode_from_builtin_profile() {
+        let (temp, paths) = write_skills_config(
+            "version = 2\n\n[[target]]\nid = \"gemini_user\"\nprofile = \"gemini\"\nscope = \"user\"\n\n[[target]]\nid = \"gemini_project\"\nagent = \"gemini\"\nscope = \"project\"\n",
+        );
+        let repo = temp.path().join("repo");
+        fs::create_dir_all(repo.join(".git")).expect("create .git");
+        let home = temp.path().join("home");
+        let profile_env = ProfileEnv {
+            cwd: repo.clone(),
+            home_dir: Some(home.clone()),
+            claude_home: None,
+        };
+
+        let config = load_config_with(&paths, &profile_env).expect("config");
+        assert_eq!(config.targets[0].agent, "gemini");
+        assert_eq!(config.targets[0].path, home.join(".gemini/skills"));
+        assert_eq!(config.targets[0].mode, TargetMode::Link);
+        assert_eq!(config.targets[1].path, repo.join(".gemini/skills"));
+        assert_eq!(config.targets[1].mode, TargetMode::Copy);
+    }
+
+    #[test]
+    fn test_custom_profile_defines_reusable_paths() {
+        let (temp, paths) = write_skills_config(
+            "version = 2\n\n[[profile]]\nagent = \"windsurf\"\nlabel = \"Windsurf\"\nuser_path = \"~/.codeium/windsurf/skills\"\nuser_mode = \"copy\"\n\n[[target]]\nid = \"windsurf_user\"\nprofile = \"windsurf\"\nscope = \"user\"\n",
+        );
+        let home = temp.path().join("home");
+        let profile_env = ProfileEnv {
+            cwd: temp.path().to_path_buf(),
+            home_dir: Some(home.clone()),
+            claude_home: None,
+        };
+
+        let config = load_config_with(&paths, &profile_env).expect("config");
+        assert_eq!(config.targets[0].agent, "windsurf");
+        assert_eq!(
+            config.targets[0].path,
+            home.join(".codeium/windsurf/skills")
+        );
+        assert_eq!(config.targets[0].mode, TargetMode::Copy);
+        let profile = config
+            .profiles
+            .iter()
+            .find(|profile| profile.agent == "windsurf")
+            .expect("custom profile");
+        assert_eq!(profile.label, "Windsurf");
+    }
+
+    #[test]
+    fn test_target_without_path_requires_profile_scope() {
+        let (temp, paths) = write_skills_config(
+            "version = 2\n\n[[target]]\nid = \"agents_user\"\nagent = \"agents\"\nscope = \"user\"\n",
+        );
+        let profile_env = ProfileEnv {
+            cwd: temp.path().to_path_buf(),
+            home_dir: Some(temp.path().join("home")),
+            claude_home: None,
+        };
+        let err = load_config_with(&paths, &profile_env).expect_err("no user path for agents");
+        assert!(err.to_string().contains("agents_user"));
+
+        let (_temp, paths) = write_skills_config(
+            "version = 2\n\n[[target]]\nid = \"x\"\nprofile = \"missing\"\nscope = \"user\"\n",
+        );
+        let err = load_config_with(&paths, &profile_env).expect_err("unknown profile");
+        assert!(err.to_string().contains("missing"));
+    }
+
+    #[test]
+    fn test_default_targets_offer_detected_optional_agents_disabled() {
+        let temp = TempDir::new().expect("temp dir");
+        let cwd = temp.path().join("work");
+        fs::create_dir_all(&cwd).expect("create cwd");
+        let home_root = temp.path().join("home");
+        fs::create_dir_all(home_root.join(".gemini")).expect("create gemini home");
+
+        let targets = default_targets_with(&cwd, Some(&home_root), None).expect("default");
+        let gemini_user = targets
+            .iter()
+            .find(|target| target.id == "gemini_user")
+            .expect("gemini_user target");
+        assert!(!gemini_user.enabled);
+        assert_eq!(gemini_user.path, home_root.join(".gemini/skills"));
+        assert!(targets.iter().all(|target| target.agent != "opencode"));
+        assert!(
+            targets
+                .iter()
+                .any(|target| target.id == "claude_user" && target.enabled)
+        );
+    }
+
     #[test]
     fn test_default_targets_include_repo_scopes_inside_git_repo() {
         let temp = TempDir::new().expect("temp dir");
@@ -0,0 +1,147 @@
+use super::{default_claude_user_dir_with, default_repo_scope_dir, expand_path_with};
+use crate::skills::catalog::types::{AgentProfile, TargetMode};
+use anyhow::Result;
+use std::env;
+use std::path::{Path, PathBuf};
+
+/// Agent whose user scope honours `$CLAUDE_HOME`.
+const CLAUDE_AGENT: &str = "claude";
+
+/// Built-in profiles, in menu order, each labelled with its agent id. `claude` and `agents`
+/// are always part of the default targets; the others are only offered when their user
+/// config directory exists.
+pub fn builtin_profiles() -> Vec<AgentProfile> {
+    vec![
+        builtin(
+            CLAUDE_AGENT,
+            "claude",
+            Some("~/.claude/skills"),
+            Some(".claude/skills"),
+            None,
+        ),
+        builtin("agents", "agents", None, Some(".agents/skills"), None),
+        builtin(
+            "opencode",
+            "opencode",
+            Some("~/.config/opencode/skill"),
+            Some(".opencode/skill"),
+            Some("~/.config/opencode"),
+        ),
+        builtin(
+            "gemini",
+            "gemini",
+            Some("~/.gemini/skills"),
+            Some(".gemini/skills"),
+            Some("~/.gemini"),
+        ),
+        builtin(
+            "cursor",
+            "cursor",
+            Some("~/.cursor/skills"),
+            Some(".cursor/skills"),
+            Some("~/.cursor"),
+        ),
+    ]
+}
+
+fn builtin(
+    agent: &str,
+    label: &str,
+    user_path: Option<&str>,
+    project_path: Option<&str>,
+    detect_path: Option<&str>,
+) -> AgentProfile {
+    AgentProfile {
+        agent: agent.to_string(),
+        label: label.to_string(),
+        user_path: user_path.map(str::to_string),
+        project_path: project_path.map(str::to_string),
+        user_mode: TargetMode::Link,
+        project_mode: TargetMode::Copy,
+        detect_path: detect_path.map(str::to_string),
+    }
+}
+
+/// Merge custom profiles over the built-ins: a custom profile with a built-in agent name
+/// replaces it in place, new agents are appended in declaration order.
+pub fn merge_profiles(custom: Vec<AgentProfile>) -> Vec<AgentProfile> {
+    let mut profiles = builtin_profiles();
+    for profile in custom {
+        match profiles
+            .iter_mut()
+            .find(|existing| existing.agent == profile.agent)
+        {
+            Some(existing) => *existing = profile,
+            None => profiles.push(profile),
+        }
+    }
+    profiles
+}
+
+/// Environment used to resolve profile paths.
+pub struct ProfileEnv {
+    pub cwd: PathBuf,
+    pub home_dir: Option<PathBuf>,
+    pub claude_home: Option<PathBuf>,
+}
+
+impl ProfileEnv {
+    pub fn current() -> Self {
+        Self {
+            cwd: env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
+            home_dir: crate::config::try_home_dir(),
+            claude_home: env::var("CLAUDE_HOME").ok().map(PathBuf::from),
+        }
+    }
+}
+
+/// Resolve the target directory and preferred mode of `profile` for `scope`.
+/// Returns `None` when the profile has no path for that scope.
+pub fn resolve_profile_scope(
+    profile: &AgentProfile,
+    scope: &str,
+    profile_env: &ProfileEnv,
+) -> Result<Option<(PathBuf, TargetMode)>> {
+    match scope {
+        "user" => {
+            let Some(raw) = profile.user_path.as_deref() else {
+                return Ok(None);
+            };
+            let path = if profile.agent == CLAUDE_AGENT && raw == "~/.claude/skills" {
+                default_claude_user_dir_with(
+                    profile_env.claude_home.as_deref(),
+                    profile_env.home_dir.as_deref(),
+                )?
+            } else {
+                expand_path_with(raw, profile_env.home_dir.as_deref(), |key| {
+                    env::var(key).ok()
+                })?
+            };
+            Ok(Some((path, profile.user_mode)))
+        }
+        "project" | "repo" => {
+            let Some(relative) = profile.project_path.as_deref() else {
+                return Ok(None);
+            };
+            let (path, mode) = default_repo_scope_dir(&profile_env.cwd, relative);
+            // Outside a git repository project scopes stay read-only.
+            let mode = if mode == TargetMode::Skip {
+                TargetMode::Skip
+            } else {
+                profile.project_mode
+            };
+            Ok(Some((path, mode)))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Whether an optional built-in profile looks installed on this machine.
+pub fn is_profile_detected(profile: &AgentProfile, home_dir: Option<&Path>) -> bool {
+    let Some(raw) = profile.detect_path.as_deref() else {
+        return true;
+    };
+    expand_path_with(raw, home_dir, |key| env::var(key).ok())
+        .map(|path| path.is_dir())
+        .unwrap_or(false)
+}
@@ -1,5 +1,7 @@
+pub mod bundle;
 pub mod catalog;
 pub mod cli;
 pub mod config;
+pub mod report;
 pub mod shared;
 pub mod targets;
@@ -0,0 +1,371 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+use std::time::SystemTime;
+
+/// Matches a skill entry file referenced from a tool call, e.g. `~/.codex/skills/foo/SKILL.md`.
+static SKILL_PATH_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"skills/([A-Za-z0-9._-]+)/SKILL\.md"#).expect("valid skill path regex")
+});
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SessionSource {
+    ClaudeCode,
+    Codex,
+}
+
+/// One observed use of a skill in a local session transcript.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkillInvocation {
+    pub skill_id: String,
+    pub project: Option<String>,
+    pub timestamp: DateTime<Utc>,
+    pub source: SessionSource,
+}
+
+/// Directories holding local session transcripts.
+#[derive(Debug, Clone, Default)]
+pub struct SessionRoots {
+    /// `~/.claude/projects` (or `$CLAUDE_HOME/projects`).
+    pub claude_projects: Option<PathBuf>,
+    /// `~/.codex/sessions` (or `$CODEX_HOME/sessions`).
+    pub codex_sessions: Option<PathBuf>,
+}
+
+impl SessionRoots {
+    pub fn current() -> Self {
+        let home = crate::config::try_home_dir();
+        let claude_home = non_empty_env("CLAUDE_HOME")
+            .map(PathBuf::from)
+            .or_else(|| home.as_ref().map(|home| home.join(".claude")));
+        let codex_home = non_empty_env("CODEX_HOME")
+            .map(PathBuf::from)
+            .or_else(|| home.as_ref().map(|home| home.join(".codex")));
+        Self {
+            claude_projects: claude_home.map(|dir| dir.join("projects")),
+            codex_sessions: codex_home.map(|dir| dir.join("sessions")),
+        }
+    }
+}
+
+fn non_empty_env(key: &str) -> Option<String> {
+    env::var(key)
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// Collect invocations of `known_skills` recorded at or after `since`. `known_skills` maps
+/// every name a transcript may use (skill name or skill directory) to the skill id.
+/// Unreadable transcripts and malformed lines are skipped.
+pub fn collect_invocations(
+    roots: &SessionRoots,
+    since: DateTime<Utc>,
+    known_skills: &HashMap<String, String>,
+) -> Result<Vec<SkillInvocation>> {
+    let mut invocations = Vec::new();
+    let sources = [
+        (roots.claude_projects.as_deref(), SessionSource::ClaudeCode),
+        (roots.codex_sessions.as_deref(), SessionSource::Codex),
+    ];
+    for (root, source) in sources {
+        let Some(root) = root else {
+            continue;
+        };
+        for path in transcript_files(root, since)? {
+            let Ok(file) = fs::File::open(&path) else {
+                continue;
+            };
+            let mut parser = TranscriptParser::new(source);
+            for line in BufReader::new(file).lines().map_while(|line| line.ok()) {
+                parser.parse_line(&line, since, known_skills, &mut invocations);
+            }
+        }
+    }
+    invocations.sort_by_key(|invocation| invocation.timestamp);
+    Ok(invocations)
+}
+
+/// `*.jsonl` files under `root` modified at or after `since`; older files cannot hold
+/// entries inside the window.
+fn transcript_files(root: &Path, since: DateTime<Utc>) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    if !root.is_dir() {
+        return Ok(files);
+    }
+    let since: SystemTime = since.into();
+    let mut stack = vec![root.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_dir() {
+                stack.push(path);
+            } else if path.extension().is_some_and(|ext| ext == "jsonl")
+                && entry
+                    .metadata()
+                    .and_then(|meta| meta.modified())
+                    .is_ok_and(|modified| modified >= since)
+            {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+struct TranscriptParser {
+    source: SessionSource,
+    /// Codex records the working directory once per session, not per line.
+    session_cwd: Option<String>,
+}
+
+impl TranscriptParser {
+    fn new(source: SessionSource) -> Self {
+        Self {
+            source,
+            session_cwd: None,
+        }
+    }
+
+    fn parse_line(
+        &mut self,
+        line: &str,
+        since: DateTime<Utc>,
+        known_skills: &HashMap<String, String>,
+        out: &mut Vec<SkillInvocation>,
+    ) {
+        let Ok(value) = serde_json::from_str::<Value>(line) else {
+            return;
+        };
+        let mut skill_ids = Vec::new();
+        let project = match self.source {
+            SessionSource::ClaudeCode => {
+                claude_skill_ids(&value, &mut skill_ids);
+                value.get("cwd").and_then(Value::as_str).map(str::to_string)
+            }
+            SessionSource::Codex => {
+                let payload = value.get("payload");
+                if let Some(cwd) = payload
+                    .and_then(|payload| payload.get("cwd"))
+                    .and_then(Value::as_str)
+                {
+                    self.session_cwd = Some(cwd.to_string());
+                }
+                if value.get("type").and_then(Value::as_str) == Some("response_item")
+                    && let Some(payload) = payload
+                {
+                    codex_skill_ids(payload, &mut skill_ids);
+                }
+                self.session_cwd.clone()
+            }
+        };
+        if skill_ids.is_empty() {
+            return;
+        }
+        let Some(timestamp) = value
+            .get("timestamp")
+            .and_then(Value::as_str)
+            .and_then(|raw| DateTime::parse_from_rfc3339(raw).ok())
+            .map(|ts| ts.with_timezone(&Utc))
+        else {
+            return;
+        };
+        if timestamp < since {
+            return;
+        }
+        let mut seen = HashSet::new();
+        for name in skill_ids {
+            if let Some(skill_id) = known_skills.get(&name)
+                && seen.insert(skill_id)
+            {
+                out.push(SkillInvocation {
+                    skill_id: skill_id.clone(),
+                    project: project.clone(),
+                    timestamp,
+                    source: self.source,
+                });
+            }
+        }
+    }
+}
+
+/// Skills used by a Claude Code assistant message: `Skill` tool calls, plus tool calls that
+/// read a skill's `SKILL.md` directly.
+fn claude_skill_ids(value: &Value, out: &mut Vec<String>) {
+    let Some(content) = value
+        .get("message")
+        .and_then(|message| message.get("content"))
+        .and_then(Value::as_array)
+    else {
+        return;
+    };
+    for item in content {
+        if item.get("type").and_then(Value::as_str) != Some("tool_use") {
+            continue;
+        }
+        let input = item.get("input").unwrap_or(&Value::Null);
+        if item.get("name").and_then(Value::as_str) == Some("Skill") {
+            if let Some(name) = ["skill", "command"]
+                .iter()
+                .find_map(|key| input.get(*key).and_then(Value::as_str))
+            {
+                out.push(normalize_skill_name(name));
+            }
+            continue;
+        }
+        skill_ids_in_text(&input.to_string(), out);
+    }
+}
+
+/// Skills whose `SKILL.md` a Codex tool call touched (function, custom or local shell calls).
+fn codex_skill_ids(payload: &Value, out: &mut Vec<String>) {
+    let is_tool_call = payload
+        .get("type")
+        .and_then(Value::as_str)
+        .is_some_and(|kind| kind.ends_with("_call"));
+    if !is_tool_call {
+        return;
+    }
+    for key in ["arguments", "input", "action"] {
+        match payload.get(key) {
+            Some(Value::String(text)) => skill_ids_in_text(text, out),
+            Some(other) => skill_ids_in_text(&other.to_string(), out),
+            None => {}
+        }
+    }
+}
+
+fn skill_ids_in_text(text: &str, out: &mut Vec<String>) {
+    let mut seen = HashSet::new();
+    for captures in SKILL_PATH_RE.captures_iter(text) {
+        let id = captures[1].to_string();
+        if seen.insert(id.clone()) {
+            out.push(id);
+        }
+    }
+}
+
+/// `/plugin:name` and `plugin:name` both refer to skill `name`.
+fn normalize_skill_name(raw: &str) -> String {
+    let trimmed = raw.trim().trim_start_matches('/');
+    trimmed
+        .rsplit_once(':')
+        .map(|(_, name)| name)
+        .unwrap_or(trimmed)
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn known(ids: &[&str]) -> HashMap<String, String> {
+        ids.iter()
+            .map(|id| (id.to_string(), id.to_string()))
+            .collect()
+    }
+
+    fn since() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2026-01-01T00:00:00Z")
+            .expect("timestamp")
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_collects_claude_skill_tool_calls_and_skill_file_reads() {
+        let temp = TempDir::new().expect("temp dir");
+        let project_dir = temp.path().join("projects").join("-work-app");
+        fs::create_dir_all(&project_dir).expect("project dir");
+        let lines = [
+            r#"{"type":"assistant","timestamp":"2026-02-01T10:00:00Z","cwd":"/work/app","message":{"content":[{"type":"tool_use","name":"Skill","input":{"skill":"team:reviewer"}}]}}"#,
+            r#"{"type":"assistant","timestamp":"2026-02-02T10:00:00Z","cwd":"/work/app","message":{"content":[{"type":"tool_use","name":"Read","input":{"file_path":"/home/u/.claude/skills/git-helper/SKILL.md"}}]}}"#,
+            r#"{"type":"assistant","timestamp":"2025-12-01T10:00:00Z","cwd":"/work/app","message":{"content":[{"type":"tool_use","name":"Skill","input":{"skill":"reviewer"}}]}}"#,
+            r#"{"type":"assistant","timestamp":"2026-02-03T10:00:00Z","cwd":"/work/app","message":{"content":[{"type":"tool_use","name":"Skill","input":{"skill":"unknown"}}]}}"#,
+            "not json",
+        ];
+        fs::write(project_dir.join("session.jsonl"), lines.join("\n")).expect("write");
+
+        let roots = SessionRoots {
+            claude_projects: Some(temp.path().join("projects")),
+            codex_sessions: None,
+        };
+        let invocations = collect_invocations(&roots, since(), &known(&["reviewer", "git-helper"]))
+            .expect("collect");
+        let ids: Vec<&str> = invocations.iter().map(|i| i.skill_id.as_str()).collect();
+        assert_eq!(ids, vec!["reviewer", "git-helper"]);
+        assert!(
+            invocations
+                .iter()
+                .all(|i| i.project.as_deref() == Some("/work/app")
+                    && i.source == SessionSource::ClaudeCode)
+        );
+    }
+
+    #[test]
+    fn test_collects_codex_skill_reads_with_session_cwd() {
+        let temp = TempDir::new().expect("temp dir");
+        let day_dir = temp
+            .path()
+            .join("sessions")
+            .join("2026")
+            .join("02")
+            .join("01");
+        fs::create_dir_all(&day_dir).expect("day dir");
+        let lines = [
+            r#"{"timestamp":"2026-02-01T09:00:00Z","type":"session_meta","payload":{"cwd":"/work/api"}}"#,
+            r#"{"timestamp":"2026-02-01T09:01:00Z","type":"response_item","payload":{"type":"function_call","name":"shell","arguments":"{\"command\":[\"bash\",\"-lc\",\"cat ~/.codex/skills/git-helper/SKILL.md\"]}"}}"#,
+            r#"{"timestamp":"2026-02-01T09:02:00Z","type":"response_item","payload":{"type":"message","content":"skills/git-helper/SKILL.md"}}"#,
+        ];
+        fs::write(day_dir.join("rollout.jsonl"), lines.join("\n")).expect("write");
+
+        let roots = SessionRoots {
+            claude_projects: None,
+            codex_sessions: Some(temp.path().join("sessions")),
+        };
+        let invocations =
+            collect_invocations(&roots, since(), &known(&["git-helper"])).expect("collect");
+        assert_eq!(invocations.len(), 1);
+        assert_eq!(invocations[0].project.as_deref(), Some("/work/api"));
+        assert_eq!(invocations[0].source, SessionSource::Codex);
+    }
+
+    #[test]
+    fn test_skill_file_reads_map_directory_to_skill_id() {
+        let temp = TempDir::new().expect("temp dir");
+        let project_dir = temp.path().join("projects").join("-work-app");
+        fs::create_dir_all(&project_dir).expect("project dir");
+        let line = r#"{"type":"assistant","timestamp":"2026-02-02T10:00:00Z","cwd":"/work/app","message":{"content":[{"type":"tool_use","name":"Read","input":{"file_path":"/home/u/.llman/skills/git-tools/SKILL.md"}}]}}"#;
+        fs::write(project_dir.join("session.jsonl"), line).expect("write");
+
+        let roots = SessionRoots {
+            claude_projects: Some(temp.path().join("projects")),
+            codex_sessions: None,
+        };
+        let mut aliases = known(&["git-helper"]);
+        aliases.insert("git-tools".to_string(), "git-helper".to_string());
+        let invocations = collect_invocations(&roots, since(), &aliases).expect("collect");
+        assert_eq!(invocations.len(), 1);
+        assert_eq!(invocations[0].skill_id, "git-helper");
+    }
+
+    #[test]
+    fn test_normalize_skill_name_strips_slash_and_plugin_prefix() {
+        assert_eq!(normalize_skill_name("/plugin:skill"), "skill");
+        assert_eq!(normalize_skill_name("skill"), "skill");
+    }
+}
@@ -0,0 +1,366 @@
+use crate::skills::catalog::types::{SkillCandidate, SkillsConfig};
+use crate::skills::report::sessions::{SessionRoots, SkillInvocation, collect_invocations};
+use crate::skills::targets::sync::{TargetEntryState, target_entry_state};
+use anyhow::Result;
+use chrono::{DateTime, Duration, Months, Utc};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+use std::time::SystemTime;
+
+/// Placeholder project for transcript lines without a working directory.
+const UNKNOWN_PROJECT: &str = "(unknown)";
+
+#[derive(Debug, Clone)]
+pub struct ReportArgs {
+    /// Usage window in days, counted back from now.
+    pub days: u32,
+    /// Sources untouched for at least this many months are reported as stale.
+    pub stale_months: u32,
+    pub json: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UsageReport {
+    pub generated_at: DateTime<Utc>,
+    pub window_days: u32,
+    pub stale_months: u32,
+    pub skills: Vec<SkillUsage>,
+    pub never_used: Vec<String>,
+    pub stale: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SkillUsage {
+    pub skill_id: String,
+    pub targets: Vec<TargetPresence>,
+    pub uses: usize,
+    pub projects: BTreeMap<String, usize>,
+    pub last_used: Option<DateTime<Utc>>,
+    pub source_modified: Option<DateTime<Utc>>,
+    pub stale: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TargetPresence {
+    pub target: String,
+    pub state: TargetEntryState,
+}
+
+pub fn run_report(
+    skills: &[SkillCandidate],
+    config: &SkillsConfig,
+    args: &ReportArgs,
+) -> Result<()> {
+    let now = Utc::now();
+    let since = now - Duration::days(i64::from(args.days));
+    let invocations = collect_invocations(&SessionRoots::current(), since, &skill_aliases(skills))?;
+    let report = build_report(skills, config, &invocations, now, args)?;
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print_report(&report);
+    }
+    Ok(())
+}
+
+/// Names transcripts use for each skill: its id (`Skill` tool calls, target entries) and its
+/// source directory (`SKILL.md` paths under the skills root), which may differ from the id.
+fn skill_aliases(skills: &[SkillCandidate]) -> HashMap<String, String> {
+    let mut aliases = HashMap::new();
+    for skill in skills {
+        if let Some(dir) = skill.skill_dir.file_name().and_then(|name| name.to_str()) {
+            aliases.insert(dir.to_string(), skill.skill_id.clone());
+        }
+    }
+    // An exact skill id wins over another skill's directory of the same name.
+    for skill in skills {
+        aliases.insert(skill.skill_id.clone(), skill.skill_id.clone());
+    }
+    aliases
+}
+
+pub fn build_report(
+    skills: &[SkillCandidate],
+    config: &SkillsConfig,
+    invocations: &[SkillInvocation],
+    now: DateTime<Utc>,
+    args: &ReportArgs,
+) -> Result<UsageReport> {
+    let stale_before = now
+        .checked_sub_months(Months::new(args.stale_months))
+        .unwrap_or(DateTime::<Utc>::MIN_UTC);
+
+    let mut entries = Vec::new();
+    for skill in skills {
+        let mut targets = Vec::new();
+        for target in &config.targets {
+            let state = target_entry_state(skill, target)?;
+            if state != TargetEntryState::Absent {
+                targets.push(TargetPresence {
+                    target: target.id.clone(),
+                    state,
+                });
+            }
+        }
+
+        let mut projects = BTreeMap::new();
+        let mut last_used = None;
+        for invocation in invocations
+            .iter()
+            .filter(|invocation| invocation.skill_id == skill.skill_id)
+        {
+            let project = invocation
+                .project
+                .clone()
+                .unwrap_or_else(|| UNKNOWN_PROJECT.to_string());
+            *projects.entry(project).or_insert(0) += 1;
+            last_used = last_used.max(Some(invocation.timestamp));
+        }
+
+        let source_modified = latest_modification(&skill.skill_dir)?.map(DateTime::<Utc>::from);
+        let stale = source_modified.is_some_and(|modified| modified <= stale_before);
+        entries.push(SkillUsage {
+            skill_id: skill.skill_id.clone(),
+            targets,
+            uses: projects.values().sum(),
+            projects,
+            last_used,
+            source_modified,
+            stale,
+        });
+    }
+
+    // Most used first, then alphabetical.
+    entries.sort_by(|a, b| b.uses.cmp(&a.uses).then(a.skill_id.cmp(&b.skill_id)));
+    let mut never_used: Vec<String> = entries
+        .iter()
+        .filter(|entry| entry.uses == 0)
+        .map(|entry| entry.skill_id.clone())
+        .collect();
+    never_used.sort();
+    let mut stale: Vec<String> = entries
+        .iter()
+        .filter(|entry| entry.stale)
+        .map(|entry| entry.skill_id.clone())
+        .collect();
+    stale.sort();
+
+    Ok(UsageReport {
+        generated_at: now,
+        window_days: args.days,
+        stale_months: args.stale_months,
+        skills: entries,
+        never_used,
+        stale,
+    })
+}
+
+/// Newest modification time of any file under `dir`, following symlinks.
+fn latest_modification(dir: &Path) -> Result<Option<SystemTime>> {
+    let mut latest = None;
+    let mut stack = vec![dir.to_path_buf()];
+    while let Some(current) = stack.pop() {
+        let Ok(entries) = fs::read_dir(&current) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.file_name().is_some_and(|name| name == ".git") {
+                continue;
+            }
+            let Ok(meta) = fs::metadata(&path) else {
+                continue;
+            };
+            if meta.is_dir() {
+                stack.push(path);
+            } else if let Ok(modified) = meta.modified() {
+                latest = latest.max(Some(modified));
+            }
+        }
+    }
+    Ok(latest)
+}
+
+fn print_report(report: &UsageReport) {
+    println!("{}", t!("skills.report.header", days = report.window_days));
+    for entry in &report.skills {
+        let targets = if entry.targets.is_empty() {
+            t!("skills.report.no_targets").to_string()
+        } else {
+            entry
+                .targets
+                .iter()
+                .map(|presence| format!("{} ({})", presence.target, presence.state))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let last_used = entry
+            .last_used
+            .map(|ts| ts.format("%Y-%m-%d").to_string())
+            .unwrap_or_else(|| "-".to_string());
+        println!(
+            "{}",
+            t!(
+                "skills.report.skill_line",
+                skill = entry.skill_id,
+                uses = entry.uses,
+                last_used = last_used,
+                targets = targets
+            )
+        );
+        for (project, count) in &entry.projects {
+            println!("    {project}: {count}");
+        }
+    }
+
+    println!();
+    if report.never_used.is_empty() {
+        println!("{}", t!("skills.report.all_used"));
+    } else {
+        println!("{}", t!("skills.report.never_used"));
+        for skill_id in &report.never_used {
+            println!("  - {skill_id}");
+        }
+    }
+
+    println!();
+    if report.stale.is_empty() {
+        println!(
+            "{}",
+            t!("skills.report.none_stale", months = report.stale_months)
+        );
+    } else {
+        println!(
+            "{}",
+            t!("skills.report.stale", months = report.stale_months)
+        );
+        for entry in report.skills.iter().filter(|entry| entry.stale) {
+            let modified = entry
+                .source_modified
+                .map(|ts| ts.format("%Y-%m-%d").to_string())
+                .unwrap_or_default();
+            println!(
+                "{}",
+                t!(
+                    "skills.report.stale_item",
+                    skill = entry.skill_id,
+                    modified = modified
+                )
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::skills::catalog::types::{ConfigEntry, TargetMode};
+    use crate::skills::report::sessions::SessionSource;
+    use std::fs::FileTimes;
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    fn ts(raw: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(raw)
+            .expect("timestamp")
+            .with_timezone(&Utc)
+    }
+
+    fn invocation(skill_id: &str, project: Option<&str>, at: &str) -> SkillInvocation {
+        SkillInvocation {
+            skill_id: skill_id.to_string(),
+            project: project.map(str::to_string),
+            timestamp: ts(at),
+            source: SessionSource::ClaudeCode,
+        }
+    }
+
+    #[test]
+    fn test_skill_aliases_map_source_directory_to_skill_id() {
+        let skills = vec![
+            SkillCandidate {
+                skill_id: "git-helper".to_string(),
+                skill_dir: PathBuf::from("/skills/git-tools"),
+            },
+            SkillCandidate {
+                skill_id: "git-tools".to_string(),
+                skill_dir: PathBuf::from("/skills/other"),
+            },
+        ];
+        let aliases = skill_aliases(&skills);
+        assert_eq!(aliases["git-helper"], "git-helper");
+        assert_eq!(aliases["git-tools"], "git-tools");
+        assert_eq!(aliases["other"], "git-tools");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_build_report_counts_usage_and_flags_unused_and_stale() {
+        let temp = TempDir::new().expect("temp dir");
+        let root = temp.path();
+        let mut skills = Vec::new();
+        for id in ["used", "idle"] {
+            let dir = root.join("skills").join(id);
+            fs::create_dir_all(&dir).expect("skill dir");
+            fs::write(dir.join("SKILL.md"), id).expect("write skill");
+            skills.push(SkillCandidate {
+                skill_id: id.to_string(),
+                skill_dir: dir,
+            });
+        }
+        let old: SystemTime = ts("2025-01-01T00:00:00Z").into();
+        fs::File::options()
+            .write(true)
+            .open(skills[1].skill_dir.join("SKILL.md"))
+            .expect("open")
+            .set_times(FileTimes::new().set_modified(old))
+            .expect("set mtime");
+
+        let target_root = root.join("target");
+        fs::create_dir_all(&target_root).expect("target dir");
+        std::os::unix::fs::symlink(&skills[0].skill_dir, target_root.join("used")).expect("link");
+        let config = SkillsConfig {
+            targets: vec![ConfigEntry {
+                id: "claude_user".to_string(),
+                agent: "claude".to_string(),
+                scope: "user".to_string(),
+                path: target_root,
+                enabled: true,
+                mode: TargetMode::Link,
+                vars: Default::default(),
+            }],
+            profiles: Vec::new(),
+        };
+        let invocations = vec![
+            invocation("used", Some("/work/a"), "2026-02-01T00:00:00Z"),
+            invocation("used", Some("/work/a"), "2026-02-03T00:00:00Z"),
+            invocation("used", None, "2026-02-02T00:00:00Z"),
+        ];
+        let args = ReportArgs {
+            days: 30,
+            stale_months: 6,
+            json: false,
+        };
+
+        let report = build_report(
+            &skills,
+            &config,
+            &invocations,
+            ts("2026-02-10T00:00:00Z"),
+            &args,
+        )
+        .expect("report");
+        let used = &report.skills[0];
+        assert_eq!(used.skill_id, "used");
+        assert_eq!(used.uses, 3);
+        assert_eq!(used.projects.get("/work/a"), Some(&2));
+        assert_eq!(used.projects.get(UNKNOWN_PROJECT), Some(&1));
+        assert_eq!(used.last_used, Some(ts("2026-02-03T00:00:00Z")));
+        assert_eq!(used.targets.len(), 1);
+        assert_eq!(used.targets[0].state, TargetEntryState::Linked);
+        assert_eq!(report.never_used, vec!["idle".to_string()]);
+        assert_eq!(report.stale, vec!["idle".to_string()]);
+    }
+}
@@ -0,0 +1,206 @@
+use crate::sdd::project::templates::render_with_units;
+use crate::skills::catalog::scan::is_template_skill_dir;
+use crate::skills::catalog::types::{ConfigEntry, SkillCandidate};
+use crate::skills::targets::sync::{compute_dir_digest, copy_dir_all_follow_links, hex_lower};
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+/// Per-skill marker recording which inputs produced a rendered copy.
+pub(crate) const RENDER_METADATA_FILE: &str = ".llman-render.json";
+
+const TEMPLATE_SUFFIX: &str = ".j2";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct RenderMetadata {
+    pub inputs_digest: String,
+    pub output_digest: String,
+}
+
+/// A skill rendered for one target into a temporary directory.
+pub(crate) struct RenderedSkill {
+    _temp: TempDir,
+    dir: PathBuf,
+    pub metadata: RenderMetadata,
+}
+
+impl RenderedSkill {
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+}
+
+pub fn is_template_skill(skill: &SkillCandidate) -> bool {
+    is_template_skill_dir(&skill.skill_dir)
+}
+
+/// Variables exposed to skill templates. Built-ins (`agent`, `scope`, `target_id`, `skill_id`,
+/// `llman_version`) take precedence over user-defined `vars` from the skills config.
+pub fn template_vars(skill: &SkillCandidate, target: &ConfigEntry) -> BTreeMap<String, String> {
+    let mut vars = target.vars.clone();
+    vars.insert("agent".to_string(), target.agent.clone());
+    vars.insert("scope".to_string(), target.scope.clone());
+    vars.insert("target_id".to_string(), target.id.clone());
+    vars.insert("skill_id".to_string(), skill.skill_id.clone());
+    vars.insert(
+        "llman_version".to_string(),
+        env!("CARGO_PKG_VERSION").to_string(),
+    );
+    vars
+}
+
+pub(crate) fn render_skill(skill: &SkillCandidate, target: &ConfigEntry) -> Result<RenderedSkill> {
+    let temp = TempDir::new()?;
+    let dir = temp.path().join(&skill.skill_id);
+    copy_dir_all_follow_links(&skill.skill_dir, &dir)?;
+
+    let vars = template_vars(skill, target);
+    render_templates_in_dir(&dir, &dir, &vars)?;
+
+    let inputs_digest = compute_inputs_digest(&skill.skill_dir, &vars)?;
+    let output_digest = compute_dir_digest(&dir)?;
+    Ok(RenderedSkill {
+        _temp: temp,
+        dir,
+        metadata: RenderMetadata {
+            inputs_digest,
+            output_digest,
+        },
+    })
+}
+
+fn render_templates_in_dir(root: &Path, dir: &Path, vars: &BTreeMap<String, String>) -> Result<()> {
+    let mut entries = fs::read_dir(dir)?.collect::<std::result::Result<Vec<_>, _>>()?;
+    entries.sort_by_key(|entry| entry.file_name());
+
+    for entry in entries {
+        let path = entry.path();
+        if entry.file_type()?.is_dir() {
+            render_templates_in_dir(root, &path, vars)?;
+            continue;
+        }
+        let name = entry.file_name().to_string_lossy().to_string();
+        let Some(output_name) = name.strip_suffix(TEMPLATE_SUFFIX) else {
+            continue;
+        };
+        if output_name.is_empty() {
+            continue;
+        }
+
+        let rel = path.strip_prefix(root).unwrap_or(&path);
+        let raw = fs::read_to_string(&path)?;
+        let rendered = render_with_units(&raw, &BTreeMap::new(), vars, 0)
+            .map_err(|e| anyhow!(t!("skills.render.failed", path = rel.display(), error = e)))?;
+        fs::write(dir.join(output_name), rendered)?;
+        fs::remove_file(&path)?;
+    }
+    Ok(())
+}
+
+fn compute_inputs_digest(skill_dir: &Path, vars: &BTreeMap<String, String>) -> Result<String> {
+    let mut hasher = Sha256::new();
+    hasher.update(compute_dir_digest(skill_dir)?.as_bytes());
+    hasher.update([0u8]);
+    for (key, value) in vars {
+        hasher.update(key.as_bytes());
+        hasher.update([0u8]);
+        hasher.update(value.as_bytes());
+        hasher.update([0u8]);
+    }
+    Ok(hex_lower(&hasher.finalize()))
+}
+
+pub(crate) fn read_render_metadata(entry_path: &Path) -> Option<RenderMetadata> {
+    let content = fs::read_to_string(entry_path.join(RENDER_METADATA_FILE)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+pub(crate) fn write_render_metadata(entry_path: &Path, metadata: &RenderMetadata) -> Result<()> {
+    let content = serde_json::to_string_pretty(metadata)?;
+    fs::write(
+        entry_path.join(RENDER_METADATA_FILE),
+        format!("{content}\n"),
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::skills::catalog::scan::SKILL_TEMPLATE_FILE;
+    use crate::skills::catalog::types::TargetMode;
+
+    fn target(agent: &str, vars: &[(&str, &str)]) -> ConfigEntry {
+        ConfigEntry {
+            id: format!("{agent}_project"),
+            agent: agent.to_string(),
+            scope: "project".to_string(),
+            path: PathBuf::from("/tmp/unused"),
+            enabled: true,
+            mode: TargetMode::Link,
+            vars: vars
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_render_skill_substitutes_agent_and_user_vars() {
+        let temp = TempDir::new().expect("temp dir");
+        let skill_dir = temp.path().join("skill");
+        fs::create_dir_all(skill_dir.join("docs")).expect("create skill dir");
+        fs::write(
+            skill_dir.join(SKILL_TEMPLATE_FILE),
+            "{% if agent == \"claude\" %}Use Bash{% else %}Use shell{% endif %} for {{ team }}.",
+        )
+        .expect("write template");
+        fs::write(skill_dir.join("docs").join("notes.md.j2"), "{{ scope }}").expect("write");
+        fs::write(skill_dir.join("plain.txt"), "{{ untouched }}").expect("write plain");
+
+        let skill = SkillCandidate {
+            skill_id: "skill".to_string(),
+            skill_dir,
+        };
+        let rendered =
+            render_skill(&skill, &target("claude", &[("team", "core")])).expect("render");
+        let dir = rendered.dir();
+        assert_eq!(
+            fs::read_to_string(dir.join("SKILL.md")).expect("read"),
+            "Use Bash for core."
+        );
+        assert!(!dir.join(SKILL_TEMPLATE_FILE).exists());
+        assert_eq!(
+            fs::read_to_string(dir.join("docs").join("notes.md")).expect("read"),
+            "project"
+        );
+        assert_eq!(
+            fs::read_to_string(dir.join("plain.txt")).expect("read"),
+            "{{ untouched }}"
+        );
+
+        let other = render_skill(&skill, &target("agents", &[("team", "core")])).expect("render");
+        assert_eq!(
+            fs::read_to_string(other.dir().join("SKILL.md")).expect("read"),
+            "Use shell for core."
+        );
+        assert_ne!(
+            rendered.metadata.inputs_digest,
+            other.metadata.inputs_digest
+        );
+    }
+
+    #[test]
+    fn test_builtin_vars_take_precedence_over_user_vars() {
+        let skill = SkillCandidate {
+            skill_id: "skill".to_string(),
+            skill_dir: PathBuf::from("/tmp/skill"),
+        };
+        let vars = template_vars(&skill, &target("claude", &[("agent", "other")]));
+        assert_eq!(vars.get("agent").map(String::as_str), Some("claude"));
+    }
+}
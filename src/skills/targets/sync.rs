@@ -2,11 +2,17 @@ use crate::path_utils::relative_path_from_dir;
 use crate::skills::catalog::types::{
     ConfigEntry, SkillCandidate, SkillsConfig, TargetConflictStrategy, TargetMode,
 };
+use crate::skills::targets::render::{
+    RENDER_METADATA_FILE, RenderedSkill, is_template_skill, read_render_metadata, render_skill,
+    write_render_metadata,
+};
 use anyhow::{Context, Result, anyhow};
 use inquire::Select;
 use inquire::error::InquireError;
+use serde::Serialize;
 use sha2::{Digest, Sha256};
 use std::collections::{HashMap, HashSet};
+use std::fmt;
 use std::fs;
 use std::io::{ErrorKind, Read};
 use std::path::{Path, PathBuf};
@@ -64,14 +70,30 @@ where
 {
     match target.mode {
         TargetMode::Skip => Ok(()),
-        TargetMode::Link => apply_target_diff_link_with_conflict_resolver(
-            skills,
-            target,
-            desired,
-            interactive,
-            target_conflict,
-            resolve_conflict,
-        ),
+        TargetMode::Link => {
+            // Rendered skills differ per target, so they are always vendored as copies.
+            let (rendered, linked): (Vec<SkillCandidate>, Vec<SkillCandidate>) =
+                skills.iter().cloned().partition(is_template_skill);
+            let mut resolve_conflict = resolve_conflict;
+            if !rendered.is_empty() {
+                apply_target_diff_copy_with_conflict_resolver(
+                    &rendered,
+                    target,
+                    desired,
+                    interactive,
+                    target_conflict,
+                    &mut resolve_conflict,
+                )?;
+            }
+            apply_target_diff_link_with_conflict_resolver(
+                &linked,
+                target,
+                desired,
+                interactive,
+                target_conflict,
+                resolve_conflict,
+            )
+        }
         TargetMode::Copy => apply_target_diff_copy_with_conflict_resolver(
             skills,
             target,
@@ -279,14 +301,120 @@ pub fn is_skill_linked(skill: &SkillCandidate, target: &ConfigEntry) -> bool {
     existing_canon == desired_canon
 }
 
+/// Mode actually used for a skill in a target: rendered skills are copied even into link targets.
+fn effective_mode(skill: &SkillCandidate, target: &ConfigEntry) -> TargetMode {
+    if target.mode == TargetMode::Link && is_template_skill(skill) {
+        TargetMode::Copy
+    } else {
+        target.mode
+    }
+}
+
 pub fn is_skill_present(skill: &SkillCandidate, target: &ConfigEntry) -> bool {
-    match target.mode {
+    match effective_mode(skill, target) {
         TargetMode::Skip => false,
         TargetMode::Link => is_skill_linked(skill, target),
         TargetMode::Copy => is_skill_vendored(skill, target).unwrap_or(false),
     }
 }
 
+/// Observed state of a skill's entry in a target, used for reporting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TargetEntryState {
+    Absent,
+    Linked,
+    /// Copy matching the current source (or rendering).
+    Copied,
+    /// Managed copy whose source changed since it was written.
+    Outdated,
+    /// Entry with the skill's name that llman does not manage.
+    Unmanaged,
+}
+
+impl fmt::Display for TargetEntryState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            Self::Absent => "absent",
+            Self::Linked => "link",
+            Self::Copied => "copy",
+            Self::Outdated => "copy, outdated",
+            Self::Unmanaged => "unmanaged",
+        };
+        f.write_str(label)
+    }
+}
+
+pub fn target_entry_state(
+    skill: &SkillCandidate,
+    target: &ConfigEntry,
+) -> Result<TargetEntryState> {
+    if is_skill_linked(skill, target) {
+        return Ok(TargetEntryState::Linked);
+    }
+    let state = copy_state(skill, target)?;
+    Ok(if state.present && state.up_to_date {
+        TargetEntryState::Copied
+    } else if state.present {
+        TargetEntryState::Outdated
+    } else if state.enable_conflict {
+        TargetEntryState::Unmanaged
+    } else {
+        TargetEntryState::Absent
+    })
+}
+
+/// Directory materialized into copy targets: the skill itself, or its rendering for the target.
+struct CopySource<'a> {
+    skill_dir: &'a Path,
+    rendered: Option<RenderedSkill>,
+}
+
+impl<'a> CopySource<'a> {
+    fn prepare(skill: &'a SkillCandidate, target: &ConfigEntry) -> Result<Self> {
+        let rendered = if is_template_skill(skill) {
+            Some(render_skill(skill, target)?)
+        } else {
+            None
+        };
+        Ok(Self {
+            skill_dir: &skill.skill_dir,
+            rendered,
+        })
+    }
+
+    fn dir(&self) -> &Path {
+        self.rendered
+            .as_ref()
+            .map(RenderedSkill::dir)
+            .unwrap_or(self.skill_dir)
+    }
+
+    /// Whether a rendered copy on disk matches what llman rendered last time (i.e. it was not
+    /// edited by hand), so template or variable changes can re-render it without a conflict.
+    fn is_unmodified_render(&self, entry_path: &Path, existing_digest: &str) -> bool {
+        self.rendered.is_some()
+            && read_render_metadata(entry_path)
+                .is_some_and(|metadata| metadata.output_digest == existing_digest)
+    }
+
+    /// Whether the recorded render inputs match the current ones.
+    fn render_inputs_current(&self, entry_path: &Path) -> bool {
+        let Some(rendered) = &self.rendered else {
+            return true;
+        };
+        read_render_metadata(entry_path)
+            .is_some_and(|metadata| metadata.inputs_digest == rendered.metadata.inputs_digest)
+    }
+
+    fn record(&self, entry_path: &Path) -> Result<()> {
+        if let Some(rendered) = &self.rendered {
+            write_render_metadata(entry_path, &rendered.metadata)?;
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 struct CopyState {
     present: bool,
@@ -340,15 +468,18 @@ fn copy_state(skill: &SkillCandidate, target: &ConfigEntry) -> Result<CopyState>
         });
     }
 
-    let desired_digest = compute_dir_digest(&skill.skill_dir)?;
+    let source = CopySource::prepare(skill, target)?;
+    let desired_digest = compute_dir_digest(source.dir())?;
     let existing_digest = compute_dir_digest(&entry_path)?;
-    let up_to_date = existing_digest == desired_digest;
+    let matches = existing_digest == desired_digest;
+    // Without vendored metadata we only consider a copied directory "managed" when it matches
+    // the current source (or, for rendered skills, the last recorded rendering). This keeps
+    // removal conservative.
+    let managed = matches || source.is_unmodified_render(&entry_path, &existing_digest);
     Ok(CopyState {
-        // Without vendored metadata we only consider a copied directory "managed" when it matches
-        // the current source. This keeps removal conservative.
-        present: up_to_date,
-        up_to_date,
-        enable_conflict: !up_to_date,
+        present: managed,
+        up_to_date: matches && source.render_inputs_current(&entry_path),
+        enable_conflict: !managed,
     })
 }
 
@@ -401,7 +532,7 @@ fn cleanup_vendored_metadata_files(target: &ConfigEntry, skills: &[SkillCandidat
     Ok(())
 }
 
-fn compute_dir_digest(root: &Path) -> Result<String> {
+pub(crate) fn compute_dir_digest(root: &Path) -> Result<String> {
     let mut hasher = Sha256::new();
     let mut stack = Vec::new();
     hash_dir_recursive(root, root, &mut hasher, &mut stack)?;
@@ -432,7 +563,9 @@ fn hash_dir_recursive(
 
         for entry in entries {
             let name = entry.file_name();
-            if name.to_string_lossy() == VENDORED_METADATA_FILE {
+            if name.to_string_lossy() == VENDORED_METADATA_FILE
+                || name.to_string_lossy() == RENDER_METADATA_FILE
+            {
                 continue;
             }
 
@@ -495,7 +628,7 @@ fn hash_dir_recursive(
     result
 }
 
-fn hex_lower(bytes: &[u8]) -> String {
+pub(crate) fn hex_lower(bytes: &[u8]) -> String {
     const HEX: &[u8; 16] = b"0123456789abcdef";
     let mut out = String::with_capacity(bytes.len() * 2);
     for b in bytes {
@@ -512,7 +645,7 @@ pub fn apply_target_link(
     interactive: bool,
     target_conflict: Option<TargetConflictStrategy>,
 ) -> Result<()> {
-    match target.mode {
+    match effective_mode(skill, target) {
         TargetMode::Skip => Ok(()),
         TargetMode::Link => {
             if enabled {
@@ -658,6 +791,7 @@ fn ensure_target_copy(
     }
 
     let entry_path = target.path.join(&skill.skill_id);
+    let source = CopySource::prepare(skill, target)?;
     if let Ok(meta) = fs::symlink_metadata(&entry_path) {
         if meta.file_type().is_symlink() {
             // Migration path: if an older config created a symlink, replace it with a vendored copy.
@@ -676,18 +810,25 @@ fn ensure_target_copy(
                 // Empty directory: safe to replace without prompting.
                 fs::remove_dir(&entry_path)?;
             } else {
-                let desired_digest = compute_dir_digest(&skill.skill_dir)?;
+                let desired_digest = compute_dir_digest(source.dir())?;
                 let existing_digest = compute_dir_digest(&entry_path)?;
                 if existing_digest == desired_digest {
                     // Legacy cleanup: older releases stored per-skill metadata markers.
                     remove_file_if_exists(&entry_path.join(VENDORED_METADATA_FILE))?;
+                    source.record(&entry_path)?;
                     return Ok(());
                 }
 
-                let decision =
-                    resolve_target_conflict(&skill.skill_id, target, interactive, target_conflict)?;
-                if decision == TargetConflictStrategy::Skip {
-                    return Ok(());
+                if !source.is_unmodified_render(&entry_path, &existing_digest) {
+                    let decision = resolve_target_conflict(
+                        &skill.skill_id,
+                        target,
+                        interactive,
+                        target_conflict,
+                    )?;
+                    if decision == TargetConflictStrategy::Skip {
+                        return Ok(());
+                    }
                 }
                 fs::remove_dir_all(&entry_path)?;
             }
@@ -701,9 +842,10 @@ fn ensure_target_copy(
         }
     }
 
-    copy_dir_all_follow_links(&skill.skill_dir, &entry_path)?;
+    copy_dir_all_follow_links(source.dir(), &entry_path)?;
     // Ensure we never create or keep vendored marker files.
     remove_file_if_exists(&entry_path.join(VENDORED_METADATA_FILE))?;
+    source.record(&entry_path)?;
     Ok(())
 }
 
@@ -732,9 +874,12 @@ fn remove_target_copy(skill: &SkillCandidate, target: &ConfigEntry) -> Result<()
             return Ok(());
         }
 
-        let desired_digest = compute_dir_digest(&skill.skill_dir)?;
+        let source = CopySource::prepare(skill, target)?;
+        let desired_digest = compute_dir_digest(source.dir())?;
         let existing_digest = compute_dir_digest(&entry_path)?;
-        if existing_digest != desired_digest {
+        if existing_digest != desired_digest
+            && !source.is_unmodified_render(&entry_path, &existing_digest)
+        {
             eprintln!(
                 "{}",
                 t!("skills.target.not_managed", path = entry_path.display())
@@ -753,7 +898,7 @@ fn remove_target_copy(skill: &SkillCandidate, target: &ConfigEntry) -> Result<()
     Ok(())
 }
 
-fn remove_existing_entry_for_copy(path: &Path) -> Result<()> {
+pub(crate) fn remove_existing_entry_for_copy(path: &Path) -> Result<()> {
     let meta = fs::symlink_metadata(path)?;
     if meta.file_type().is_symlink() || meta.is_file() {
         fs::remove_file(path)?;
@@ -765,7 +910,7 @@ fn remove_existing_entry_for_copy(path: &Path) -> Result<()> {
     Ok(())
 }
 
-fn copy_dir_all_follow_links(from: &Path, to: &Path) -> Result<()> {
+pub(crate) fn copy_dir_all_follow_links(from: &Path, to: &Path) -> Result<()> {
     let mut stack = Vec::new();
     copy_dir_all_follow_links_inner(from, to, &mut stack)
 }
@@ -840,6 +985,17 @@ fn resolve_target_conflict(
     target: &ConfigEntry,
     interactive: bool,
     target_conflict: Option<TargetConflictStrategy>,
+) -> Result<TargetConflictStrategy> {
+    resolve_entry_conflict(skill_id, &target.id, interactive, target_conflict)
+}
+
+/// Resolve an existing-entry conflict for `skill_id` in `target_label` using the same
+/// flag/prompt contract as target sync.
+pub(crate) fn resolve_entry_conflict(
+    skill_id: &str,
+    target_label: &str,
+    interactive: bool,
+    target_conflict: Option<TargetConflictStrategy>,
 ) -> Result<TargetConflictStrategy> {
     if let Some(conflict) = target_conflict {
         return Ok(conflict);
@@ -848,13 +1004,13 @@ fn resolve_target_conflict(
         return Err(anyhow!(t!(
             "skills.target_conflict.requires_flag",
             skill = skill_id,
-            target = target.id
+            target = target_label
         )));
     }
     let prompt = t!(
         "skills.target_conflict.prompt",
         skill = skill_id,
-        target = target.id
+        target = target_label
     );
     let overwrite_label = t!("skills.target_conflict.option_overwrite").to_string();
     let skip_label = t!("skills.target_conflict.option_skip").to_string();
@@ -938,9 +1094,11 @@ mod tests {
             path: target_root,
             enabled: true,
             mode: TargetMode::Link,
+            vars: Default::default(),
         };
         let config = SkillsConfig {
             targets: vec![target],
+            profiles: Vec::new(),
         };
         let desired_by_target = HashMap::new();
 
@@ -974,9 +1132,11 @@ mod tests {
             path: target_root.clone(),
             enabled: true,
             mode: TargetMode::Link,
+            vars: Default::default(),
         };
         let config = SkillsConfig {
             targets: vec![target],
+            profiles: Vec::new(),
         };
         let desired_by_target = HashMap::new();
 
@@ -1022,9 +1182,11 @@ mod tests {
             path: target_root.clone(),
             enabled: true,
             mode: TargetMode::Link,
+            vars: Default::default(),
         };
         let config = SkillsConfig {
             targets: vec![target],
+            profiles: Vec::new(),
         };
         let desired_by_target = HashMap::new();
 
@@ -1071,6 +1233,7 @@ mod tests {
             path: target_root,
             enabled: true,
             mode: TargetMode::Link,
+            vars: Default::default(),
         };
 
         assert!(is_skill_linked(&skill, &target));
@@ -1106,6 +1269,7 @@ mod tests {
             path: target_root.clone(),
             enabled: true,
             mode: TargetMode::Link,
+            vars: Default::default(),
         };
         let mut desired = HashSet::new();
 
@@ -1154,6 +1318,7 @@ mod tests {
             path: target_root.clone(),
             enabled: true,
             mode: TargetMode::Link,
+            vars: Default::default(),
         };
 
         let mut desired = HashSet::new();
@@ -1196,6 +1361,7 @@ mod tests {
             path: target_root.clone(),
             enabled: true,
             mode: TargetMode::Link,
+            vars: Default::default(),
         };
 
         apply_target_link(
@@ -1246,6 +1412,7 @@ mod tests {
             path: target_root,
             enabled: true,
             mode: TargetMode::Link,
+            vars: Default::default(),
         };
 
         let mut desired = HashSet::new();
@@ -1288,6 +1455,7 @@ mod tests {
             path: target_root.clone(),
             enabled: true,
             mode: TargetMode::Copy,
+            vars: Default::default(),
         };
 
         let mut desired = HashSet::new();
@@ -1334,6 +1502,7 @@ mod tests {
             path: target_root.clone(),
             enabled: true,
             mode: TargetMode::Copy,
+            vars: Default::default(),
         };
 
         let mut desired = HashSet::new();
@@ -1380,6 +1549,7 @@ mod tests {
             path: target_root.clone(),
             enabled: true,
             mode: TargetMode::Copy,
+            vars: Default::default(),
         };
 
         let mut desired = HashSet::new();
@@ -1419,6 +1589,7 @@ mod tests {
             path: target_root.clone(),
             enabled: true,
             mode: TargetMode::Copy,
+            vars: Default::default(),
         };
 
         let mut desired = HashSet::new();
@@ -1474,9 +1645,11 @@ mod tests {
             path: target_root,
             enabled: true,
             mode: TargetMode::Copy,
+            vars: Default::default(),
         };
         let config = SkillsConfig {
             targets: vec![target],
+            profiles: Vec::new(),
         };
         let desired_by_target = HashMap::new();
 
@@ -1512,6 +1685,7 @@ mod tests {
             path: target_root.clone(),
             enabled: true,
             mode: TargetMode::Copy,
+            vars: Default::default(),
         };
 
         apply_target_link(&skill, &target, true, false, None).expect("migrate");
@@ -1526,4 +1700,81 @@ mod tests {
             "should not create vendored metadata file"
         );
     }
+    #[cfg(unix)]
+    #[test]
+    fn test_template_skill_is_rendered_into_link_target() {
+        let temp = TempDir::new().expect("temp dir");
+        let skill_dir = temp.path().join("skills").join("skill");
+        fs::create_dir_all(&skill_dir).expect("create skill dir");
+        fs::write(
+            skill_dir.join("SKILL.md.j2"),
+            "agent={{ agent }} team={{ team }}",
+        )
+        .expect("write template");
+
+        let target_root = temp.path().join("targets");
+        let skill = SkillCandidate {
+            skill_id: "skill".to_string(),
+            skill_dir: skill_dir.clone(),
+        };
+        let mut target = ConfigEntry {
+            id: "claude_user".to_string(),
+            agent: "claude".to_string(),
+            scope: "user".to_string(),
+            path: target_root.clone(),
+            enabled: true,
+            mode: TargetMode::Link,
+            vars: [("team".to_string(), "core".to_string())].into(),
+        };
+
+        let mut desired = HashSet::new();
+        desired.insert("skill".to_string());
+        apply_target_diff(std::slice::from_ref(&skill), &target, &desired, false, None)
+            .expect("apply diff");
+
+        let entry_path = target_root.join("skill");
+        let meta = fs::symlink_metadata(&entry_path).expect("metadata");
+        assert!(meta.is_dir() && !meta.file_type().is_symlink());
+        assert_eq!(
+            fs::read_to_string(entry_path.join("SKILL.md")).expect("read rendered"),
+            "agent=claude team=core"
+        );
+        assert!(!entry_path.join("SKILL.md.j2").exists());
+        assert!(is_skill_present(&skill, &target));
+
+        // Changing a render input re-renders without requiring --target-conflict.
+        target.vars.insert("team".to_string(), "infra".to_string());
+        apply_target_diff(std::slice::from_ref(&skill), &target, &desired, false, None)
+            .expect("re-render");
+        assert_eq!(
+            fs::read_to_string(entry_path.join("SKILL.md")).expect("read rendered"),
+            "agent=claude team=infra"
+        );
+
+        // Hand edits are conflicts.
+        fs::write(entry_path.join("SKILL.md"), "edited").expect("edit copy");
+        target.vars.insert("team".to_string(), "core".to_string());
+        let err = apply_target_diff(std::slice::from_ref(&skill), &target, &desired, false, None)
+            .expect_err("edited copy should conflict");
+        assert!(err.to_string().contains("--target-conflict"));
+
+        // Disabling removes an unmodified rendered copy.
+        apply_target_diff(
+            std::slice::from_ref(&skill),
+            &target,
+            &desired,
+            false,
+            Some(TargetConflictStrategy::Overwrite),
+        )
+        .expect("overwrite");
+        apply_target_diff(
+            std::slice::from_ref(&skill),
+            &target,
+            &HashSet::new(),
+            false,
+            None,
+        )
+        .expect("remove");
+        assert!(!entry_path.exists());
+    }
 }
@@ -1,3 +1,5 @@
+pub mod http_stub;
+
 use std::collections::HashMap;
 use std::env;
 use std::ffi::{OsStr, OsString};
@@ -85,6 +85,14 @@ pub enum SyncIgnoreTarget {
     Opencode,
     /// Project root `.cursorignore`
     Cursor,
+    /// Project root `.aiderignore`
+    Aider,
+    /// Project root `.geminiignore`
+    Gemini,
+    /// Project root `.copilotignore`
+    Copilot,
+    /// llman ignore block in `AGENTS.md` (guidance for Codex, which has no ignore file)
+    Codex,
     /// `.claude/settings.json`
     ClaudeShared,
     /// `.claude/settings.local.json`
@@ -95,7 +103,7 @@ pub enum SyncIgnoreTarget {
 
 #[derive(Parser, Debug, Clone)]
 #[command(
-    after_help = "Examples:\n  llman tool sync-ignore\n  llman tool sync-ignore -y\n  llman tool sync-ignore --target cursor --target claude-shared -y\n  llman tool sync-ignore --interactive\n"
+    after_help = "Examples:\n  llman tool sync-ignore\n  llman tool sync-ignore -y\n  llman tool sync-ignore --target cursor --target claude-shared -y\n  llman tool sync-ignore --target aider,gemini,codex -y\n  llman tool sync-ignore --interactive\n"
 )]
 pub struct SyncIgnoreArgs {
     /// Apply changes (default: dry-run preview)
@@ -0,0 +1,349 @@
+//! Ignore patterns as a small semantic model shared by the sync-ignore dialects.
+//!
+//! gitignore-style files and Claude `Read(...)` deny rules spell the same intent differently:
+//! `/build/`, `build/**` and `Read(./build/**)` all hide the root `build` directory, while
+//! `.env` (any depth) and `Read(./.env)` (root only) do not mean the same thing. Patterns are
+//! parsed into [`IgnorePattern`] so they can be compared and re-rendered without losing
+//! negation, directory-only or anchoring, and rule sets are checked by evaluating them with
+//! the `ignore` crate against the repository's actual files.
+
+use anyhow::Result;
+use ignore::gitignore::{Gitignore, GitignoreBuilder, Glob};
+use ignore::{Match, WalkBuilder};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct IgnorePattern {
+    /// `!pattern`: re-include what an earlier pattern ignored.
+    pub negated: bool,
+    /// Matches relative to the project root only (otherwise at any depth).
+    pub anchored: bool,
+    /// Matches directories (and so everything below them) only.
+    pub dir_only: bool,
+    /// Glob without the `!`, leading `/`, leading `**/` or trailing `/`.
+    pub glob: String,
+}
+
+impl IgnorePattern {
+    /// Parse one gitignore line; `None` for blank lines and comments.
+    pub fn parse_gitignore(line: &str) -> Option<Self> {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            return None;
+        }
+        let (negated, rest) = match line.strip_prefix('!') {
+            Some(rest) => (true, rest.trim_start()),
+            None => (false, line),
+        };
+        let (anchored, rest) = match rest.strip_prefix('/') {
+            Some(rest) => (true, rest),
+            None => (false, rest),
+        };
+        Self::from_parts(negated, anchored, rest, false)
+    }
+
+    /// Parse a Claude Code `Read(...)` deny rule; `None` for other tools and for paths
+    /// outside the project (`//absolute`, `~/home`).
+    ///
+    /// `./path` and `/path` are relative to the project root. Claude has no directory-only
+    /// syntax, so `dir/**` is read as the directory itself.
+    pub fn parse_claude_read(rule: &str) -> Option<Self> {
+        let inner = rule.trim().strip_prefix("Read(")?.strip_suffix(')')?.trim();
+        if inner.starts_with("//") || inner.starts_with('~') {
+            return None;
+        }
+        let (anchored, rest) = match inner.strip_prefix("./").or_else(|| inner.strip_prefix('/')) {
+            Some(rest) => (true, rest),
+            None => (false, inner),
+        };
+        Self::from_parts(false, anchored, rest, true)
+    }
+
+    fn from_parts(negated: bool, anchored: bool, rest: &str, dir_glob: bool) -> Option<Self> {
+        let mut anchored = anchored;
+        let mut rest = rest;
+        let mut any_depth = false;
+        while let Some(stripped) = rest.strip_prefix("**/") {
+            rest = stripped;
+            anchored = false;
+            any_depth = true;
+        }
+        let mut dir_only = false;
+        if dir_glob && let Some(stripped) = rest.strip_suffix("/**") {
+            rest = stripped;
+            dir_only = true;
+        }
+        if rest.ends_with('/') {
+            rest = rest.trim_end_matches('/');
+            dir_only = true;
+        }
+        if rest.is_empty() {
+            return None;
+        }
+        // gitignore: a slash anywhere but the end anchors the pattern.
+        if !any_depth && rest.contains('/') {
+            anchored = true;
+        }
+        Some(Self {
+            negated,
+            anchored,
+            dir_only,
+            glob: rest.to_string(),
+        })
+    }
+
+    /// Canonical gitignore spelling.
+    pub fn to_gitignore(&self) -> String {
+        let mut out = String::new();
+        if self.negated {
+            out.push('!');
+        }
+        match (self.anchored, self.glob.contains('/')) {
+            (true, false) => out.push('/'),
+            (false, true) => out.push_str("**/"),
+            _ => {}
+        }
+        out.push_str(&self.glob);
+        if self.dir_only {
+            out.push('/');
+        }
+        out
+    }
+
+    /// Claude `Read(...)` deny rule; `None` for negations, which Claude cannot express.
+    pub fn to_claude_read(&self) -> Option<String> {
+        if self.negated {
+            return None;
+        }
+        let prefix = if self.anchored { "./" } else { "./**/" };
+        let suffix = if self.dir_only { "/**" } else { "" };
+        Some(format!("Read({prefix}{}{suffix})", self.glob))
+    }
+
+    /// Identity for de-duplication: `dir/**` and `dir/` hide the same files.
+    ///
+    /// They differ once a `!dir/file` re-include follows: walkers prune an ignored `dir/`
+    /// without looking inside, while `dir/**` leaves the directory open. See
+    /// [`IgnorePattern::opens_dir`].
+    pub fn key(&self) -> (bool, bool, &str, bool) {
+        match self.glob.strip_suffix("/**") {
+            Some(dir) if !self.dir_only => (self.negated, self.anchored, dir, true),
+            _ => (self.negated, self.anchored, &self.glob, self.dir_only),
+        }
+    }
+
+    /// The `dir/**` spelling: hides a directory's contents without pruning the directory.
+    pub fn opens_dir(&self) -> bool {
+        !self.dir_only && self.glob.ends_with("/**")
+    }
+}
+
+/// Directories [`repo_files`] does not descend into: VCS metadata and dependency or build
+/// caches too large to evaluate file by file.
+pub const SKIPPED_DIRS: [&str; 7] = [
+    ".git",
+    "node_modules",
+    "target",
+    ".venv",
+    "venv",
+    "__pycache__",
+    ".tox",
+];
+
+/// Files in the repository (relative paths), including gitignored ones such as `.env`, but
+/// not below [`SKIPPED_DIRS`].
+pub fn repo_files(root: &Path) -> Vec<PathBuf> {
+    let walker = WalkBuilder::new(root)
+        .standard_filters(false)
+        .filter_entry(|entry| {
+            !(entry.file_type().is_some_and(|t| t.is_dir())
+                && entry
+                    .file_name()
+                    .to_str()
+                    .is_some_and(|name| SKIPPED_DIRS.contains(&name)))
+        })
+        .build();
+    let mut files: Vec<PathBuf> = walker
+        .flatten()
+        .filter(|entry| entry.file_type().is_some_and(|t| t.is_file()))
+        .filter_map(|entry| entry.path().strip_prefix(root).ok().map(Path::to_path_buf))
+        .collect();
+    files.sort();
+    files
+}
+
+/// A matcher for gitignore `lines`, evaluated relative to `root`.
+pub fn build_matcher<'a>(
+    root: &Path,
+    lines: impl IntoIterator<Item = &'a str>,
+) -> Result<Gitignore> {
+    let mut builder = GitignoreBuilder::new(root);
+    for line in lines {
+        builder.add_line(None, line)?;
+    }
+    Ok(builder.build()?)
+}
+
+/// How a directory walker sees `file`: the first ignored ancestor directory prunes the walk,
+/// so `!` patterns for the file itself are only consulted when no ancestor is ignored.
+pub fn walker_match<'a>(matcher: &'a Gitignore, file: &Path) -> Match<&'a Glob> {
+    let mut dir = PathBuf::new();
+    for component in file.parent().into_iter().flat_map(Path::components) {
+        dir.push(component);
+        let matched = matcher.matched(&dir, true);
+        if matched.is_ignore() {
+            return matched;
+        }
+    }
+    matcher.matched(file, false)
+}
+
+/// `files` the matcher hides, evaluated the way a walker does (see [`walker_match`]).
+pub fn hidden_files(matcher: &Gitignore, files: &[PathBuf]) -> BTreeSet<PathBuf> {
+    files
+        .iter()
+        .filter(|file| walker_match(matcher, file).is_ignore())
+        .cloned()
+        .collect()
+}
+
+/// One source ignores files that another source re-includes.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Contradiction {
+    pub ignored_by: String,
+    pub ignore_pattern: String,
+    pub included_by: String,
+    pub include_pattern: String,
+    pub files: usize,
+}
+
+/// Contradictions between named `sources`, found by evaluating each against `files`.
+pub fn find_contradictions(
+    sources: &[(String, Gitignore)],
+    files: &[PathBuf],
+) -> Vec<Contradiction> {
+    let mut found: Vec<Contradiction> = Vec::new();
+    for file in files {
+        let matches: Vec<_> = sources
+            .iter()
+            .map(|(label, matcher)| (label, walker_match(matcher, file)))
+            .collect();
+        for (ignored_by, ignore) in &matches {
+            let Some(ignore_glob) = ignore.inner().filter(|_| ignore.is_ignore()) else {
+                continue;
+            };
+            for (included_by, include) in &matches {
+                let Some(include_glob) = include.inner().filter(|_| include.is_whitelist()) else {
+                    continue;
+                };
+                let entry = Contradiction {
+                    ignored_by: (*ignored_by).clone(),
+                    ignore_pattern: ignore_glob.original().to_string(),
+                    included_by: (*included_by).clone(),
+                    include_pattern: include_glob.original().to_string(),
+                    files: 0,
+                };
+                match found.iter_mut().find(|known| {
+                    Contradiction {
+                        files: 0,
+                        ..(*known).clone()
+                    } == entry
+                }) {
+                    Some(known) => known.files += 1,
+                    None => found.push(Contradiction { files: 1, ..entry }),
+                }
+            }
+        }
+    }
+    found.sort();
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn dialects_normalize_to_the_same_model() {
+        fn parse(line: &str) -> IgnorePattern {
+            IgnorePattern::parse_gitignore(line).unwrap()
+        }
+        fn claude(rule: &str) -> IgnorePattern {
+            IgnorePattern::parse_claude_read(rule).unwrap()
+        }
+
+        assert_eq!(parse("/build/").key(), parse("build/**").key());
+        assert_eq!(claude("Read(./build/**)"), parse("/build/"));
+        assert_eq!(claude("Read(./**/.env)"), parse(".env"));
+        assert_ne!(claude("Read(./.env)"), parse(".env"));
+        assert_eq!(parse("**/logs/app").to_gitignore(), "**/logs/app");
+        assert_eq!(parse("/docs/api").to_gitignore(), "docs/api");
+        assert_eq!(parse("!keep.txt").to_gitignore(), "!keep.txt");
+        assert!(IgnorePattern::parse_claude_read("Read(~/.ssh/**)").is_none());
+        assert!(IgnorePattern::parse_claude_read("WebFetch(domain:x)").is_none());
+
+        for line in ["secrets/**", "/.env", "*.log", "target/", "**/a/b/"] {
+            let pattern = parse(line);
+            let rule = pattern.to_claude_read().unwrap();
+            assert_eq!(claude(&rule).key(), pattern.key(), "{line} -> {rule}");
+            assert_eq!(parse(&pattern.to_gitignore()), pattern, "{line}");
+        }
+    }
+
+    #[test]
+    fn evaluation_finds_hidden_files_and_contradictions() {
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path();
+        for file in [
+            "src/main.rs",
+            "secrets/key.pem",
+            "secrets/README.md",
+            "app.log",
+        ] {
+            let path = root.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "").unwrap();
+        }
+        // Gitignored files are what agents most need hidden, so they are evaluated too.
+        fs::write(root.join(".gitignore"), "app.log\n").unwrap();
+        fs::create_dir_all(root.join("node_modules/dep")).unwrap();
+        fs::write(root.join("node_modules/dep/index.js"), "").unwrap();
+        let files = repo_files(root);
+        assert_eq!(files.len(), 5);
+        assert!(files.contains(&PathBuf::from("app.log")));
+        assert!(!files.iter().any(|file| file.starts_with("node_modules")));
+
+        let cursor = build_matcher(root, ["secrets/", "*.log"]).unwrap();
+        let opencode = build_matcher(root, ["*.log", "!secrets/README.md"]).unwrap();
+        let hidden = hidden_files(&cursor, &files);
+        assert!(hidden.contains(Path::new("secrets/key.pem")));
+        assert!(hidden.contains(Path::new("app.log")));
+        assert_eq!(hidden.len(), 3);
+
+        // A walker never looks inside an ignored directory, so the re-include is dead.
+        let pruned = build_matcher(root, ["secrets/", "!secrets/README.md"]).unwrap();
+        assert!(hidden_files(&pruned, &files).contains(Path::new("secrets/README.md")));
+        let open = build_matcher(root, ["secrets/**", "!secrets/README.md"]).unwrap();
+        assert!(!hidden_files(&open, &files).contains(Path::new("secrets/README.md")));
+
+        let found = find_contradictions(
+            &[
+                (".cursorignore".to_string(), cursor),
+                (".ignore".to_string(), opencode),
+            ],
+            &files,
+        );
+        assert_eq!(
+            found,
+            vec![Contradiction {
+                ignored_by: ".cursorignore".to_string(),
+                ignore_pattern: "secrets/".to_string(),
+                included_by: ".ignore".to_string(),
+                include_pattern: "!secrets/README.md".to_string(),
+                files: 1,
+            }]
+        );
+    }
+}
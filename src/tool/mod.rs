@@ -1,6 +1,7 @@
 pub mod clean_comments;
 pub mod command;
 pub mod config;
+pub mod ignore_patterns;
 pub mod processor;
 pub mod rm_empty_dirs;
 pub mod sync_ignore;
@@ -1,7 +1,41 @@
+//! `llman tool sync-ignore`: keep the ignore rules of several agent tools in step.
+//!
+//! Every target is also read back as a source, so rules can be authored in any of them:
+//!
+//! | `--target`      | File                            | Written as                                   |
+//! |-----------------|---------------------------------|----------------------------------------------|
+//! | `opencode`      | `.ignore`                       | gitignore syntax                             |
+//! | `cursor`        | `.cursorignore`                 | gitignore syntax                             |
+//! | `aider`         | `.aiderignore`                  | gitignore syntax                             |
+//! | `gemini`        | `.geminiignore`                 | gitignore syntax                             |
+//! | `copilot`       | `.copilotignore`                | gitignore syntax                             |
+//! | `codex`         | `AGENTS.md`                     | `LLMAN-IGNORE` block with a fenced gitignore |
+//! |                 |                                 | list (Codex has no ignore file; guidance)    |
+//! | `claude-shared` | `.claude/settings.json`         | `permissions.deny` `Read(./pattern)` rules   |
+//! | `claude-local`  | `.claude/settings.local.json`   | same as `claude-shared`                      |
+//!
+//! Claude deny rules cannot express `!include` patterns, so those are dropped for Claude.
+//!
+//! Rules from every dialect are normalized through [`IgnorePattern`], so `secrets/**`,
+//! `/secrets/` and `Read(./secrets/**)` merge into one rule. Before writing, each source and
+//! each target is evaluated against the repository's files: sources that ignore what another
+//! re-includes are reported, and so is any target that would not hide exactly the same files
+//! as the merged rules.
+
 use crate::fs_utils::atomic_write_with_mode;
+use crate::jsonc::{
+    find_matching_bracket, find_object_member_value_start, line_indent, skip_ws_and_comments,
+};
+use crate::managed_block::{
+    LLMAN_IGNORE_MARKER_END, LLMAN_IGNORE_MARKER_START, remove_text_with_markers,
+    text_between_markers, update_text_with_markers,
+};
 use crate::path_utils::safe_parent_for_creation;
 use crate::skills::shared::git::find_git_root;
 use crate::tool::command::{SyncIgnoreArgs, SyncIgnoreTarget};
+use crate::tool::ignore_patterns::{
+    IgnorePattern, SKIPPED_DIRS, build_matcher, find_contradictions, hidden_files, repo_files,
+};
 use anyhow::{Context, Result, bail};
 use llm_json::{RepairOptions, loads};
 use rust_i18n::t;
@@ -21,9 +55,52 @@ impl IgnoreRules {
         self.ignore.is_empty() && self.include.is_empty()
     }
 
+    /// Add a pattern in its canonical spelling unless an equivalent one is already present.
+    ///
+    /// Of `dir/` and `dir/**` the latter is kept: only it lets a `!dir/file` re-include work.
+    pub fn insert(&mut self, pattern: &IgnorePattern) {
+        let positive = IgnorePattern {
+            negated: false,
+            ..pattern.clone()
+        };
+        let set = if pattern.negated {
+            &mut self.include
+        } else {
+            &mut self.ignore
+        };
+        let known = set
+            .iter()
+            .find(|line| {
+                IgnorePattern::parse_gitignore(line)
+                    .is_some_and(|known| known.key() == positive.key())
+            })
+            .cloned();
+        match known {
+            Some(line) if positive.opens_dir() => {
+                set.remove(&line);
+                set.insert(positive.to_gitignore());
+            }
+            Some(_) => {}
+            None => {
+                set.insert(positive.to_gitignore());
+            }
+        }
+    }
+
     pub fn union_inplace(&mut self, other: &IgnoreRules) {
-        self.ignore.extend(other.ignore.iter().cloned());
-        self.include.extend(other.include.iter().cloned());
+        for line in &other.ignore {
+            if let Some(pattern) = IgnorePattern::parse_gitignore(line) {
+                self.insert(&pattern);
+            }
+        }
+        for line in &other.include {
+            if let Some(pattern) = IgnorePattern::parse_gitignore(line) {
+                self.insert(&IgnorePattern {
+                    negated: true,
+                    ..pattern
+                });
+            }
+        }
     }
 }
 
@@ -31,28 +108,52 @@ impl IgnoreRules {
 pub enum Target {
     OpenCodeIgnore,
     CursorIgnore,
+    AiderIgnore,
+    GeminiIgnore,
+    CopilotIgnore,
+    CodexAgents,
     ClaudeShared,
     ClaudeLocal,
 }
 
 impl Target {
+    pub const ALL: [Target; 8] = [
+        Target::OpenCodeIgnore,
+        Target::CursorIgnore,
+        Target::AiderIgnore,
+        Target::GeminiIgnore,
+        Target::CopilotIgnore,
+        Target::CodexAgents,
+        Target::ClaudeShared,
+        Target::ClaudeLocal,
+    ];
+
     pub fn path(self, root: &Path) -> PathBuf {
-        match self {
-            Target::OpenCodeIgnore => root.join(".ignore"),
-            Target::CursorIgnore => root.join(".cursorignore"),
-            Target::ClaudeShared => root.join(".claude/settings.json"),
-            Target::ClaudeLocal => root.join(".claude/settings.local.json"),
-        }
+        root.join(self.label())
     }
 
     pub fn label(self) -> &'static str {
         match self {
             Target::OpenCodeIgnore => ".ignore",
             Target::CursorIgnore => ".cursorignore",
+            Target::AiderIgnore => ".aiderignore",
+            Target::GeminiIgnore => ".geminiignore",
+            Target::CopilotIgnore => ".copilotignore",
+            Target::CodexAgents => "AGENTS.md",
             Target::ClaudeShared => ".claude/settings.json",
             Target::ClaudeLocal => ".claude/settings.local.json",
         }
     }
+
+    /// Whether this target already holds rules (for `AGENTS.md`: an llman ignore block).
+    fn is_present(self, root: &Path) -> bool {
+        let path = self.path(root);
+        match self {
+            Target::CodexAgents => fs::read_to_string(&path)
+                .is_ok_and(|content| extract_ignore_block(&content).is_some()),
+            _ => path.exists(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -123,10 +224,12 @@ fn resolve_root(cwd: &Path, force: bool) -> Result<PathBuf> {
 
 fn run_non_interactive(args: &SyncIgnoreArgs, root: &Path) -> Result<()> {
     let sources = discover_sources(root, &args.input)?;
-    let (union, parse_notes) = load_union_rules(&sources, args.verbose)?;
+    let files = repo_files(root);
+    let (union, parse_notes) = load_union_rules(root, &sources, &files, args.verbose)?;
     let targets = resolve_targets(args, root)?;
 
-    let plans = build_plans(root, &union, &targets, args.verbose)?;
+    let mut plans = build_plans(root, &union, &targets, args.verbose)?;
+    verify_plans(root, &union, &files, &mut plans)?;
     print_preview(
         root,
         &sources,
@@ -151,32 +254,16 @@ fn run_interactive(args: &SyncIgnoreArgs, root: &Path) -> Result<()> {
     use inquire::{Confirm, MultiSelect};
 
     let sources = discover_sources(root, &args.input)?;
-    let (union, parse_notes) = load_union_rules(&sources, args.verbose)?;
-
-    let targets_all = [
-        Target::OpenCodeIgnore,
-        Target::CursorIgnore,
-        Target::ClaudeShared,
-        Target::ClaudeLocal,
-    ];
+    let files = repo_files(root);
+    let (union, parse_notes) = load_union_rules(root, &sources, &files, args.verbose)?;
 
-    let defaults: Vec<Target> = {
-        let mut out = vec![
-            Target::OpenCodeIgnore,
-            Target::CursorIgnore,
-            Target::ClaudeShared,
-        ];
-        if root.join(".claude/settings.local.json").exists() {
-            out.push(Target::ClaudeLocal);
-        }
-        out
-    };
+    let targets_all = Target::ALL;
+    let defaults = default_targets(root);
 
     let options: Vec<String> = targets_all
         .iter()
         .map(|target| {
-            let path = target.path(root);
-            let status_key = if path.exists() {
+            let status_key = if target.is_present(root) {
                 "tool.sync_ignore.interactive.exists"
             } else {
                 "tool.sync_ignore.interactive.missing"
@@ -208,7 +295,7 @@ fn run_interactive(args: &SyncIgnoreArgs, root: &Path) -> Result<()> {
     let deselected_existing: Vec<Target> = targets_all
         .iter()
         .copied()
-        .filter(|target| target.path(root).exists() && !selected_targets.contains(target))
+        .filter(|target| target.is_present(root) && !selected_targets.contains(target))
         .collect();
 
     let mut delete_targets = Vec::new();
@@ -234,6 +321,7 @@ fn run_interactive(args: &SyncIgnoreArgs, root: &Path) -> Result<()> {
             notes: Vec::new(),
         });
     }
+    verify_plans(root, &union, &files, &mut plans)?;
 
     print_preview(
         root,
@@ -260,15 +348,9 @@ fn run_interactive(args: &SyncIgnoreArgs, root: &Path) -> Result<()> {
 
 fn discover_sources(root: &Path, extra_inputs: &[PathBuf]) -> Result<Vec<PathBuf>> {
     let mut sources = Vec::new();
-    let candidates = [
-        root.join(".ignore"),
-        root.join(".cursorignore"),
-        root.join(".claude/settings.json"),
-        root.join(".claude/settings.local.json"),
-    ];
-    for path in candidates {
-        if path.exists() {
-            sources.push(path);
+    for target in Target::ALL {
+        if target.is_present(root) {
+            sources.push(target.path(root));
         }
     }
 
@@ -292,7 +374,13 @@ fn discover_sources(root: &Path, extra_inputs: &[PathBuf]) -> Result<Vec<PathBuf
     Ok(sources)
 }
 
-fn load_union_rules(sources: &[PathBuf], verbose: bool) -> Result<(IgnoreRules, Vec<SyncNote>)> {
+/// Merge the rules of all `sources`, warning where one source re-includes files another ignores.
+fn load_union_rules(
+    root: &Path,
+    sources: &[PathBuf],
+    files: &[PathBuf],
+    verbose: bool,
+) -> Result<(IgnoreRules, Vec<SyncNote>)> {
     let mut union = IgnoreRules::default();
     let mut notes = Vec::new();
 
@@ -304,6 +392,7 @@ fn load_union_rules(sources: &[PathBuf], verbose: bool) -> Result<(IgnoreRules,
         return Ok((union, notes));
     }
 
+    let mut matchers = Vec::new();
     for path in sources {
         let content = fs::read_to_string(path)
             .with_context(|| t!("tool.sync_ignore.error.read_failed", path = path.display()))?;
@@ -315,11 +404,36 @@ fn load_union_rules(sources: &[PathBuf], verbose: bool) -> Result<(IgnoreRules,
                 ));
             }
             parse_claude_settings(&content, verbose)?
+        } else if looks_like_markdown(path) {
+            parse_agents_guidance(path, &content)
         } else {
             (parse_gitignore_like(&content), Vec::new())
         };
         union.union_inplace(&rules);
         notes.append(&mut rule_notes);
+
+        let label = path
+            .strip_prefix(root)
+            .unwrap_or(path)
+            .display()
+            .to_string();
+        let lines = render_gitignore_like(&rules);
+        matchers.push((label, build_matcher(root, lines.lines())?));
+    }
+
+    for found in find_contradictions(&matchers, files) {
+        notes.push(SyncNote {
+            kind: SyncNoteKind::Warning,
+            message: t!(
+                "tool.sync_ignore.warn.contradiction",
+                ignored_by = found.ignored_by,
+                ignore = found.ignore_pattern,
+                included_by = found.included_by,
+                include = found.include_pattern,
+                count = found.files
+            )
+            .to_string(),
+        });
     }
 
     Ok((union, notes))
@@ -331,6 +445,12 @@ fn looks_like_json(path: &Path) -> bool {
         .is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
 }
 
+fn looks_like_markdown(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("md"))
+}
+
 fn looks_like_claude_settings(path: &Path) -> bool {
     let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
         return false;
@@ -350,24 +470,51 @@ fn looks_like_claude_settings(path: &Path) -> bool {
 
 fn parse_gitignore_like(content: &str) -> IgnoreRules {
     let mut out = IgnoreRules::default();
-    for raw in content.lines() {
-        let line = raw.trim();
-        if line.is_empty() {
-            continue;
-        }
-        if line.starts_with('#') {
+    for pattern in content.lines().filter_map(IgnorePattern::parse_gitignore) {
+        out.insert(&pattern);
+    }
+    out
+}
+
+/// Rules from the llman ignore block of an `AGENTS.md`-style file.
+fn parse_agents_guidance(path: &Path, content: &str) -> (IgnoreRules, Vec<SyncNote>) {
+    let Some(block) = extract_ignore_block(content) else {
+        let note = SyncNote {
+            kind: SyncNoteKind::Warning,
+            message: t!(
+                "tool.sync_ignore.warn.agents_no_block",
+                path = path.display()
+            )
+            .to_string(),
+        };
+        return (IgnoreRules::default(), vec![note]);
+    };
+    let mut in_fence = false;
+    let mut patterns = String::new();
+    for line in block.lines() {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
             continue;
         }
-        if let Some(rest) = line.strip_prefix('!') {
-            let pattern = rest.trim();
-            if !pattern.is_empty() {
-                out.include.insert(pattern.to_string());
-            }
-            continue;
+        if in_fence {
+            patterns.push_str(line);
+            patterns.push('\n');
         }
-        out.ignore.insert(line.to_string());
     }
-    out
+    (parse_gitignore_like(&patterns), Vec::new())
+}
+
+/// Text between the llman ignore markers, if the block exists.
+fn extract_ignore_block(content: &str) -> Option<&str> {
+    text_between_markers(content, LLMAN_IGNORE_MARKER_START, LLMAN_IGNORE_MARKER_END)
+}
+
+fn render_agents_guidance(rules: &IgnoreRules) -> String {
+    format!(
+        "## Ignored paths\n\nDo not read, search or edit files matching these patterns \
+         (gitignore syntax; `!` re-includes a path):\n\n```gitignore\n{}```",
+        render_gitignore_like(rules)
+    )
 }
 
 fn parse_claude_settings(content: &str, verbose: bool) -> Result<(IgnoreRules, Vec<SyncNote>)> {
@@ -398,8 +545,8 @@ fn parse_claude_settings(content: &str, verbose: bool) -> Result<(IgnoreRules, V
         let Some(rule) = item.as_str() else {
             continue;
         };
-        if let Some(pattern) = extract_read_pattern(rule) {
-            out.ignore.insert(pattern);
+        if let Some(pattern) = IgnorePattern::parse_claude_read(rule) {
+            out.insert(&pattern);
         } else if verbose {
             notes.push(SyncNote {
                 kind: SyncNoteKind::Info,
@@ -411,35 +558,22 @@ fn parse_claude_settings(content: &str, verbose: bool) -> Result<(IgnoreRules, V
     Ok((out, notes))
 }
 
-fn extract_read_pattern(rule: &str) -> Option<String> {
-    let trimmed = rule.trim();
-    let inner = trimmed.strip_prefix("Read(")?.strip_suffix(')')?;
-    let inner_trimmed = inner.trim();
-    let mut pattern = inner_trimmed;
-    if let Some(rest) = pattern.strip_prefix("./") {
-        pattern = rest;
-    }
-    if let Some(rest) = pattern.strip_prefix('/') {
-        pattern = rest;
-    }
-    let normalized = pattern.trim();
-    if normalized.is_empty() {
-        return None;
-    }
-    Some(normalized.to_string())
+/// `.ignore`, `.cursorignore` and Claude shared settings, plus any other target already in use.
+fn default_targets(root: &Path) -> Vec<Target> {
+    Target::ALL
+        .into_iter()
+        .filter(|target| {
+            matches!(
+                target,
+                Target::OpenCodeIgnore | Target::CursorIgnore | Target::ClaudeShared
+            ) || target.is_present(root)
+        })
+        .collect()
 }
 
 fn resolve_targets(args: &SyncIgnoreArgs, root: &Path) -> Result<Vec<Target>> {
     if args.target.is_empty() {
-        let mut targets = vec![
-            Target::OpenCodeIgnore,
-            Target::CursorIgnore,
-            Target::ClaudeShared,
-        ];
-        if root.join(".claude/settings.local.json").exists() {
-            targets.push(Target::ClaudeLocal);
-        }
-        return Ok(targets);
+        return Ok(default_targets(root));
     }
 
     let mut out = Vec::new();
@@ -447,13 +581,16 @@ fn resolve_targets(args: &SyncIgnoreArgs, root: &Path) -> Result<Vec<Target>> {
         match target {
             SyncIgnoreTarget::Opencode => push_unique(&mut out, Target::OpenCodeIgnore),
             SyncIgnoreTarget::Cursor => push_unique(&mut out, Target::CursorIgnore),
+            SyncIgnoreTarget::Aider => push_unique(&mut out, Target::AiderIgnore),
+            SyncIgnoreTarget::Gemini => push_unique(&mut out, Target::GeminiIgnore),
+            SyncIgnoreTarget::Copilot => push_unique(&mut out, Target::CopilotIgnore),
+            SyncIgnoreTarget::Codex => push_unique(&mut out, Target::CodexAgents),
             SyncIgnoreTarget::ClaudeShared => push_unique(&mut out, Target::ClaudeShared),
             SyncIgnoreTarget::ClaudeLocal => push_unique(&mut out, Target::ClaudeLocal),
             SyncIgnoreTarget::All => {
-                push_unique(&mut out, Target::OpenCodeIgnore);
-                push_unique(&mut out, Target::CursorIgnore);
-                push_unique(&mut out, Target::ClaudeShared);
-                push_unique(&mut out, Target::ClaudeLocal);
+                for target in Target::ALL {
+                    push_unique(&mut out, target);
+                }
             }
         }
     }
@@ -477,12 +614,11 @@ fn build_plans(
     for target in targets {
         let path = target.path(root);
         let plan = match target {
-            Target::OpenCodeIgnore | Target::CursorIgnore => {
-                build_gitignore_like_plan(*target, &path, union)?
-            }
             Target::ClaudeShared | Target::ClaudeLocal => {
                 build_claude_plan(*target, &path, union, verbose)?
             }
+            Target::CodexAgents => build_agents_guidance_plan(*target, &path, union)?,
+            _ => build_gitignore_like_plan(*target, &path, union)?,
         };
         plans.push(plan);
     }
@@ -523,6 +659,50 @@ fn build_gitignore_like_plan(
     })
 }
 
+fn build_agents_guidance_plan(
+    target: Target,
+    path: &Path,
+    union: &IgnoreRules,
+) -> Result<TargetPlan> {
+    let current = if path.exists() {
+        fs::read_to_string(path)
+            .with_context(|| t!("tool.sync_ignore.error.read_failed", path = path.display()))?
+    } else {
+        String::new()
+    };
+
+    let mut plan = TargetPlan {
+        target,
+        path: path.to_path_buf(),
+        action: PlanAction::Unchanged,
+        content: None,
+        added_claude_rules: Vec::new(),
+        notes: Vec::new(),
+    };
+    if union.is_empty() && extract_ignore_block(&current).is_none() {
+        return Ok(plan);
+    }
+
+    let desired = update_text_with_markers(
+        &current,
+        &render_agents_guidance(union),
+        true,
+        LLMAN_IGNORE_MARKER_START,
+        LLMAN_IGNORE_MARKER_END,
+    );
+    plan.action = if !path.exists() {
+        PlanAction::Create
+    } else if normalize_newlines(&current) == normalize_newlines(&desired) {
+        PlanAction::Unchanged
+    } else {
+        PlanAction::Update
+    };
+    if plan.action != PlanAction::Unchanged {
+        plan.content = Some(desired.into_bytes());
+    }
+    Ok(plan)
+}
+
 fn build_claude_plan(
     target: Target,
     path: &Path,
@@ -620,16 +800,8 @@ fn render_gitignore_like(rules: &IgnoreRules) -> String {
 fn render_claude_read_rules(ignore: &BTreeSet<String>) -> Vec<String> {
     ignore
         .iter()
-        .map(|pattern| {
-            let mut normalized = pattern.as_str();
-            if let Some(rest) = normalized.strip_prefix("./") {
-                normalized = rest;
-            }
-            if let Some(rest) = normalized.strip_prefix('/') {
-                normalized = rest;
-            }
-            format!("Read(./{})", normalized)
-        })
+        .filter_map(|line| IgnorePattern::parse_gitignore(line))
+        .filter_map(|pattern| pattern.to_claude_read())
         .collect()
 }
 
@@ -693,11 +865,21 @@ fn merge_deny_rules(
         }
     }
 
+    // `Read(./dir/**)` and `Read(/dir/**)` are the same rule; don't add a second spelling.
+    let mut known: Vec<IgnorePattern> = merged
+        .iter()
+        .filter_map(|rule| IgnorePattern::parse_claude_read(rule))
+        .collect();
     let mut added = Vec::new();
     for item in desired_read_rules {
-        if seen.insert(item.clone()) {
+        let pattern = IgnorePattern::parse_claude_read(item);
+        let equivalent = pattern
+            .as_ref()
+            .is_some_and(|pattern| known.iter().any(|rule| rule.key() == pattern.key()));
+        if !equivalent && seen.insert(item.clone()) {
             merged.push(item.clone());
             added.push(item.clone());
+            known.extend(pattern);
         }
     }
 
@@ -729,6 +911,82 @@ fn build_updated_claude_settings(mut value: Value, deny: Vec<String>) -> Value {
     value
 }
 
+/// Evaluate each target as it will read after the sync against the repository's files and
+/// note whether it hides exactly the files the merged rules hide. Files below
+/// [`SKIPPED_DIRS`] are not part of the comparison.
+fn verify_plans(
+    root: &Path,
+    union: &IgnoreRules,
+    files: &[PathBuf],
+    plans: &mut [TargetPlan],
+) -> Result<()> {
+    let expected = hidden_files(
+        &build_matcher(root, render_gitignore_like(union).lines())?,
+        files,
+    );
+    for plan in plans.iter_mut() {
+        if plan.action == PlanAction::Delete {
+            continue;
+        }
+        let text = match &plan.content {
+            Some(content) => String::from_utf8_lossy(content).into_owned(),
+            None => fs::read_to_string(&plan.path).unwrap_or_default(),
+        };
+        let lines = effective_ignore_lines(plan.target, &plan.path, &text)?;
+        let actual = hidden_files(&build_matcher(root, lines.lines())?, files);
+        if actual == expected {
+            plan.notes.push(SyncNote {
+                kind: SyncNoteKind::Info,
+                message: t!(
+                    "tool.sync_ignore.note.verify_match",
+                    target = plan.target.label(),
+                    count = expected.len(),
+                    skipped = SKIPPED_DIRS.join(", ")
+                )
+                .to_string(),
+            });
+            continue;
+        }
+        let extra: Vec<&PathBuf> = actual.difference(&expected).collect();
+        let missing: Vec<&PathBuf> = expected.difference(&actual).collect();
+        let example = extra
+            .iter()
+            .chain(missing.iter())
+            .next()
+            .map(|path| path.display().to_string())
+            .unwrap_or_default();
+        plan.notes.push(SyncNote {
+            kind: SyncNoteKind::Warning,
+            message: t!(
+                "tool.sync_ignore.warn.verify_mismatch",
+                target = plan.target.label(),
+                extra = extra.len(),
+                missing = missing.len(),
+                example = example
+            )
+            .to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// The gitignore lines a target's `text` amounts to.
+fn effective_ignore_lines(target: Target, path: &Path, text: &str) -> Result<String> {
+    let rules = match target {
+        Target::ClaudeShared | Target::ClaudeLocal if text.trim().is_empty() => {
+            IgnoreRules::default()
+        }
+        Target::ClaudeShared | Target::ClaudeLocal => parse_claude_settings(text, false)?.0,
+        Target::CodexAgents => parse_agents_guidance(path, text).0,
+        Target::OpenCodeIgnore
+        | Target::CursorIgnore
+        | Target::AiderIgnore
+        | Target::GeminiIgnore
+        | Target::CopilotIgnore => return Ok(text.to_string()),
+    };
+    Ok(render_gitignore_like(&rules))
+}
+
 fn normalize_newlines(text: &str) -> String {
     text.replace("\r\n", "\n")
 }
@@ -737,6 +995,9 @@ fn apply_plans(plans: &[TargetPlan]) -> Result<()> {
     for plan in plans {
         match plan.action {
             PlanAction::Unchanged => continue,
+            PlanAction::Delete if plan.target == Target::CodexAgents => {
+                remove_agents_guidance(&plan.path)?;
+            }
             PlanAction::Delete => {
                 if plan.path.exists() {
                     fs::remove_file(&plan.path).with_context(|| {
@@ -772,6 +1033,29 @@ fn apply_plans(plans: &[TargetPlan]) -> Result<()> {
     Ok(())
 }
 
+/// Strip the llman ignore block; `AGENTS.md` is deleted only if nothing else is left.
+fn remove_agents_guidance(path: &Path) -> Result<()> {
+    let Ok(current) = fs::read_to_string(path) else {
+        return Ok(());
+    };
+    let Some(updated) =
+        remove_text_with_markers(&current, LLMAN_IGNORE_MARKER_START, LLMAN_IGNORE_MARKER_END)
+    else {
+        return Ok(());
+    };
+    if updated.trim().is_empty() {
+        fs::remove_file(path).with_context(|| {
+            t!(
+                "tool.sync_ignore.error.delete_failed",
+                path = path.display()
+            )
+        })
+    } else {
+        atomic_write_with_mode(path, updated.as_bytes(), existing_file_mode(path))
+            .with_context(|| t!("tool.sync_ignore.error.write_failed", path = path.display()))
+    }
+}
+
 fn desired_write_mode(path: &Path, action: PlanAction) -> Option<u32> {
     let default_mode = 0o644;
     match action {
@@ -882,8 +1166,8 @@ fn render_preview_table(union: &IgnoreRules, plans: &[TargetPlan]) -> String {
 
 fn plan_written_rule_counts(union: &IgnoreRules, plan: &TargetPlan) -> (usize, usize) {
     match plan.target {
-        Target::OpenCodeIgnore | Target::CursorIgnore => (union.ignore.len(), union.include.len()),
         Target::ClaudeShared | Target::ClaudeLocal => (union.ignore.len(), 0),
+        _ => (union.ignore.len(), union.include.len()),
     }
 }
 
@@ -974,7 +1258,12 @@ fn print_preview_details(plans: &[TargetPlan], verbose: bool) {
     for plan in plans {
         match plan.action {
             PlanAction::Create | PlanAction::Update => match plan.target {
-                Target::OpenCodeIgnore | Target::CursorIgnore => {
+                Target::OpenCodeIgnore
+                | Target::CursorIgnore
+                | Target::AiderIgnore
+                | Target::GeminiIgnore
+                | Target::CopilotIgnore
+                | Target::CodexAgents => {
                     let Some(content) = &plan.content else {
                         continue;
                     };
@@ -987,7 +1276,11 @@ fn print_preview_details(plans: &[TargetPlan], verbose: bool) {
                         )
                     );
                     let text = String::from_utf8_lossy(content);
-                    let lines: Vec<&str> = text.lines().collect();
+                    let text = match plan.target {
+                        Target::CodexAgents => extract_ignore_block(&text).unwrap_or(&text),
+                        _ => &text,
+                    };
+                    let lines: Vec<&str> = text.trim_matches('\n').lines().collect();
                     let limit = if verbose { lines.len() } else { MAX_LINES };
                     for line in lines.iter().take(limit) {
                         println!("{line}");
@@ -1035,14 +1328,12 @@ fn print_preview_details(plans: &[TargetPlan], verbose: bool) {
                 }
             },
             PlanAction::Delete => {
+                let key = match plan.target {
+                    Target::CodexAgents => "tool.sync_ignore.preview.remove_block_title",
+                    _ => "tool.sync_ignore.preview.delete_title",
+                };
                 println!();
-                println!(
-                    "{}",
-                    t!(
-                        "tool.sync_ignore.preview.delete_title",
-                        path = plan.target.label()
-                    )
-                );
+                println!("{}", t!(key, path = plan.target.label()));
             }
             PlanAction::Unchanged => {}
         }
@@ -1114,173 +1405,6 @@ fn render_json_string_array(items: &[String], line_indent: &str) -> String {
     out
 }
 
-fn line_indent(text: &str, idx: usize) -> String {
-    let line_start = text[..idx].rfind('\n').map(|pos| pos + 1).unwrap_or(0);
-    let slice = &text[line_start..idx];
-    slice.chars().take_while(|ch| ch.is_whitespace()).collect()
-}
-
-fn skip_ws_and_comments(text: &str, mut idx: usize) -> usize {
-    let bytes = text.as_bytes();
-    loop {
-        while idx < bytes.len() && bytes[idx].is_ascii_whitespace() {
-            idx += 1;
-        }
-
-        if idx + 1 < bytes.len() && bytes[idx] == b'/' && bytes[idx + 1] == b'/' {
-            idx += 2;
-            while idx < bytes.len() && bytes[idx] != b'\n' {
-                idx += 1;
-            }
-            continue;
-        }
-
-        if idx + 1 < bytes.len() && bytes[idx] == b'/' && bytes[idx + 1] == b'*' {
-            idx += 2;
-            while idx + 1 < bytes.len() {
-                if bytes[idx] == b'*' && bytes[idx + 1] == b'/' {
-                    idx += 2;
-                    break;
-                }
-                idx += 1;
-            }
-            continue;
-        }
-
-        break;
-    }
-    idx
-}
-
-fn parse_json_string(text: &str, start_quote: usize) -> Option<(String, usize)> {
-    let bytes = text.as_bytes();
-    if bytes.get(start_quote)? != &b'"' {
-        return None;
-    }
-
-    let mut out = String::new();
-    let mut idx = start_quote + 1;
-    while idx < bytes.len() {
-        match bytes[idx] {
-            b'\\' => {
-                idx += 1;
-                let esc = *bytes.get(idx)?;
-                match esc {
-                    b'"' => out.push('"'),
-                    b'\\' => out.push('\\'),
-                    b'/' => out.push('/'),
-                    b'b' => out.push('\u{0008}'),
-                    b'f' => out.push('\u{000c}'),
-                    b'n' => out.push('\n'),
-                    b'r' => out.push('\r'),
-                    b't' => out.push('\t'),
-                    b'u' => {
-                        let hex = text.get(idx + 1..idx + 5)?;
-                        let code = u16::from_str_radix(hex, 16).ok()?;
-                        out.push(char::from_u32(code as u32)?);
-                        idx += 4;
-                    }
-                    _ => return None,
-                }
-                idx += 1;
-            }
-            b'"' => return Some((out, idx + 1)),
-            other => {
-                out.push(other as char);
-                idx += 1;
-            }
-        }
-    }
-
-    None
-}
-
-fn find_object_member_value_start(
-    text: &str,
-    start: usize,
-    end: usize,
-    key: &str,
-) -> Option<usize> {
-    let bytes = text.as_bytes();
-    let mut idx = start;
-    let mut depth_obj = 0usize;
-    let mut depth_arr = 0usize;
-
-    while idx < end {
-        idx = skip_ws_and_comments(text, idx);
-        if idx >= end {
-            break;
-        }
-
-        match bytes[idx] {
-            b'"' => {
-                let (s, next) = parse_json_string(text, idx)?;
-                let after = skip_ws_and_comments(text, next);
-                if depth_obj == 0 && depth_arr == 0 && s == key && bytes.get(after) == Some(&b':') {
-                    return Some(skip_ws_and_comments(text, after + 1));
-                }
-                idx = next;
-            }
-            b'{' => {
-                depth_obj += 1;
-                idx += 1;
-            }
-            b'}' => {
-                depth_obj = depth_obj.saturating_sub(1);
-                idx += 1;
-            }
-            b'[' => {
-                depth_arr += 1;
-                idx += 1;
-            }
-            b']' => {
-                depth_arr = depth_arr.saturating_sub(1);
-                idx += 1;
-            }
-            _ => idx += 1,
-        }
-    }
-
-    None
-}
-
-fn find_matching_bracket(text: &str, start: usize, open: u8, close: u8) -> Option<usize> {
-    let bytes = text.as_bytes();
-    if bytes.get(start)? != &open {
-        return None;
-    }
-
-    let mut idx = start;
-    let mut depth = 0usize;
-    while idx < bytes.len() {
-        idx = skip_ws_and_comments(text, idx);
-        if idx >= bytes.len() {
-            break;
-        }
-
-        match bytes[idx] {
-            b'"' => {
-                let (_, next) = parse_json_string(text, idx)?;
-                idx = next;
-            }
-            ch if ch == open => {
-                depth += 1;
-                idx += 1;
-            }
-            ch if ch == close => {
-                depth = depth.saturating_sub(1);
-                idx += 1;
-                if depth == 0 {
-                    return Some(idx - 1);
-                }
-            }
-            _ => idx += 1,
-        }
-    }
-
-    None
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1312,16 +1436,68 @@ secrets/**
     }
 
     #[test]
-    fn test_extract_read_pattern_normalizes_prefixes() {
+    fn test_union_merges_dialects_and_verification_catches_lossy_targets() {
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path();
+        for file in [
+            "secrets/key.pem",
+            "secrets/README.md",
+            "src/.env",
+            "src/main.rs",
+        ] {
+            let path = root.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "").unwrap();
+        }
+        fs::write(root.join(".ignore"), "/secrets/\n.env\n").unwrap();
+        fs::write(
+            root.join(".cursorignore"),
+            "secrets/**\n!secrets/README.md\n",
+        )
+        .unwrap();
+        fs::create_dir_all(root.join(".claude")).unwrap();
+        fs::write(
+            root.join(".claude/settings.json"),
+            r#"{"permissions": {"deny": ["Read(./secrets/**)", "Read(./**/.env)"]}}"#,
+        )
+        .unwrap();
+
+        let sources = discover_sources(root, &[]).unwrap();
+        let files = repo_files(root);
+        let (union, notes) = load_union_rules(root, &sources, &files, false).unwrap();
         assert_eq!(
-            extract_read_pattern("Read(./.env)").as_deref(),
-            Some(".env")
+            render_gitignore_like(&union),
+            ".env\nsecrets/**\n!secrets/README.md\n"
         );
-        assert_eq!(extract_read_pattern("Read(.env)").as_deref(), Some(".env"));
-        assert_eq!(
-            extract_read_pattern("Read(/secrets/**)").as_deref(),
-            Some("secrets/**")
+        assert!(
+            notes.iter().any(|note| note.kind == SyncNoteKind::Warning
+                && note.message.contains(".cursorignore")
+                && note.message.contains("!secrets/README.md")),
+            "{notes:?}"
+        );
+
+        let targets = [Target::OpenCodeIgnore, Target::ClaudeShared];
+        let mut plans = build_plans(root, &union, &targets, false).unwrap();
+        assert!(plans[1].added_claude_rules.is_empty());
+        verify_plans(root, &union, &files, &mut plans).unwrap();
+        assert!(
+            plans[0]
+                .notes
+                .iter()
+                .all(|note| note.kind == SyncNoteKind::Info)
         );
+        // Claude cannot re-include secrets/README.md, so it hides one file too many.
+        assert!(plans[1].notes.iter().any(|note| {
+            note.kind == SyncNoteKind::Warning && note.message.contains("secrets/README.md")
+        }));
+
+        // A walker prunes `secrets/` before it could see the re-include.
+        plans[0].content = Some(b"/secrets/\n.env\n!secrets/README.md\n".to_vec());
+        plans[0].notes.clear();
+        verify_plans(root, &union, &files, &mut plans[..1]).unwrap();
+        assert!(plans[0].notes.iter().any(|note| {
+            note.kind == SyncNoteKind::Warning && note.message.contains("secrets/README.md")
+        }));
     }
 
     #[test]
@@ -1371,8 +1547,68 @@ secrets/**
 }
 "#;
         let (rules, _) = parse_claude_settings(content, true).expect("parse settings");
-        assert!(rules.ignore.contains("secrets/**"));
-        assert!(!rules.ignore.contains("./secrets/**"));
+        assert!(rules.ignore.contains("/secrets/"));
+        assert_eq!(rules.ignore.len(), 1);
+    }
+
+    #[test]
+    fn test_agents_guidance_round_trips_and_new_targets_are_sources() {
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path();
+        fs::write(root.join(".aiderignore"), "secrets/\n!secrets/README.md\n").unwrap();
+        fs::write(root.join("AGENTS.md"), "# Agents\n\nUse anyhow.\n").unwrap();
+
+        let sources = discover_sources(root, &[]).unwrap();
+        assert_eq!(sources, vec![root.join(".aiderignore")]);
+        let (union, _) = load_union_rules(root, &sources, &repo_files(root), false).unwrap();
+
+        let plan = build_agents_guidance_plan(
+            Target::CodexAgents,
+            &Target::CodexAgents.path(root),
+            &union,
+        )
+        .unwrap();
+        assert_eq!(plan.action, PlanAction::Update);
+        apply_plans(std::slice::from_ref(&plan)).unwrap();
+
+        let agents = fs::read_to_string(root.join("AGENTS.md")).unwrap();
+        assert!(agents.starts_with("# Agents\n\nUse anyhow.\n"), "{agents}");
+        let (parsed, notes) = parse_agents_guidance(&root.join("AGENTS.md"), &agents);
+        assert_eq!(parsed, union);
+        assert!(notes.is_empty());
+        assert!(default_targets(root).contains(&Target::CodexAgents));
+        assert!(default_targets(root).contains(&Target::AiderIgnore));
+        assert!(!default_targets(root).contains(&Target::GeminiIgnore));
+
+        let plan = build_agents_guidance_plan(
+            Target::CodexAgents,
+            &Target::CodexAgents.path(root),
+            &union,
+        )
+        .unwrap();
+        assert_eq!(plan.action, PlanAction::Unchanged);
+
+        remove_agents_guidance(&root.join("AGENTS.md")).unwrap();
+        assert_eq!(
+            fs::read_to_string(root.join("AGENTS.md")).unwrap(),
+            "# Agents\n\nUse anyhow.\n"
+        );
+    }
+
+    #[test]
+    fn test_quoted_ignore_markers_are_not_a_block() {
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path();
+        let agents = "# Agents\n\nllman writes between `<!-- LLMAN-IGNORE:START -->` and \
+                      `<!-- LLMAN-IGNORE:END -->`.\n";
+        fs::write(root.join("AGENTS.md"), agents).unwrap();
+
+        assert_eq!(extract_ignore_block(agents), None);
+        assert!(!Target::CodexAgents.is_present(root));
+        assert!(discover_sources(root, &[]).unwrap().is_empty());
+
+        let block = "intro\n  <!-- LLMAN-IGNORE:START -->\nbody\n<!-- LLMAN-IGNORE:END -->\n";
+        assert_eq!(extract_ignore_block(block), Some("body\n"));
     }
 
     #[test]
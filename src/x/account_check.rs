@@ -0,0 +1,473 @@
+//! Connectivity checks for account groups.
+//!
+//! Each check sends the smallest authenticated request the configured wire API accepts and
+//! classifies the response, so expired keys or unavailable models show up before an agent
+//! session starts.
+
+use anyhow::{Context, Result};
+use reqwest::StatusCode;
+use rust_i18n::t;
+use serde::Serialize;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+pub const ANTHROPIC_DEFAULT_BASE_URL: &str = "https://api.anthropic.com";
+/// Model checked when neither `--model`, the group's `check_model` nor its env names one.
+pub const ANTHROPIC_DEFAULT_MODEL: &str = "claude-haiku-4-5";
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+const PING: &str = "ping";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WireApi {
+    AnthropicMessages,
+    #[serde(rename = "openai_responses")]
+    OpenAiResponses,
+    #[serde(rename = "openai_chat")]
+    OpenAiChat,
+    /// `GET /models`: lists the endpoint's models and, when a model is known, looks it up.
+    #[serde(rename = "openai_models")]
+    OpenAiModels,
+}
+
+impl WireApi {
+    /// Map a Codex `wire_api` value. Without a model, or for a wire API that cannot be
+    /// requested directly, the check falls back to the `/models` list.
+    pub fn from_codex(wire_api: &str, has_model: bool) -> Self {
+        match (wire_api, has_model) {
+            ("chat", true) => Self::OpenAiChat,
+            ("responses", true) => Self::OpenAiResponses,
+            _ => Self::OpenAiModels,
+        }
+    }
+}
+
+/// How the API key is sent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuthStyle {
+    Bearer,
+    /// Anthropic `x-api-key` header.
+    ApiKeyHeader,
+}
+
+#[derive(Debug, Clone)]
+pub struct CheckTarget {
+    pub name: String,
+    pub wire_api: WireApi,
+    pub base_url: String,
+    pub api_key: Option<String>,
+    pub auth: AuthStyle,
+    pub model: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    Ok,
+    /// Credentials work but the endpoint is throttling.
+    RateLimited,
+    AuthFailed,
+    ModelUnavailable,
+    MissingKey,
+    HttpError,
+    Unreachable,
+}
+
+impl CheckStatus {
+    pub fn is_ok(self) -> bool {
+        matches!(self, Self::Ok | Self::RateLimited)
+    }
+
+    fn label(self) -> String {
+        match self {
+            Self::Ok => t!("x.account_check.status.ok"),
+            Self::RateLimited => t!("x.account_check.status.rate_limited"),
+            Self::AuthFailed => t!("x.account_check.status.auth_failed"),
+            Self::ModelUnavailable => t!("x.account_check.status.model_unavailable"),
+            Self::MissingKey => t!("x.account_check.status.missing_key"),
+            Self::HttpError => t!("x.account_check.status.http_error"),
+            Self::Unreachable => t!("x.account_check.status.unreachable"),
+        }
+        .to_string()
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckResult {
+    pub name: String,
+    pub wire_api: WireApi,
+    pub url: String,
+    pub model: Option<String>,
+    pub status: CheckStatus,
+    pub http_status: Option<u16>,
+    pub latency_ms: Option<u64>,
+    pub message: Option<String>,
+}
+
+/// Target for a Claude Code group from its resolved env: `ANTHROPIC_AUTH_TOKEN` is sent as a
+/// bearer token, `ANTHROPIC_API_KEY` as `x-api-key`.
+pub fn claude_code_target(
+    name: &str,
+    env: &HashMap<String, String>,
+    model: Option<&str>,
+) -> CheckTarget {
+    let var = |key: &str| env.get(key).filter(|value| !value.is_empty()).cloned();
+    let (api_key, auth) = match var("ANTHROPIC_AUTH_TOKEN") {
+        Some(token) => (Some(token), AuthStyle::Bearer),
+        None => (var("ANTHROPIC_API_KEY"), AuthStyle::ApiKeyHeader),
+    };
+    let model = model
+        .map(str::to_string)
+        .or_else(|| var("ANTHROPIC_MODEL"))
+        .or_else(|| var("ANTHROPIC_SMALL_FAST_MODEL"))
+        .unwrap_or_else(|| ANTHROPIC_DEFAULT_MODEL.to_string());
+    CheckTarget {
+        name: name.to_string(),
+        wire_api: WireApi::AnthropicMessages,
+        base_url: var("ANTHROPIC_BASE_URL")
+            .unwrap_or_else(|| ANTHROPIC_DEFAULT_BASE_URL.to_string()),
+        api_key,
+        auth,
+        model: Some(model),
+    }
+}
+
+/// Run every check sequentially with a per-request timeout.
+pub fn run_checks(targets: &[CheckTarget], timeout: Duration) -> Result<Vec<CheckResult>> {
+    let client = reqwest::Client::builder()
+        .timeout(timeout)
+        .build()
+        .context(t!("x.account_check.client_failed"))?;
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?;
+    Ok(runtime.block_on(async {
+        let mut results = Vec::with_capacity(targets.len());
+        for target in targets {
+            results.push(check_one(&client, target).await);
+        }
+        results
+    }))
+}
+
+async fn check_one(client: &reqwest::Client, target: &CheckTarget) -> CheckResult {
+    let base = target.base_url.trim_end_matches('/');
+    let url = match target.wire_api {
+        WireApi::AnthropicMessages => format!("{base}/v1/messages"),
+        WireApi::OpenAiResponses => format!("{base}/responses"),
+        WireApi::OpenAiChat => format!("{base}/chat/completions"),
+        WireApi::OpenAiModels => format!("{base}/models"),
+    };
+    let mut result = CheckResult {
+        name: target.name.clone(),
+        wire_api: target.wire_api,
+        url: url.clone(),
+        model: target.model.clone(),
+        status: CheckStatus::MissingKey,
+        http_status: None,
+        latency_ms: None,
+        message: None,
+    };
+    let Some(api_key) = target.api_key.as_deref().filter(|key| !key.is_empty()) else {
+        return result;
+    };
+
+    let model = target.model.as_deref().unwrap_or_default();
+    let request = match target.wire_api {
+        WireApi::OpenAiModels => client.get(&url),
+        WireApi::AnthropicMessages => client
+            .post(&url)
+            .header("anthropic-version", ANTHROPIC_VERSION)
+            .json(&json!({
+                "model": model,
+                "max_tokens": 1,
+                "messages": [{"role": "user", "content": PING}],
+            })),
+        WireApi::OpenAiResponses => client.post(&url).json(&json!({
+            "model": model,
+            "input": PING,
+            "max_output_tokens": 16,
+        })),
+        WireApi::OpenAiChat => client.post(&url).json(&json!({
+            "model": model,
+            "max_tokens": 1,
+            "messages": [{"role": "user", "content": PING}],
+        })),
+    };
+    let request = match target.auth {
+        AuthStyle::Bearer => request.bearer_auth(api_key),
+        AuthStyle::ApiKeyHeader => request.header("x-api-key", api_key),
+    };
+
+    let started = Instant::now();
+    let response = request.send().await;
+    result.latency_ms = Some(started.elapsed().as_millis() as u64);
+    match response {
+        Ok(response) => {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            result.http_status = Some(status.as_u16());
+            result.status = classify(status, &body);
+            if !result.status.is_ok() {
+                result.message = error_message(&body);
+            } else if target.wire_api == WireApi::OpenAiModels && status.is_success() {
+                check_model_list(&mut result, &body);
+            }
+        }
+        Err(e) => {
+            result.status = CheckStatus::Unreachable;
+            result.message = Some(e.to_string());
+        }
+    }
+    result
+}
+
+/// Look the target model up in a `/models` response (`{"data": [{"id": ...}]}`), or report
+/// how many models the endpoint lists when no model is known.
+fn check_model_list(result: &mut CheckResult, body: &str) {
+    let ids: Vec<String> = serde_json::from_str::<Value>(body)
+        .ok()
+        .and_then(|value| value.get("data").and_then(Value::as_array).cloned())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|model| model.get("id").and_then(Value::as_str).map(str::to_string))
+        .collect();
+    match result.model.as_deref() {
+        Some(model) if !ids.iter().any(|id| id == model) => {
+            result.status = CheckStatus::ModelUnavailable;
+            result.message =
+                Some(t!("x.account_check.model_not_listed", model = model).to_string());
+        }
+        Some(_) => {}
+        None => {
+            result.message =
+                Some(t!("x.account_check.models_listed", count = ids.len()).to_string());
+        }
+    }
+}
+
+/// Anthropic and OpenAI both report unknown models as 404 (sometimes 400) with an error
+/// body that mentions the model.
+fn classify(status: StatusCode, body: &str) -> CheckStatus {
+    if status.is_success() {
+        return CheckStatus::Ok;
+    }
+    match status {
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => CheckStatus::AuthFailed,
+        StatusCode::TOO_MANY_REQUESTS => CheckStatus::RateLimited,
+        StatusCode::NOT_FOUND | StatusCode::BAD_REQUEST
+            if body.to_ascii_lowercase().contains("model") =>
+        {
+            CheckStatus::ModelUnavailable
+        }
+        _ => CheckStatus::HttpError,
+    }
+}
+
+fn error_message(body: &str) -> Option<String> {
+    let trimmed = body.trim();
+    if trimmed.is_empty() {
+        return None;
+    }
+    let message = serde_json::from_str::<Value>(trimmed)
+        .ok()
+        .and_then(|value| {
+            value
+                .pointer("/error/message")
+                .or_else(|| value.get("message"))
+                .and_then(Value::as_str)
+                .map(str::to_string)
+        })
+        .unwrap_or_else(|| trimmed.chars().take(200).collect());
+    Some(message)
+}
+
+pub fn print_results(results: &[CheckResult], json: bool) -> Result<()> {
+    if json {
+        println!("{}", serde_json::to_string_pretty(results)?);
+        return Ok(());
+    }
+
+    use comfy_table::{Cell, ContentArrangement, Table};
+    let mut table = Table::new();
+    table.load_preset(comfy_table::presets::UTF8_BORDERS_ONLY);
+    table.set_content_arrangement(ContentArrangement::Dynamic);
+    table.set_header(vec![
+        Cell::new(t!("x.account_check.header.name").to_string()),
+        Cell::new(t!("x.account_check.header.status").to_string()),
+        Cell::new(t!("x.account_check.header.model").to_string()),
+        Cell::new(t!("x.account_check.header.latency").to_string()),
+        Cell::new(t!("x.account_check.header.detail").to_string()),
+    ]);
+    for result in results {
+        let status = match result.http_status {
+            Some(code) => format!("{} ({code})", result.status.label()),
+            None => result.status.label(),
+        };
+        table.add_row(vec![
+            Cell::new(&result.name),
+            Cell::new(status),
+            Cell::new(result.model.as_deref().unwrap_or("-")),
+            Cell::new(
+                result
+                    .latency_ms
+                    .map(|ms| format!("{ms} ms"))
+                    .unwrap_or_else(|| "-".to_string()),
+            ),
+            Cell::new(result.message.as_deref().unwrap_or(&result.url)),
+        ]);
+    }
+    println!("{table}");
+    Ok(())
+}
+
+/// Fail the command when any check did not pass, after the results were printed.
+pub fn ensure_all_ok(results: &[CheckResult]) -> Result<()> {
+    let failed = results
+        .iter()
+        .filter(|result| !result.status.is_ok())
+        .count();
+    if failed > 0 {
+        anyhow::bail!(t!("x.account_check.failed", count = failed));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::http_stub as stub;
+
+    fn target(base_url: &str, wire_api: WireApi, auth: AuthStyle) -> CheckTarget {
+        CheckTarget {
+            name: "g".to_string(),
+            wire_api,
+            base_url: base_url.to_string(),
+            api_key: Some("sk-test".to_string()),
+            auth,
+            model: Some("m1".to_string()),
+        }
+    }
+
+    #[test]
+    fn anthropic_check_sends_minimal_message_with_api_key_header() {
+        let (base, requests) = stub::serve(vec![(200, r#"{"id":"msg"}"#)]);
+        let results = run_checks(
+            &[target(
+                &base,
+                WireApi::AnthropicMessages,
+                AuthStyle::ApiKeyHeader,
+            )],
+            Duration::from_secs(5),
+        )
+        .expect("run");
+
+        assert_eq!(results[0].status, CheckStatus::Ok);
+        assert!(results[0].latency_ms.is_some());
+        let request = requests.recv().expect("request");
+        assert!(request.starts_with("POST /v1/messages "), "{request}");
+        assert!(request.contains("x-api-key: sk-test"), "{request}");
+        assert!(request.contains("anthropic-version"), "{request}");
+        assert!(request.contains(r#""max_tokens":1"#), "{request}");
+    }
+
+    #[test]
+    fn classifies_auth_model_and_missing_key_failures() {
+        let (base, requests) = stub::serve(vec![
+            (401, r#"{"error":{"message":"invalid x-api-key"}}"#),
+            (
+                404,
+                r#"{"error":{"message":"The model `m1` does not exist","code":"model_not_found"}}"#,
+            ),
+        ]);
+        let mut missing = target(&base, WireApi::OpenAiChat, AuthStyle::Bearer);
+        missing.api_key = None;
+        let results = run_checks(
+            &[
+                target(&base, WireApi::OpenAiResponses, AuthStyle::Bearer),
+                target(&base, WireApi::OpenAiChat, AuthStyle::Bearer),
+                missing,
+            ],
+            Duration::from_secs(5),
+        )
+        .expect("run");
+
+        assert_eq!(results[0].status, CheckStatus::AuthFailed);
+        assert_eq!(results[0].message.as_deref(), Some("invalid x-api-key"));
+        assert_eq!(results[1].status, CheckStatus::ModelUnavailable);
+        assert_eq!(results[2].status, CheckStatus::MissingKey);
+        assert!(ensure_all_ok(&results).is_err());
+
+        assert!(
+            requests
+                .recv()
+                .expect("first")
+                .contains("authorization: Bearer sk-test")
+        );
+        assert!(
+            requests
+                .recv()
+                .expect("second")
+                .starts_with("POST /chat/completions ")
+        );
+    }
+
+    #[test]
+    fn claude_code_target_prefers_auth_token_and_group_model() {
+        let env: HashMap<String, String> = [
+            ("ANTHROPIC_BASE_URL", "https://proxy.example.com/"),
+            ("ANTHROPIC_AUTH_TOKEN", "tok"),
+            ("ANTHROPIC_API_KEY", "key"),
+            ("ANTHROPIC_MODEL", "group-model"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+        let target = claude_code_target("g", &env, None);
+        assert_eq!(target.auth, AuthStyle::Bearer);
+        assert_eq!(target.api_key.as_deref(), Some("tok"));
+        assert_eq!(target.model.as_deref(), Some("group-model"));
+
+        let target = claude_code_target("g", &HashMap::new(), Some("override"));
+        assert_eq!(target.base_url, ANTHROPIC_DEFAULT_BASE_URL);
+        assert_eq!(target.model.as_deref(), Some("override"));
+        assert!(target.api_key.is_none());
+    }
+
+    #[test]
+    fn codex_wire_api_without_model_lists_models() {
+        assert_eq!(WireApi::from_codex("chat", false), WireApi::OpenAiModels);
+        assert_eq!(WireApi::from_codex("chat", true), WireApi::OpenAiChat);
+        assert_eq!(
+            WireApi::from_codex("responses", true),
+            WireApi::OpenAiResponses
+        );
+        assert_eq!(WireApi::from_codex("other", true), WireApi::OpenAiModels);
+    }
+
+    #[test]
+    fn models_list_reports_whether_the_model_is_available() {
+        let listing = r#"{"data":[{"id":"m1"},{"id":"m2"}]}"#;
+        let (base, _requests) = stub::serve(vec![(200, listing), (200, listing), (200, listing)]);
+        let mut unlisted = target(&base, WireApi::OpenAiModels, AuthStyle::Bearer);
+        unlisted.model = Some("m3".to_string());
+        let mut unknown = target(&base, WireApi::OpenAiModels, AuthStyle::Bearer);
+        unknown.model = None;
+        let results = run_checks(
+            &[
+                target(&base, WireApi::OpenAiModels, AuthStyle::Bearer),
+                unlisted,
+                unknown,
+            ],
+            Duration::from_secs(5),
+        )
+        .expect("run");
+
+        assert_eq!(results[0].status, CheckStatus::Ok);
+        assert_eq!(results[1].status, CheckStatus::ModelUnavailable);
+        assert!(results[1].message.as_deref().unwrap().contains("m3"));
+        assert_eq!(results[2].status, CheckStatus::Ok);
+        assert!(results[2].message.as_deref().unwrap().contains('2'));
+    }
+}
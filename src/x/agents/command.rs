@@ -0,0 +1,235 @@
+use crate::config::resolve_config_dir;
+use crate::skills::cli::interactive::is_interactive;
+use crate::x::agents::convert::{AgentFormat, convert, output_stem};
+use crate::x::agents::plan::{self, Plan, PlanOp, backup_path_for, list_stems, select_stems};
+use anyhow::{Context, Result, bail};
+use clap::{Args, Subcommand};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+#[derive(Args, Debug, Clone)]
+#[command(about = "Tool-independent agent definition commands")]
+pub struct AgentsArgs {
+    #[command(subcommand)]
+    pub command: AgentsCommand,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum AgentsCommand {
+    /// Convert agent definitions between Codex TOML and Claude Code markdown
+    ///
+    /// Reads the managed agents of one tool and writes the other tool's managed agents,
+    /// so one side can stay the single source; deploy with `x codex agents sync` or
+    /// `x claude-code agents sync`.
+    Convert(ConvertArgs),
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct ConvertArgs {
+    /// Source format
+    #[arg(long, value_enum)]
+    pub from: AgentFormat,
+
+    /// Target format
+    #[arg(long, value_enum)]
+    pub to: AgentFormat,
+
+    /// Source directory (default: $LLMAN_CONFIG_DIR/<from>/agents)
+    #[arg(long = "input-dir")]
+    pub input_dir: Option<PathBuf>,
+
+    /// Output directory (default: $LLMAN_CONFIG_DIR/<to>/agents)
+    #[arg(long = "output-dir")]
+    pub output_dir: Option<PathBuf>,
+
+    /// Only convert selected agent files (repeatable; matches the source file stem)
+    #[arg(long, value_delimiter = ',', action = clap::ArgAction::Append)]
+    pub only: Vec<String>,
+
+    /// Model mapping applied before conversion, e.g. `gpt-5.4-mini=haiku` (repeatable)
+    #[arg(long = "model-map", value_delimiter = ',', action = clap::ArgAction::Append)]
+    pub model_map: Vec<String>,
+
+    /// Exit non-zero if any converted output differs from the existing file
+    #[arg(long, conflicts_with = "dry_run")]
+    pub check: bool,
+
+    /// Show plan but do not write any files
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Assume "yes" for confirmations (required for non-interactive write operations)
+    #[arg(long)]
+    pub yes: bool,
+
+    /// Force write operations without interactive confirmation (alias of --yes)
+    #[arg(long)]
+    pub force: bool,
+}
+
+pub fn run(args: &AgentsArgs) -> Result<()> {
+    match &args.command {
+        AgentsCommand::Convert(convert_args) => run_convert(convert_args),
+    }
+}
+
+fn run_convert(args: &ConvertArgs) -> Result<()> {
+    if args.from == args.to {
+        bail!("--from and --to must be different formats");
+    }
+    let model_map = parse_model_map(&args.model_map)?;
+    let input_dir = match &args.input_dir {
+        Some(dir) => dir.clone(),
+        None => managed_agents_dir(args.from)?,
+    };
+    let output_dir = match &args.output_dir {
+        Some(dir) => dir.clone(),
+        None => managed_agents_dir(args.to)?,
+    };
+
+    println!("Source agents dir: {}", input_dir.display());
+    println!("Output agents dir: {}", output_dir.display());
+
+    let available = list_stems(&input_dir, args.from.extension())?;
+    let selected = select_stems(&available, &args.only, "agent name")?;
+
+    let mut plan = Plan::default();
+    if !output_dir.exists() {
+        plan.ops.push(PlanOp::EnsureDir {
+            path: output_dir.clone(),
+        });
+    }
+    let mut outdated = Vec::new();
+    for stem in &selected {
+        let source = input_dir.join(format!("{stem}.{}", args.from.extension()));
+        let content =
+            fs::read_to_string(&source).with_context(|| format!("read: {}", source.display()))?;
+        let converted = convert(&content, stem, args.from, args.to, &model_map)
+            .with_context(|| format!("convert: {}", source.display()))?;
+        let Some(converted) = converted else {
+            plan.ops.push(PlanOp::Note {
+                message: format!("skip (not an agent definition): {}", source.display()),
+            });
+            continue;
+        };
+        for note in &converted.notes {
+            println!("note: {stem}: {note}");
+        }
+
+        let path = output_dir.join(format!(
+            "{}.{}",
+            output_stem(&converted.name, args.to),
+            args.to.extension()
+        ));
+        let existing = fs::read_to_string(&path).ok();
+        if existing.as_deref() == Some(converted.content.as_str()) {
+            continue;
+        }
+        outdated.push(path.clone());
+        if fs::symlink_metadata(&path).is_ok() {
+            plan.ops.push(PlanOp::Backup {
+                from: path.clone(),
+                to: backup_path_for(&path)?,
+            });
+        }
+        plan.ops.push(PlanOp::WriteFile {
+            path,
+            content: converted.content,
+        });
+    }
+
+    if args.check {
+        if outdated.is_empty() {
+            println!("All converted agents are up to date.");
+            return Ok(());
+        }
+        for path in &outdated {
+            println!("outdated: {}", path.display());
+        }
+        bail!(
+            "{} converted agent(s) out of date; re-run without --check to update",
+            outdated.len()
+        );
+    }
+
+    plan::apply_plan(plan, args.dry_run, is_interactive(), args.yes || args.force)
+}
+
+fn managed_agents_dir(format: AgentFormat) -> Result<PathBuf> {
+    Ok(resolve_config_dir(None)?.join(format.app()).join("agents"))
+}
+
+fn parse_model_map(entries: &[String]) -> Result<BTreeMap<String, String>> {
+    let mut map = BTreeMap::new();
+    for entry in entries {
+        let Some((from, to)) = entry.split_once('=') else {
+            bail!("Invalid --model-map entry (expected FROM=TO): {entry}");
+        };
+        let (from, to) = (from.trim(), to.trim());
+        if from.is_empty() || to.is_empty() {
+            bail!("Invalid --model-map entry (expected FROM=TO): {entry}");
+        }
+        map.insert(from.to_string(), to.to_string());
+    }
+    Ok(map)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::override_runtime_config_dir;
+    use crate::test_utils::TestProcess;
+    use tempfile::TempDir;
+
+    fn convert_args(check: bool) -> ConvertArgs {
+        ConvertArgs {
+            from: AgentFormat::ClaudeCode,
+            to: AgentFormat::Codex,
+            input_dir: None,
+            output_dir: None,
+            only: vec![],
+            model_map: vec!["opus=gpt-5.4".to_string()],
+            check,
+            dry_run: false,
+            yes: true,
+            force: false,
+        }
+    }
+
+    #[test]
+    fn convert_writes_managed_codex_agents_and_check_detects_drift() {
+        let _proc = TestProcess::new();
+        let temp = TempDir::new().expect("temp dir");
+        let _guard = override_runtime_config_dir(temp.path().join("llman-config"));
+
+        let claude_dir = managed_agents_dir(AgentFormat::ClaudeCode).unwrap();
+        let codex_dir = managed_agents_dir(AgentFormat::Codex).unwrap();
+        fs::create_dir_all(&claude_dir).unwrap();
+        fs::write(
+            claude_dir.join("reviewer.md"),
+            "---\nname: reviewer\ndescription: Reviews diffs\nmodel: opus\n---\n\nReview.\n",
+        )
+        .unwrap();
+
+        let err = run_convert(&convert_args(true)).expect_err("missing output is drift");
+        assert!(err.to_string().contains("out of date"));
+
+        run_convert(&convert_args(false)).expect("convert");
+        let doc: toml::Table =
+            toml::from_str(&fs::read_to_string(codex_dir.join("reviewer.toml")).unwrap()).unwrap();
+        assert_eq!(doc["model"].as_str(), Some("gpt-5.4"));
+        assert_eq!(doc["developer_instructions"].as_str(), Some("Review.\n"));
+
+        run_convert(&convert_args(true)).expect("up to date after convert");
+    }
+
+    #[test]
+    fn parse_model_map_rejects_malformed_entries() {
+        assert!(parse_model_map(&["opus".to_string()]).is_err());
+        assert_eq!(
+            parse_model_map(&["a = b".to_string()]).unwrap()["a"],
+            "b".to_string()
+        );
+    }
+}
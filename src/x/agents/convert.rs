@@ -0,0 +1,441 @@
+//! Conversion between Codex agent TOMLs and Claude Code subagent markdown.
+//!
+//! Both sides are parsed into an [`AgentDefinition`]; fields without a counterpart in the
+//! other tool are reported as notes instead of being silently dropped.
+
+use crate::sdd::spec::frontmatter::{compose_with_frontmatter, split_frontmatter};
+use anyhow::{Context, Result, anyhow};
+use clap::ValueEnum;
+use serde_yaml::{Mapping, Value as YamlValue};
+use std::collections::BTreeMap;
+
+/// Tools that let a Claude Code agent change files or run commands.
+const WRITE_TOOLS: [&str; 5] = ["Write", "Edit", "MultiEdit", "NotebookEdit", "Bash"];
+
+/// Claude Code tools used for agents converted from a read-only Codex sandbox.
+const READ_ONLY_TOOLS: [&str; 3] = ["Read", "Grep", "Glob"];
+
+const CLAUDE_MODEL_ALIASES: [&str; 4] = ["sonnet", "opus", "haiku", "inherit"];
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AgentFormat {
+    /// Codex agent TOML (`developer_instructions`)
+    Codex,
+    /// Claude Code subagent markdown with YAML frontmatter
+    #[value(name = "claude-code")]
+    ClaudeCode,
+}
+
+impl AgentFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            AgentFormat::Codex => "toml",
+            AgentFormat::ClaudeCode => "md",
+        }
+    }
+
+    pub fn app(self) -> &'static str {
+        match self {
+            AgentFormat::Codex => crate::config::CODEX_APP,
+            AgentFormat::ClaudeCode => crate::config::CLAUDE_CODE_APP,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            AgentFormat::Codex => "Codex",
+            AgentFormat::ClaudeCode => "Claude Code",
+        }
+    }
+}
+
+/// Tool-neutral view of one agent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AgentDefinition {
+    pub name: String,
+    pub description: String,
+    pub instructions: String,
+    pub model: Option<String>,
+    /// Claude Code tool allowlist (`None` = all tools)
+    pub tools: Option<Vec<String>>,
+    /// Agent cannot modify files (Codex `sandbox_mode = "read-only"`)
+    pub read_only: bool,
+}
+
+/// A converted agent file plus what could not be carried over.
+#[derive(Debug, Clone)]
+pub struct Converted {
+    pub name: String,
+    pub content: String,
+    pub notes: Vec<String>,
+}
+
+/// Convert one agent file; `Ok(None)` means the source is not an agent definition
+/// (e.g. a Codex config overlay without instructions).
+pub fn convert(
+    content: &str,
+    stem: &str,
+    from: AgentFormat,
+    to: AgentFormat,
+    model_map: &BTreeMap<String, String>,
+) -> Result<Option<Converted>> {
+    let parsed = match from {
+        AgentFormat::Codex => parse_codex(content, stem)?,
+        AgentFormat::ClaudeCode => parse_claude(content, stem)?,
+    };
+    let Some((def, mut notes)) = parsed else {
+        return Ok(None);
+    };
+
+    let content = match to {
+        AgentFormat::Codex => render_codex(&def, model_map, &mut notes),
+        AgentFormat::ClaudeCode => render_claude(&def, model_map, &mut notes)?,
+    };
+    Ok(Some(Converted {
+        name: def.name,
+        content,
+        notes,
+    }))
+}
+
+fn parse_codex(content: &str, stem: &str) -> Result<Option<(AgentDefinition, Vec<String>)>> {
+    let doc: toml::Table = toml::from_str(content).context("parse Codex agent TOML")?;
+    let Some(instructions) = doc.get("developer_instructions").and_then(|v| v.as_str()) else {
+        return Ok(None);
+    };
+
+    let mut notes = Vec::new();
+    let text = |key: &str| doc.get(key).and_then(|v| v.as_str()).map(str::to_string);
+    let sandbox_mode = text("sandbox_mode");
+    let read_only = sandbox_mode.as_deref() == Some("read-only");
+    if let Some(mode) = sandbox_mode.as_deref().filter(|_| !read_only) {
+        notes.push(format!(
+            "dropped `sandbox_mode = \"{mode}\"` (only read-only has a Claude Code equivalent)"
+        ));
+    }
+    for key in doc.keys() {
+        if ![
+            "name",
+            "description",
+            "developer_instructions",
+            "model",
+            "sandbox_mode",
+        ]
+        .contains(&key.as_str())
+        {
+            notes.push(format!("dropped `{key}` (no Claude Code equivalent)"));
+        }
+    }
+
+    let def = AgentDefinition {
+        name: text("name").unwrap_or_else(|| codex_agent_name(stem)),
+        description: text("description").unwrap_or_default(),
+        instructions: instructions.trim().to_string(),
+        model: text("model"),
+        tools: None,
+        read_only,
+    };
+    Ok(Some((def, notes)))
+}
+
+fn parse_claude(content: &str, stem: &str) -> Result<Option<(AgentDefinition, Vec<String>)>> {
+    let (Some(yaml), body) = split_frontmatter(content) else {
+        return Ok(None);
+    };
+    let frontmatter: Mapping = serde_yaml::from_str(&yaml).context("parse agent frontmatter")?;
+    let text = |key: &str| {
+        frontmatter
+            .get(key)
+            .and_then(YamlValue::as_str)
+            .map(str::to_string)
+    };
+
+    let tools = match frontmatter.get("tools") {
+        Some(YamlValue::String(list)) => Some(
+            list.split(',')
+                .map(str::trim)
+                .filter(|tool| !tool.is_empty())
+                .map(str::to_string)
+                .collect::<Vec<_>>(),
+        ),
+        Some(YamlValue::Sequence(items)) => Some(
+            items
+                .iter()
+                .filter_map(YamlValue::as_str)
+                .map(str::to_string)
+                .collect(),
+        ),
+        _ => None,
+    };
+    let read_only = tools.as_ref().is_some_and(|tools| {
+        !tools
+            .iter()
+            .any(|tool| WRITE_TOOLS.contains(&tool.as_str()) || tool.starts_with("mcp__"))
+    });
+
+    let mut notes = Vec::new();
+    for key in frontmatter.keys().filter_map(YamlValue::as_str) {
+        if !["name", "description", "tools", "model"].contains(&key) {
+            notes.push(format!("dropped `{key}` (no Codex equivalent)"));
+        }
+    }
+
+    let def = AgentDefinition {
+        name: text("name").unwrap_or_else(|| stem.to_string()),
+        description: text("description").unwrap_or_default(),
+        instructions: body.trim().to_string(),
+        model: text("model"),
+        tools,
+        read_only,
+    };
+    Ok(Some((def, notes)))
+}
+
+fn render_codex(
+    def: &AgentDefinition,
+    model_map: &BTreeMap<String, String>,
+    notes: &mut Vec<String>,
+) -> String {
+    let mut out = String::new();
+    out.push_str(&format!("name = {}\n", toml_string(&def.name)));
+    out.push_str(&format!(
+        "description = {}\n",
+        toml_string(&def.description)
+    ));
+    if let Some(model) = map_model(def.model.as_deref(), AgentFormat::Codex, model_map, notes) {
+        out.push_str(&format!("model = {}\n", toml_string(&model)));
+    }
+
+    match &def.tools {
+        Some(tools) if def.read_only => {
+            notes.push(format!(
+                "approximated `tools: {}` as sandbox_mode = \"read-only\"",
+                tools.join(", ")
+            ));
+        }
+        Some(tools) => notes.push(format!(
+            "dropped `tools: {}` (Codex has no per-agent tool allowlist)",
+            tools.join(", ")
+        )),
+        None => {}
+    }
+    if def.read_only {
+        out.push_str("sandbox_mode = \"read-only\"\n");
+    }
+
+    // Triple-quoted so `llman x codex agents inject` can manage a block inside it.
+    let escaped = def
+        .instructions
+        .replace('\\', "\\\\")
+        .replace("\"\"\"", "\"\"\\\"");
+    out.push_str(&format!(
+        "\ndeveloper_instructions = \"\"\"\n{escaped}\n\"\"\"\n"
+    ));
+    out
+}
+
+fn render_claude(
+    def: &AgentDefinition,
+    model_map: &BTreeMap<String, String>,
+    notes: &mut Vec<String>,
+) -> Result<String> {
+    let mut frontmatter = Mapping::new();
+    frontmatter.insert("name".into(), claude_agent_name(&def.name, notes).into());
+    frontmatter.insert("description".into(), def.description.clone().into());
+
+    let tools = match (&def.tools, def.read_only) {
+        (Some(tools), _) => Some(tools.clone()),
+        (None, true) => {
+            notes.push(format!(
+                "approximated sandbox_mode = \"read-only\" as `tools: {}`",
+                READ_ONLY_TOOLS.join(", ")
+            ));
+            Some(READ_ONLY_TOOLS.iter().map(|t| t.to_string()).collect())
+        }
+        (None, false) => None,
+    };
+    if let Some(tools) = tools {
+        frontmatter.insert("tools".into(), tools.join(", ").into());
+    }
+    if let Some(model) = map_model(
+        def.model.as_deref(),
+        AgentFormat::ClaudeCode,
+        model_map,
+        notes,
+    ) {
+        frontmatter.insert("model".into(), model.into());
+    }
+
+    let yaml = serde_yaml::to_string(&frontmatter)
+        .map_err(|e| anyhow!("serialize agent frontmatter: {e}"))?;
+    let mut out = compose_with_frontmatter(Some(&yaml), &def.instructions);
+    if !out.ends_with('\n') {
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+/// Apply `--model-map`, then keep the model only if the target tool can use it.
+fn map_model(
+    model: Option<&str>,
+    to: AgentFormat,
+    model_map: &BTreeMap<String, String>,
+    notes: &mut Vec<String>,
+) -> Option<String> {
+    let model = model?;
+    let mapped = model_map.get(model).map(String::as_str).unwrap_or(model);
+    let is_claude = CLAUDE_MODEL_ALIASES.contains(&mapped) || mapped.starts_with("claude-");
+    let usable = match to {
+        AgentFormat::ClaudeCode => is_claude,
+        AgentFormat::Codex => !is_claude,
+    };
+    if usable {
+        return Some(mapped.to_string());
+    }
+    if mapped != "inherit" {
+        notes.push(format!(
+            "dropped `model = {model}` (not a {} model; map it with --model-map {model}=<model>)",
+            to.label()
+        ));
+    }
+    None
+}
+
+/// Claude Code agent names are lowercase letters, digits and hyphens.
+fn claude_agent_name(name: &str, notes: &mut Vec<String>) -> String {
+    let mut out = String::new();
+    for c in name.trim().chars() {
+        if c.is_ascii_alphanumeric() {
+            out.push(c.to_ascii_lowercase());
+        } else if !out.ends_with('-') {
+            out.push('-');
+        }
+    }
+    let out = out.trim_matches('-').to_string();
+    if out != name {
+        notes.push(format!("renamed `{name}` to `{out}`"));
+    }
+    out
+}
+
+/// Codex stores the built-in `default` agent as `defaults.toml`.
+fn codex_agent_name(stem: &str) -> String {
+    if stem == "defaults" {
+        "default".to_string()
+    } else {
+        stem.to_string()
+    }
+}
+
+/// Output file stem for a converted agent.
+pub fn output_stem(name: &str, to: AgentFormat) -> String {
+    match to {
+        AgentFormat::Codex if name == "default" => "defaults".to_string(),
+        AgentFormat::Codex => name.to_string(),
+        AgentFormat::ClaudeCode => claude_agent_name(name, &mut Vec::new()),
+    }
+}
+
+fn toml_string(value: &str) -> String {
+    toml::Value::String(value.to_string()).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CODEX_REVIEWER: &str = r#"name = "reviewer"
+description = "Reviews diffs for bugs"
+model = "gpt-5.4-mini"
+sandbox_mode = "read-only"
+model_reasoning_effort = "high"
+
+developer_instructions = """
+Review the diff.
+Report bugs only.
+"""
+"#;
+
+    #[test]
+    fn codex_to_claude_maps_fields_and_reports_the_rest() {
+        let map = BTreeMap::from([("gpt-5.4-mini".to_string(), "haiku".to_string())]);
+        let out = convert(
+            CODEX_REVIEWER,
+            "reviewer",
+            AgentFormat::Codex,
+            AgentFormat::ClaudeCode,
+            &map,
+        )
+        .unwrap()
+        .unwrap();
+
+        assert_eq!(
+            out.content,
+            "---\nname: reviewer\ndescription: Reviews diffs for bugs\ntools: Read, Grep, Glob\nmodel: haiku\n---\n\nReview the diff.\nReport bugs only.\n"
+        );
+        assert!(
+            out.notes
+                .iter()
+                .any(|n| n.contains("model_reasoning_effort"))
+        );
+        assert!(out.notes.iter().any(|n| n.contains("approximated")));
+    }
+
+    #[test]
+    fn claude_to_codex_roundtrips_instructions_and_drops_unknown_models() {
+        let claude = "---\nname: planner\ndescription: Plans work\ntools: Read, Bash\nmodel: opus\ncolor: blue\n---\n\nPlan with \"\"\"care\"\"\" and C:\\paths.\n";
+        let out = convert(
+            claude,
+            "planner",
+            AgentFormat::ClaudeCode,
+            AgentFormat::Codex,
+            &BTreeMap::new(),
+        )
+        .unwrap()
+        .unwrap();
+
+        let doc: toml::Table = toml::from_str(&out.content).unwrap();
+        assert_eq!(doc["name"].as_str(), Some("planner"));
+        assert_eq!(
+            doc["developer_instructions"].as_str(),
+            Some("Plan with \"\"\"care\"\"\" and C:\\paths.\n")
+        );
+        assert!(!doc.contains_key("model"));
+        assert!(!doc.contains_key("sandbox_mode"));
+        for field in ["color", "model = opus", "tools: Read, Bash"] {
+            assert!(
+                out.notes.iter().any(|n| n.contains(field)),
+                "{field}: {:?}",
+                out.notes
+            );
+        }
+
+        let back = convert(
+            &out.content,
+            "planner",
+            AgentFormat::Codex,
+            AgentFormat::ClaudeCode,
+            &BTreeMap::new(),
+        )
+        .unwrap()
+        .unwrap();
+        assert!(
+            back.content
+                .ends_with("Plan with \"\"\"care\"\"\" and C:\\paths.\n")
+        );
+    }
+
+    #[test]
+    fn codex_overlays_without_instructions_are_skipped() {
+        let out = convert(
+            "model = \"o3\"\n",
+            "defaults",
+            AgentFormat::Codex,
+            AgentFormat::ClaudeCode,
+            &BTreeMap::new(),
+        )
+        .unwrap();
+        assert!(out.is_none());
+        assert_eq!(output_stem("default", AgentFormat::Codex), "defaults");
+    }
+}
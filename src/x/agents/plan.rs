@@ -0,0 +1,366 @@
+//! Plan/apply model shared by the per-tool agent commands.
+//!
+//! Agent files live in an llman-managed directory and are imported from / synced to a
+//! tool's agents directory. Every write is planned first so `--dry-run` can print it.
+
+use crate::fs_utils::atomic_write_with_mode;
+use crate::path_utils::validate_path_segment;
+use anyhow::{Context, Result, bail};
+use clap::ValueEnum;
+use inquire::Confirm;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncMode {
+    #[value(name = "link")]
+    Link,
+    #[value(name = "copy")]
+    Copy,
+}
+
+#[derive(Debug, Clone)]
+pub enum PlanOp {
+    EnsureDir { path: PathBuf },
+    Backup { from: PathBuf, to: PathBuf },
+    Copy { from: PathBuf, to: PathBuf },
+    Link { from: PathBuf, to: PathBuf },
+    WriteFile { path: PathBuf, content: String },
+    Note { message: String },
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Plan {
+    pub ops: Vec<PlanOp>,
+}
+
+impl Plan {
+    pub fn has_writes(&self) -> bool {
+        self.ops.iter().any(|op| {
+            matches!(
+                op,
+                PlanOp::EnsureDir { .. }
+                    | PlanOp::Backup { .. }
+                    | PlanOp::Copy { .. }
+                    | PlanOp::Link { .. }
+                    | PlanOp::WriteFile { .. }
+            )
+        })
+    }
+
+    pub fn print(&self) {
+        for op in &self.ops {
+            match op {
+                PlanOp::EnsureDir { path } => println!("PLAN mkdir -p {}", path.display()),
+                PlanOp::Backup { from, to } => {
+                    println!("PLAN backup {} -> {}", from.display(), to.display())
+                }
+                PlanOp::Copy { from, to } => {
+                    println!("PLAN copy {} -> {}", from.display(), to.display())
+                }
+                PlanOp::Link { from, to } => {
+                    println!("PLAN link {} -> {}", to.display(), from.display())
+                }
+                PlanOp::WriteFile { path, .. } => println!("PLAN write {}", path.display()),
+                PlanOp::Note { message } => println!("PLAN note: {}", message),
+            }
+        }
+    }
+}
+
+/// Print `plan`, confirm when needed, then execute it (unless `dry_run`).
+pub fn apply_plan(plan: Plan, dry_run: bool, interactive: bool, confirm_all: bool) -> Result<()> {
+    if plan.ops.is_empty() {
+        println!("No changes.");
+        return Ok(());
+    }
+
+    plan.print();
+
+    if dry_run {
+        return Ok(());
+    }
+
+    if plan.has_writes() && !confirm_all {
+        if !interactive {
+            bail!(
+                "This operation would write files. Re-run with --dry-run to preview or --yes/--force to proceed."
+            );
+        }
+
+        let proceed = Confirm::new("Proceed with these changes?")
+            .with_default(false)
+            .prompt()
+            .context("confirm apply")?;
+        if !proceed {
+            println!("Cancelled.");
+            return Ok(());
+        }
+    }
+
+    execute_plan(&plan)
+}
+
+pub fn execute_plan(plan: &Plan) -> Result<()> {
+    for op in &plan.ops {
+        match op {
+            PlanOp::EnsureDir { path } => {
+                fs::create_dir_all(path)
+                    .with_context(|| format!("create dir: {}", path.display()))?;
+            }
+            PlanOp::Backup { from, to } => {
+                fs::rename(from, to)
+                    .with_context(|| format!("backup {} -> {}", from.display(), to.display()))?;
+            }
+            PlanOp::Copy { from, to } => {
+                let content =
+                    fs::read(from).with_context(|| format!("read source: {}", from.display()))?;
+                if let Some(parent) = to.parent() {
+                    fs::create_dir_all(parent)
+                        .with_context(|| format!("create dir: {}", parent.display()))?;
+                }
+                atomic_write_with_mode(to, &content, None)
+                    .with_context(|| format!("write: {}", to.display()))?;
+            }
+            PlanOp::Link { from, to } => {
+                #[cfg(unix)]
+                {
+                    use std::os::unix::fs as unix_fs;
+                    if let Some(parent) = to.parent() {
+                        fs::create_dir_all(parent)
+                            .with_context(|| format!("create dir: {}", parent.display()))?;
+                    }
+                    unix_fs::symlink(from, to).with_context(|| {
+                        format!("symlink {} -> {}", to.display(), from.display())
+                    })?;
+                }
+                #[cfg(not(unix))]
+                {
+                    let _ = (from, to);
+                    bail!("symlink mode is not supported on this platform; use --mode copy");
+                }
+            }
+            PlanOp::WriteFile { path, content } => {
+                if let Some(parent) = path.parent() {
+                    fs::create_dir_all(parent)
+                        .with_context(|| format!("create dir: {}", parent.display()))?;
+                }
+                atomic_write_with_mode(path, content.as_bytes(), None)
+                    .with_context(|| format!("write: {}", path.display()))?;
+            }
+            PlanOp::Note { .. } => {}
+        }
+    }
+    Ok(())
+}
+
+/// Plan copying `<target_dir>/<stem>.<ext>` into the managed directory.
+pub fn plan_import(
+    managed_dir: &Path,
+    target_dir: &Path,
+    stems: &[String],
+    ext: &str,
+) -> Result<Plan> {
+    let mut plan = Plan::default();
+    if !managed_dir.exists() {
+        plan.ops.push(PlanOp::EnsureDir {
+            path: managed_dir.to_path_buf(),
+        });
+    }
+
+    for stem in stems {
+        let from = target_dir.join(format!("{stem}.{ext}"));
+        let to = managed_dir.join(format!("{stem}.{ext}"));
+
+        if !from.exists() {
+            plan.ops.push(PlanOp::Note {
+                message: format!("skip missing source: {}", from.display()),
+            });
+            continue;
+        }
+
+        let source_bytes = fs::read(&from).with_context(|| format!("read: {}", from.display()))?;
+        let needs_overwrite = if fs::symlink_metadata(&to).is_ok_and(|m| m.file_type().is_symlink())
+        {
+            true
+        } else if to.exists() {
+            fs::read(&to)
+                .map(|existing| existing != source_bytes)
+                .unwrap_or(true)
+        } else {
+            true
+        };
+
+        if !needs_overwrite {
+            continue;
+        }
+
+        if fs::symlink_metadata(&to).is_ok() {
+            let backup = backup_path_for(&to)?;
+            plan.ops.push(PlanOp::Backup {
+                from: to.clone(),
+                to: backup,
+            });
+        }
+
+        plan.ops.push(PlanOp::Copy { from, to });
+    }
+
+    Ok(plan)
+}
+
+/// Plan linking or copying managed `<stem>.<ext>` files into the target directory.
+pub fn plan_sync(
+    managed_dir: &Path,
+    target_dir: &Path,
+    stems: &[String],
+    mode: SyncMode,
+    ext: &str,
+) -> Result<Plan> {
+    let mut plan = Plan::default();
+    if !target_dir.exists() {
+        plan.ops.push(PlanOp::EnsureDir {
+            path: target_dir.to_path_buf(),
+        });
+    }
+
+    for stem in stems {
+        let from = managed_dir.join(format!("{stem}.{ext}"));
+        let to = target_dir.join(format!("{stem}.{ext}"));
+
+        if !from.exists() {
+            plan.ops.push(PlanOp::Note {
+                message: format!("skip missing managed file: {}", from.display()),
+            });
+            continue;
+        }
+
+        if mode == SyncMode::Link {
+            if is_correct_symlink(&from, &to).unwrap_or(false) {
+                continue;
+            }
+        } else if mode == SyncMode::Copy && to.exists() {
+            let meta = fs::symlink_metadata(&to)?;
+            if !meta.file_type().is_symlink() {
+                let a = fs::read(&from).unwrap_or_default();
+                let b = fs::read(&to).unwrap_or_default();
+                if a == b {
+                    continue;
+                }
+            }
+        }
+
+        if fs::symlink_metadata(&to).is_ok() {
+            let backup = backup_path_for(&to)?;
+            plan.ops.push(PlanOp::Backup {
+                from: to.clone(),
+                to: backup,
+            });
+        }
+
+        match mode {
+            SyncMode::Link => plan.ops.push(PlanOp::Link { from, to }),
+            SyncMode::Copy => plan.ops.push(PlanOp::Copy { from, to }),
+        }
+    }
+
+    Ok(plan)
+}
+
+/// File stems of `*.<ext>` files directly under `dir`, sorted.
+pub fn list_stems(dir: &Path, ext: &str) -> Result<Vec<String>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let suffix = format!(".{ext}");
+    let mut stems = Vec::new();
+    for entry in fs::read_dir(dir).with_context(|| format!("read dir: {}", dir.display()))? {
+        let entry = entry?;
+        let file_name = entry.file_name();
+        let Some(name) = file_name.to_str() else {
+            continue;
+        };
+        let Some(stem) = name.strip_suffix(&suffix) else {
+            continue;
+        };
+        if stem.is_empty() {
+            continue;
+        }
+        stems.push(stem.to_string());
+    }
+
+    stems.sort();
+    stems.dedup();
+    Ok(stems)
+}
+
+pub fn select_stems(available: &[String], only: &[String], what: &str) -> Result<Vec<String>> {
+    if only.is_empty() {
+        return Ok(available.to_vec());
+    }
+
+    let mut wanted = Vec::new();
+    let available_set: HashSet<&str> = available.iter().map(|s| s.as_str()).collect();
+    for raw in only {
+        let stem = validate_path_segment(raw, what)?;
+        if !available_set.contains(stem.as_str()) {
+            bail!("{what} not found: {stem}");
+        }
+        wanted.push(stem);
+    }
+    wanted.sort();
+    wanted.dedup();
+    Ok(wanted)
+}
+
+pub fn backup_path_for(path: &Path) -> Result<PathBuf> {
+    let ts = chrono::Utc::now().format("%Y%m%d%H%M%S").to_string();
+    let file_name = path
+        .file_name()
+        .and_then(|s| s.to_str())
+        .unwrap_or("backup");
+    Ok(path.with_file_name(format!("{file_name}.llman.bak.{ts}")))
+}
+
+pub fn is_correct_symlink(from: &Path, link: &Path) -> Result<bool> {
+    let meta =
+        fs::symlink_metadata(link).with_context(|| format!("metadata: {}", link.display()))?;
+    if !meta.file_type().is_symlink() {
+        return Ok(false);
+    }
+
+    let target = fs::read_link(link).with_context(|| format!("readlink: {}", link.display()))?;
+    let resolved = if target.is_absolute() {
+        target
+    } else {
+        link.parent().unwrap_or_else(|| Path::new(".")).join(target)
+    };
+
+    Ok(resolved == from)
+}
+
+/// `missing`, `linked`, `wrong-link`, `copied` or `diff` for a synced target file.
+pub fn describe_target_state(managed: &Path, target: &Path) -> Result<String> {
+    if fs::symlink_metadata(target).is_err() {
+        return Ok("missing".to_string());
+    }
+
+    let meta =
+        fs::symlink_metadata(target).with_context(|| format!("metadata: {}", target.display()))?;
+    if meta.file_type().is_symlink() {
+        let ok = is_correct_symlink(managed, target).unwrap_or(false);
+        return Ok(if ok { "linked" } else { "wrong-link" }.to_string());
+    }
+
+    let managed_bytes =
+        fs::read(managed).with_context(|| format!("read: {}", managed.display()))?;
+    let target_bytes = fs::read(target).with_context(|| format!("read: {}", target.display()))?;
+    Ok(if managed_bytes == target_bytes {
+        "copied"
+    } else {
+        "diff"
+    }
+    .to_string())
+}
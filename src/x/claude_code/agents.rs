@@ -0,0 +1,525 @@
+use crate::config::{CLAUDE_CODE_APP, Config, resolve_config_dir};
+use crate::managed_block::{
+    LLMAN_PROMPTS_MARKER_END, LLMAN_PROMPTS_MARKER_START, has_llman_prompt_markers,
+    update_text_with_markers,
+};
+use crate::prompts::paths::claude_home_dir;
+use crate::prompts::render::{GLOBAL_SCOPE, PromptRenderer};
+use crate::prompts::store as prompt_store;
+use crate::sdd::spec::frontmatter::split_frontmatter;
+use crate::skills::cli::interactive::is_interactive;
+use crate::skills::shared::git::find_git_root;
+use crate::x::agents::plan::{
+    self, Plan, PlanOp, SyncMode, describe_target_state, list_stems, plan_import, plan_sync,
+    select_stems,
+};
+use anyhow::{Context, Result, bail};
+use clap::{Args, Subcommand, ValueEnum};
+use inquire::{MultiSelect, Select};
+use serde_yaml::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const AGENT_EXT: &str = "md";
+
+/// Model aliases Claude Code accepts besides full `claude-*` model ids.
+const MODEL_ALIASES: [&str; 4] = ["sonnet", "opus", "haiku", "inherit"];
+
+#[derive(Args, Debug, Clone)]
+#[command(about = "Manage Claude Code subagents (markdown files with YAML frontmatter)")]
+#[command(subcommand_required = false)]
+pub struct ClaudeCodeAgentsArgs {
+    #[command(subcommand)]
+    pub command: Option<ClaudeCodeAgentsCommand>,
+
+    /// Managed agents directory (default: $LLMAN_CONFIG_DIR/claude-code/agents)
+    #[arg(long = "managed-dir", global = true)]
+    pub managed_dir: Option<PathBuf>,
+
+    /// Target agents directory: user (~/.claude/agents) or project (.claude/agents)
+    #[arg(long, value_enum, default_value_t = AgentScope::User, global = true)]
+    pub scope: AgentScope,
+
+    /// Override the target agents directory directly
+    #[arg(long = "agents-dir", global = true)]
+    pub agents_dir: Option<PathBuf>,
+
+    /// Only apply to selected agent files (repeatable; matches `<name>`.md)
+    #[arg(long, value_delimiter = ',', action = clap::ArgAction::Append, global = true)]
+    pub only: Vec<String>,
+
+    /// Show plan but do not write any files
+    #[arg(long, global = true)]
+    pub dry_run: bool,
+
+    /// Assume "yes" for confirmations (required for non-interactive write operations)
+    #[arg(long, global = true)]
+    pub yes: bool,
+
+    /// Force write operations without interactive confirmation (alias of --yes)
+    #[arg(long, global = true)]
+    pub force: bool,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum ClaudeCodeAgentsCommand {
+    /// Show managed/target status and frontmatter checks (read-only)
+    Status,
+    /// Import target agents into managed directory
+    Import,
+    /// Sync managed agents to the Claude Code agents directory
+    Sync {
+        #[arg(long, value_enum, default_value_t = SyncMode::Link)]
+        mode: SyncMode,
+    },
+    /// Inject prompt templates into the body of managed agent files
+    Inject {
+        /// Prompt template name(s) under $LLMAN_CONFIG_DIR/prompt/claude-code/*.md (repeatable)
+        #[arg(long, value_delimiter = ',', action = clap::ArgAction::Append)]
+        template: Vec<String>,
+    },
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AgentScope {
+    /// ~/.claude/agents (or $CLAUDE_HOME/agents)
+    User,
+    /// .claude/agents in the current git repository
+    Project,
+}
+
+pub fn run(args: &ClaudeCodeAgentsArgs) -> Result<()> {
+    let interactive = is_interactive();
+    match &args.command {
+        None => {
+            if interactive {
+                return run_wizard(args);
+            }
+            bail!("In non-interactive mode, a subcommand is required (status/import/sync/inject).");
+        }
+        Some(ClaudeCodeAgentsCommand::Status) => run_status(args),
+        Some(ClaudeCodeAgentsCommand::Import) => run_import(args, interactive),
+        Some(ClaudeCodeAgentsCommand::Sync { mode }) => run_sync(args, *mode, interactive),
+        Some(ClaudeCodeAgentsCommand::Inject { template }) => {
+            run_inject(args, template, interactive)
+        }
+    }
+}
+
+fn run_status(args: &ClaudeCodeAgentsArgs) -> Result<()> {
+    let managed_dir = resolve_managed_dir(args)?;
+    let target_dir = resolve_target_agents_dir(args)?;
+
+    println!("Managed agents dir: {}", managed_dir.display());
+    println!("Target agents dir:  {}", target_dir.display());
+
+    let managed = list_stems(&managed_dir, AGENT_EXT)?;
+    if managed.is_empty() {
+        println!("No managed agent files found.");
+        return Ok(());
+    }
+
+    let selected = select_stems(&managed, &args.only, "agent name")?;
+    println!();
+
+    for stem in selected {
+        let managed_file = managed_dir.join(format!("{stem}.{AGENT_EXT}"));
+        let target_file = target_dir.join(format!("{stem}.{AGENT_EXT}"));
+        let content = fs::read_to_string(&managed_file)
+            .with_context(|| format!("read: {}", managed_file.display()))?;
+
+        let issues = validate_agent(&content);
+        let schema_state = if issues.is_empty() {
+            "ok".to_string()
+        } else {
+            format!("invalid({})", issues.join(","))
+        };
+        let target_state = describe_target_state(&managed_file, &target_file)?;
+        let inject_state = if has_llman_prompt_markers(&content) {
+            "managed"
+        } else {
+            "injectable"
+        };
+
+        println!("- {stem}: schema={schema_state}; target={target_state}; inject={inject_state}");
+    }
+
+    Ok(())
+}
+
+fn run_import(args: &ClaudeCodeAgentsArgs, interactive: bool) -> Result<()> {
+    let managed_dir = resolve_managed_dir(args)?;
+    let target_dir = resolve_target_agents_dir(args)?;
+    let available = list_stems(&target_dir, AGENT_EXT)?;
+    let selected = select_stems(&available, &args.only, "agent name")?;
+
+    let plan = plan_import(&managed_dir, &target_dir, &selected, AGENT_EXT)?;
+    apply_plan(plan, args, interactive)
+}
+
+fn run_sync(args: &ClaudeCodeAgentsArgs, mode: SyncMode, interactive: bool) -> Result<()> {
+    let managed_dir = resolve_managed_dir(args)?;
+    let target_dir = resolve_target_agents_dir(args)?;
+    let available = list_stems(&managed_dir, AGENT_EXT)?;
+    let selected = select_stems(&available, &args.only, "agent name")?;
+
+    let plan = plan_validated_sync(&managed_dir, &target_dir, &selected, mode)?;
+    apply_plan(plan, args, interactive)
+}
+
+fn run_inject(args: &ClaudeCodeAgentsArgs, templates: &[String], interactive: bool) -> Result<()> {
+    if templates.is_empty() {
+        bail!("--template is required");
+    }
+
+    let managed_dir = resolve_managed_dir(args)?;
+    let available = list_stems(&managed_dir, AGENT_EXT)?;
+    let selected = select_stems(&available, &args.only, "agent name")?;
+
+    let body = build_injection_body(templates)?;
+    let plan = plan_inject(&managed_dir, &selected, &body)?;
+    apply_plan(plan, args, interactive)
+}
+
+fn run_wizard(args: &ClaudeCodeAgentsArgs) -> Result<()> {
+    let managed_dir = resolve_managed_dir(args)?;
+    let target_dir = resolve_target_agents_dir(args)?;
+
+    println!("Managed agents dir: {}", managed_dir.display());
+    println!("Target agents dir:  {}", target_dir.display());
+    println!();
+
+    let action = Select::new(
+        "Select an action:",
+        vec!["status", "import", "inject", "sync"],
+    )
+    .prompt()
+    .context("select action")?;
+
+    if action == "status" {
+        return run_status(args);
+    }
+    let source_dir = if action == "import" {
+        &target_dir
+    } else {
+        &managed_dir
+    };
+
+    let available = list_stems(source_dir, AGENT_EXT)?;
+    if available.is_empty() {
+        bail!("No agent files found under: {}", source_dir.display());
+    }
+    let selected = MultiSelect::new(&format!("Select agents to {action}:"), available)
+        .with_all_selected_by_default()
+        .prompt()
+        .context("select agents")?;
+
+    let plan = match action {
+        "import" => plan_import(&managed_dir, &target_dir, &selected, AGENT_EXT)?,
+        "inject" => {
+            let templates = prompt_store::list_templates(&Config::new()?, CLAUDE_CODE_APP)?;
+            if templates.is_empty() {
+                bail!(
+                    "No claude-code prompt templates found under $LLMAN_CONFIG_DIR/prompt/claude-code"
+                );
+            }
+            let picked = MultiSelect::new("Select prompt templates to inject:", templates)
+                .prompt()
+                .context("select templates")?;
+            if picked.is_empty() {
+                bail!("At least one template is required for inject");
+            }
+            plan_inject(&managed_dir, &selected, &build_injection_body(&picked)?)?
+        }
+        "sync" => {
+            let mode = match Select::new("Select sync mode:", vec!["link", "copy"])
+                .prompt()
+                .context("select sync mode")?
+            {
+                "link" => SyncMode::Link,
+                _ => SyncMode::Copy,
+            };
+            plan_validated_sync(&managed_dir, &target_dir, &selected, mode)?
+        }
+        _ => unreachable!("validated selection"),
+    };
+    plan::apply_plan(plan, args.dry_run, true, args.yes || args.force)
+}
+
+fn apply_plan(plan: Plan, args: &ClaudeCodeAgentsArgs, interactive: bool) -> Result<()> {
+    plan::apply_plan(plan, args.dry_run, interactive, args.yes || args.force)
+}
+
+/// Sync plan that leaves out agents Claude Code would reject.
+fn plan_validated_sync(
+    managed_dir: &Path,
+    target_dir: &Path,
+    stems: &[String],
+    mode: SyncMode,
+) -> Result<Plan> {
+    let mut notes = Vec::new();
+    let mut valid = Vec::new();
+    for stem in stems {
+        let path = managed_dir.join(format!("{stem}.{AGENT_EXT}"));
+        let content =
+            fs::read_to_string(&path).with_context(|| format!("read: {}", path.display()))?;
+        let issues = validate_agent(&content);
+        if issues.is_empty() {
+            valid.push(stem.clone());
+        } else {
+            notes.push(PlanOp::Note {
+                message: format!("skip invalid agent {stem} ({})", issues.join(",")),
+            });
+        }
+    }
+
+    let mut plan = plan_sync(managed_dir, target_dir, &valid, mode, AGENT_EXT)?;
+    plan.ops.extend(notes);
+    Ok(plan)
+}
+
+fn plan_inject(managed_dir: &Path, stems: &[String], body: &str) -> Result<Plan> {
+    let mut plan = Plan::default();
+
+    for stem in stems {
+        let path = managed_dir.join(format!("{stem}.{AGENT_EXT}"));
+        if !path.exists() {
+            plan.ops.push(PlanOp::Note {
+                message: format!("skip missing managed file: {}", path.display()),
+            });
+            continue;
+        }
+
+        let content =
+            fs::read_to_string(&path).with_context(|| format!("read: {}", path.display()))?;
+        let Some(updated) = inject_into_agent_body(&content, body) else {
+            plan.ops.push(PlanOp::Note {
+                message: format!("skip (no frontmatter): {}", path.display()),
+            });
+            continue;
+        };
+
+        if updated == content {
+            continue;
+        }
+
+        plan.ops.push(PlanOp::WriteFile {
+            path,
+            content: updated,
+        });
+    }
+
+    Ok(plan)
+}
+
+/// Insert or refresh the LLMAN-PROMPTS block in the system prompt (markdown body).
+///
+/// Returns `None` for files without frontmatter, which Claude Code does not load as agents.
+fn inject_into_agent_body(content: &str, body: &str) -> Option<String> {
+    let (frontmatter, _) = split_frontmatter(content);
+    frontmatter?;
+    Some(update_text_with_markers(
+        content,
+        body,
+        true,
+        LLMAN_PROMPTS_MARKER_START,
+        LLMAN_PROMPTS_MARKER_END,
+    ))
+}
+
+/// Check the frontmatter fields Claude Code reads; returns issue codes (empty when valid).
+pub fn validate_agent(content: &str) -> Vec<String> {
+    let (Some(yaml), _) = split_frontmatter(content) else {
+        return vec!["no-frontmatter".to_string()];
+    };
+    let doc: Value = match serde_yaml::from_str(&yaml) {
+        Ok(Value::Mapping(map)) => Value::Mapping(map),
+        _ => return vec!["invalid-yaml".to_string()],
+    };
+
+    let mut issues = Vec::new();
+    match doc.get("name").and_then(Value::as_str).map(str::trim) {
+        None | Some("") => issues.push("missing-name"),
+        Some(name) if !is_valid_agent_name(name) => issues.push("bad-name"),
+        Some(_) => {}
+    }
+    if doc
+        .get("description")
+        .and_then(Value::as_str)
+        .is_none_or(|s| s.trim().is_empty())
+    {
+        issues.push("missing-description");
+    }
+    let tools_ok = match doc.get("tools") {
+        None | Some(Value::Null) => true,
+        Some(Value::String(s)) => s.split(',').all(|tool| !tool.trim().is_empty()),
+        Some(Value::Sequence(items)) => items
+            .iter()
+            .all(|item| item.as_str().is_some_and(|s| !s.trim().is_empty())),
+        Some(_) => false,
+    };
+    if !tools_ok {
+        issues.push("bad-tools");
+    }
+    let model_ok = match doc.get("model") {
+        None | Some(Value::Null) => true,
+        Some(Value::String(model)) => {
+            MODEL_ALIASES.contains(&model.as_str()) || model.starts_with("claude-")
+        }
+        Some(_) => false,
+    };
+    if !model_ok {
+        issues.push("bad-model");
+    }
+
+    issues.into_iter().map(str::to_string).collect()
+}
+
+/// Agent names are lowercase letters, digits and hyphens.
+fn is_valid_agent_name(name: &str) -> bool {
+    name.chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        && !name.starts_with('-')
+}
+
+fn build_injection_body(templates: &[String]) -> Result<String> {
+    let config = Config::new()?;
+    let renderer = PromptRenderer::new(&config, None)?;
+    prompt_store::build_llman_prompts_body(
+        &config,
+        CLAUDE_CODE_APP,
+        templates,
+        &renderer,
+        GLOBAL_SCOPE,
+    )
+}
+
+fn resolve_managed_dir(args: &ClaudeCodeAgentsArgs) -> Result<PathBuf> {
+    if let Some(path) = args.managed_dir.as_ref() {
+        return Ok(path.clone());
+    }
+    Ok(resolve_config_dir(None)?
+        .join(CLAUDE_CODE_APP)
+        .join("agents"))
+}
+
+fn resolve_target_agents_dir(args: &ClaudeCodeAgentsArgs) -> Result<PathBuf> {
+    if let Some(path) = args.agents_dir.as_ref() {
+        return Ok(path.clone());
+    }
+    match args.scope {
+        AgentScope::User => Ok(claude_home_dir()?.join("agents")),
+        AgentScope::Project => {
+            let cwd = std::env::current_dir().context("get current directory")?;
+            let root = find_git_root(&cwd).unwrap_or(cwd);
+            Ok(root.join(".claude").join("agents"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::override_runtime_config_dir;
+    use crate::test_utils::TestProcess;
+    use tempfile::TempDir;
+
+    const REVIEWER: &str = "---\nname: reviewer\ndescription: Reviews diffs\ntools: Read, Grep\nmodel: sonnet\n---\n\nYou review code.\n";
+
+    fn args(command: ClaudeCodeAgentsCommand, agents_dir: &Path) -> ClaudeCodeAgentsArgs {
+        ClaudeCodeAgentsArgs {
+            command: Some(command),
+            managed_dir: None,
+            scope: AgentScope::User,
+            agents_dir: Some(agents_dir.to_path_buf()),
+            only: vec![],
+            dry_run: false,
+            yes: true,
+            force: false,
+        }
+    }
+
+    #[test]
+    fn validate_agent_reports_frontmatter_issues() {
+        assert!(validate_agent(REVIEWER).is_empty());
+        assert_eq!(validate_agent("You review code.\n"), vec!["no-frontmatter"]);
+        assert_eq!(
+            validate_agent("---\nname: Code Reviewer\ntools: 3\nmodel: gpt-5\n---\nbody\n"),
+            vec!["bad-name", "missing-description", "bad-tools", "bad-model"]
+        );
+        assert!(
+            validate_agent(
+                "---\nname: a\ndescription: b\ntools: [Read, Bash]\nmodel: claude-opus-4-1\n---\n"
+            )
+            .is_empty()
+        );
+    }
+
+    #[test]
+    fn inject_appends_block_to_body_and_updates_in_place() {
+        let out = inject_into_agent_body(REVIEWER, "first").expect("has frontmatter");
+        assert!(out.starts_with("---\nname: reviewer\n"));
+        assert!(out.contains(&format!("{LLMAN_PROMPTS_MARKER_START}\nfirst\n")));
+
+        let again = inject_into_agent_body(&out, "second").expect("has frontmatter");
+        assert!(again.contains("second"));
+        assert!(!again.contains("first"));
+        assert_eq!(again.matches(LLMAN_PROMPTS_MARKER_START).count(), 1);
+
+        assert!(inject_into_agent_body("plain markdown\n", "x").is_none());
+    }
+
+    #[test]
+    fn sync_copies_valid_agents_and_skips_invalid_ones() {
+        let _proc = TestProcess::new();
+        let temp = TempDir::new().expect("temp dir");
+        let _guard = override_runtime_config_dir(temp.path().join("llman-config"));
+
+        let managed_dir = resolve_config_dir(None)
+            .unwrap()
+            .join(CLAUDE_CODE_APP)
+            .join("agents");
+        let target_dir = temp.path().join("claude").join("agents");
+        fs::create_dir_all(&managed_dir).unwrap();
+        fs::write(managed_dir.join("reviewer.md"), REVIEWER).unwrap();
+        fs::write(managed_dir.join("broken.md"), "no frontmatter\n").unwrap();
+
+        let sync = args(
+            ClaudeCodeAgentsCommand::Sync {
+                mode: SyncMode::Copy,
+            },
+            &target_dir,
+        );
+        run(&sync).expect("run");
+
+        assert_eq!(
+            fs::read_to_string(target_dir.join("reviewer.md")).unwrap(),
+            REVIEWER
+        );
+        assert!(!target_dir.join("broken.md").exists());
+    }
+
+    #[test]
+    fn non_interactive_write_requires_yes_or_force() {
+        let _proc = TestProcess::new();
+        let temp = TempDir::new().expect("temp dir");
+        let _guard = override_runtime_config_dir(temp.path().join("llman-config"));
+
+        let target_dir = temp.path().join("agents");
+        fs::create_dir_all(&target_dir).unwrap();
+        fs::write(target_dir.join("reviewer.md"), REVIEWER).unwrap();
+
+        let mut import = args(ClaudeCodeAgentsCommand::Import, &target_dir);
+        import.yes = false;
+        let err = run(&import).expect_err("should require --yes/--force");
+        assert!(err.to_string().contains("--yes"));
+
+        import.dry_run = true;
+        run(&import).expect("dry run");
+        let managed_dir = resolve_config_dir(None)
+            .unwrap()
+            .join(CLAUDE_CODE_APP)
+            .join("agents");
+        assert!(!managed_dir.join("reviewer.md").exists());
+    }
+}
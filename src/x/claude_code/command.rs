@@ -2,20 +2,29 @@ use crate::arg_utils::split_shell_args;
 use crate::editor::{parse_editor_command, select_editor_raw};
 use crate::fs_utils::atomic_write_new_with_mode;
 use crate::path_utils::safe_parent_for_creation;
+use crate::secrets::SecretResolver;
 use crate::tool::command::{SyncIgnoreArgs as ToolSyncIgnoreArgs, SyncIgnoreTarget};
-use crate::x::claude_code::config::{ClaudeCodeConfig, ConfigGroup};
+use crate::x::account_check;
+use crate::x::claude_code::agents::ClaudeCodeAgentsArgs;
+use crate::x::claude_code::config::{ClaudeCodeConfig, ConfigGroup, EffectiveGroup, resolve_group};
 use crate::x::claude_code::env_injection::{
-    EnvSyntax, env_syntax_for_current_platform, prepare_env_injection, render_env_injection_lines,
+    EnvSyntax, env_syntax_for_current_platform, prepare_env_injection,
+    render_annotated_env_injection_lines,
 };
+use crate::x::claude_code::hooks::{ClaudeCodeHooksArgs, HookLibrary};
 use crate::x::claude_code::interactive;
+use crate::x::claude_code::permissions::ClaudeCodePermissionsArgs;
 use crate::x::claude_code::prompts::ClaudeCodePromptsArgs;
-use crate::x::claude_code::security::{SecurityChecker, SecurityWarning};
+use crate::x::claude_code::security::{SecurityChecker, print_security_warnings, to_sarif};
+use crate::x::project_accounts::{AccountApp, ProjectAccountPolicy};
 use anyhow::{Context, Result, bail};
 use clap::{Args, Subcommand};
 use rust_i18n::t;
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::time::Duration;
 
 #[derive(Args)]
 #[command(
@@ -69,6 +78,27 @@ pub enum ClaudeCodeCommands {
         args: Vec<String>,
     },
     Prompts(ClaudeCodePromptsArgs),
+    Agents(ClaudeCodeAgentsArgs),
+    Hooks(ClaudeCodeHooksArgs),
+    Permissions(ClaudeCodePermissionsArgs),
+    /// Audit Claude Code settings, hooks and MCP servers for dangerous configuration
+    ///
+    /// Checks user, project and local settings, `.mcp.json`, `~/.claude.json` (including each
+    /// project's `mcpServers`) and the managed policy file. `run` only blocks on dangerous
+    /// permissions in the settings files.
+    /// Hooks running commands outside the llman hook library are flagged too.
+    /// Exits non-zero when any issue is found, so it can gate CI.
+    Audit {
+        /// Output JSON instead of a report
+        #[arg(long, conflicts_with = "sarif")]
+        json: bool,
+        /// Output a SARIF 2.1.0 log for code-scanning tools
+        #[arg(long)]
+        sarif: bool,
+        /// Do not flag hooks whose command is missing from the hook library
+        #[arg(long)]
+        allow_unvetted_hooks: bool,
+    },
     /// Sync ignore rules to Claude Code settings (forward to `llman tool sync-ignore`)
     #[command(name = "sync-ignore", alias = "si")]
     SyncIgnore(ClaudeCodeSyncIgnoreArgs),
@@ -117,7 +147,11 @@ pub enum AccountAction {
     /// showing their names and the environment variables they contain.
     /// Sensitive values like API keys are partially masked for security.
     #[command(about = "List all configuration groups")]
-    List,
+    List {
+        /// Show each group's effective environment, including inherited variables
+        #[arg(short, long)]
+        verbose: bool,
+    },
     /// Import a new configuration group from JSON format
     ///
     /// Interactive mode allows you to paste JSON configuration in two formats:
@@ -162,6 +196,23 @@ pub enum AccountAction {
         #[arg(help = "Name of the configuration group")]
         name: String,
     },
+    /// Send a minimal authenticated request for one or all groups
+    ///
+    /// Reports authentication errors, model availability and latency.
+    #[command(about = "Check API connectivity of configuration groups")]
+    Check {
+        #[arg(help = "Group to check (default: all groups)")]
+        name: Option<String>,
+        /// Model to request (default: the group's check_model, then its ANTHROPIC_MODEL)
+        #[arg(long)]
+        model: Option<String>,
+        /// Per-request timeout in seconds
+        #[arg(long, default_value_t = 20)]
+        timeout: u64,
+        /// Output JSON instead of a table
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 pub fn run(args: &ClaudeCodeArgs) -> Result<()> {
@@ -177,6 +228,16 @@ pub fn run(args: &ClaudeCodeArgs) -> Result<()> {
             handle_run_command(*interactive, group.as_deref(), args.clone())?;
         }
         Some(ClaudeCodeCommands::Prompts(prompts)) => crate::x::claude_code::prompts::run(prompts)?,
+        Some(ClaudeCodeCommands::Agents(agents)) => crate::x::claude_code::agents::run(agents)?,
+        Some(ClaudeCodeCommands::Hooks(hooks)) => crate::x::claude_code::hooks::run(hooks)?,
+        Some(ClaudeCodeCommands::Permissions(permissions)) => {
+            crate::x::claude_code::permissions::run(permissions)?
+        }
+        Some(ClaudeCodeCommands::Audit {
+            json,
+            sarif,
+            allow_unvetted_hooks,
+        }) => handle_audit_command(*json, *sarif, *allow_unvetted_hooks)?,
         Some(ClaudeCodeCommands::SyncIgnore(sync_args)) => {
             crate::tool::sync_ignore::run(&ToolSyncIgnoreArgs {
                 yes: sync_args.yes,
@@ -202,20 +263,24 @@ fn handle_main_command(args: &[String]) -> Result<()> {
         bail!(no_configs_message());
     }
 
-    if let Some(selected_group) = interactive::select_config_group(&config)? {
-        let group = config.get_group(&selected_group).ok_or_else(|| {
-            anyhow::anyhow!(format!(
-                "{}\n{}",
-                t!("claude_code.account.group_not_found", name = selected_group),
-                t!("claude_code.account.use_list_command")
-            ))
-        })?;
+    let policy = ProjectAccountPolicy::current(AccountApp::ClaudeCode)?;
+    let selected_group = match policy.default_group() {
+        Some(group) => {
+            policy.announce_default();
+            Some(group.to_string())
+        }
+        None => interactive::select_config_group(&config)?,
+    };
+
+    if let Some(selected_group) = selected_group {
+        policy.ensure_allowed(&selected_group)?;
+        let group = effective_group_or_bail(&config, &selected_group)?;
 
         enforce_security_check(&config)?;
 
         // Execute claude command with all environment variables set
         let mut cmd = Command::new("claude");
-        inject_env_vars(&mut cmd, group)?;
+        inject_env_vars(&mut cmd, &group.vars)?;
         for arg in args {
             cmd.arg(arg);
         }
@@ -248,7 +313,7 @@ fn handle_account_command(action: Option<&AccountAction>) -> Result<()> {
 
     match action {
         Some(cli_action) => execute_account_action(&mut config, cli_action)?,
-        None => handle_list_groups(&config),
+        None => handle_list_groups(&config, false),
     }
 
     Ok(())
@@ -257,10 +322,16 @@ fn handle_account_command(action: Option<&AccountAction>) -> Result<()> {
 fn execute_account_action(config: &mut ClaudeCodeConfig, action: &AccountAction) -> Result<()> {
     match action {
         AccountAction::Edit => unreachable!("Edit is handled before config load"),
-        AccountAction::List => handle_list_groups(config),
+        AccountAction::List { verbose } => handle_list_groups(config, *verbose),
         AccountAction::Import { force } => handle_import_group(config, *force)?,
         AccountAction::Use { name, args } => handle_use_group(config, name, args.clone())?,
         AccountAction::Env { name } => handle_env_group(config, name)?,
+        AccountAction::Check {
+            name,
+            model,
+            timeout,
+            json,
+        } => handle_check_groups(config, name.as_deref(), model.as_deref(), *timeout, *json)?,
     }
     Ok(())
 }
@@ -346,7 +417,7 @@ fn handle_import_group(config: &mut ClaudeCodeConfig, force: bool) -> Result<()>
             }
         }
 
-        config.add_group(name.clone(), group);
+        config.add_group(name.clone(), group.into());
         config
             .save()
             .with_context(|| t!("claude_code.error.save_after_import_failed"))?;
@@ -358,39 +429,44 @@ fn handle_import_group(config: &mut ClaudeCodeConfig, force: bool) -> Result<()>
     Ok(())
 }
 
-fn handle_list_groups(config: &ClaudeCodeConfig) {
-    interactive::display_config_list(config);
+fn handle_list_groups(config: &ClaudeCodeConfig, verbose: bool) {
+    interactive::display_config_list(config, verbose);
 }
 
-fn handle_use_group(config: &ClaudeCodeConfig, name: &str, args: Vec<String>) -> Result<()> {
-    if let Some(group) = config.get_group(name) {
-        enforce_security_check(config)?;
+/// Merged environment of `name`, or a "group not found" error.
+fn effective_group_or_bail(config: &ClaudeCodeConfig, name: &str) -> Result<EffectiveGroup> {
+    config.effective_group(name)?.ok_or_else(|| {
+        anyhow::anyhow!(format!(
+            "{}\n{}",
+            t!("claude_code.account.group_not_found", name = name),
+            t!("claude_code.account.use_list_command")
+        ))
+    })
+}
 
-        // Execute claude command with all environment variables set
-        let mut cmd = Command::new("claude");
-        inject_env_vars(&mut cmd, group)?;
+fn handle_use_group(config: &ClaudeCodeConfig, name: &str, args: Vec<String>) -> Result<()> {
+    let group = effective_group_or_bail(config, name)?;
+    ProjectAccountPolicy::current(AccountApp::ClaudeCode)?.ensure_allowed(name)?;
+    enforce_security_check(config)?;
 
-        // Add any additional arguments
-        for arg in args {
-            cmd.arg(arg);
-        }
+    // Execute claude command with all environment variables set
+    let mut cmd = Command::new("claude");
+    inject_env_vars(&mut cmd, &group.vars)?;
 
-        let status = cmd
-            .status()
-            .context(t!("claude_code.error.execute_failed"))?;
+    // Add any additional arguments
+    for arg in args {
+        cmd.arg(arg);
+    }
 
-        if !status.success() {
-            bail!(t!("claude_code.error.failed_claude_command"));
-        }
+    let status = cmd
+        .status()
+        .context(t!("claude_code.error.execute_failed"))?;
 
-        println!("\n{}", t!("claude_code.run.session_ended", name = name));
-    } else {
-        bail!(
-            "{}\n{}",
-            t!("claude_code.account.group_not_found", name = name),
-            t!("claude_code.account.use_list_command")
-        );
+    if !status.success() {
+        bail!(t!("claude_code.error.failed_claude_command"));
     }
+
+    println!("\n{}", t!("claude_code.run.session_ended", name = name));
     Ok(())
 }
 
@@ -399,16 +475,23 @@ fn handle_env_group(config: &ClaudeCodeConfig, name: &str) -> Result<()> {
         bail!(no_configs_message());
     }
 
-    let group = config.get_group(name).ok_or_else(|| {
-        anyhow::anyhow!(format!(
-            "{}\n{}",
-            t!("claude_code.account.group_not_found", name = name),
-            t!("claude_code.account.use_list_command")
-        ))
-    })?;
+    let group = effective_group_or_bail(config, name)?;
+    ProjectAccountPolicy::current(AccountApp::ClaudeCode)?.ensure_allowed(name)?;
+    // Inherited variables carry the group they come from.
+    let notes: HashMap<String, String> = group
+        .sources
+        .iter()
+        .filter(|(_, source)| source.as_str() != name)
+        .map(|(key, source)| {
+            (
+                key.clone(),
+                t!("claude_code.interactive.inherited_from", group = source).to_string(),
+            )
+        })
+        .collect();
 
     let syntax = env_syntax_for_current_platform();
-    let lines = render_env_injection_lines(group, syntax)?;
+    let lines = render_annotated_env_injection_lines(&resolve_group(&group.vars)?, syntax, &notes)?;
 
     match syntax {
         EnvSyntax::PosixExport => {
@@ -426,6 +509,35 @@ fn handle_env_group(config: &ClaudeCodeConfig, name: &str) -> Result<()> {
     Ok(())
 }
 
+fn handle_check_groups(
+    config: &ClaudeCodeConfig,
+    name: Option<&str>,
+    model: Option<&str>,
+    timeout: u64,
+    json: bool,
+) -> Result<()> {
+    if config.is_empty() {
+        bail!(no_configs_message());
+    }
+    let names = match name {
+        Some(name) => vec![name.to_string()],
+        None => config.group_names(),
+    };
+
+    let mut resolver = SecretResolver::new();
+    let mut targets = Vec::with_capacity(names.len());
+    for name in names {
+        let group = effective_group_or_bail(config, &name)?;
+        let env = resolver.resolve_map(&group.vars)?;
+        let model = model.or(group.check_model.as_deref());
+        targets.push(account_check::claude_code_target(&name, &env, model));
+    }
+
+    let results = account_check::run_checks(&targets, Duration::from_secs(timeout))?;
+    account_check::print_results(&results, json)?;
+    account_check::ensure_all_ok(&results)
+}
+
 fn handle_run_command(
     interactive: bool,
     group_name: Option<&str>,
@@ -437,6 +549,15 @@ fn handle_run_command(
         bail!(no_configs_message());
     }
 
+    let policy = ProjectAccountPolicy::current(AccountApp::ClaudeCode)?;
+    let group_name = match group_name {
+        None if !interactive && policy.default_group().is_some() => {
+            policy.announce_default();
+            policy.default_group()
+        }
+        other => other,
+    };
+
     // 验证参数组合
     if !interactive && group_name.is_none() {
         bail!(
@@ -455,43 +576,38 @@ fn handle_run_command(
         (group, args)
     };
 
+    policy.ensure_allowed(&selected_group)?;
+
     // 执行 claude 命令
-    if let Some(env_vars) = config.get_group(&selected_group) {
-        println!(
-            "{}",
-            t!("claude_code.run.using_config", name = selected_group)
-        );
+    let group = effective_group_or_bail(&config, &selected_group)?;
+    println!(
+        "{}",
+        t!("claude_code.run.using_config", name = selected_group)
+    );
 
-        enforce_security_check(&config)?;
+    enforce_security_check(&config)?;
 
-        let mut cmd = Command::new("claude");
-        inject_env_vars(&mut cmd, env_vars)?;
+    let mut cmd = Command::new("claude");
+    inject_env_vars(&mut cmd, &group.vars)?;
 
-        // 添加传递的参数
-        for arg in claude_args {
-            cmd.arg(arg);
-        }
-
-        let status = cmd
-            .status()
-            .context(t!("claude_code.error.execute_failed"))?;
+    // 添加传递的参数
+    for arg in claude_args {
+        cmd.arg(arg);
+    }
 
-        if !status.success() {
-            bail!(t!("claude_code.error.failed_claude_command"));
-        }
+    let status = cmd
+        .status()
+        .context(t!("claude_code.error.execute_failed"))?;
 
-        println!(
-            "\n{}",
-            t!("claude_code.run.session_ended", name = selected_group)
-        );
-    } else {
-        bail!(
-            "{}\n{}",
-            t!("claude_code.account.group_not_found", name = selected_group),
-            t!("claude_code.account.use_list_command")
-        );
+    if !status.success() {
+        bail!(t!("claude_code.error.failed_claude_command"));
     }
 
+    println!(
+        "\n{}",
+        t!("claude_code.run.session_ended", name = selected_group)
+    );
+
     Ok(())
 }
 
@@ -548,67 +664,40 @@ fn handle_interactive_mode(config: &ClaudeCodeConfig) -> Result<(String, Vec<Str
     Ok((selected_group, claude_args))
 }
 
-/// Print security warnings to stderr
-fn print_security_warnings(warnings: &[SecurityWarning]) {
-    if warnings.is_empty() {
-        return;
+/// llman config file holding the Claude Code security rules
+const CLAUDE_CODE_RULES_FILE: &str = "claude-code.toml";
+
+fn handle_audit_command(json: bool, sarif: bool, allow_unvetted_hooks: bool) -> Result<()> {
+    let config = ClaudeCodeConfig::load().context(t!("claude_code.error.load_config_failed"))?;
+    let mut checker = SecurityChecker::from_config(&config)?;
+    if !allow_unvetted_hooks {
+        checker = checker.with_vetted_hooks(HookLibrary::load()?.vetted_commands());
+    }
+    let warnings = checker.audit()?;
+
+    if sarif {
+        println!("{}", serde_json::to_string_pretty(&to_sarif(&warnings))?);
+    } else if json {
+        println!("{}", serde_json::to_string_pretty(&warnings)?);
+    } else if warnings.is_empty() {
+        println!("{}", t!("claude_code.security.audit.clean"));
+    } else {
+        print_security_warnings(&warnings, CLAUDE_CODE_RULES_FILE);
     }
 
-    eprintln!(
-        "\n🔒 {}",
-        t!(
-            "claude_code.security.warning_header",
+    if !warnings.is_empty() {
+        bail!(t!(
+            "claude_code.security.audit.failed",
             count = warnings.len()
-        )
-    );
-    eprintln!(
-        "{}",
-        t!("claude_code.security.warning_separator_char").repeat(60)
-    );
-
-    for warning in warnings {
-        eprintln!(
-            "\n{} [{}] {}",
-            warning.severity.display_symbol(),
-            warning.severity.display_name_localized(),
-            t!("claude_code.security.warning_item_title")
-        );
-        eprintln!(
-            "  📍 {} {}",
-            t!("claude_code.security.label_location"),
-            warning.config_path
-        );
-        eprintln!(
-            "  ⚙️  {} {}",
-            t!("claude_code.security.label_setting"),
-            warning.config_item
-        );
-        eprintln!(
-            "  🎯 {} {}",
-            t!("claude_code.security.label_pattern"),
-            warning.matched_pattern
-        );
-        eprintln!(
-            "  📝 {} {}",
-            t!("claude_code.security.label_description"),
-            warning.description
-        );
-        eprintln!(
-            "  💡 {} {}",
-            t!("claude_code.security.label_recommendation"),
-            warning.recommendation
-        );
+        ));
     }
-
-    eprintln!("\n⚠️ {}", t!("claude_code.security.footer_line1"));
-    eprintln!("  {}", t!("claude_code.security.footer_line2"));
-    eprintln!();
+    Ok(())
 }
 
 fn enforce_security_check(config: &ClaudeCodeConfig) -> Result<()> {
     let security_checker = SecurityChecker::from_config(config)?;
     let warnings = security_checker.check_claude_settings()?;
-    print_security_warnings(&warnings);
+    print_security_warnings(&warnings, CLAUDE_CODE_RULES_FILE);
     if !warnings.is_empty() {
         bail!(t!(
             "claude_code.security.blocked_by_warnings",
@@ -618,10 +707,11 @@ fn enforce_security_check(config: &ClaudeCodeConfig) -> Result<()> {
     Ok(())
 }
 
-/// Inject environment variables from a config group into a Command
+/// Inject environment variables from a config group into a Command, resolving secret references
 fn inject_env_vars(cmd: &mut Command, group: &ConfigGroup) -> Result<()> {
     prepare_env_injection(group)?;
-    for (key, value) in group {
+    let resolved = resolve_group(group)?;
+    for (key, value) in resolved {
         cmd.env(key, value);
     }
     Ok(())
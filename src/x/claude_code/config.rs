@@ -1,4 +1,5 @@
 use crate::path_utils::safe_parent_for_creation;
+use crate::secrets::{SecretRef, SecretResolver, SecretValue};
 use anyhow::{Context, Result};
 use llm_json::{RepairOptions, loads};
 use rust_i18n::t;
@@ -8,12 +9,49 @@ use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
-// Simplified ConfigGroup: 直接映射为环境变量
-pub type ConfigGroup = HashMap<String, String>;
+// Simplified ConfigGroup: 直接映射为环境变量，值可以是明文或密钥引用
+pub type ConfigGroup = HashMap<String, SecretValue>;
+
+/// A config group with every secret reference resolved, ready for injection.
+pub type ResolvedGroup = HashMap<String, String>;
+
+/// A group as written in `claude-code.toml`: its own variables plus optional parents.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GroupDefinition {
+    /// Parent groups, merged in order; later parents override earlier ones.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extends: Vec<String>,
+    /// Inherited variables to drop.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unset: Vec<String>,
+    /// Model `account check` requests for this group (not injected into the environment).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub check_model: Option<String>,
+    #[serde(flatten)]
+    pub vars: ConfigGroup,
+}
+
+impl From<ConfigGroup> for GroupDefinition {
+    fn from(vars: ConfigGroup) -> Self {
+        Self {
+            vars,
+            ..Self::default()
+        }
+    }
+}
+
+/// Fully merged environment of a group, with the group that supplied each variable.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EffectiveGroup {
+    pub vars: ConfigGroup,
+    pub sources: HashMap<String, String>,
+    /// `check_model` of the group or its nearest parent that sets one.
+    pub check_model: Option<String>,
+}
 
 #[derive(Debug, Default, Serialize, Deserialize)]
 pub struct ClaudeCodeConfig {
-    pub groups: HashMap<String, ConfigGroup>,
+    pub groups: HashMap<String, GroupDefinition>,
     pub security: Option<SecurityConfig>,
 }
 
@@ -70,13 +108,9 @@ impl SecurityConfig {
 
     /// Get Claude Code settings files to check (default files if none specified)
     pub fn get_claude_settings_files(&self) -> Vec<String> {
-        self.claude_settings_files.clone().unwrap_or_else(|| {
-            vec![
-                ".claude/settings.local.json".to_string(),
-                ".claude/settings.json".to_string(),
-                "~/.claude/settings.json".to_string(),
-            ]
-        })
+        self.claude_settings_files.clone().unwrap_or_else(
+            crate::x::claude_code::security::SecurityChecker::default_settings_files,
+        )
     }
 }
 
@@ -127,23 +161,74 @@ impl ClaudeCodeConfig {
         let mut new_config = ClaudeCodeConfig::default();
         for (name, old_group) in old_config.groups {
             let mut env_vars = HashMap::new();
-            env_vars.insert("ANTHROPIC_BASE_URL".to_string(), old_group.api_host);
-            env_vars.insert("ANTHROPIC_AUTH_TOKEN".to_string(), old_group.api_key);
+            env_vars.insert("ANTHROPIC_BASE_URL".to_string(), old_group.api_host.into());
+            env_vars.insert("ANTHROPIC_AUTH_TOKEN".to_string(), old_group.api_key.into());
 
-            new_config.add_group(name, env_vars);
+            new_config.add_group(name, env_vars.into());
         }
 
         Ok(new_config)
     }
 
-    pub fn add_group(&mut self, name: String, group: ConfigGroup) {
+    pub fn add_group(&mut self, name: String, group: GroupDefinition) {
         self.groups.insert(name, group);
     }
 
-    pub fn get_group(&self, name: &str) -> Option<&ConfigGroup> {
+    pub fn get_group(&self, name: &str) -> Option<&GroupDefinition> {
         self.groups.get(name)
     }
 
+    /// Merge `extends` chains: parents in listed order, then `unset`, then the group's own
+    /// variables. Unknown parents and cycles are errors.
+    pub fn effective_group(&self, name: &str) -> Result<Option<EffectiveGroup>> {
+        if !self.groups.contains_key(name) {
+            return Ok(None);
+        }
+        let mut stack = Vec::new();
+        self.merge_group(name, &mut stack).map(Some)
+    }
+
+    fn merge_group(&self, name: &str, stack: &mut Vec<String>) -> Result<EffectiveGroup> {
+        if stack.iter().any(|entry| entry == name) {
+            let mut chain = stack.clone();
+            chain.push(name.to_string());
+            anyhow::bail!(t!(
+                "claude_code.config.extends_cycle",
+                chain = chain.join(" -> ")
+            ));
+        }
+        let Some(definition) = self.groups.get(name) else {
+            anyhow::bail!(t!(
+                "claude_code.config.extends_unknown",
+                group = stack.last().map(String::as_str).unwrap_or_default(),
+                parent = name
+            ));
+        };
+
+        stack.push(name.to_string());
+        let mut effective = EffectiveGroup::default();
+        for parent in &definition.extends {
+            let inherited = self.merge_group(parent, stack)?;
+            effective.vars.extend(inherited.vars);
+            effective.sources.extend(inherited.sources);
+            effective.check_model = inherited.check_model.or(effective.check_model);
+        }
+        stack.pop();
+
+        for key in &definition.unset {
+            effective.vars.remove(key);
+            effective.sources.remove(key);
+        }
+        for (key, value) in &definition.vars {
+            effective.vars.insert(key.clone(), value.clone());
+            effective.sources.insert(key.clone(), name.to_string());
+        }
+        if definition.check_model.is_some() {
+            effective.check_model = definition.check_model.clone();
+        }
+        Ok(effective)
+    }
+
     pub fn group_names(&self) -> Vec<String> {
         let mut names: Vec<String> = self.groups.keys().cloned().collect();
         names.sort();
@@ -194,7 +279,7 @@ pub fn is_sensitive_env_key(key: &str) -> bool {
         .any(|marker| upper.contains(marker))
 }
 
-// Utility function for display formatting
+// Utility function for display formatting. Secret references are shown, never resolved.
 pub fn get_display_vars(group: &ConfigGroup) -> Vec<(String, String)> {
     let mut vars: Vec<_> = group.iter().collect();
     vars.sort_by(|a, b| a.0.cmp(b.0));
@@ -202,16 +287,23 @@ pub fn get_display_vars(group: &ConfigGroup) -> Vec<(String, String)> {
         .map(|(k, v)| {
             (
                 k.clone(),
-                if is_sensitive_env_key(k) {
-                    mask_secret(v)
-                } else {
-                    v.clone()
-                },
+                v.display_with(|plain| {
+                    if is_sensitive_env_key(k) {
+                        mask_secret(plain)
+                    } else {
+                        plain.to_string()
+                    }
+                }),
             )
         })
         .collect()
 }
 
+/// Resolve the secret references of a group at use time.
+pub fn resolve_group(group: &ConfigGroup) -> Result<ResolvedGroup> {
+    SecretResolver::new().resolve_map(group)
+}
+
 pub fn mask_secret(value: &str) -> String {
     if value.len() <= 8 {
         "*".repeat(value.len())
@@ -245,11 +337,11 @@ pub fn parse_json_config(json_str: &str) -> Result<ConfigGroup> {
             // Handle both {"env": {...}} and direct {...} formats
             if let Some(env_obj) = map.remove("env") {
                 match env_obj {
-                    Value::Object(env_map) => convert_env_map(env_map),
+                    Value::Object(env_map) => convert_env_map(env_map)?,
                     _ => anyhow::bail!(t!("claude_code.config.json_env_invalid")),
                 }
             } else {
-                convert_env_map(map)
+                convert_env_map(map)?
             }
         }
         _ => anyhow::bail!(t!("claude_code.config.json_must_object")),
@@ -258,21 +350,45 @@ pub fn parse_json_config(json_str: &str) -> Result<ConfigGroup> {
     Ok(env_vars)
 }
 
-fn convert_env_map(env_map: serde_json::Map<String, Value>) -> ConfigGroup {
+fn convert_env_map(env_map: serde_json::Map<String, Value>) -> Result<ConfigGroup> {
     let mut config_group = HashMap::new();
 
     for (key, value) in env_map {
-        let value_str = match value {
-            Value::String(s) => s,
-            Value::Number(n) => n.to_string(),
-            Value::Bool(b) => b.to_string(),
-            Value::Null => String::new(),
-            _ => value.to_string(), // For complex objects, convert to string representation
+        let value = match value {
+            Value::String(s) => s.into(),
+            Value::Number(n) => n.to_string().into(),
+            Value::Bool(b) => b.to_string().into(),
+            Value::Null => String::new().into(),
+            Value::Object(object) => match secret_ref_from_object(&object) {
+                Some(reference) => SecretValue::Ref(reference),
+                None => anyhow::bail!(t!("claude_code.config.json_env_value_invalid", key = key)),
+            },
+            _ => value.to_string().into(), // For complex values, convert to string representation
         };
-        config_group.insert(key, value_str);
+        config_group.insert(key, value);
     }
 
-    config_group
+    Ok(config_group)
+}
+
+/// Only the documented reference shape: a single `cmd`, `file`, `env` or `store` key with a
+/// non-empty string value.
+fn secret_ref_from_object(object: &serde_json::Map<String, Value>) -> Option<SecretRef> {
+    let mut entries = object.iter();
+    let (kind, Value::String(target)) = entries.next()? else {
+        return None;
+    };
+    if entries.next().is_some() || target.trim().is_empty() {
+        return None;
+    }
+    let target = target.clone();
+    match kind.as_str() {
+        "cmd" => Some(SecretRef::Cmd(target)),
+        "file" => Some(SecretRef::File(target)),
+        "env" => Some(SecretRef::Env(target)),
+        "store" => Some(SecretRef::Store(target)),
+        _ => None,
+    }
 }
 
 #[cfg(test)]
@@ -300,10 +416,10 @@ mod tests {
     #[test]
     fn get_display_vars_masks_password_values() {
         let mut group = HashMap::new();
-        group.insert("DB_PASSWORD".to_string(), "super-secret-value".to_string());
+        group.insert("DB_PASSWORD".to_string(), "super-secret-value".into());
         group.insert(
             "ANTHROPIC_BASE_URL".to_string(),
-            "https://example.com".to_string(),
+            "https://example.com".into(),
         );
 
         let display = get_display_vars(&group);
@@ -320,4 +436,123 @@ mod tests {
             .expect("url key");
         assert_eq!(url.1, "https://example.com");
     }
+
+    #[test]
+    fn get_display_vars_shows_references_without_resolving() {
+        let mut group = HashMap::new();
+        group.insert(
+            "ANTHROPIC_AUTH_TOKEN".to_string(),
+            SecretValue::Ref(SecretRef::Cmd("pass show anthropic".to_string())),
+        );
+        let display = get_display_vars(&group);
+        assert_eq!(display[0].1, "<cmd: pass show anthropic>");
+    }
+
+    #[test]
+    fn config_roundtrips_secret_references() {
+        let config: ClaudeCodeConfig = toml::from_str(
+            r#"
+[groups.work]
+ANTHROPIC_BASE_URL = "https://api.anthropic.com"
+ANTHROPIC_AUTH_TOKEN = { file = "~/.secrets/anthropic" }
+"#,
+        )
+        .expect("parse");
+        let saved = toml::to_string_pretty(&config).expect("serialize");
+        let reparsed: ClaudeCodeConfig = toml::from_str(&saved).expect("reparse");
+        assert_eq!(
+            reparsed.get_group("work").expect("group").vars["ANTHROPIC_AUTH_TOKEN"],
+            SecretValue::Ref(SecretRef::File("~/.secrets/anthropic".to_string()))
+        );
+    }
+
+    #[test]
+    fn parse_json_config_keeps_reference_objects() {
+        let group = parse_json_config(r#"{"env": {"API_KEY": {"env": "OTHER"}, "PORT": 8080}}"#)
+            .expect("parse");
+        assert_eq!(
+            group["API_KEY"],
+            SecretValue::Ref(SecretRef::Env("OTHER".to_string()))
+        );
+        assert_eq!(group["PORT"], SecretValue::from("8080"));
+    }
+
+    #[test]
+    fn parse_json_config_rejects_objects_that_are_not_references() {
+        for value in [
+            r#"{"command": "echo hi"}"#,
+            r#"{"cmd": "echo hi", "file": "x"}"#,
+            r#"{"cmd": ["echo", "hi"]}"#,
+            r#"{"cmd": ""}"#,
+            r#"{}"#,
+        ] {
+            let json = format!(r#"{{"env": {{"API_KEY": {value}}}}}"#);
+            let err = parse_json_config(&json).expect_err(value);
+            assert!(err.to_string().contains("API_KEY"), "{err}");
+        }
+    }
+
+    #[test]
+    fn effective_group_merges_parents_in_order_with_unset_and_provenance() {
+        let config: ClaudeCodeConfig = toml::from_str(
+            r#"
+[groups.base]
+check_model = "base-check"
+ANTHROPIC_BASE_URL = "https://api.example.com"
+ANTHROPIC_AUTH_TOKEN = "sk-base"
+DEBUG = "1"
+
+[groups.eu]
+ANTHROPIC_BASE_URL = "https://eu.example.com"
+
+[groups.fast]
+extends = ["base", "eu"]
+unset = ["DEBUG"]
+ANTHROPIC_MODEL = "fast-model"
+"#,
+        )
+        .expect("parse");
+
+        let effective = config
+            .effective_group("fast")
+            .expect("resolve")
+            .expect("group exists");
+        assert_eq!(
+            effective.vars["ANTHROPIC_BASE_URL"],
+            SecretValue::from("https://eu.example.com")
+        );
+        assert_eq!(effective.sources["ANTHROPIC_BASE_URL"], "eu");
+        assert_eq!(effective.sources["ANTHROPIC_AUTH_TOKEN"], "base");
+        assert_eq!(effective.sources["ANTHROPIC_MODEL"], "fast");
+        assert!(!effective.vars.contains_key("DEBUG"));
+        assert_eq!(effective.check_model.as_deref(), Some("base-check"));
+        assert!(!effective.vars.contains_key("check_model"));
+
+        let saved = toml::to_string_pretty(&config).expect("serialize");
+        let reparsed: ClaudeCodeConfig = toml::from_str(&saved).expect("reparse");
+        assert_eq!(reparsed.get_group("fast"), config.get_group("fast"));
+        assert!(config.effective_group("missing").expect("ok").is_none());
+    }
+
+    #[test]
+    fn effective_group_rejects_cycles_and_unknown_parents() {
+        let config: ClaudeCodeConfig = toml::from_str(
+            r#"
+[groups.a]
+extends = ["b"]
+
+[groups.b]
+extends = ["a"]
+
+[groups.c]
+extends = ["nope"]
+"#,
+        )
+        .expect("parse");
+
+        let err = config.effective_group("a").expect_err("cycle");
+        assert!(err.to_string().contains("a -> b -> a"), "{err}");
+        let err = config.effective_group("c").expect_err("unknown parent");
+        assert!(err.to_string().contains("nope"), "{err}");
+    }
 }
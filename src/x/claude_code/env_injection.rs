@@ -1,7 +1,8 @@
 use crate::env_safety::{find_dangerous_env_keys, is_valid_env_key};
-use crate::x::claude_code::config::ConfigGroup;
+use crate::x::claude_code::config::ResolvedGroup;
 use anyhow::{Result, bail};
 use rust_i18n::t;
+use std::collections::HashMap;
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub enum EnvSyntax {
@@ -17,7 +18,16 @@ pub fn env_syntax_for_current_platform() -> EnvSyntax {
     }
 }
 
-pub fn render_env_injection_lines(group: &ConfigGroup, syntax: EnvSyntax) -> Result<Vec<String>> {
+pub fn render_env_injection_lines(group: &ResolvedGroup, syntax: EnvSyntax) -> Result<Vec<String>> {
+    render_annotated_env_injection_lines(group, syntax, &HashMap::new())
+}
+
+/// Like [`render_env_injection_lines`], appending `# <note>` to the lines of annotated keys.
+pub fn render_annotated_env_injection_lines(
+    group: &ResolvedGroup,
+    syntax: EnvSyntax,
+    notes: &HashMap<String, String>,
+) -> Result<Vec<String>> {
     let mut invalid_keys: Vec<&str> = group
         .keys()
         .filter(|key| !is_valid_env_key(key))
@@ -46,10 +56,13 @@ pub fn render_env_injection_lines(group: &ConfigGroup, syntax: EnvSyntax) -> Res
             EnvSyntax::PowerShell => quote_powershell_single(value),
         };
 
-        let line = match syntax {
+        let mut line = match syntax {
             EnvSyntax::PosixExport => format!("export {key}={quoted_value}"),
             EnvSyntax::PowerShell => format!("$env:{key}={quoted_value}"),
         };
+        if let Some(note) = notes.get(key) {
+            line.push_str(&format!(" # {note}"));
+        }
 
         lines.push(line);
     }
@@ -57,7 +70,8 @@ pub fn render_env_injection_lines(group: &ConfigGroup, syntax: EnvSyntax) -> Res
     Ok(lines)
 }
 
-pub fn prepare_env_injection(group: &ConfigGroup) -> Result<()> {
+/// Validate group keys before any secret reference is resolved.
+pub fn prepare_env_injection<V>(group: &HashMap<String, V>) -> Result<()> {
     let mut invalid_keys: Vec<&str> = group
         .keys()
         .filter(|key| !is_valid_env_key(key))
@@ -92,7 +106,6 @@ fn quote_powershell_single(value: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::collections::HashMap;
 
     #[test]
     fn quote_posix_single_escapes_single_quote() {
@@ -126,7 +139,7 @@ mod tests {
 
     #[test]
     fn render_env_injection_lines_sorts_keys() {
-        let group: ConfigGroup = HashMap::from([
+        let group: ResolvedGroup = HashMap::from([
             ("B".to_string(), "2".to_string()),
             ("A".to_string(), "1".to_string()),
         ]);
@@ -137,7 +150,7 @@ mod tests {
 
     #[test]
     fn render_env_injection_lines_rejects_invalid_keys() {
-        let group: ConfigGroup = HashMap::from([("BAD-KEY".to_string(), "1".to_string())]);
+        let group: ResolvedGroup = HashMap::from([("BAD-KEY".to_string(), "1".to_string())]);
         let err =
             render_env_injection_lines(&group, EnvSyntax::PosixExport).expect_err("should fail");
         assert!(
@@ -148,7 +161,8 @@ mod tests {
 
     #[test]
     fn prepare_env_injection_rejects_dangerous_keys() {
-        let group: ConfigGroup = HashMap::from([("LD_PRELOAD".to_string(), "/tmp/x".to_string())]);
+        let group: ResolvedGroup =
+            HashMap::from([("LD_PRELOAD".to_string(), "/tmp/x".to_string())]);
         let err = prepare_env_injection(&group).expect_err("should fail");
         assert!(
             err.to_string().contains("dangerous"),
@@ -0,0 +1,679 @@
+//! `llman x cc hooks`: a library of Claude Code hook definitions synced into settings files.
+//!
+//! The library lives in `<config>/claude-code/hooks.toml`. A hook is identified in settings by
+//! its event, matcher and command, so entries llman did not write are left alone.
+
+use crate::config::resolve_config_dir;
+use crate::fs_utils::atomic_write_with_mode;
+use crate::path_utils::safe_parent_for_creation;
+use crate::x::claude_code::config::ClaudeCodeConfig;
+use crate::x::claude_code::security::{SecurityChecker, print_security_warnings, rules};
+use crate::x::claude_code::settings::{
+    SettingsChange, SettingsFile, SettingsScope, apply_changes, patch_member,
+};
+use anyhow::{Context, Result, bail};
+use clap::{Args, Subcommand};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value, json};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Hook events Claude Code dispatches.
+pub const HOOK_EVENTS: [&str; 9] = [
+    "PreToolUse",
+    "PostToolUse",
+    "Notification",
+    "UserPromptSubmit",
+    "Stop",
+    "SubagentStop",
+    "PreCompact",
+    "SessionStart",
+    "SessionEnd",
+];
+
+#[derive(Args, Debug, Clone)]
+#[command(about = "Manage a library of Claude Code hooks synced into settings files")]
+pub struct ClaudeCodeHooksArgs {
+    #[command(subcommand)]
+    pub command: ClaudeCodeHooksCommand,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum ClaudeCodeHooksCommand {
+    /// List hooks in the library, optionally with their state in a settings file
+    List {
+        /// Also show whether each hook is installed in this scope
+        #[arg(long, value_enum)]
+        scope: Option<SettingsScope>,
+    },
+    /// Add (or replace) a hook definition in the library
+    Add {
+        /// Hook name in the library
+        name: String,
+        /// Event that triggers the hook (e.g. PreToolUse, PostToolUse, Stop)
+        #[arg(long)]
+        event: String,
+        /// Tool name pattern for tool events (e.g. "Edit|Write"); omit to match all
+        #[arg(long)]
+        matcher: Option<String>,
+        /// Shell command to run
+        #[arg(long)]
+        command: String,
+        /// Timeout in seconds
+        #[arg(long)]
+        timeout: Option<u64>,
+        /// Short description shown by `list`
+        #[arg(long)]
+        description: Option<String>,
+        /// Replace an existing hook with the same name
+        #[arg(long)]
+        force: bool,
+        /// Scopes to uninstall the replaced hook from when its event, matcher or command changes
+        #[command(flatten)]
+        write: HooksWriteArgs,
+    },
+    /// Remove a hook from the library (and uninstall it from the given scopes)
+    Remove {
+        /// Hook name in the library
+        name: String,
+        #[command(flatten)]
+        write: HooksWriteArgs,
+    },
+    /// Install library hooks into settings files
+    Sync {
+        #[command(flatten)]
+        write: HooksWriteArgs,
+
+        /// Only sync these hooks (repeatable, or comma-separated)
+        #[arg(long, value_delimiter = ',')]
+        hook: Vec<String>,
+    },
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct HooksWriteArgs {
+    /// Settings file(s) to update (default for sync: project)
+    #[arg(long, value_enum, value_delimiter = ',')]
+    pub scope: Vec<SettingsScope>,
+
+    /// Show the diff but do not write any files
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Assume "yes" for confirmations (required for non-interactive writes)
+    #[arg(long, short = 'y')]
+    pub yes: bool,
+}
+
+/// One hook definition in the library.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HookDefinition {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub event: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub matcher: Option<String>,
+    pub command: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<u64>,
+}
+
+impl HookDefinition {
+    fn validate(&self, name: &str) -> Result<()> {
+        if !HOOK_EVENTS.contains(&self.event.as_str()) {
+            bail!(t!(
+                "claude_code.hooks.unknown_event",
+                name = name,
+                event = self.event,
+                expected = HOOK_EVENTS.join(", ")
+            ));
+        }
+        if self.command.trim().is_empty() {
+            bail!(t!("claude_code.hooks.empty_command", name = name));
+        }
+        Ok(())
+    }
+
+    /// Event, matcher and command: what identifies the hook in a settings file.
+    fn identity(&self) -> (&str, &str, &str) {
+        (&self.event, self.matcher(), &self.command)
+    }
+
+    fn matcher(&self) -> &str {
+        self.matcher.as_deref().unwrap_or_default()
+    }
+
+    /// The `{"type": "command", ...}` entry Claude Code expects.
+    fn entry(&self) -> Value {
+        let mut entry = Map::new();
+        entry.insert("type".into(), json!("command"));
+        entry.insert("command".into(), json!(self.command));
+        if let Some(timeout) = self.timeout {
+            entry.insert("timeout".into(), json!(timeout));
+        }
+        Value::Object(entry)
+    }
+}
+
+/// The llman-managed hook library (`<config>/claude-code/hooks.toml`).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HookLibrary {
+    #[serde(default)]
+    pub hooks: BTreeMap<String, HookDefinition>,
+}
+
+impl HookLibrary {
+    pub fn file_path() -> Result<PathBuf> {
+        Ok(resolve_config_dir(None)?
+            .join("claude-code")
+            .join("hooks.toml"))
+    }
+
+    pub fn load() -> Result<Self> {
+        Self::load_from_path(&Self::file_path()?)
+    }
+
+    pub fn load_from_path(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path)
+            .with_context(|| t!("claude_code.hooks.read_failed", path = path.display()))?;
+        let library: Self = toml::from_str(&content)
+            .with_context(|| t!("claude_code.hooks.parse_failed", path = path.display()))?;
+        for (name, hook) in &library.hooks {
+            hook.validate(name)?;
+        }
+        Ok(library)
+    }
+
+    pub fn save_to_path(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = safe_parent_for_creation(path) {
+            fs::create_dir_all(parent).with_context(|| {
+                t!(
+                    "claude_code.settings.create_dir_failed",
+                    path = parent.display()
+                )
+            })?;
+        }
+        let content =
+            toml::to_string_pretty(self).context(t!("claude_code.hooks.serialize_failed"))?;
+        atomic_write_with_mode(path, content.as_bytes(), Some(0o600))
+            .with_context(|| t!("claude_code.settings.write_failed", path = path.display()))
+    }
+
+    /// Commands the library vouches for; the audit flags hook commands outside this set.
+    pub fn vetted_commands(&self) -> BTreeSet<String> {
+        self.hooks
+            .values()
+            .map(|hook| hook.command.trim().to_string())
+            .collect()
+    }
+}
+
+/// Install state of one library hook in one settings file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookState {
+    Installed,
+    Missing,
+    Differs,
+}
+
+impl HookState {
+    pub fn label(self) -> &'static str {
+        match self {
+            HookState::Installed => "installed",
+            HookState::Missing => "missing",
+            HookState::Differs => "differs",
+        }
+    }
+}
+
+/// `hooks.<event>` array of a settings document.
+fn event_groups(settings: &Value, event: &str) -> Vec<Value> {
+    settings
+        .get("hooks")
+        .and_then(|hooks| hooks.get(event))
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default()
+}
+
+fn group_matcher(group: &Value) -> &str {
+    group
+        .get("matcher")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+}
+
+fn entry_command(entry: &Value) -> Option<&str> {
+    entry.get("command").and_then(Value::as_str)
+}
+
+pub fn hook_state(settings: &Value, hook: &HookDefinition) -> HookState {
+    let expected = hook.entry();
+    let mut state = HookState::Missing;
+    for group in event_groups(settings, &hook.event) {
+        if group_matcher(&group) != hook.matcher() {
+            continue;
+        }
+        for entry in group
+            .get("hooks")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            if entry_command(entry) == Some(hook.command.as_str()) {
+                if *entry == expected {
+                    return HookState::Installed;
+                }
+                state = HookState::Differs;
+            }
+        }
+    }
+    state
+}
+
+/// `hooks.<event>` with `hook` installed (replacing an entry with the same command).
+fn install_hook(mut groups: Vec<Value>, hook: &HookDefinition) -> Vec<Value> {
+    let entry = hook.entry();
+    let group = groups.iter_mut().find(|group| {
+        group_matcher(group) == hook.matcher() && group.get("hooks").is_some_and(Value::is_array)
+    });
+    match group {
+        Some(group) => {
+            let entries = group["hooks"].as_array_mut().expect("array");
+            match entries
+                .iter_mut()
+                .find(|existing| entry_command(existing) == Some(hook.command.as_str()))
+            {
+                Some(existing) => *existing = entry,
+                None => entries.push(entry),
+            }
+        }
+        None => {
+            let mut group = Map::new();
+            if let Some(matcher) = &hook.matcher {
+                group.insert("matcher".into(), json!(matcher));
+            }
+            group.insert("hooks".into(), json!([entry]));
+            groups.push(Value::Object(group));
+        }
+    }
+    groups
+}
+
+/// `hooks.<event>` without `hook`; matcher groups left empty are dropped.
+fn uninstall_hook(groups: Vec<Value>, hook: &HookDefinition) -> Vec<Value> {
+    groups
+        .into_iter()
+        .filter_map(|mut group| {
+            if group_matcher(&group) != hook.matcher() {
+                return Some(group);
+            }
+            let Some(entries) = group.get_mut("hooks").and_then(Value::as_array_mut) else {
+                return Some(group);
+            };
+            let before = entries.len();
+            entries.retain(|entry| entry_command(entry) != Some(hook.command.as_str()));
+            (before == entries.len() || !entries.is_empty()).then_some(group)
+        })
+        .collect()
+}
+
+/// Apply `update` to each touched event array and patch the file text accordingly.
+fn render_update(
+    file: &SettingsFile,
+    hooks: &[&HookDefinition],
+    update: fn(Vec<Value>, &HookDefinition) -> Vec<Value>,
+) -> Result<Option<String>> {
+    let mut events: BTreeMap<&str, Vec<Value>> = BTreeMap::new();
+    for hook in hooks {
+        let groups = events
+            .remove(hook.event.as_str())
+            .unwrap_or_else(|| event_groups(&file.value, &hook.event));
+        events.insert(&hook.event, update(groups, hook));
+    }
+
+    let mut content = file.content();
+    let mut changed = false;
+    for (event, groups) in events {
+        if groups == event_groups(&file.value, event) {
+            continue;
+        }
+        changed = true;
+        let value = (!groups.is_empty()).then(|| Value::Array(groups));
+        content = patch_member(&content, &["hooks", event], value.as_ref())?;
+    }
+    Ok(changed.then_some(content))
+}
+
+pub fn run(args: &ClaudeCodeHooksArgs) -> Result<()> {
+    match &args.command {
+        ClaudeCodeHooksCommand::List { scope } => run_list(*scope),
+        ClaudeCodeHooksCommand::Add {
+            name,
+            event,
+            matcher,
+            command,
+            timeout,
+            description,
+            force,
+            write,
+        } => run_add(
+            name,
+            HookDefinition {
+                description: description.clone(),
+                event: event.clone(),
+                matcher: matcher.clone(),
+                command: command.clone(),
+                timeout: *timeout,
+            },
+            *force,
+            write,
+        ),
+        ClaudeCodeHooksCommand::Remove { name, write } => run_remove(name, write),
+        ClaudeCodeHooksCommand::Sync { write, hook } => run_sync(write, hook),
+    }
+}
+
+fn run_list(scope: Option<SettingsScope>) -> Result<()> {
+    let library = HookLibrary::load()?;
+    println!(
+        "{}",
+        t!(
+            "claude_code.hooks.list_header",
+            path = HookLibrary::file_path()?.display()
+        )
+    );
+    if library.hooks.is_empty() {
+        println!("{}", t!("claude_code.hooks.empty"));
+        return Ok(());
+    }
+
+    let settings = scope.map(SettingsFile::read).transpose()?;
+    if let Some(file) = &settings {
+        println!(
+            "{}",
+            t!(
+                "claude_code.hooks.list_settings",
+                path = file.path.display(),
+                scope = file.scope.label()
+            )
+        );
+    }
+    for (name, hook) in &library.hooks {
+        let trigger = match &hook.matcher {
+            Some(matcher) => format!("{}[{matcher}]", hook.event),
+            None => hook.event.clone(),
+        };
+        let state = settings
+            .as_ref()
+            .map(|file| format!(" ({})", hook_state(&file.value, hook).label()))
+            .unwrap_or_default();
+        println!(
+            "{}",
+            t!(
+                "claude_code.hooks.list_item",
+                name = name,
+                trigger = trigger,
+                command = hook.command,
+                state = state
+            )
+        );
+        if let Some(description) = &hook.description {
+            println!("    {description}");
+        }
+    }
+    Ok(())
+}
+
+fn run_add(name: &str, hook: HookDefinition, force: bool, args: &HooksWriteArgs) -> Result<()> {
+    hook.validate(name)?;
+    let path = HookLibrary::file_path()?;
+    let mut library = HookLibrary::load_from_path(&path)?;
+    if library.hooks.contains_key(name) && !force {
+        bail!(t!("claude_code.hooks.already_exists", name = name));
+    }
+    // Settings identify a hook by event, matcher and command; `sync` cannot find the old
+    // entry once any of them changes, so it has to be uninstalled now.
+    let replaced = library
+        .hooks
+        .get(name)
+        .filter(|previous| previous.identity() != hook.identity())
+        .cloned();
+
+    let config = ClaudeCodeConfig::load().context(t!("claude_code.error.load_config_failed"))?;
+    let checker = SecurityChecker::from_config(&config)?;
+    if let Some(warning) = checker.pattern_warning(
+        rules::DANGEROUS_HOOK,
+        &path.display().to_string(),
+        format!("hooks.{name}.command = \"{}\"", hook.command),
+        &hook.command,
+    ) {
+        print_security_warnings(&[warning], "claude-code.toml");
+    }
+
+    if let Some(previous) = &replaced
+        && !uninstall_replaced(name, previous, args)?
+    {
+        return Ok(());
+    }
+
+    library.hooks.insert(name.to_string(), hook);
+    library.save_to_path(&path)?;
+    println!(
+        "{}",
+        t!(
+            "claude_code.hooks.saved",
+            name = name,
+            path = path.display()
+        )
+    );
+    Ok(())
+}
+
+/// Uninstall the definition `add --force` replaces from `args.scope` and warn about other
+/// scopes that still run it. Returns false when the library should be left unchanged.
+fn uninstall_replaced(
+    name: &str,
+    previous: &HookDefinition,
+    args: &HooksWriteArgs,
+) -> Result<bool> {
+    let mut changes = Vec::new();
+    for scope in [
+        SettingsScope::User,
+        SettingsScope::Project,
+        SettingsScope::Local,
+    ] {
+        if args.scope.contains(&scope) {
+            let file = SettingsFile::read(scope)?;
+            if let Some(after) = render_update(&file, &[previous], uninstall_hook)? {
+                changes.push(SettingsChange::new(&file, after));
+            }
+        } else if let Ok(file) = SettingsFile::read(scope)
+            && hook_state(&file.value, previous) != HookState::Missing
+        {
+            eprintln!(
+                "{}",
+                t!(
+                    "claude_code.hooks.replaced_still_installed",
+                    name = name,
+                    command = previous.command,
+                    path = file.path.display(),
+                    scope = scope.label()
+                )
+            );
+        }
+    }
+
+    if changes.is_empty() {
+        return Ok(!args.dry_run);
+    }
+    Ok(apply_changes(&changes, args.dry_run, args.yes)? && !args.dry_run)
+}
+
+fn run_remove(name: &str, args: &HooksWriteArgs) -> Result<()> {
+    let path = HookLibrary::file_path()?;
+    let mut library = HookLibrary::load_from_path(&path)?;
+    let Some(hook) = library.hooks.get(name).cloned() else {
+        bail!(t!("claude_code.hooks.unknown", name = name));
+    };
+
+    let mut changes = Vec::new();
+    for scope in &args.scope {
+        let file = SettingsFile::read(*scope)?;
+        if let Some(after) = render_update(&file, &[&hook], uninstall_hook)? {
+            changes.push(SettingsChange::new(&file, after));
+        }
+    }
+    if !changes.is_empty() && !apply_changes(&changes, args.dry_run, args.yes)? {
+        return Ok(());
+    }
+    if args.dry_run {
+        return Ok(());
+    }
+
+    library.hooks.remove(name);
+    library.save_to_path(&path)?;
+    println!(
+        "{}",
+        t!(
+            "claude_code.hooks.removed",
+            name = name,
+            path = path.display()
+        )
+    );
+    Ok(())
+}
+
+fn run_sync(args: &HooksWriteArgs, only: &[String]) -> Result<()> {
+    let library = HookLibrary::load()?;
+    let unknown: Vec<&str> = only
+        .iter()
+        .filter(|name| !library.hooks.contains_key(*name))
+        .map(String::as_str)
+        .collect();
+    if !unknown.is_empty() {
+        bail!(t!(
+            "claude_code.hooks.unknown_many",
+            names = unknown.join(", ")
+        ));
+    }
+    let hooks: Vec<&HookDefinition> = library
+        .hooks
+        .iter()
+        .filter(|(name, _)| only.is_empty() || only.contains(name))
+        .map(|(_, hook)| hook)
+        .collect();
+    if hooks.is_empty() {
+        println!("{}", t!("claude_code.hooks.empty"));
+        return Ok(());
+    }
+
+    let scopes = if args.scope.is_empty() {
+        vec![SettingsScope::Project]
+    } else {
+        args.scope.clone()
+    };
+    let mut changes = Vec::new();
+    for scope in scopes {
+        let file = SettingsFile::read(scope)?;
+        if let Some(after) = render_update(&file, &hooks, install_hook)? {
+            changes.push(SettingsChange::new(&file, after));
+        }
+    }
+
+    if changes.is_empty() {
+        println!("{}", t!("claude_code.settings.no_changes"));
+        return Ok(());
+    }
+    apply_changes(&changes, args.dry_run, args.yes)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fmt_hook() -> HookDefinition {
+        HookDefinition {
+            description: None,
+            event: "PostToolUse".to_string(),
+            matcher: Some("Edit|Write".to_string()),
+            command: "cargo fmt".to_string(),
+            timeout: Some(60),
+        }
+    }
+
+    #[test]
+    fn install_and_uninstall_keep_unrelated_hooks_and_comments() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("settings.json");
+        fs::write(
+            &path,
+            r#"{
+  // team settings
+  "model": "opus",
+  "hooks": {
+    "PostToolUse": [
+      { "matcher": "Edit|Write", "hooks": [{ "type": "command", "command": "./lint.sh" }] }
+    ]
+  }
+}
+"#,
+        )
+        .unwrap();
+        let hook = fmt_hook();
+
+        let file = SettingsFile::read_from(SettingsScope::Project, &path).unwrap();
+        assert_eq!(hook_state(&file.value, &hook), HookState::Missing);
+        let installed = render_update(&file, &[&hook], install_hook)
+            .unwrap()
+            .expect("changed");
+        assert!(installed.contains("// team settings"), "{installed}");
+        fs::write(&path, &installed).unwrap();
+
+        let file = SettingsFile::read_from(SettingsScope::Project, &path).unwrap();
+        assert_eq!(hook_state(&file.value, &hook), HookState::Installed);
+        assert_eq!(file.value["model"], "opus");
+        let group = &file.value["hooks"]["PostToolUse"][0]["hooks"];
+        assert_eq!(group.as_array().unwrap().len(), 2);
+        assert!(
+            render_update(&file, &[&hook], install_hook)
+                .unwrap()
+                .is_none()
+        );
+
+        let retimed = HookDefinition {
+            timeout: Some(5),
+            ..hook.clone()
+        };
+        assert_eq!(hook_state(&file.value, &retimed), HookState::Differs);
+
+        let removed = render_update(&file, &[&hook], uninstall_hook)
+            .unwrap()
+            .expect("changed");
+        fs::write(&path, &removed).unwrap();
+        let file = SettingsFile::read_from(SettingsScope::Project, &path).unwrap();
+        assert_eq!(hook_state(&file.value, &hook), HookState::Missing);
+        assert_eq!(
+            file.value["hooks"]["PostToolUse"][0]["hooks"][0]["command"],
+            "./lint.sh"
+        );
+    }
+
+    #[test]
+    fn library_rejects_unknown_events() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("hooks.toml");
+        fs::write(
+            &path,
+            "[hooks.x]\nevent = \"AfterEdit\"\ncommand = \"true\"\n",
+        )
+        .unwrap();
+        let err = HookLibrary::load_from_path(&path).unwrap_err();
+        assert!(format!("{err:#}").contains("AfterEdit"), "{err:#}");
+    }
+}
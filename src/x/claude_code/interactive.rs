@@ -139,7 +139,9 @@ pub fn prompt_import_config() -> Result<Option<(String, ConfigGroup)>> {
     }
 }
 
-pub fn display_config_list(config: &ClaudeCodeConfig) {
+/// List groups as written; `verbose` shows each group's merged environment with the group
+/// every inherited variable comes from.
+pub fn display_config_list(config: &ClaudeCodeConfig, verbose: bool) {
     if config.is_empty() {
         println!("{}", t!("claude_code.interactive.no_config_groups"));
         println!("{}", t!("claude_code.interactive.use_import_command"));
@@ -167,7 +169,18 @@ pub fn display_config_list(config: &ClaudeCodeConfig) {
     for name in config.group_names() {
         if let Some(group) = config.get_group(&name) {
             println!("  📝 {}", name);
-            let display_vars = get_display_vars(group);
+            if !group.extends.is_empty() {
+                println!("     extends = [{}]", group.extends.join(", "));
+            }
+            if !group.unset.is_empty() {
+                println!("     unset = [{}]", group.unset.join(", "));
+            }
+            if verbose {
+                display_effective_vars(config, &name);
+                println!();
+                continue;
+            }
+            let display_vars = get_display_vars(&group.vars);
             if display_vars.is_empty() {
                 println!("     ({})", t!("claude_code.interactive.no_env_vars"));
             } else {
@@ -179,3 +192,27 @@ pub fn display_config_list(config: &ClaudeCodeConfig) {
         }
     }
 }
+
+fn display_effective_vars(config: &ClaudeCodeConfig, name: &str) {
+    let effective = match config.effective_group(name) {
+        Ok(Some(effective)) => effective,
+        Ok(None) => return,
+        Err(e) => {
+            println!("     ⚠️ {e}");
+            return;
+        }
+    };
+    let display_vars = get_display_vars(&effective.vars);
+    if display_vars.is_empty() {
+        println!("     ({})", t!("claude_code.interactive.no_env_vars"));
+    }
+    for (key, value) in display_vars {
+        match effective.sources.get(&key).filter(|source| *source != name) {
+            Some(source) => println!(
+                "     {key} = {value}  ({})",
+                t!("claude_code.interactive.inherited_from", group = source)
+            ),
+            None => println!("     {key} = {value}"),
+        }
+    }
+}
@@ -1,6 +1,10 @@
+pub mod agents;
 pub mod command;
 pub mod config;
 pub mod env_injection;
+pub mod hooks;
 pub mod interactive;
+pub mod permissions;
 pub mod prompts;
 pub mod security;
+pub mod settings;
@@ -0,0 +1,422 @@
+//! `llman x cc permissions`: named permission profiles applied to Claude Code settings.
+//!
+//! Profiles live in `<config>/claude-code/permissions.toml`; until that file exists the
+//! built-in profiles from `templates/claude-code/permissions.toml` are used.
+
+use crate::config::resolve_config_dir;
+use crate::editor::{parse_editor_command, select_editor_raw};
+use crate::fs_utils::atomic_write_new_with_mode;
+use crate::path_utils::safe_parent_for_creation;
+use crate::x::claude_code::config::ClaudeCodeConfig;
+use crate::x::claude_code::security::{SecurityChecker, print_security_warnings, rules};
+use crate::x::claude_code::settings::{
+    SettingsChange, SettingsFile, SettingsScope, apply_changes, parse_settings, patch_member,
+};
+use anyhow::{Context, Result, anyhow, bail};
+use clap::{Args, Subcommand, ValueEnum};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const BUILTIN_PROFILES: &str = include_str!("../../../templates/claude-code/permissions.toml");
+
+/// Permission lists, in the order they are added to settings that lack them.
+const PERMISSION_KINDS: [&str; 3] = ["allow", "ask", "deny"];
+
+#[derive(Args, Debug, Clone)]
+#[command(about = "Apply named permission profiles to Claude Code settings")]
+pub struct ClaudeCodePermissionsArgs {
+    #[command(subcommand)]
+    pub command: ClaudeCodePermissionsCommand,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum ClaudeCodePermissionsCommand {
+    /// List permission profiles
+    List,
+    /// Edit the profiles file (<config>/claude-code/permissions.toml)
+    Edit,
+    /// Write a profile's rules into a settings file
+    Apply {
+        /// Profile name
+        profile: String,
+
+        /// Settings file to update
+        #[arg(long, value_enum, default_value = "project")]
+        scope: SettingsScope,
+
+        /// Merge into existing rules or replace them
+        #[arg(long, value_enum, default_value = "merge")]
+        mode: ApplyMode,
+
+        /// Write `allow` rules even when they match dangerous patterns
+        #[arg(long)]
+        allow_dangerous: bool,
+
+        /// Show the diff but do not write any files
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Assume "yes" for confirmations (required for non-interactive writes)
+        #[arg(long, short = 'y')]
+        yes: bool,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ApplyMode {
+    /// Append missing rules, keep existing ones
+    Merge,
+    /// Make each list exactly the profile's rules
+    Replace,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PermissionProfile {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allow: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ask: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deny: Vec<String>,
+}
+
+impl PermissionProfile {
+    fn rules(&self, kind: &str) -> &[String] {
+        match kind {
+            "allow" => &self.allow,
+            "ask" => &self.ask,
+            _ => &self.deny,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PermissionProfiles {
+    #[serde(default)]
+    pub profiles: BTreeMap<String, PermissionProfile>,
+}
+
+impl PermissionProfiles {
+    pub fn file_path() -> Result<PathBuf> {
+        Ok(resolve_config_dir(None)?
+            .join("claude-code")
+            .join("permissions.toml"))
+    }
+
+    pub fn load() -> Result<Self> {
+        Self::load_from_path(&Self::file_path()?)
+    }
+
+    pub fn load_from_path(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(toml::from_str(BUILTIN_PROFILES).expect("built-in profiles parse"));
+        }
+        let content = fs::read_to_string(path)
+            .with_context(|| t!("claude_code.permissions.read_failed", path = path.display()))?;
+        toml::from_str(&content).with_context(|| {
+            t!(
+                "claude_code.permissions.parse_failed",
+                path = path.display()
+            )
+        })
+    }
+
+    pub fn get(&self, name: &str) -> Result<&PermissionProfile> {
+        self.profiles.get(name).with_context(|| {
+            let known: Vec<&str> = self.profiles.keys().map(String::as_str).collect();
+            t!(
+                "claude_code.permissions.unknown_profile",
+                name = name,
+                available = known.join(", ")
+            )
+        })
+    }
+}
+
+/// `permissions.<kind>` of a settings document.
+fn current_rules(settings: &Value, kind: &str) -> Vec<String> {
+    settings
+        .get("permissions")
+        .and_then(|permissions| permissions.get(kind))
+        .and_then(Value::as_array)
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| item.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// The list `kind` should hold after applying `profile`.
+fn desired_rules(current: &[String], profile: &[String], mode: ApplyMode) -> Vec<String> {
+    let mut seen = HashSet::new();
+    let base = match mode {
+        ApplyMode::Merge => current,
+        ApplyMode::Replace => &[],
+    };
+    base.iter()
+        .chain(profile)
+        .filter(|rule| seen.insert(rule.as_str()))
+        .cloned()
+        .collect()
+}
+
+/// Settings text after applying `profile`, or `None` when nothing changes.
+fn render_apply(
+    file: &SettingsFile,
+    profile: &PermissionProfile,
+    mode: ApplyMode,
+) -> Result<Option<String>> {
+    let mut content = file.content();
+    let mut changed = false;
+    for kind in PERMISSION_KINDS {
+        let current = current_rules(&file.value, kind);
+        let desired = desired_rules(&current, profile.rules(kind), mode);
+        if desired == current {
+            continue;
+        }
+        changed = true;
+        let value = (!desired.is_empty()).then(|| serde_json::json!(desired));
+        content = patch_member(&content, &["permissions", kind], value.as_ref())?;
+    }
+    Ok(changed.then_some(content))
+}
+
+/// Rules that are both allowed and denied (or asked for).
+fn conflicting_rules(settings: &Value) -> Vec<String> {
+    let allow = current_rules(settings, "allow");
+    let mut restricted = current_rules(settings, "deny");
+    restricted.extend(current_rules(settings, "ask"));
+    allow
+        .into_iter()
+        .filter(|rule| restricted.contains(rule))
+        .collect()
+}
+
+pub fn run(args: &ClaudeCodePermissionsArgs) -> Result<()> {
+    match &args.command {
+        ClaudeCodePermissionsCommand::List => run_list(),
+        ClaudeCodePermissionsCommand::Edit => run_edit(),
+        ClaudeCodePermissionsCommand::Apply {
+            profile,
+            scope,
+            mode,
+            allow_dangerous,
+            dry_run,
+            yes,
+        } => run_apply(profile, *scope, *mode, *allow_dangerous, *dry_run, *yes),
+    }
+}
+
+fn run_list() -> Result<()> {
+    let path = PermissionProfiles::file_path()?;
+    let profiles = PermissionProfiles::load_from_path(&path)?;
+    if path.exists() {
+        println!(
+            "{}",
+            t!("claude_code.permissions.list_header", path = path.display())
+        );
+    } else {
+        println!("{}", t!("claude_code.permissions.list_header_builtin"));
+    }
+    for (name, profile) in &profiles.profiles {
+        println!(
+            "{}",
+            t!(
+                "claude_code.permissions.list_item",
+                name = name,
+                allow = profile.allow.len(),
+                ask = profile.ask.len(),
+                deny = profile.deny.len()
+            )
+        );
+        if let Some(description) = &profile.description {
+            println!("    {description}");
+        }
+    }
+    Ok(())
+}
+
+fn run_edit() -> Result<()> {
+    let path = PermissionProfiles::file_path()?;
+    if let Some(parent) = safe_parent_for_creation(&path) {
+        fs::create_dir_all(parent).with_context(|| {
+            t!(
+                "claude_code.settings.create_dir_failed",
+                path = parent.display()
+            )
+        })?;
+    }
+    if atomic_write_new_with_mode(&path, BUILTIN_PROFILES.as_bytes(), Some(0o600))? {
+        println!(
+            "{}",
+            t!("claude_code.permissions.created", path = path.display())
+        );
+    }
+
+    let editor_raw = select_editor_raw();
+    let (editor_cmd, editor_args) = parse_editor_command(&editor_raw).map_err(|e| {
+        anyhow!(t!(
+            "claude_code.error.invalid_editor_command",
+            editor = editor_raw,
+            error = e
+        ))
+    })?;
+    let status = Command::new(&editor_cmd)
+        .args(editor_args)
+        .arg(&path)
+        .status()
+        .with_context(|| t!("claude_code.error.open_editor_failed", editor = editor_raw))?;
+    if !status.success() {
+        bail!(t!("claude_code.error.editor_exit_status", status = status));
+    }
+
+    PermissionProfiles::load_from_path(&path)?;
+    Ok(())
+}
+
+fn run_apply(
+    name: &str,
+    scope: SettingsScope,
+    mode: ApplyMode,
+    allow_dangerous: bool,
+    dry_run: bool,
+    yes: bool,
+) -> Result<()> {
+    let profiles = PermissionProfiles::load()?;
+    let profile = profiles.get(name)?;
+
+    let config = ClaudeCodeConfig::load().context(t!("claude_code.error.load_config_failed"))?;
+    let checker = SecurityChecker::from_config(&config)?;
+    let warnings: Vec<_> = profile
+        .allow
+        .iter()
+        .enumerate()
+        .filter_map(|(index, rule)| {
+            checker.pattern_warning(
+                rules::DANGEROUS_PERMISSION,
+                &format!("profile {name}"),
+                format!("allow[{index}] = \"{rule}\""),
+                rule,
+            )
+        })
+        .collect();
+    if !warnings.is_empty() {
+        print_security_warnings(&warnings, "claude-code.toml");
+        if !allow_dangerous {
+            bail!(t!(
+                "claude_code.permissions.dangerous_rules",
+                name = name,
+                count = warnings.len()
+            ));
+        }
+    }
+
+    let file = SettingsFile::read(scope)?;
+    let Some(after) = render_apply(&file, profile, mode)? else {
+        println!("{}", t!("claude_code.settings.no_changes"));
+        return Ok(());
+    };
+
+    let applied = parse_settings(&after).with_context(|| {
+        t!(
+            "claude_code.permissions.result_invalid",
+            path = file.path.display()
+        )
+    })?;
+    for rule in conflicting_rules(&applied) {
+        eprintln!(
+            "{}",
+            t!("claude_code.permissions.conflicting_rule", rule = rule)
+        );
+    }
+
+    apply_changes(&[SettingsChange::new(&file, after)], dry_run, yes)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_profiles_parse_and_pass_default_security_patterns() {
+        // Prefixes that run an arbitrary command; allowing `<runner>:*` allows everything.
+        const RUNNERS: [&str; 12] = [
+            "uv run",
+            "poetry run",
+            "pipx run",
+            "npx",
+            "npm exec",
+            "pnpm dlx",
+            "cargo run",
+            "python",
+            "python -m",
+            "sh",
+            "bash",
+            "env",
+        ];
+        let profiles: PermissionProfiles = toml::from_str(BUILTIN_PROFILES).unwrap();
+        let checker = SecurityChecker::with_patterns(None);
+        for name in ["readonly", "rust-dev", "python-dev"] {
+            let profile = profiles.get(name).unwrap();
+            for rule in &profile.allow {
+                assert!(
+                    checker
+                        .pattern_warning(rules::DANGEROUS_PERMISSION, "p", String::new(), rule)
+                        .is_none(),
+                    "{name}: {rule}"
+                );
+                let prefix = rule
+                    .strip_prefix("Bash(")
+                    .and_then(|rest| rest.strip_suffix(":*)"));
+                assert!(
+                    !prefix.is_some_and(|prefix| RUNNERS.contains(&prefix.trim())),
+                    "{name}: {rule} allows any command"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn apply_merges_or_replaces_and_keeps_comments() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("settings.json");
+        fs::write(
+            &path,
+            "{\n  // team\n  \"permissions\": {\n    \"allow\": [\"WebSearch\", \"Bash(cargo test:*)\"]\n  }\n}\n",
+        )
+        .unwrap();
+        let file = SettingsFile::read_from(SettingsScope::Project, &path).unwrap();
+        let profile = PermissionProfile {
+            allow: vec!["Bash(cargo test:*)".into(), "Bash(cargo check:*)".into()],
+            deny: vec!["Read(./.env)".into()],
+            ..Default::default()
+        };
+
+        let merged = render_apply(&file, &profile, ApplyMode::Merge)
+            .unwrap()
+            .expect("changed");
+        assert!(merged.contains("// team"), "{merged}");
+        let merged: Value = llm_json::loads(&merged, &Default::default()).unwrap();
+        assert_eq!(
+            current_rules(&merged, "allow"),
+            vec!["WebSearch", "Bash(cargo test:*)", "Bash(cargo check:*)"]
+        );
+        assert_eq!(current_rules(&merged, "deny"), vec!["Read(./.env)"]);
+
+        let replaced = render_apply(&file, &profile, ApplyMode::Replace)
+            .unwrap()
+            .expect("changed");
+        let replaced: Value = llm_json::loads(&replaced, &Default::default()).unwrap();
+        assert_eq!(current_rules(&replaced, "allow"), profile.allow);
+        assert!(conflicting_rules(&replaced).is_empty());
+    }
+}
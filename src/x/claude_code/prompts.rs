@@ -1,6 +1,8 @@
 use crate::config::{CLAUDE_CODE_APP, Config};
-use crate::prompts::managed_file::write_llman_managed_block;
+use crate::prompts::command::{AppStatusArgs, CLAUDE_MEMORY_FILE, PromptApp};
+use crate::prompts::managed_file::write_generated_block;
 use crate::prompts::paths::{claude_home_dir, cwd, project_root};
+use crate::prompts::render::{GLOBAL_SCOPE, PROJECT_SCOPE, PromptRenderer};
 use crate::prompts::store as prompt_store;
 use crate::skills::cli::interactive::is_interactive;
 use anyhow::{Result, anyhow, bail};
@@ -9,8 +11,6 @@ use inquire::{Confirm, MultiSelect};
 use std::fs;
 use std::path::{Path, PathBuf};
 
-const CLAUDE_MEMORY_FILE: &str = "CLAUDE.md";
-
 #[derive(Args, Debug, Clone)]
 #[command(about = "Manage Claude Code prompt templates and inject CLAUDE.md")]
 #[command(subcommand_required = false)]
@@ -34,6 +34,10 @@ pub enum ClaudeCodePromptsCommand {
         #[arg(long)]
         force: bool,
     },
+    /// Show whether generated targets still match their templates
+    Status(AppStatusArgs),
+    /// Like `status`, but exit non-zero when any target drifted (for CI)
+    Check(AppStatusArgs),
     /// List available templates
     List,
     /// Create or update a template
@@ -59,6 +63,15 @@ pub enum PromptScopeArg {
     Project,
 }
 
+impl PromptScopeArg {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PromptScopeArg::Global => GLOBAL_SCOPE,
+            PromptScopeArg::Project => PROJECT_SCOPE,
+        }
+    }
+}
+
 #[derive(Args, Debug, Clone)]
 #[group(required = true, multiple = false)]
 pub struct TemplateContentSource {
@@ -75,13 +88,17 @@ pub fn run(args: &ClaudeCodePromptsArgs) -> Result<()> {
             if interactive {
                 return run_wizard();
             }
-            bail!("In non-interactive mode, a subcommand is required (gen/list/upsert/rm).");
+            bail!(
+                "In non-interactive mode, a subcommand is required (gen/status/check/list/upsert/rm)."
+            );
         }
         Some(ClaudeCodePromptsCommand::Gen {
             template,
             scope,
             force,
         }) => run_gen(template, scope, *force, interactive),
+        Some(ClaudeCodePromptsCommand::Status(status)) => status.run(PromptApp::ClaudeCode, false),
+        Some(ClaudeCodePromptsCommand::Check(status)) => status.run(PromptApp::ClaudeCode, true),
         Some(ClaudeCodePromptsCommand::List) => run_list(),
         Some(ClaudeCodePromptsCommand::Upsert { name, content }) => {
             run_upsert(name, content.content.as_deref(), content.file.as_deref())
@@ -155,8 +172,11 @@ fn run_gen(
     interactive: bool,
 ) -> Result<()> {
     let config = Config::new()?;
-    let content = prompt_store::read_template(&config, CLAUDE_CODE_APP, template)?;
+    // Fail early on unknown templates, before any scope is resolved.
+    prompt_store::read_template(&config, CLAUDE_CODE_APP, template)?;
+    let templates = [template.to_string()];
     let cwd = cwd()?;
+    let renderer = PromptRenderer::for_dir(&config, &cwd)?;
 
     let mut first_error: Option<anyhow::Error> = None;
 
@@ -175,7 +195,23 @@ fn run_gen(
             }
         };
 
-        match write_llman_managed_block(&path, &content, force, interactive) {
+        let body = match prompt_store::build_stamped_prompts_body(
+            &config,
+            CLAUDE_CODE_APP,
+            &templates,
+            &renderer,
+            scope.as_str(),
+        ) {
+            Ok(body) => body,
+            Err(e) => {
+                if first_error.is_none() {
+                    first_error = Some(e);
+                }
+                continue;
+            }
+        };
+
+        match write_generated_block(&path, &body, force, interactive) {
             Ok(true) => println!("{}", t!("messages.rule_generated", path = path.display())),
             Ok(false) => {}
             Err(e) => {
@@ -241,16 +277,22 @@ fn run_wizard() -> Result<()> {
         return Ok(());
     }
 
-    let body = prompt_store::build_llman_prompts_body(&config, CLAUDE_CODE_APP, &picked_templates)?;
-
     let cwd = cwd()?;
+    let renderer = PromptRenderer::for_dir(&config, &cwd)?;
     let interactive = true;
     for scope in scopes {
         let path = match claude_memory_path(&cwd, scope, false, interactive)? {
             Some(path) => path,
             None => continue,
         };
-        if write_llman_managed_block(&path, &body, false, interactive)? {
+        let body = prompt_store::build_stamped_prompts_body(
+            &config,
+            CLAUDE_CODE_APP,
+            &picked_templates,
+            &renderer,
+            scope.as_str(),
+        )?;
+        if write_generated_block(&path, &body, false, interactive)? {
             println!("{}", t!("messages.rule_generated", path = path.display()));
         }
     }
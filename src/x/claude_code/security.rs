@@ -3,13 +3,17 @@ use anyhow::{Context, Result};
 use llm_json::{RepairOptions, loads};
 use regex::Regex;
 use rust_i18n::t;
-use serde_json::Value;
+use serde::Serialize;
+use serde_json::{Value, json};
+use std::collections::BTreeSet;
 use std::fs;
 use std::path::{Path, PathBuf};
 
 /// Represents a security warning for dangerous Claude Code settings
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct SecurityWarning {
+    /// Stable rule identifier, used as the SARIF `ruleId`
+    pub rule_id: &'static str,
     pub config_path: String,
     pub config_item: String,
     pub reason: String,
@@ -20,7 +24,8 @@ pub struct SecurityWarning {
 }
 
 /// Severity levels for security warnings
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum SecurityWarningSeverity {
     Critical, // Can cause system damage or data loss
     High,     // Can compromise security or privacy
@@ -59,13 +64,93 @@ impl SecurityWarningSeverity {
             SecurityWarningSeverity::Low => t!("claude_code.security.severity.low").to_string(),
         }
     }
+
+    /// SARIF result level for this severity
+    pub fn sarif_level(&self) -> &'static str {
+        match self {
+            SecurityWarningSeverity::Critical | SecurityWarningSeverity::High => "error",
+            SecurityWarningSeverity::Medium => "warning",
+            SecurityWarningSeverity::Low => "note",
+        }
+    }
+}
+
+/// Rule identifiers reported by the settings audit
+pub mod rules {
+    pub const DANGEROUS_PERMISSION: &str = "dangerous-permission";
+    pub const DANGEROUS_HOOK: &str = "dangerous-hook";
+    pub const UNVETTED_HOOK: &str = "unvetted-hook";
+    pub const DANGEROUS_COMMAND_SETTING: &str = "dangerous-command-setting";
+    pub const BYPASS_PERMISSIONS_MODE: &str = "bypass-permissions-mode";
+    pub const ALL_PROJECT_MCP_SERVERS: &str = "all-project-mcp-servers";
+    pub const DANGEROUS_MCP_SERVER: &str = "dangerous-mcp-server";
+    pub const MCP_INLINE_SHELL: &str = "mcp-inline-shell";
+    pub const DANGEROUS_ENV: &str = "dangerous-env";
+    pub const TLS_VERIFICATION_DISABLED: &str = "tls-verification-disabled";
+
+    /// All rules, in the order they are listed in SARIF output
+    pub const ALL: &[&str] = &[
+        DANGEROUS_PERMISSION,
+        DANGEROUS_HOOK,
+        UNVETTED_HOOK,
+        DANGEROUS_COMMAND_SETTING,
+        BYPASS_PERMISSIONS_MODE,
+        ALL_PROJECT_MCP_SERVERS,
+        DANGEROUS_MCP_SERVER,
+        MCP_INLINE_SHELL,
+        DANGEROUS_ENV,
+        TLS_VERIFICATION_DISABLED,
+    ];
+
+    /// Short human-readable rule title
+    pub fn title(rule_id: &str) -> String {
+        use rust_i18n::t;
+        match rule_id {
+            DANGEROUS_PERMISSION => t!("claude_code.security.rule_title.dangerous_permission"),
+            DANGEROUS_HOOK => t!("claude_code.security.rule_title.dangerous_hook"),
+            UNVETTED_HOOK => t!("claude_code.security.rule_title.unvetted_hook"),
+            DANGEROUS_COMMAND_SETTING => {
+                t!("claude_code.security.rule_title.dangerous_command_setting")
+            }
+            BYPASS_PERMISSIONS_MODE => {
+                t!("claude_code.security.rule_title.bypass_permissions_mode")
+            }
+            ALL_PROJECT_MCP_SERVERS => {
+                t!("claude_code.security.rule_title.all_project_mcp_servers")
+            }
+            DANGEROUS_MCP_SERVER => t!("claude_code.security.rule_title.dangerous_mcp_server"),
+            MCP_INLINE_SHELL => t!("claude_code.security.rule_title.mcp_inline_shell"),
+            DANGEROUS_ENV => t!("claude_code.security.rule_title.dangerous_env"),
+            TLS_VERIFICATION_DISABLED => {
+                t!("claude_code.security.rule_title.tls_verification_disabled")
+            }
+            _ => t!("claude_code.security.rule_title.unknown"),
+        }
+        .to_string()
+    }
 }
 
+/// Shell interpreters whose `-c` argument is an inline script
+const SHELL_INTERPRETERS: &[&str] = &[
+    "sh",
+    "bash",
+    "zsh",
+    "fish",
+    "dash",
+    "cmd",
+    "powershell",
+    "pwsh",
+];
+
 /// Security checker for Claude Code settings
 pub struct SecurityChecker {
     dangerous_patterns: Vec<String>,
     settings_files: Vec<String>,
+    /// Files checked before launching `claude`; defaults to the settings files alone
+    run_settings_files: Vec<String>,
     enabled: bool,
+    /// Hook commands from the llman hook library; when set, other hook commands are flagged
+    vetted_hooks: Option<BTreeSet<String>>,
 }
 
 impl SecurityChecker {
@@ -83,28 +168,76 @@ impl SecurityChecker {
             .filter(|pattern| !pattern.is_empty())
             .collect();
 
-        let settings_files = security_config
-            .and_then(|s| s.claude_settings_files.clone())
+        let configured_files = security_config.and_then(|s| s.claude_settings_files.clone());
+        let settings_files = configured_files
+            .clone()
             .unwrap_or_else(Self::default_settings_files);
+        let run_settings_files = configured_files.unwrap_or_else(Self::default_run_settings_files);
 
         Ok(Self {
             dangerous_patterns,
             settings_files,
+            run_settings_files,
             enabled,
+            vetted_hooks: None,
         })
     }
 
+    /// Create a checker that only matches text against dangerous patterns
+    ///
+    /// Used by other agent integrations that reuse the pattern rules with their own file formats.
+    pub fn with_patterns(patterns: Option<Vec<String>>) -> Self {
+        Self {
+            dangerous_patterns: patterns
+                .unwrap_or_else(Self::default_dangerous_patterns)
+                .into_iter()
+                .map(|pattern| pattern.trim().to_lowercase())
+                .filter(|pattern| !pattern.is_empty())
+                .collect(),
+            settings_files: vec![],
+            run_settings_files: vec![],
+            enabled: true,
+            vetted_hooks: None,
+        }
+    }
+
+    /// Flag hook commands that are not in `commands` (the llman hook library)
+    pub fn with_vetted_hooks(mut self, commands: BTreeSet<String>) -> Self {
+        self.vetted_hooks = Some(commands);
+        self
+    }
+
     /// Check Claude Code settings for dangerous permissions
+    ///
+    /// This is the gate in front of `x cc run`: it only applies the permission patterns, so the
+    /// broader `audit` rules never block a launch.
     pub fn check_claude_settings(&self) -> Result<Vec<SecurityWarning>> {
         if !self.enabled {
             return Ok(vec![]);
         }
 
+        Ok(self.scan_files(
+            &self.run_settings_files,
+            Self::extract_dangerous_permissions,
+        ))
+    }
+
+    /// Audit every configured settings file, regardless of the `enabled` switch
+    pub fn audit(&self) -> Result<Vec<SecurityWarning>> {
+        Ok(self.scan_files(&self.settings_files, Self::audit_settings))
+    }
+
+    /// Run `rules` against each of `files`, reporting unreadable files without failing
+    fn scan_files(
+        &self,
+        files: &[String],
+        rules: fn(&Self, &Value, &str) -> Vec<SecurityWarning>,
+    ) -> Vec<SecurityWarning> {
         let mut all_warnings = Vec::new();
 
-        for file_pattern in &self.settings_files {
+        for file_pattern in files {
             if let Some(path) = self.resolve_settings_file_path(file_pattern) {
-                match self.check_settings_file(&path) {
+                match self.check_settings_file(&path, rules) {
                     Ok(mut warnings) => all_warnings.append(&mut warnings),
                     Err(e) => {
                         // Log error but continue checking other files
@@ -121,11 +254,15 @@ impl SecurityChecker {
             }
         }
 
-        Ok(all_warnings)
+        all_warnings
     }
 
-    /// Check a specific settings file for dangerous permissions
-    fn check_settings_file(&self, path: &Path) -> Result<Vec<SecurityWarning>> {
+    /// Check a specific settings file with the given rules
+    fn check_settings_file(
+        &self,
+        path: &Path,
+        rules: fn(&Self, &Value, &str) -> Vec<SecurityWarning>,
+    ) -> Result<Vec<SecurityWarning>> {
         if !path.exists() {
             return Ok(vec![]);
         }
@@ -154,7 +291,18 @@ impl SecurityChecker {
             }
         };
 
-        Ok(self.extract_dangerous_permissions(&settings, &path.display().to_string()))
+        Ok(rules(self, &settings, &path.display().to_string()))
+    }
+
+    /// Run every audit rule against one parsed settings (or `.mcp.json`) document
+    fn audit_settings(&self, settings: &Value, config_path: &str) -> Vec<SecurityWarning> {
+        let mut warnings = self.extract_dangerous_permissions(settings, config_path);
+        warnings.extend(self.extract_permission_mode(settings, config_path));
+        warnings.extend(self.extract_dangerous_hooks(settings, config_path));
+        warnings.extend(self.extract_dangerous_command_settings(settings, config_path));
+        warnings.extend(self.extract_mcp_risks(settings, config_path));
+        warnings.extend(self.extract_dangerous_env(settings, config_path));
+        warnings
     }
 
     /// Extract dangerous permissions from parsed settings
@@ -175,6 +323,7 @@ impl SecurityChecker {
                         self.get_dangerous_pattern_info(permission)
                 {
                     warnings.push(SecurityWarning {
+                        rule_id: rules::DANGEROUS_PERMISSION,
                         config_path: config_path.to_string(),
                         config_item: format!("permissions.allow[{}] = \"{}\"", index, permission),
                         reason: t!(
@@ -194,6 +343,253 @@ impl SecurityChecker {
         warnings
     }
 
+    /// Flag `permissions.defaultMode = "bypassPermissions"`
+    fn extract_permission_mode(&self, settings: &Value, config_path: &str) -> Vec<SecurityWarning> {
+        let mode = settings
+            .get("permissions")
+            .and_then(|permissions| permissions.get("defaultMode"))
+            .and_then(Value::as_str);
+        match mode {
+            Some(mode @ "bypassPermissions") => vec![SecurityWarning {
+                rule_id: rules::BYPASS_PERMISSIONS_MODE,
+                config_path: config_path.to_string(),
+                config_item: format!("permissions.defaultMode = \"{}\"", mode),
+                reason: t!("claude_code.security.rule.bypass_mode.reason").to_string(),
+                severity: SecurityWarningSeverity::Critical,
+                matched_pattern: mode.to_string(),
+                description: t!("claude_code.security.rule.bypass_mode.description").to_string(),
+                recommendation: t!("claude_code.security.rule.bypass_mode.recommendation")
+                    .to_string(),
+            }],
+            _ => vec![],
+        }
+    }
+
+    /// Check hook commands (`hooks.<Event>[i].hooks[j].command`) against dangerous patterns
+    fn extract_dangerous_hooks(&self, settings: &Value, config_path: &str) -> Vec<SecurityWarning> {
+        let mut warnings = Vec::new();
+        let Some(events) = settings.get("hooks").and_then(Value::as_object) else {
+            return warnings;
+        };
+
+        for (event, matchers) in events {
+            let Some(matchers) = matchers.as_array() else {
+                continue;
+            };
+            for (matcher_index, matcher) in matchers.iter().enumerate() {
+                let Some(hooks) = matcher.get("hooks").and_then(Value::as_array) else {
+                    continue;
+                };
+                for (hook_index, hook) in hooks.iter().enumerate() {
+                    let Some(command) = hook.get("command").and_then(Value::as_str) else {
+                        continue;
+                    };
+                    let item = format!(
+                        "hooks.{}[{}].hooks[{}].command = \"{}\"",
+                        event, matcher_index, hook_index, command
+                    );
+                    if let Some(warning) = self.pattern_warning(
+                        rules::DANGEROUS_HOOK,
+                        config_path,
+                        item.clone(),
+                        command,
+                    ) {
+                        warnings.push(warning);
+                    } else if let Some(vetted) = &self.vetted_hooks
+                        && !vetted.contains(command.trim())
+                    {
+                        warnings.push(SecurityWarning {
+                            rule_id: rules::UNVETTED_HOOK,
+                            config_path: config_path.to_string(),
+                            config_item: item,
+                            reason: t!("claude_code.security.rule.unvetted_hook.reason")
+                                .to_string(),
+                            severity: SecurityWarningSeverity::Medium,
+                            matched_pattern: command.to_string(),
+                            description: t!("claude_code.security.rule.unvetted_hook.description")
+                                .to_string(),
+                            recommendation: t!(
+                                "claude_code.security.rule.unvetted_hook.recommendation"
+                            )
+                            .to_string(),
+                        });
+                    }
+                }
+            }
+        }
+
+        warnings
+    }
+
+    /// Check settings that run shell commands (`apiKeyHelper`, `statusLine.command`)
+    fn extract_dangerous_command_settings(
+        &self,
+        settings: &Value,
+        config_path: &str,
+    ) -> Vec<SecurityWarning> {
+        let candidates = [
+            ("apiKeyHelper", settings.get("apiKeyHelper")),
+            (
+                "statusLine.command",
+                settings.get("statusLine").and_then(|s| s.get("command")),
+            ),
+        ];
+
+        candidates
+            .into_iter()
+            .filter_map(|(key, value)| {
+                let command = value?.as_str()?;
+                self.pattern_warning(
+                    rules::DANGEROUS_COMMAND_SETTING,
+                    config_path,
+                    format!("{} = \"{}\"", key, command),
+                    command,
+                )
+            })
+            .collect()
+    }
+
+    /// Flag `enableAllProjectMcpServers` and risky `mcpServers` entries
+    fn extract_mcp_risks(&self, settings: &Value, config_path: &str) -> Vec<SecurityWarning> {
+        let mut warnings = Vec::new();
+
+        if settings
+            .get("enableAllProjectMcpServers")
+            .and_then(Value::as_bool)
+            .unwrap_or(false)
+        {
+            warnings.push(SecurityWarning {
+                rule_id: rules::ALL_PROJECT_MCP_SERVERS,
+                config_path: config_path.to_string(),
+                config_item: "enableAllProjectMcpServers = true".to_string(),
+                reason: t!("claude_code.security.rule.all_project_mcp.reason").to_string(),
+                severity: SecurityWarningSeverity::High,
+                matched_pattern: "enableAllProjectMcpServers".to_string(),
+                description: t!("claude_code.security.rule.all_project_mcp.description")
+                    .to_string(),
+                recommendation: t!("claude_code.security.rule.all_project_mcp.recommendation")
+                    .to_string(),
+            });
+        }
+
+        if let Some(servers) = settings.get("mcpServers").and_then(Value::as_object) {
+            warnings.extend(self.mcp_server_warnings(servers, "mcpServers", config_path));
+        }
+
+        // `~/.claude.json` keeps local-scope servers per project directory.
+        if let Some(projects) = settings.get("projects").and_then(Value::as_object) {
+            for (project, entry) in projects {
+                if let Some(servers) = entry.get("mcpServers").and_then(Value::as_object) {
+                    let prefix = format!("projects.{}.mcpServers", project);
+                    warnings.extend(self.mcp_server_warnings(servers, &prefix, config_path));
+                }
+            }
+        }
+
+        warnings
+    }
+
+    /// Check the `command`/`args` of each server in one `mcpServers` table
+    fn mcp_server_warnings(
+        &self,
+        servers: &serde_json::Map<String, Value>,
+        prefix: &str,
+        config_path: &str,
+    ) -> Vec<SecurityWarning> {
+        let mut warnings = Vec::new();
+        for (name, server) in servers {
+            let Some(command) = server.get("command").and_then(Value::as_str) else {
+                continue;
+            };
+            let args: Vec<&str> = server
+                .get("args")
+                .and_then(Value::as_array)
+                .map(|args| args.iter().filter_map(Value::as_str).collect())
+                .unwrap_or_default();
+            let command_line = std::iter::once(command)
+                .chain(args.iter().copied())
+                .collect::<Vec<_>>()
+                .join(" ");
+            let item = format!("{}.{}.command = \"{}\"", prefix, name, command_line);
+
+            if let Some(warning) = self.pattern_warning(
+                rules::DANGEROUS_MCP_SERVER,
+                config_path,
+                item.clone(),
+                &command_line,
+            ) {
+                warnings.push(warning);
+            } else if let Some(warning) =
+                inline_shell_warning(rules::MCP_INLINE_SHELL, config_path, &item, command, &args)
+            {
+                warnings.push(warning);
+            }
+        }
+
+        warnings
+    }
+
+    /// Check `env` overrides for disabled TLS verification and dangerous values
+    fn extract_dangerous_env(&self, settings: &Value, config_path: &str) -> Vec<SecurityWarning> {
+        let mut warnings = Vec::new();
+        let Some(env) = settings.get("env").and_then(Value::as_object) else {
+            return warnings;
+        };
+
+        for (key, value) in env {
+            let Some(value) = value.as_str() else {
+                continue;
+            };
+            let item = format!("env.{} = \"{}\"", key, value);
+
+            if key == "NODE_TLS_REJECT_UNAUTHORIZED" && value.trim() == "0" {
+                warnings.push(SecurityWarning {
+                    rule_id: rules::TLS_VERIFICATION_DISABLED,
+                    config_path: config_path.to_string(),
+                    config_item: item,
+                    reason: t!("claude_code.security.rule.tls_disabled.reason").to_string(),
+                    severity: SecurityWarningSeverity::High,
+                    matched_pattern: format!("{}=0", key),
+                    description: t!("claude_code.security.rule.tls_disabled.description")
+                        .to_string(),
+                    recommendation: t!("claude_code.security.rule.tls_disabled.recommendation")
+                        .to_string(),
+                });
+                continue;
+            }
+
+            warnings.extend(self.pattern_warning(rules::DANGEROUS_ENV, config_path, item, value));
+        }
+
+        warnings
+    }
+
+    /// Build a warning when `text` matches one of the dangerous patterns
+    pub fn pattern_warning(
+        &self,
+        rule_id: &'static str,
+        config_path: &str,
+        config_item: String,
+        text: &str,
+    ) -> Option<SecurityWarning> {
+        let (matched_pattern, (severity, description, recommendation)) =
+            self.get_dangerous_pattern_info(text)?;
+        Some(SecurityWarning {
+            rule_id,
+            config_path: config_path.to_string(),
+            config_item,
+            reason: t!(
+                "claude_code.security.reason_pattern_detected",
+                pattern = text
+            )
+            .to_string(),
+            severity,
+            matched_pattern,
+            description,
+            recommendation,
+        })
+    }
+
     /// Get detailed information about a dangerous pattern
     fn get_dangerous_pattern_info(
         &self,
@@ -399,17 +795,7 @@ impl SecurityChecker {
 
     /// Resolve settings file path from pattern (handles ~ expansion)
     fn resolve_settings_file_path(&self, file_pattern: &str) -> Option<PathBuf> {
-        let path = if let Some(stripped) = file_pattern.strip_prefix("~/") {
-            if let Some(home_dir) = crate::config::try_home_dir() {
-                home_dir.join(stripped)
-            } else {
-                PathBuf::from(file_pattern)
-            }
-        } else {
-            PathBuf::from(file_pattern)
-        };
-
-        Some(path)
+        Some(expand_home(file_pattern))
     }
 
     /// Default dangerous command patterns
@@ -444,16 +830,203 @@ impl SecurityChecker {
         ]
     }
 
+    /// Settings files checked before `x cc run` when none are configured
+    fn default_run_settings_files() -> Vec<String> {
+        vec![
+            ".claude/settings.local.json".to_string(),
+            ".claude/settings.json".to_string(),
+            "~/.claude/settings.json".to_string(),
+        ]
+    }
+
     /// Default Claude Code settings files to check
-    fn default_settings_files() -> Vec<String> {
+    pub fn default_settings_files() -> Vec<String> {
         vec![
             ".claude/settings.local.json".to_string(),
             ".claude/settings.json".to_string(),
+            ".mcp.json".to_string(),
             "~/.claude/settings.json".to_string(),
+            "~/.claude.json".to_string(),
+            MANAGED_SETTINGS_PATH.to_string(),
         ]
     }
 }
 
+/// Enterprise managed policy file read by Claude Code
+#[cfg(target_os = "macos")]
+pub const MANAGED_SETTINGS_PATH: &str =
+    "/Library/Application Support/ClaudeCode/managed-settings.json";
+#[cfg(windows)]
+pub const MANAGED_SETTINGS_PATH: &str = r"C:\ProgramData\ClaudeCode\managed-settings.json";
+#[cfg(not(any(target_os = "macos", windows)))]
+pub const MANAGED_SETTINGS_PATH: &str = "/etc/claude-code/managed-settings.json";
+
+/// Expand a leading `~/` to the home directory
+pub fn expand_home(file_pattern: &str) -> PathBuf {
+    if let Some(stripped) = file_pattern.strip_prefix("~/")
+        && let Some(home_dir) = crate::config::try_home_dir()
+    {
+        return home_dir.join(stripped);
+    }
+    PathBuf::from(file_pattern)
+}
+
+/// Print security warnings to stderr
+///
+/// `rules_file` names the llman config file holding the security rules (e.g. `claude-code.toml`).
+pub fn print_security_warnings(warnings: &[SecurityWarning], rules_file: &str) {
+    if warnings.is_empty() {
+        return;
+    }
+
+    eprintln!(
+        "\n🔒 {}",
+        t!(
+            "claude_code.security.warning_header",
+            count = warnings.len()
+        )
+    );
+    eprintln!(
+        "{}",
+        t!("claude_code.security.warning_separator_char").repeat(60)
+    );
+
+    for warning in warnings {
+        eprintln!(
+            "\n{} [{}] {}",
+            warning.severity.display_symbol(),
+            warning.severity.display_name_localized(),
+            t!("claude_code.security.warning_item_title")
+        );
+        eprintln!(
+            "  📍 {} {}",
+            t!("claude_code.security.label_location"),
+            warning.config_path
+        );
+        eprintln!(
+            "  ⚙️  {} {}",
+            t!("claude_code.security.label_setting"),
+            warning.config_item
+        );
+        eprintln!(
+            "  🎯 {} {}",
+            t!("claude_code.security.label_pattern"),
+            warning.matched_pattern
+        );
+        eprintln!(
+            "  📝 {} {}",
+            t!("claude_code.security.label_description"),
+            warning.description
+        );
+        eprintln!(
+            "  💡 {} {}",
+            t!("claude_code.security.label_recommendation"),
+            warning.recommendation
+        );
+    }
+
+    eprintln!("\n⚠️ {}", t!("claude_code.security.footer_line1"));
+    eprintln!(
+        "  {}",
+        t!("claude_code.security.footer_line2", file = rules_file)
+    );
+    eprintln!();
+}
+
+/// Flag a command that hands an inline script to a shell interpreter (`bash -c "..."`)
+pub fn inline_shell_warning(
+    rule_id: &'static str,
+    config_path: &str,
+    config_item: &str,
+    command: &str,
+    args: &[&str],
+) -> Option<SecurityWarning> {
+    let program = Path::new(command)
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or(command)
+        .to_lowercase();
+    if !SHELL_INTERPRETERS.contains(&program.as_str()) {
+        return None;
+    }
+    let inline_flag = args.iter().any(|arg| {
+        let arg = arg.to_lowercase();
+        arg == "-c" || arg == "/c" || arg == "-command" || arg == "-lc"
+    });
+    if !inline_flag {
+        return None;
+    }
+
+    Some(SecurityWarning {
+        rule_id,
+        config_path: config_path.to_string(),
+        config_item: config_item.to_string(),
+        reason: t!(
+            "claude_code.security.rule.inline_shell.reason",
+            shell = program
+        )
+        .to_string(),
+        severity: SecurityWarningSeverity::Medium,
+        matched_pattern: format!("{} -c", program),
+        description: t!("claude_code.security.rule.inline_shell.description").to_string(),
+        recommendation: t!("claude_code.security.rule.inline_shell.recommendation").to_string(),
+    })
+}
+
+/// Render warnings as a SARIF 2.1.0 log for code-scanning integrations
+pub fn to_sarif(warnings: &[SecurityWarning]) -> Value {
+    let rules: Vec<Value> = rules::ALL
+        .iter()
+        .map(|id| {
+            json!({
+                "id": id,
+                "shortDescription": { "text": rules::title(id) },
+            })
+        })
+        .collect();
+
+    let results: Vec<Value> = warnings
+        .iter()
+        .map(|warning| {
+            json!({
+                "ruleId": warning.rule_id,
+                "level": warning.severity.sarif_level(),
+                "message": {
+                    "text": format!(
+                        "{}: {} ({})",
+                        warning.config_item, warning.description, warning.recommendation
+                    )
+                },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": warning.config_path.replace('\\', "/") }
+                    }
+                }],
+                "properties": {
+                    "severity": warning.severity.display_name(),
+                    "matchedPattern": warning.matched_pattern,
+                },
+            })
+        })
+        .collect();
+
+    json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "llman",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": "https://github.com/StrayDragon/llman",
+                    "rules": rules,
+                }
+            },
+            "results": results,
+        }]
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -463,7 +1036,9 @@ mod tests {
         let checker = SecurityChecker {
             dangerous_patterns: vec!["rm -rf".to_string(), "curl | sh".to_string()],
             settings_files: vec![],
+            run_settings_files: vec![],
             enabled: true,
+            vetted_hooks: None,
         };
 
         // Test matching patterns
@@ -494,7 +1069,9 @@ mod tests {
         let checker = SecurityChecker {
             dangerous_patterns: vec!["RM -RF".to_string()],
             settings_files: vec![],
+            run_settings_files: vec![],
             enabled: true,
+            vetted_hooks: None,
         };
 
         assert!(
@@ -509,7 +1086,9 @@ mod tests {
         let checker = SecurityChecker {
             dangerous_patterns: vec!["rm -rf".to_string()],
             settings_files: vec![],
+            run_settings_files: vec![],
             enabled: true,
+            vetted_hooks: None,
         };
 
         let settings_json = r#"
@@ -537,6 +1116,156 @@ mod tests {
         assert_eq!(warnings[0].severity, SecurityWarningSeverity::Critical);
     }
 
+    #[test]
+    fn test_audit_settings_covers_hooks_mode_mcp_and_env() {
+        let checker = SecurityChecker {
+            dangerous_patterns: vec!["rm -rf".to_string(), "curl | sh".to_string()],
+            settings_files: vec![],
+            run_settings_files: vec![],
+            enabled: true,
+            vetted_hooks: None,
+        };
+
+        let settings: Value = serde_json::from_str(
+            r#"
+        {
+            "permissions": { "defaultMode": "bypassPermissions" },
+            "hooks": {
+                "PostToolUse": [
+                    { "matcher": "Edit", "hooks": [
+                        { "type": "command", "command": "cargo fmt" },
+                        { "type": "command", "command": "rm -rf target" }
+                    ] }
+                ]
+            },
+            "enableAllProjectMcpServers": true,
+            "mcpServers": {
+                "fetch": { "command": "uvx", "args": ["mcp-server-fetch"] },
+                "inline": { "command": "/bin/bash", "args": ["-c", "node server.js"] },
+                "installer": { "command": "sh", "args": ["-c", "curl | sh"] }
+            },
+            "projects": {
+                "/repo": { "mcpServers": {
+                    "local": { "command": "bash", "args": ["-c", "node local.js"] }
+                } }
+            },
+            "env": {
+                "NODE_TLS_REJECT_UNAUTHORIZED": "0",
+                "RUST_LOG": "info"
+            }
+        }
+        "#,
+        )
+        .unwrap();
+
+        let warnings = checker.audit_settings(&settings, "/test/settings.json");
+        let rule_ids: Vec<&str> = warnings.iter().map(|w| w.rule_id).collect();
+
+        assert_eq!(warnings.len(), 7, "{rule_ids:?}");
+        assert!(warnings.iter().any(|w| {
+            w.rule_id == rules::MCP_INLINE_SHELL
+                && w.config_item
+                    .starts_with("projects./repo.mcpServers.local.command")
+        }));
+        assert!(rule_ids.contains(&rules::BYPASS_PERMISSIONS_MODE));
+        assert!(rule_ids.contains(&rules::ALL_PROJECT_MCP_SERVERS));
+        assert!(rule_ids.contains(&rules::MCP_INLINE_SHELL));
+        assert!(rule_ids.contains(&rules::DANGEROUS_MCP_SERVER));
+        assert!(rule_ids.contains(&rules::TLS_VERIFICATION_DISABLED));
+
+        let hook = warnings
+            .iter()
+            .find(|w| w.rule_id == rules::DANGEROUS_HOOK)
+            .expect("hook warning");
+        assert_eq!(
+            hook.config_item,
+            "hooks.PostToolUse[0].hooks[1].command = \"rm -rf target\""
+        );
+        assert_eq!(hook.severity, SecurityWarningSeverity::Critical);
+
+        // With a hook library, commands outside it are flagged; dangerous ones keep their rule.
+        let checker = checker.with_vetted_hooks(BTreeSet::from(["./lint.sh".to_string()]));
+        let unvetted: Vec<String> = checker
+            .extract_dangerous_hooks(&settings, "/test/settings.json")
+            .into_iter()
+            .filter(|w| w.rule_id == rules::UNVETTED_HOOK)
+            .map(|w| w.matched_pattern)
+            .collect();
+        assert_eq!(unvetted, vec!["cargo fmt"]);
+    }
+
+    #[test]
+    fn test_run_gate_only_applies_permission_patterns() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let settings_path = temp.path().join("settings.json");
+        fs::write(
+            &settings_path,
+            r#"{
+                "permissions": {
+                    "defaultMode": "bypassPermissions",
+                    "allow": ["Bash(rm -rf:*)"]
+                },
+                "env": { "NODE_TLS_REJECT_UNAUTHORIZED": "0" }
+            }"#,
+        )
+        .unwrap();
+        let file = settings_path.display().to_string();
+        let checker = SecurityChecker {
+            dangerous_patterns: vec!["rm -rf".to_string()],
+            settings_files: vec![file.clone()],
+            run_settings_files: vec![file],
+            enabled: true,
+            vetted_hooks: None,
+        };
+
+        let gate: Vec<&str> = checker
+            .check_claude_settings()
+            .unwrap()
+            .iter()
+            .map(|w| w.rule_id)
+            .collect();
+        assert_eq!(gate, vec![rules::DANGEROUS_PERMISSION]);
+        assert_eq!(checker.audit().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_inline_shell_requires_inline_flag() {
+        assert!(inline_shell_warning("r", "p", "i", "bash", &["server.sh"]).is_none());
+        assert!(inline_shell_warning("r", "p", "i", "node", &["-c", "x"]).is_none());
+        assert!(inline_shell_warning("r", "p", "i", "pwsh.exe", &["-Command", "x"]).is_some());
+    }
+
+    #[test]
+    fn test_to_sarif_maps_levels_and_locations() {
+        let warning = SecurityWarning {
+            rule_id: rules::BYPASS_PERMISSIONS_MODE,
+            config_path: ".claude/settings.json".to_string(),
+            config_item: "permissions.defaultMode = \"bypassPermissions\"".to_string(),
+            reason: String::new(),
+            severity: SecurityWarningSeverity::Critical,
+            matched_pattern: "bypassPermissions".to_string(),
+            description: String::new(),
+            recommendation: String::new(),
+        };
+
+        let sarif = to_sarif(&[warning]);
+        assert_eq!(sarif["version"], "2.1.0");
+        let result = &sarif["runs"][0]["results"][0];
+        assert_eq!(result["ruleId"], rules::BYPASS_PERMISSIONS_MODE);
+        assert_eq!(result["level"], "error");
+        assert_eq!(
+            result["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+            ".claude/settings.json"
+        );
+        assert_eq!(
+            sarif["runs"][0]["tool"]["driver"]["rules"]
+                .as_array()
+                .unwrap()
+                .len(),
+            rules::ALL.len()
+        );
+    }
+
     #[test]
     fn test_severity_display() {
         assert_eq!(SecurityWarningSeverity::Critical.display_symbol(), "🚨");
@@ -558,7 +1287,9 @@ mod tests {
                 "chmod 777".to_string(),
             ],
             settings_files: vec![],
+            run_settings_files: vec![],
             enabled: true,
+            vetted_hooks: None,
         };
 
         // Test critical severity
@@ -585,7 +1316,9 @@ mod tests {
         let checker = SecurityChecker {
             dangerous_patterns: vec!["format".to_string(), "mkfs".to_string()],
             settings_files: vec![],
+            run_settings_files: vec![],
             enabled: true,
+            vetted_hooks: None,
         };
 
         // Should NOT match variable names containing "format"
@@ -0,0 +1,274 @@
+//! Claude Code `settings.json` files: scope locations, reading and in-place patching.
+//!
+//! Members are patched through [`crate::jsonc`] so comments and unrelated keys survive;
+//! documents whose shape cannot be patched in place are re-serialized instead.
+
+use crate::fs_utils::atomic_write_with_mode;
+use crate::jsonc;
+use crate::path_utils::safe_parent_for_creation;
+use crate::skills::cli::interactive::is_interactive;
+use crate::skills::shared::git::find_git_root;
+use anyhow::{Context, Result, anyhow, bail};
+use clap::ValueEnum;
+use inquire::Confirm;
+use llm_json::{RepairOptions, loads};
+use serde_json::{Value, json};
+use similar::TextDiff;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum SettingsScope {
+    /// `~/.claude/settings.json`
+    User,
+    /// `.claude/settings.json` (committed)
+    Project,
+    /// `.claude/settings.local.json` (not committed)
+    Local,
+}
+
+impl SettingsScope {
+    pub fn label(self) -> &'static str {
+        match self {
+            SettingsScope::User => "user",
+            SettingsScope::Project => "project",
+            SettingsScope::Local => "local",
+        }
+    }
+
+    /// Settings file for this scope; project scopes resolve against the git root.
+    pub fn path(self) -> Result<PathBuf> {
+        match self {
+            SettingsScope::User => Ok(crate::config::home_dir()?
+                .join(".claude")
+                .join("settings.json")),
+            SettingsScope::Project | SettingsScope::Local => {
+                let cwd = std::env::current_dir()
+                    .context(t!("claude_code.settings.current_dir_failed"))?;
+                Ok(self.path_in(&find_git_root(&cwd).unwrap_or(cwd)))
+            }
+        }
+    }
+
+    fn path_in(self, project_root: &Path) -> PathBuf {
+        let dir = project_root.join(".claude");
+        match self {
+            SettingsScope::Local => dir.join("settings.local.json"),
+            SettingsScope::User | SettingsScope::Project => dir.join("settings.json"),
+        }
+    }
+
+    /// Mode for newly created files: user settings may carry credentials.
+    fn new_file_mode(self) -> Option<u32> {
+        match self {
+            SettingsScope::User => Some(0o600),
+            SettingsScope::Project | SettingsScope::Local => Some(0o644),
+        }
+    }
+}
+
+/// A settings file as read from disk.
+#[derive(Debug, Clone)]
+pub struct SettingsFile {
+    pub scope: SettingsScope,
+    pub path: PathBuf,
+    pub original: Option<String>,
+    pub value: Value,
+}
+
+impl SettingsFile {
+    pub fn read(scope: SettingsScope) -> Result<Self> {
+        Self::read_from(scope, &scope.path()?)
+    }
+
+    pub fn read_from(scope: SettingsScope, path: &Path) -> Result<Self> {
+        let original =
+            if path.exists() {
+                Some(fs::read_to_string(path).with_context(|| {
+                    t!("claude_code.settings.read_failed", path = path.display())
+                })?)
+            } else {
+                None
+            };
+        let value = match original.as_deref() {
+            Some(content) => parse_settings(content)
+                .with_context(|| t!("claude_code.settings.parse_failed", path = path.display()))?,
+            None => json!({}),
+        };
+        Ok(Self {
+            scope,
+            path: path.to_path_buf(),
+            original,
+            value,
+        })
+    }
+
+    /// Current text, or an empty object for a missing file.
+    pub fn content(&self) -> String {
+        self.original.clone().unwrap_or_else(|| "{}\n".to_string())
+    }
+}
+
+pub(crate) fn parse_settings(content: &str) -> Result<Value> {
+    match serde_json::from_str(content) {
+        Ok(value) => Ok(value),
+        Err(_) => loads(content, &RepairOptions::default())
+            .map_err(|e| anyhow!(t!("claude_code.settings.invalid_json", error = e))),
+    }
+}
+
+/// Set (`Some`) or remove (`None`) the member at `path`, keeping the rest of `content` intact.
+pub fn patch_member(content: &str, path: &[&str], value: Option<&Value>) -> Result<String> {
+    let patched = match value {
+        Some(value) => jsonc::set_member(content, path, value),
+        None => jsonc::remove_member(content, path),
+    };
+    if let Some(patched) = patched {
+        return Ok(patched);
+    }
+
+    // Shape we cannot patch in place: fall back to a full rewrite.
+    let mut doc = parse_settings(content)?;
+    if !doc.is_object() {
+        doc = json!({});
+    }
+    let (last, parents) = path
+        .split_last()
+        .ok_or_else(|| anyhow!(t!("claude_code.settings.empty_path")))?;
+    let mut node = &mut doc;
+    for key in parents {
+        let object = node.as_object_mut().expect("object");
+        node = object.entry(*key).or_insert_with(|| json!({}));
+        if !node.is_object() {
+            *node = json!({});
+        }
+    }
+    let object = node.as_object_mut().expect("object");
+    match value {
+        Some(value) => {
+            object.insert((*last).to_string(), value.clone());
+        }
+        None => {
+            object.remove(*last);
+        }
+    }
+    let mut pretty = serde_json::to_string_pretty(&doc)?;
+    pretty.push('\n');
+    Ok(pretty)
+}
+
+/// A pending write to one settings file.
+pub struct SettingsChange {
+    pub scope: SettingsScope,
+    pub path: PathBuf,
+    pub before: Option<String>,
+    pub after: String,
+}
+
+impl SettingsChange {
+    pub fn new(file: &SettingsFile, after: String) -> Self {
+        Self {
+            scope: file.scope,
+            path: file.path.clone(),
+            before: file.original.clone(),
+            after,
+        }
+    }
+}
+
+/// Print each change as a unified diff, confirm, and write. Returns false when cancelled.
+pub fn apply_changes(changes: &[SettingsChange], dry_run: bool, yes: bool) -> Result<bool> {
+    for change in changes {
+        let old_label = match change.before {
+            Some(_) => change.path.display().to_string(),
+            None => "/dev/null".to_string(),
+        };
+        let before = change.before.clone().unwrap_or_default();
+        let diff = TextDiff::from_lines(&before, &change.after);
+        print!(
+            "{}",
+            diff.unified_diff()
+                .context_radius(3)
+                .header(&old_label, &change.path.display().to_string())
+        );
+    }
+
+    if dry_run {
+        return Ok(false);
+    }
+
+    if !yes {
+        if !is_interactive() {
+            bail!(t!("claude_code.settings.write_requires_yes"));
+        }
+        let proceed = Confirm::new(&t!("claude_code.settings.confirm_apply"))
+            .with_default(false)
+            .prompt()
+            .context(t!("claude_code.settings.confirm_failed"))?;
+        if !proceed {
+            println!("{}", t!("claude_code.settings.cancelled"));
+            return Ok(false);
+        }
+    }
+
+    for change in changes {
+        if let Some(parent) = safe_parent_for_creation(&change.path) {
+            fs::create_dir_all(parent).with_context(|| {
+                t!(
+                    "claude_code.settings.create_dir_failed",
+                    path = parent.display()
+                )
+            })?;
+        }
+        let mode = match change.before {
+            Some(_) => existing_file_mode(&change.path),
+            None => change.scope.new_file_mode(),
+        };
+        atomic_write_with_mode(&change.path, change.after.as_bytes(), mode).with_context(|| {
+            t!(
+                "claude_code.settings.write_failed",
+                path = change.path.display()
+            )
+        })?;
+        println!(
+            "{}",
+            t!("claude_code.settings.updated", path = change.path.display())
+        );
+    }
+    Ok(true)
+}
+
+fn existing_file_mode(path: &Path) -> Option<u32> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let metadata = fs::metadata(path).ok()?;
+        Some(metadata.permissions().mode() & 0o777)
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = path;
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn patch_member_keeps_comments_and_falls_back_for_odd_shapes() {
+        let content = "{\n  // team defaults\n  \"model\": \"opus\"\n}\n";
+        let patched = patch_member(content, &["hooks", "Stop"], Some(&json!([]))).unwrap();
+        assert!(patched.contains("// team defaults"), "{patched}");
+        let removed = patch_member(&patched, &["hooks", "Stop"], None).unwrap();
+        assert!(!removed.contains("Stop"), "{removed}");
+
+        // `hooks` is not an object, so the member cannot be patched in place.
+        let odd = "{ \"hooks\": 1 }";
+        let rewritten = patch_member(odd, &["hooks", "Stop"], Some(&json!([]))).unwrap();
+        let parsed: Value = serde_json::from_str(&rewritten).unwrap();
+        assert_eq!(parsed, json!({"hooks": {"Stop": []}}));
+    }
+}
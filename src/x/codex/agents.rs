@@ -1,14 +1,18 @@
 use crate::config::resolve_config_dir;
-use crate::fs_utils::atomic_write_with_mode;
 use crate::managed_block::{
     LLMAN_PROMPTS_MARKER_END, LLMAN_PROMPTS_MARKER_START, has_llman_prompt_markers,
     update_text_with_markers,
 };
 use crate::path_utils::validate_path_segment;
+use crate::prompts::render::{GLOBAL_SCOPE, PromptRenderer};
 use crate::prompts::store as prompt_store;
 use crate::skills::cli::interactive::is_interactive;
+use crate::x::agents::plan::{
+    self, Plan, PlanOp, SyncMode, describe_target_state, list_stems, plan_import, plan_sync,
+    select_stems,
+};
 use anyhow::{Context, Result, bail};
-use clap::{Args, Subcommand, ValueEnum};
+use clap::{Args, Subcommand};
 use inquire::{Confirm, MultiSelect, Select};
 use std::collections::HashSet;
 use std::env;
@@ -83,14 +87,6 @@ pub enum CodexAgentsCommand {
     },
 }
 
-#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
-pub enum SyncMode {
-    #[value(name = "link")]
-    Link,
-    #[value(name = "copy")]
-    Copy,
-}
-
 pub fn run(args: &CodexAgentsArgs) -> Result<()> {
     let interactive = is_interactive();
     match &args.command {
@@ -185,7 +181,7 @@ fn run_import(args: &CodexAgentsArgs, interactive: bool) -> Result<()> {
     let available = list_toml_stems(&target_dir)?;
     let selected = select_stems(&available, &args.only, "agent name")?;
 
-    let plan = plan_import(&managed_dir, &target_dir, &selected)?;
+    let plan = plan_import(&managed_dir, &target_dir, &selected, "toml")?;
     apply_plan(plan, args, interactive)
 }
 
@@ -201,7 +197,7 @@ fn run_sync(
     let available = list_toml_stems(&managed_dir)?;
     let selected = select_stems(&available, &args.only, "agent name")?;
 
-    let mut plan = plan_sync(&managed_dir, &target_dir, &selected, mode)?;
+    let mut plan = plan_sync(&managed_dir, &target_dir, &selected, mode, "toml")?;
 
     let should_upsert_config = upsert_config || !upsert_agents.is_empty();
     if should_upsert_config {
@@ -261,7 +257,7 @@ fn run_wizard(args: &CodexAgentsArgs) -> Result<()> {
                 .with_all_selected_by_default()
                 .prompt()
                 .context("select agents to import")?;
-            let plan = plan_import(&managed_dir, &target_dir, &selected)?;
+            let plan = plan_import(&managed_dir, &target_dir, &selected, "toml")?;
             apply_plan_with_override(plan, args, true, confirm_all)
         }
         "inject" => {
@@ -314,7 +310,7 @@ fn run_wizard(args: &CodexAgentsArgs) -> Result<()> {
                 _ => unreachable!("validated selection"),
             };
 
-            let mut plan = plan_sync(&managed_dir, &target_dir, &selected, mode)?;
+            let mut plan = plan_sync(&managed_dir, &target_dir, &selected, mode, "toml")?;
 
             let config_path_display = resolve_target_codex_config_path(args)
                 .map(|p| p.display().to_string())
@@ -602,55 +598,6 @@ fn file_stem_for_agent_name(agent_name: &str) -> String {
     }
 }
 
-#[derive(Debug, Clone)]
-enum PlanOp {
-    EnsureDir { path: PathBuf },
-    Backup { from: PathBuf, to: PathBuf },
-    Copy { from: PathBuf, to: PathBuf },
-    Link { from: PathBuf, to: PathBuf },
-    WriteFile { path: PathBuf, content: String },
-    Note { message: String },
-}
-
-#[derive(Debug, Clone, Default)]
-struct Plan {
-    ops: Vec<PlanOp>,
-}
-
-impl Plan {
-    fn has_writes(&self) -> bool {
-        self.ops.iter().any(|op| {
-            matches!(
-                op,
-                PlanOp::EnsureDir { .. }
-                    | PlanOp::Backup { .. }
-                    | PlanOp::Copy { .. }
-                    | PlanOp::Link { .. }
-                    | PlanOp::WriteFile { .. }
-            )
-        })
-    }
-
-    fn print(&self) {
-        for op in &self.ops {
-            match op {
-                PlanOp::EnsureDir { path } => println!("PLAN mkdir -p {}", path.display()),
-                PlanOp::Backup { from, to } => {
-                    println!("PLAN backup {} -> {}", from.display(), to.display())
-                }
-                PlanOp::Copy { from, to } => {
-                    println!("PLAN copy {} -> {}", from.display(), to.display())
-                }
-                PlanOp::Link { from, to } => {
-                    println!("PLAN link {} -> {}", to.display(), from.display())
-                }
-                PlanOp::WriteFile { path, .. } => println!("PLAN write {}", path.display()),
-                PlanOp::Note { message } => println!("PLAN note: {}", message),
-            }
-        }
-    }
-}
-
 fn apply_plan(plan: Plan, args: &CodexAgentsArgs, interactive: bool) -> Result<()> {
     apply_plan_with_override(plan, args, interactive, args.yes || args.force)
 }
@@ -661,193 +608,7 @@ fn apply_plan_with_override(
     interactive: bool,
     confirm_all: bool,
 ) -> Result<()> {
-    if plan.ops.is_empty() {
-        println!("No changes.");
-        return Ok(());
-    }
-
-    plan.print();
-
-    if args.dry_run {
-        return Ok(());
-    }
-
-    if plan.has_writes() && !confirm_all {
-        if !interactive {
-            bail!(
-                "This operation would write files. Re-run with --dry-run to preview or --yes/--force to proceed."
-            );
-        }
-
-        let proceed = Confirm::new("Proceed with these changes?")
-            .with_default(false)
-            .prompt()
-            .context("confirm apply")?;
-        if !proceed {
-            println!("Cancelled.");
-            return Ok(());
-        }
-    }
-
-    execute_plan(&plan)
-}
-
-fn execute_plan(plan: &Plan) -> Result<()> {
-    for op in &plan.ops {
-        match op {
-            PlanOp::EnsureDir { path } => {
-                fs::create_dir_all(path)
-                    .with_context(|| format!("create dir: {}", path.display()))?;
-            }
-            PlanOp::Backup { from, to } => {
-                fs::rename(from, to)
-                    .with_context(|| format!("backup {} -> {}", from.display(), to.display()))?;
-            }
-            PlanOp::Copy { from, to } => {
-                let content =
-                    fs::read(from).with_context(|| format!("read source: {}", from.display()))?;
-                if let Some(parent) = to.parent() {
-                    fs::create_dir_all(parent)
-                        .with_context(|| format!("create dir: {}", parent.display()))?;
-                }
-                atomic_write_with_mode(to, &content, None)
-                    .with_context(|| format!("write: {}", to.display()))?;
-            }
-            PlanOp::Link { from, to } => {
-                #[cfg(unix)]
-                {
-                    use std::os::unix::fs as unix_fs;
-                    if let Some(parent) = to.parent() {
-                        fs::create_dir_all(parent)
-                            .with_context(|| format!("create dir: {}", parent.display()))?;
-                    }
-                    unix_fs::symlink(from, to).with_context(|| {
-                        format!("symlink {} -> {}", to.display(), from.display())
-                    })?;
-                }
-                #[cfg(not(unix))]
-                {
-                    let _ = (from, to);
-                    bail!("symlink mode is not supported on this platform; use --mode copy");
-                }
-            }
-            PlanOp::WriteFile { path, content } => {
-                if let Some(parent) = path.parent() {
-                    fs::create_dir_all(parent)
-                        .with_context(|| format!("create dir: {}", parent.display()))?;
-                }
-                atomic_write_with_mode(path, content.as_bytes(), None)
-                    .with_context(|| format!("write: {}", path.display()))?;
-            }
-            PlanOp::Note { .. } => {}
-        }
-    }
-    Ok(())
-}
-
-fn plan_import(managed_dir: &Path, target_dir: &Path, stems: &[String]) -> Result<Plan> {
-    let mut plan = Plan::default();
-    if !managed_dir.exists() {
-        plan.ops.push(PlanOp::EnsureDir {
-            path: managed_dir.to_path_buf(),
-        });
-    }
-
-    for stem in stems {
-        let from = target_dir.join(format!("{stem}.toml"));
-        let to = managed_dir.join(format!("{stem}.toml"));
-
-        if !from.exists() {
-            plan.ops.push(PlanOp::Note {
-                message: format!("skip missing source: {}", from.display()),
-            });
-            continue;
-        }
-
-        let source_bytes = fs::read(&from).with_context(|| format!("read: {}", from.display()))?;
-        let needs_overwrite = if fs::symlink_metadata(&to).is_ok_and(|m| m.file_type().is_symlink())
-        {
-            true
-        } else if to.exists() {
-            fs::read(&to)
-                .map(|existing| existing != source_bytes)
-                .unwrap_or(true)
-        } else {
-            true
-        };
-
-        if !needs_overwrite {
-            continue;
-        }
-
-        if fs::symlink_metadata(&to).is_ok() {
-            let backup = backup_path_for(&to)?;
-            plan.ops.push(PlanOp::Backup {
-                from: to.clone(),
-                to: backup,
-            });
-        }
-
-        plan.ops.push(PlanOp::Copy { from, to });
-    }
-
-    Ok(plan)
-}
-
-fn plan_sync(
-    managed_dir: &Path,
-    target_dir: &Path,
-    stems: &[String],
-    mode: SyncMode,
-) -> Result<Plan> {
-    let mut plan = Plan::default();
-    if !target_dir.exists() {
-        plan.ops.push(PlanOp::EnsureDir {
-            path: target_dir.to_path_buf(),
-        });
-    }
-
-    for stem in stems {
-        let from = managed_dir.join(format!("{stem}.toml"));
-        let to = target_dir.join(format!("{stem}.toml"));
-
-        if !from.exists() {
-            plan.ops.push(PlanOp::Note {
-                message: format!("skip missing managed file: {}", from.display()),
-            });
-            continue;
-        }
-
-        if mode == SyncMode::Link {
-            if is_correct_symlink(&from, &to).unwrap_or(false) {
-                continue;
-            }
-        } else if mode == SyncMode::Copy && to.exists() {
-            let meta = fs::symlink_metadata(&to)?;
-            if !meta.file_type().is_symlink() {
-                let a = fs::read(&from).unwrap_or_default();
-                let b = fs::read(&to).unwrap_or_default();
-                if a == b {
-                    continue;
-                }
-            }
-        }
-
-        if fs::symlink_metadata(&to).is_ok() {
-            let backup = backup_path_for(&to)?;
-            plan.ops.push(PlanOp::Backup {
-                from: to.clone(),
-                to: backup,
-            });
-        }
-
-        match mode {
-            SyncMode::Link => plan.ops.push(PlanOp::Link { from, to }),
-            SyncMode::Copy => plan.ops.push(PlanOp::Copy { from, to }),
-        }
-    }
-
-    Ok(plan)
+    plan::apply_plan(plan, args.dry_run, interactive, confirm_all)
 }
 
 fn plan_inject(managed_dir: &Path, stems: &[String], body: &str) -> Result<Plan> {
@@ -940,98 +701,7 @@ fn resolve_target_codex_home_dir(args: &CodexAgentsArgs) -> Result<PathBuf> {
 }
 
 fn list_toml_stems(dir: &Path) -> Result<Vec<String>> {
-    if !dir.exists() {
-        return Ok(Vec::new());
-    }
-
-    let mut stems = Vec::new();
-    for entry in fs::read_dir(dir).with_context(|| format!("read dir: {}", dir.display()))? {
-        let entry = entry?;
-        let file_name = entry.file_name();
-        let Some(name) = file_name.to_str() else {
-            continue;
-        };
-        if !name.ends_with(".toml") {
-            continue;
-        }
-        let stem = name.trim_end_matches(".toml").to_string();
-        if stem.is_empty() {
-            continue;
-        }
-        stems.push(stem);
-    }
-
-    stems.sort();
-    stems.dedup();
-    Ok(stems)
-}
-
-fn select_stems(available: &[String], only: &[String], what: &str) -> Result<Vec<String>> {
-    if only.is_empty() {
-        return Ok(available.to_vec());
-    }
-
-    let mut wanted = Vec::new();
-    let available_set: HashSet<&str> = available.iter().map(|s| s.as_str()).collect();
-    for raw in only {
-        let stem = validate_path_segment(raw, what)?;
-        if !available_set.contains(stem.as_str()) {
-            bail!("{what} not found: {stem}");
-        }
-        wanted.push(stem);
-    }
-    wanted.sort();
-    wanted.dedup();
-    Ok(wanted)
-}
-
-fn backup_path_for(path: &Path) -> Result<PathBuf> {
-    let ts = chrono::Utc::now().format("%Y%m%d%H%M%S").to_string();
-    let file_name = path
-        .file_name()
-        .and_then(|s| s.to_str())
-        .unwrap_or("backup");
-    Ok(path.with_file_name(format!("{file_name}.llman.bak.{ts}")))
-}
-
-fn is_correct_symlink(from: &Path, link: &Path) -> Result<bool> {
-    let meta =
-        fs::symlink_metadata(link).with_context(|| format!("metadata: {}", link.display()))?;
-    if !meta.file_type().is_symlink() {
-        return Ok(false);
-    }
-
-    let target = fs::read_link(link).with_context(|| format!("readlink: {}", link.display()))?;
-    let resolved = if target.is_absolute() {
-        target
-    } else {
-        link.parent().unwrap_or_else(|| Path::new(".")).join(target)
-    };
-
-    Ok(resolved == from)
-}
-
-fn describe_target_state(managed: &Path, target: &Path) -> Result<String> {
-    if fs::symlink_metadata(target).is_err() {
-        return Ok("missing".to_string());
-    }
-
-    let meta =
-        fs::symlink_metadata(target).with_context(|| format!("metadata: {}", target.display()))?;
-    if meta.file_type().is_symlink() {
-        let ok = is_correct_symlink(managed, target).unwrap_or(false);
-        return Ok(if ok { "linked" } else { "wrong-link" }.to_string());
-    }
-
-    let managed_bytes =
-        fs::read(managed).with_context(|| format!("read: {}", managed.display()))?;
-    let target_bytes = fs::read(target).with_context(|| format!("read: {}", target.display()))?;
-    Ok(if managed_bytes == target_bytes {
-        "copied"
-    } else {
-        "diff"
-    }
-    .to_string())
+    list_stems(dir, "toml")
 }
 
 fn describe_inject_state(managed: &Path) -> Result<String> {
@@ -1103,7 +773,14 @@ fn list_codex_prompt_templates() -> Result<Vec<String>> {
 
 fn build_injection_body(templates: &[String]) -> Result<String> {
     let config = crate::config::Config::new()?;
-    prompt_store::build_llman_prompts_body(&config, crate::config::CODEX_APP, templates)
+    let renderer = PromptRenderer::new(&config, None)?;
+    prompt_store::build_llman_prompts_body(
+        &config,
+        crate::config::CODEX_APP,
+        templates,
+        &renderer,
+        GLOBAL_SCOPE,
+    )
 }
 
 fn inject_into_toml_developer_instructions(content: &str, body: &str) -> Result<Option<String>> {
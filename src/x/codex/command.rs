@@ -1,16 +1,28 @@
 use crate::arg_utils::split_shell_args;
 use crate::editor::{parse_editor_command, select_editor_raw};
 use crate::fs_utils::atomic_write_new_with_mode;
+use crate::secrets::SecretResolver;
+use crate::x::account_check::{self, AuthStyle, CheckTarget, WireApi};
+use crate::x::claude_code::env_injection::{
+    EnvSyntax, env_syntax_for_current_platform, prepare_env_injection, render_env_injection_lines,
+};
+use crate::x::claude_code::security::print_security_warnings;
 use crate::x::codex::agents::CodexAgentsArgs;
-use crate::x::codex::config::{Config, upsert_to_codex_config};
+use crate::x::codex::config::{
+    Config, configured_model, effective_provider_name, read_codex_home_config,
+    upsert_to_codex_config,
+};
 use crate::x::codex::interactive;
 use crate::x::codex::prompts::CodexPromptsArgs;
+use crate::x::codex::security::CodexSecurityChecker;
+use crate::x::project_accounts::{AccountApp, ProjectAccountPolicy};
 use anyhow::{Context, Result, bail};
 use clap::{Args, Subcommand};
 use rust_i18n::t;
 use std::fs;
 use std::path::Path;
 use std::process::Command;
+use std::time::Duration;
 
 #[derive(Args)]
 #[command(
@@ -50,7 +62,11 @@ pub enum CodexCommands {
         )]
         interactive: bool,
 
-        #[arg(long = "group", help = "Configuration group name to use")]
+        #[arg(
+            long = "group",
+            visible_alias = "provider",
+            help = "Configuration group name to use"
+        )]
         group: Option<String>,
 
         #[arg(
@@ -70,8 +86,71 @@ pub enum CodexCommands {
 pub enum AccountAction {
     /// Edit codex configuration file
     Edit,
+    /// List all provider groups (API keys are masked)
+    List,
     /// Import a new provider configuration interactively
-    Import,
+    Import {
+        /// Force overwrite existing provider group
+        #[arg(short, long, action = clap::ArgAction::SetTrue)]
+        force: bool,
+    },
+    /// Activate a provider group and run codex with it
+    ///
+    /// Examples:
+    ///   llman x codex account use minimax -- --version
+    #[command(about = "Use/select a provider group")]
+    Use {
+        #[arg(help = "Name of the provider group to use")]
+        name: String,
+        #[arg(
+            trailing_var_arg = true,
+            allow_hyphen_values = true,
+            help = "Arguments to pass to codex"
+        )]
+        args: Vec<String>,
+    },
+    /// Emit shell-consumable env injection statements for a provider group
+    ///
+    /// Examples:
+    ///   bash/zsh:  eval "$(llman x codex account env minimax)"
+    ///   PowerShell: llman x codex account env minimax | Out-String | Invoke-Expression
+    #[command(about = "Emit env injection statements for a provider group")]
+    Env {
+        #[arg(help = "Name of the provider group")]
+        name: String,
+    },
+    /// Remove a provider group from the llman codex configuration
+    #[command(alias = "rm")]
+    Remove {
+        #[arg(help = "Name of the provider group to remove")]
+        name: String,
+    },
+    /// Send a minimal authenticated request for one or all provider groups
+    ///
+    /// Without --model, the model Codex is configured to use with the provider is checked
+    /// (top-level or profile `model` in `$CODEX_HOME/config.toml`); with no model at all, the
+    /// endpoint's model list is fetched instead.
+    #[command(about = "Check API connectivity of provider groups")]
+    Check {
+        #[arg(help = "Provider group to check (default: all providers)")]
+        name: Option<String>,
+        /// Model to request through the provider's wire API
+        #[arg(long)]
+        model: Option<String>,
+        /// Per-request timeout in seconds
+        #[arg(long, default_value_t = 20)]
+        timeout: u64,
+        /// Output JSON instead of a table
+        #[arg(long)]
+        json: bool,
+    },
+    /// Rename a provider group
+    Rename {
+        #[arg(help = "Current provider group name")]
+        old: String,
+        #[arg(help = "New provider group name")]
+        new: String,
+    },
 }
 
 pub fn run(args: &CodexArgs) -> Result<()> {
@@ -97,8 +176,17 @@ fn handle_main_command(args: &[String]) -> Result<()> {
         bail!(no_configs_message());
     }
 
-    if let Some(selected) = interactive::select_provider(&config)? {
-        activate_and_exec(&config, &selected, args)?;
+    let policy = ProjectAccountPolicy::current(AccountApp::Codex)?;
+    let selected = match policy.default_group() {
+        Some(group) => {
+            policy.announce_default();
+            Some(group.to_string())
+        }
+        None => interactive::select_provider(&config)?,
+    };
+
+    if let Some(selected) = selected {
+        activate_and_exec(&config, &policy, &selected, args)?;
     }
 
     Ok(())
@@ -107,7 +195,35 @@ fn handle_main_command(args: &[String]) -> Result<()> {
 fn handle_account_command(action: Option<&AccountAction>) -> Result<()> {
     match action {
         Some(AccountAction::Edit) | None => handle_account_edit()?,
-        Some(AccountAction::Import) => handle_account_import()?,
+        Some(AccountAction::List) => {
+            let config = Config::load().context(t!("codex.error.load_config_failed"))?;
+            interactive::display_provider_list(&config);
+        }
+        Some(AccountAction::Import { force }) => handle_account_import(*force)?,
+        Some(AccountAction::Use { name, args }) => {
+            let config = Config::load().context(t!("codex.error.load_config_failed"))?;
+            if config.is_empty() {
+                bail!(no_configs_message());
+            }
+            let policy = ProjectAccountPolicy::current(AccountApp::Codex)?;
+            activate_and_exec(&config, &policy, name, args)?;
+        }
+        Some(AccountAction::Env { name }) => handle_account_env(name)?,
+        Some(AccountAction::Remove { name }) => {
+            let mut config = Config::load().context(t!("codex.error.load_config_failed"))?;
+            if config.remove_provider(name).is_none() {
+                bail!(t!("codex.error.group_not_found", name = name));
+            }
+            config.save()?;
+            println!("{}", t!("codex.account.removed", name = name));
+        }
+        Some(AccountAction::Rename { old, new }) => handle_account_rename(old, new)?,
+        Some(AccountAction::Check {
+            name,
+            model,
+            timeout,
+            json,
+        }) => handle_account_check(name.as_deref(), model.as_deref(), *timeout, *json)?,
     }
     Ok(())
 }
@@ -169,12 +285,15 @@ fn handle_account_edit_with(config_path: &Path, editor_raw: &str) -> Result<()>
     Ok(())
 }
 
-fn handle_account_import() -> Result<()> {
+fn handle_account_import(force: bool) -> Result<()> {
     if let Some((key, provider)) = interactive::prompt_import()? {
         let mut config = Config::load().context(t!("codex.error.load_config_failed"))?;
 
         if config.model_providers.contains_key(&key) {
-            bail!(t!("codex.error.group_exists", name = key));
+            if !force {
+                bail!(t!("codex.error.group_exists", name = key));
+            }
+            println!("{}", t!("codex.account.overwriting", name = key));
         }
 
         config.add_provider(key.clone(), provider);
@@ -184,6 +303,104 @@ fn handle_account_import() -> Result<()> {
     Ok(())
 }
 
+fn handle_account_env(name: &str) -> Result<()> {
+    let config = Config::load().context(t!("codex.error.load_config_failed"))?;
+    if config.is_empty() {
+        bail!(no_configs_message());
+    }
+    let provider = config
+        .get_provider(name)
+        .ok_or_else(|| anyhow::anyhow!(t!("codex.error.group_not_found", name = name)))?;
+    ProjectAccountPolicy::current(AccountApp::Codex)?.ensure_allowed(name)?;
+
+    prepare_env_injection(&provider.env)?;
+    let syntax = env_syntax_for_current_platform();
+    let env = SecretResolver::new().resolve_map(&provider.env)?;
+    let lines = render_env_injection_lines(&env, syntax)?;
+
+    match syntax {
+        EnvSyntax::PosixExport => {
+            println!("# Bash/Zsh: source <(llman x codex account env {name}) && codex ...")
+        }
+        EnvSyntax::PowerShell => println!(
+            "# PowerShell: llman x codex account env {name} | Out-String | Invoke-Expression"
+        ),
+    }
+    for line in lines {
+        println!("{line}");
+    }
+    Ok(())
+}
+
+fn handle_account_check(
+    name: Option<&str>,
+    model: Option<&str>,
+    timeout: u64,
+    json: bool,
+) -> Result<()> {
+    let config = Config::load().context(t!("codex.error.load_config_failed"))?;
+    if config.is_empty() {
+        bail!(no_configs_message());
+    }
+    let names = match name {
+        Some(name) => vec![name.to_string()],
+        None => config.provider_names(),
+    };
+
+    let codex_config = read_codex_home_config();
+    let mut resolver = SecretResolver::new();
+    let mut targets = Vec::with_capacity(names.len());
+    for name in names {
+        let provider = config
+            .get_provider(&name)
+            .ok_or_else(|| anyhow::anyhow!(t!("codex.error.group_not_found", name = name)))?;
+        let model = model.map(str::to_string).or_else(|| {
+            codex_config.as_ref().and_then(|codex_config| {
+                configured_model(codex_config, effective_provider_name(&name, provider))
+            })
+        });
+        // Codex reads the key from `env_key`; fall back to the caller's environment.
+        let api_key = match provider.env.get(&provider.env_key) {
+            Some(value) => Some(resolver.resolve(value)?),
+            None => std::env::var(&provider.env_key).ok(),
+        };
+        targets.push(CheckTarget {
+            name,
+            wire_api: WireApi::from_codex(&provider.wire_api, model.is_some()),
+            base_url: provider.base_url.clone(),
+            api_key,
+            auth: AuthStyle::Bearer,
+            model,
+        });
+    }
+
+    let results = account_check::run_checks(&targets, Duration::from_secs(timeout))?;
+    account_check::print_results(&results, json)?;
+    account_check::ensure_all_ok(&results)
+}
+
+fn handle_account_rename(old: &str, new: &str) -> Result<()> {
+    let new = new.trim();
+    if new.is_empty() {
+        bail!(t!("codex.error.group_name_blank"));
+    }
+    let mut config = Config::load().context(t!("codex.error.load_config_failed"))?;
+    config.rename_provider(old, new)?;
+    config.save()?;
+    println!("{}", t!("codex.account.renamed", old = old, new = new));
+
+    // Without override_name the provider id in $CODEX_HOME/config.toml follows the group name.
+    let pinned = config
+        .get_provider(new)
+        .and_then(|provider| provider.llman_configs.as_ref())
+        .and_then(|cfg| cfg.override_name.as_deref())
+        .is_some();
+    if !pinned {
+        println!("{}", t!("codex.account.rename_provider_id_hint", old = old));
+    }
+    Ok(())
+}
+
 fn handle_run_command(
     interactive_mode: bool,
     group_name: Option<&str>,
@@ -195,6 +412,15 @@ fn handle_run_command(
         bail!(no_configs_message());
     }
 
+    let policy = ProjectAccountPolicy::current(AccountApp::Codex)?;
+    let group_name = match group_name {
+        None if !interactive_mode && policy.default_group().is_some() => {
+            policy.announce_default();
+            policy.default_group()
+        }
+        other => other,
+    };
+
     if !interactive_mode && group_name.is_none() {
         bail!(
             "{}\n{}",
@@ -209,18 +435,26 @@ fn handle_run_command(
         (group_name.unwrap().to_string(), args)
     };
 
-    activate_and_exec(&config, &selected, &codex_args)?;
+    activate_and_exec(&config, &policy, &selected, &codex_args)?;
 
     Ok(())
 }
 
-/// Core: upsert provider to codex config, inject env vars, exec codex.
-fn activate_and_exec(config: &Config, provider_key: &str, args: &[String]) -> Result<()> {
+/// Core: check the project allow-list, upsert provider to codex config, inject env vars, exec codex.
+fn activate_and_exec(
+    config: &Config,
+    policy: &ProjectAccountPolicy,
+    provider_key: &str,
+    args: &[String],
+) -> Result<()> {
+    policy.ensure_allowed(provider_key)?;
     let provider = config
         .get_provider(provider_key)
         .ok_or_else(|| anyhow::anyhow!(t!("codex.error.group_not_found", name = provider_key)))?;
 
-    // Upsert provider to ~/.codex/config.toml
+    enforce_security_check(config)?;
+
+    // Upsert provider to $CODEX_HOME/config.toml
     let wrote = upsert_to_codex_config(provider_key, provider)?;
     if wrote {
         println!("{}", t!("codex.run.provider_synced", name = provider_key));
@@ -251,8 +485,9 @@ fn activate_and_exec(config: &Config, provider_key: &str, args: &[String]) -> Re
         ));
     }
 
+    let env = SecretResolver::new().resolve_map(&provider.env)?;
     let mut cmd = Command::new("codex");
-    for (key, value) in &provider.env {
+    for (key, value) in env {
         cmd.env(key, value);
     }
     for arg in args {
@@ -270,6 +505,19 @@ fn activate_and_exec(config: &Config, provider_key: &str, args: &[String]) -> Re
     Ok(())
 }
 
+/// Abort before touching `$CODEX_HOME/config.toml` when the Codex configuration is risky.
+fn enforce_security_check(config: &Config) -> Result<()> {
+    let warnings = CodexSecurityChecker::from_config(config).check_codex_config()?;
+    print_security_warnings(&warnings, "codex.toml");
+    if !warnings.is_empty() {
+        bail!(t!(
+            "codex.security.blocked_by_warnings",
+            count = warnings.len()
+        ));
+    }
+    Ok(())
+}
+
 fn no_configs_message() -> String {
     let config_path = Config::config_file_path()
         .map(|p| p.display().to_string())
@@ -372,6 +620,35 @@ mod tests {
         assert_eq!(args, vec!["--help", "-m", "o3"]);
     }
 
+    #[test]
+    fn run_command_accepts_provider_alias() {
+        let cli = Cli::try_parse_from([
+            "llman",
+            "x",
+            "codex",
+            "run",
+            "--provider",
+            "minimax",
+            "--",
+            "exec",
+            "hi",
+        ])
+        .expect("parse");
+
+        let Some(Commands::X(x_args)) = cli.command else {
+            panic!("expected x subcommand");
+        };
+        let XCommands::Codex(codex_args) = x_args.command else {
+            panic!("expected x codex subcommand");
+        };
+        let Some(CodexCommands::Run { group, args, .. }) = codex_args.command else {
+            panic!("expected codex run subcommand");
+        };
+
+        assert_eq!(group.as_deref(), Some("minimax"));
+        assert_eq!(args, vec!["exec", "hi"]);
+    }
+
     #[test]
     fn editor_parsing_supports_args_and_quotes() {
         let (cmd, args) = parse_editor_command("code --wait").expect("parse");
@@ -476,11 +753,11 @@ MINIMAX_KEY = "sk-minimax"
 
         let openai = config.get_provider("openai").expect("openai");
         assert_eq!(openai.base_url, "https://api.openai.com/v1");
-        assert_eq!(openai.env.get("OPENAI_API_KEY").unwrap(), "sk-test-key");
+        assert_eq!(openai.env["OPENAI_API_KEY"], "sk-test-key".into());
 
         let minimax = config.get_provider("minimax").expect("minimax");
         assert_eq!(minimax.env_key, "MINIMAX_KEY");
-        assert_eq!(minimax.env.get("MINIMAX_KEY").unwrap(), "sk-minimax");
+        assert_eq!(minimax.env["MINIMAX_KEY"], "sk-minimax".into());
     }
 
     #[test]
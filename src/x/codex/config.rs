@@ -1,4 +1,5 @@
 use crate::path_utils::safe_parent_for_creation;
+use crate::secrets::SecretValue;
 use anyhow::{Context, Result, anyhow};
 use rust_i18n::t;
 use serde::{Deserialize, Serialize};
@@ -19,8 +20,9 @@ pub struct ProviderConfig {
     #[serde(default = "default_wire_api")]
     pub wire_api: String,
     pub env_key: String,
+    /// Values may be plain strings or secret references resolved at run time.
     #[serde(default)]
-    pub env: HashMap<String, String>,
+    pub env: HashMap<String, SecretValue>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub llman_configs: Option<ProviderLlmanConfigs>,
     #[serde(default, flatten)]
@@ -35,6 +37,19 @@ fn default_wire_api() -> String {
 pub struct Config {
     #[serde(default)]
     pub model_providers: HashMap<String, ProviderConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub security: Option<CodexSecurityConfig>,
+}
+
+/// Security configuration for checking Codex configuration before launch
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CodexSecurityConfig {
+    /// Whether security checks are enabled (default: true)
+    pub enabled: Option<bool>,
+    /// Dangerous command patterns (default: the Claude Code checker's patterns)
+    pub dangerous_patterns: Option<Vec<String>>,
+    /// Codex config files to check (default: `$CODEX_HOME/config.toml` and `.codex/config.toml`)
+    pub codex_config_files: Option<Vec<String>>,
 }
 
 impl Config {
@@ -73,6 +88,26 @@ impl Config {
         self.model_providers.insert(key, provider);
     }
 
+    pub fn remove_provider(&mut self, key: &str) -> Option<ProviderConfig> {
+        self.model_providers.remove(key)
+    }
+
+    /// Rename a provider group. A `name` that mirrored the old group name follows the rename.
+    pub fn rename_provider(&mut self, old: &str, new: &str) -> Result<()> {
+        if self.model_providers.contains_key(new) {
+            return Err(anyhow!(t!("codex.error.group_exists", name = new)));
+        }
+        let mut provider = self
+            .model_providers
+            .remove(old)
+            .ok_or_else(|| anyhow!(t!("codex.error.group_not_found", name = old)))?;
+        if provider.name == old {
+            provider.name = new.to_string();
+        }
+        self.model_providers.insert(new.to_string(), provider);
+        Ok(())
+    }
+
     pub fn config_file_path() -> Result<PathBuf> {
         Ok(crate::config::resolve_config_dir(None)?.join("codex.toml"))
     }
@@ -209,10 +244,80 @@ pub fn upsert_to_codex_config(provider_key: &str, provider: &ProviderConfig) ->
     Ok(true)
 }
 
-/// Get the path to `~/.codex/config.toml`.
+/// Codex home directory: `$CODEX_HOME`, falling back to `~/.codex`.
+pub fn codex_home_dir() -> Result<PathBuf> {
+    match std::env::var("CODEX_HOME") {
+        Ok(home) if !home.trim().is_empty() => Ok(PathBuf::from(home.trim())),
+        _ => Ok(crate::config::home_dir()
+            .context(t!("codex.error.home_dir_failed"))?
+            .join(".codex")),
+    }
+}
+
+/// Name Codex knows the provider by (`llman_configs.override_name`, else the group key).
+pub fn effective_provider_name<'a>(provider_key: &'a str, provider: &'a ProviderConfig) -> &'a str {
+    provider
+        .llman_configs
+        .as_ref()
+        .and_then(|cfg| cfg.override_name.as_deref())
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .unwrap_or(provider_key)
+}
+
+/// Model Codex would use with `provider_name`: the top-level `model` when `model_provider`
+/// selects it, else the `model` of the first profile (by name) that selects it.
+pub fn configured_model(codex_config: &Value, provider_name: &str) -> Option<String> {
+    let model_for = |table: &toml::map::Map<String, Value>| {
+        (table.get("model_provider").and_then(Value::as_str) == Some(provider_name))
+            .then(|| table.get("model").and_then(Value::as_str))
+            .flatten()
+            .map(str::to_string)
+    };
+    let root = codex_config.as_table()?;
+    model_for(root).or_else(|| {
+        let profiles = root.get("profiles")?.as_table()?;
+        let mut names: Vec<&String> = profiles.keys().collect();
+        names.sort();
+        names
+            .into_iter()
+            .find_map(|name| profiles[name].as_table().and_then(model_for))
+    })
+}
+
+/// `$CODEX_HOME/config.toml` parsed as TOML; `None` when missing or unreadable.
+pub fn read_codex_home_config() -> Option<Value> {
+    let path = codex_config_path().ok()?;
+    let content = fs::read_to_string(path).ok()?;
+    toml::from_str(&content).ok()
+}
+
+/// Get the path to `$CODEX_HOME/config.toml` (`~/.codex/config.toml` by default).
 fn codex_config_path() -> Result<PathBuf> {
-    let home = crate::config::home_dir().context(t!("codex.error.home_dir_failed"))?;
-    Ok(home.join(".codex").join("config.toml"))
+    Ok(codex_home_dir()?.join("config.toml"))
+}
+
+/// Env vars of a provider for display: secret references are shown as-is, plaintext
+/// values of sensitive keys (and of `env_key`) are masked.
+pub fn get_display_env(provider: &ProviderConfig) -> Vec<(String, String)> {
+    let mut vars: Vec<_> = provider.env.iter().collect();
+    vars.sort_by(|a, b| a.0.cmp(b.0));
+    vars.into_iter()
+        .map(|(key, value)| {
+            let sensitive = key == &provider.env_key
+                || crate::x::claude_code::config::is_sensitive_env_key(key);
+            (
+                key.clone(),
+                value.display_with(|plain| {
+                    if sensitive {
+                        mask_secret(plain)
+                    } else {
+                        plain.to_string()
+                    }
+                }),
+            )
+        })
+        .collect()
 }
 
 pub fn mask_secret(value: &str) -> String {
@@ -230,6 +335,34 @@ mod tests {
     use std::fs;
     use tempfile::TempDir;
 
+    #[test]
+    fn configured_model_prefers_active_provider_then_profiles() {
+        let codex_config: Value = toml::from_str(
+            r#"
+model = "gpt-5"
+model_provider = "openai"
+
+[profiles.zz]
+model_provider = "minimax"
+model = "late"
+
+[profiles.fast]
+model_provider = "minimax"
+model = "MiniMax-M2"
+"#,
+        )
+        .expect("parse");
+        assert_eq!(
+            configured_model(&codex_config, "openai").as_deref(),
+            Some("gpt-5")
+        );
+        assert_eq!(
+            configured_model(&codex_config, "minimax").as_deref(),
+            Some("MiniMax-M2")
+        );
+        assert_eq!(configured_model(&codex_config, "other"), None);
+    }
+
     #[test]
     fn provider_to_codex_table_includes_extra_excludes_env_and_llman_configs_and_overrides_name() {
         let provider = ProviderConfig {
@@ -237,7 +370,7 @@ mod tests {
             base_url: "https://api.example.com/v1".to_string(),
             wire_api: "responses".to_string(),
             env_key: "CODEX_API_KEY".to_string(),
-            env: [("CODEX_API_KEY".to_string(), "sk-test".to_string())]
+            env: [("CODEX_API_KEY".to_string(), "sk-test".into())]
                 .into_iter()
                 .collect(),
             llman_configs: Some(ProviderLlmanConfigs {
@@ -339,11 +472,64 @@ override_name = "a"
         );
     }
 
+    fn provider(name: &str, api_key: &str) -> ProviderConfig {
+        ProviderConfig {
+            name: name.to_string(),
+            base_url: "https://api.example.com/v1".to_string(),
+            wire_api: "responses".to_string(),
+            env_key: "MY_TOKEN".to_string(),
+            env: [
+                ("MY_TOKEN".to_string(), api_key.into()),
+                ("REGION".to_string(), "eu".into()),
+            ]
+            .into_iter()
+            .collect(),
+            llman_configs: None,
+            extra: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn rename_provider_moves_group_and_follows_mirrored_name() {
+        let mut config = Config::default();
+        config.add_provider("old".to_string(), provider("old", "sk-1"));
+        config.add_provider("other".to_string(), provider("Custom", "sk-2"));
+
+        config.rename_provider("old", "new").expect("rename");
+        assert!(config.get_provider("old").is_none());
+        assert_eq!(config.get_provider("new").expect("new").name, "new");
+
+        config
+            .rename_provider("other", "renamed")
+            .expect("rename other");
+        assert_eq!(
+            config.get_provider("renamed").expect("renamed").name,
+            "Custom"
+        );
+
+        assert!(config.rename_provider("missing", "x").is_err());
+        assert!(config.rename_provider("new", "renamed").is_err());
+        assert!(config.get_provider("new").is_some());
+    }
+
+    #[test]
+    fn display_env_masks_env_key_and_keeps_plain_values() {
+        let display = get_display_env(&provider("p", "sk-abcdefghijkl"));
+        assert_eq!(
+            display,
+            vec![
+                ("MY_TOKEN".to_string(), "sk-a...ijkl".to_string()),
+                ("REGION".to_string(), "eu".to_string()),
+            ]
+        );
+    }
+
     #[test]
     fn upsert_to_codex_config_uses_override_name_and_is_idempotent() {
         let temp = TempDir::new().expect("temp dir");
         let mut proc = TestProcess::new();
         proc.set_var("HOME", temp.path());
+        proc.remove_var("CODEX_HOME");
 
         let provider = ProviderConfig {
             name: "b".to_string(),
@@ -396,4 +582,32 @@ override_name = "a"
         let content2 = fs::read_to_string(&codex_config_path).expect("read config 2");
         assert_eq!(content1, content2);
     }
+
+    #[test]
+    fn upsert_to_codex_config_writes_under_codex_home() {
+        let temp = TempDir::new().expect("temp dir");
+        let codex_home = temp.path().join("codex-home");
+        let mut proc = TestProcess::new();
+        proc.set_var("HOME", temp.path());
+        proc.set_var("CODEX_HOME", &codex_home);
+
+        let provider = ProviderConfig {
+            name: "a".to_string(),
+            base_url: "https://api.example.com/v1".to_string(),
+            wire_api: "responses".to_string(),
+            env_key: "CODEX_API_KEY".to_string(),
+            env: HashMap::new(),
+            llman_configs: None,
+            extra: Default::default(),
+        };
+        assert!(upsert_to_codex_config("a", &provider).expect("upsert"));
+
+        assert!(codex_home.join("config.toml").exists());
+        assert!(!temp.path().join(".codex").join("config.toml").exists());
+        let written = read_codex_home_config().expect("read back");
+        assert_eq!(
+            written.get("model_provider").and_then(Value::as_str),
+            Some("a")
+        );
+    }
 }
@@ -1,4 +1,4 @@
-use crate::x::codex::config::{Config, ProviderConfig};
+use crate::x::codex::config::{Config, ProviderConfig, get_display_env};
 use anyhow::{Context, Result};
 use inquire::{Select, Text};
 use rust_i18n::t;
@@ -45,7 +45,7 @@ pub fn prompt_import() -> Result<Option<(String, ProviderConfig)>> {
         .context(t!("codex.error.import_prompt_failed"))?;
 
     let mut env = std::collections::HashMap::new();
-    env.insert(env_key_id.clone(), api_key_value);
+    env.insert(env_key_id.clone(), api_key_value.into());
 
     let provider = ProviderConfig {
         name: group_name.clone(),
@@ -59,3 +59,39 @@ pub fn prompt_import() -> Result<Option<(String, ProviderConfig)>> {
 
     Ok(Some((group_name, provider)))
 }
+
+pub fn display_provider_list(config: &Config) {
+    if config.is_empty() {
+        println!("{}", t!("codex.main.no_configs_found"));
+        println!("  {}", t!("codex.main.command_import"));
+        return;
+    }
+
+    println!("{}:", t!("codex.account.providers"));
+    println!();
+
+    for name in config.provider_names() {
+        let Some(provider) = config.get_provider(&name) else {
+            continue;
+        };
+        match provider
+            .llman_configs
+            .as_ref()
+            .and_then(|cfg| cfg.override_name.as_deref())
+        {
+            Some(override_name) => println!("  📝 {name} (→ {override_name})"),
+            None => println!("  📝 {name}"),
+        }
+        println!("     base_url = {}", provider.base_url);
+        println!("     wire_api = {}", provider.wire_api);
+        println!("     env_key = {}", provider.env_key);
+        let display_env = get_display_env(provider);
+        if display_env.is_empty() {
+            println!("     ({})", t!("codex.account.no_env_vars"));
+        }
+        for (key, value) in display_env {
+            println!("     {key} = {value}");
+        }
+        println!();
+    }
+}
@@ -1,9 +1,13 @@
 use crate::config::{CODEX_APP, Config};
 use crate::fs_utils::atomic_write_with_mode;
 use crate::path_utils::{safe_parent_for_creation, validate_path_segment};
+use crate::prompts::command::{
+    AppStatusArgs, CODEX_AGENTS_FILE, CODEX_AGENTS_OVERRIDE_FILE, PromptApp,
+};
 use crate::prompts::confirm::confirm_overwrite;
-use crate::prompts::managed_file::write_llman_managed_block;
+use crate::prompts::managed_file::write_generated_block;
 use crate::prompts::paths::{codex_home_dir, cwd, project_root};
+use crate::prompts::render::{GLOBAL_SCOPE, PROJECT_SCOPE, PromptRenderer};
 use crate::prompts::store as prompt_store;
 use crate::skills::cli::interactive::is_interactive;
 use anyhow::{Result, anyhow, bail};
@@ -12,9 +16,6 @@ use inquire::MultiSelect;
 use std::fs;
 use std::path::{Path, PathBuf};
 
-const CODEX_AGENTS_FILE: &str = "AGENTS.md";
-const CODEX_AGENTS_OVERRIDE_FILE: &str = "AGENTS.override.md";
-
 #[derive(Args, Debug, Clone)]
 #[command(about = "Manage Codex prompt templates and inject Codex configuration files")]
 #[command(subcommand_required = false)]
@@ -50,6 +51,10 @@ pub enum CodexPromptsCommand {
         #[arg(long)]
         force: bool,
     },
+    /// Show whether generated targets still match their templates
+    Status(AppStatusArgs),
+    /// Like `status`, but exit non-zero when any target drifted (for CI)
+    Check(AppStatusArgs),
     /// List available templates
     List,
     /// Create or update a template
@@ -75,6 +80,15 @@ pub enum PromptScopeArg {
     Project,
 }
 
+impl PromptScopeArg {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PromptScopeArg::Global => GLOBAL_SCOPE,
+            PromptScopeArg::Project => PROJECT_SCOPE,
+        }
+    }
+}
+
 #[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Ord, PartialOrd)]
 pub enum CodexTargetArg {
     /// Codex custom prompts under `prompts/*.md`
@@ -100,7 +114,9 @@ pub fn run(args: &CodexPromptsArgs) -> Result<()> {
             if interactive {
                 return run_wizard();
             }
-            bail!("In non-interactive mode, a subcommand is required (gen/list/upsert/rm).");
+            bail!(
+                "In non-interactive mode, a subcommand is required (gen/status/check/list/upsert/rm)."
+            );
         }
         Some(CodexPromptsCommand::Gen {
             template,
@@ -118,6 +134,8 @@ pub fn run(args: &CodexPromptsArgs) -> Result<()> {
             *force,
             interactive,
         ),
+        Some(CodexPromptsCommand::Status(status)) => status.run(PromptApp::Codex, false),
+        Some(CodexPromptsCommand::Check(status)) => status.run(PromptApp::Codex, true),
         Some(CodexPromptsCommand::List) => run_list(),
         Some(CodexPromptsCommand::Upsert { name, content }) => {
             run_upsert(name, content.content.as_deref(), content.file.as_deref())
@@ -216,18 +234,31 @@ fn run_gen(
         .map_err(|e| anyhow!("invalid prompt name: {e}"))?;
 
     let cwd = cwd()?;
+    let renderer = PromptRenderer::for_dir(&config, &cwd)?;
+    let render = |scope: PromptScopeArg| {
+        renderer.render_app_template(template, &content, CODEX_APP, scope.as_str())
+    };
 
     let mut first_error: Option<anyhow::Error> = None;
 
     if targets.contains(&CodexTargetArg::Prompts)
         && let Err(e) =
-            write_codex_prompt_files(&cwd, &output_name, scopes, force, interactive, &content)
+            write_codex_prompt_files(&cwd, &output_name, scopes, force, interactive, &render)
     {
         first_error = Some(e);
     }
 
     if targets.contains(&CodexTargetArg::ProjectDoc) {
-        let body = format!("## llman prompts: {template}\n\n{}", content.trim_end());
+        let templates = [template.to_string()];
+        let body = |scope: PromptScopeArg| {
+            prompt_store::build_stamped_prompts_body(
+                &config,
+                CODEX_APP,
+                &templates,
+                &renderer,
+                scope.as_str(),
+            )
+        };
         if let Err(e) =
             write_codex_project_doc_files(&cwd, scopes, override_file, force, interactive, &body)
             && first_error.is_none()
@@ -248,7 +279,7 @@ fn write_codex_prompt_files(
     scopes: &[PromptScopeArg],
     force: bool,
     interactive: bool,
-    content: &str,
+    content: &dyn Fn(PromptScopeArg) -> Result<String>,
 ) -> Result<()> {
     let mut first_error: Option<anyhow::Error> = None;
 
@@ -267,6 +298,16 @@ fn write_codex_prompt_files(
             }
         };
 
+        let content = match content(scope) {
+            Ok(content) => content,
+            Err(e) => {
+                if first_error.is_none() {
+                    first_error = Some(e);
+                }
+                continue;
+            }
+        };
+
         if target_path.exists() && !force {
             let overwrite = match confirm_overwrite(&target_path, interactive) {
                 Ok(v) => v,
@@ -317,7 +358,7 @@ fn write_codex_project_doc_files(
     override_file: bool,
     force: bool,
     interactive: bool,
-    body: &str,
+    body: &dyn Fn(PromptScopeArg) -> Result<String>,
 ) -> Result<()> {
     let file_name = if override_file {
         CODEX_AGENTS_OVERRIDE_FILE
@@ -342,7 +383,17 @@ fn write_codex_project_doc_files(
             }
         };
 
-        match write_llman_managed_block(&path, body, force, interactive) {
+        let body = match body(scope) {
+            Ok(body) => body,
+            Err(e) => {
+                if first_error.is_none() {
+                    first_error = Some(e);
+                }
+                continue;
+            }
+        };
+
+        match write_generated_block(&path, &body, force, interactive) {
             Ok(true) => {
                 println!("{}", t!("messages.rule_generated", path = path.display()));
             }
@@ -444,6 +495,7 @@ fn run_wizard() -> Result<()> {
     }
 
     let cwd = cwd()?;
+    let renderer = PromptRenderer::for_dir(&config, &cwd)?;
     let interactive = true;
 
     if targets.contains(&"prompts") {
@@ -451,17 +503,28 @@ fn run_wizard() -> Result<()> {
             let content = prompt_store::read_template(&config, CODEX_APP, template)?;
             let name = validate_path_segment(template, "prompt name")
                 .map_err(|e| anyhow!("invalid prompt name: {e}"))?;
-            write_codex_prompt_files(&cwd, &name, &scopes, false, interactive, &content)?;
+            let render = |scope: PromptScopeArg| {
+                renderer.render_app_template(template, &content, CODEX_APP, scope.as_str())
+            };
+            write_codex_prompt_files(&cwd, &name, &scopes, false, interactive, &render)?;
         }
     }
 
+    let body = |scope: PromptScopeArg| {
+        prompt_store::build_stamped_prompts_body(
+            &config,
+            CODEX_APP,
+            &picked_templates,
+            &renderer,
+            scope.as_str(),
+        )
+    };
+
     if targets.contains(&CODEX_AGENTS_FILE) {
-        let body = prompt_store::build_llman_prompts_body(&config, CODEX_APP, &picked_templates)?;
         write_codex_project_doc_files(&cwd, &scopes, false, false, interactive, &body)?;
     }
 
     if targets.contains(&CODEX_AGENTS_OVERRIDE_FILE) {
-        let body = prompt_store::build_llman_prompts_body(&config, CODEX_APP, &picked_templates)?;
         write_codex_project_doc_files(&cwd, &scopes, true, false, interactive, &body)?;
     }
 
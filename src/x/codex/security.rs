@@ -0,0 +1,546 @@
+use crate::x::claude_code::security::{
+    SecurityChecker, SecurityWarning, SecurityWarningSeverity, expand_home, inline_shell_warning,
+    rules,
+};
+use crate::x::codex::config::{Config, codex_home_dir};
+use anyhow::{Context, Result};
+use rust_i18n::t;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use toml::Value;
+
+/// Rule identifiers specific to Codex configuration
+pub mod codex_rules {
+    pub const FULL_ACCESS_NO_APPROVAL: &str = "codex-full-access-no-approval";
+    pub const FULL_ACCESS_SANDBOX: &str = "codex-full-access-sandbox";
+    pub const NO_APPROVAL: &str = "codex-no-approval";
+    pub const NETWORK_SANDBOX: &str = "codex-network-sandbox";
+    pub const UNRESTRICTED_AGENT: &str = "codex-unrestricted-agent";
+    pub const DANGEROUS_AGENT_INSTRUCTIONS: &str = "codex-dangerous-agent-instructions";
+    pub const UNREADABLE_AGENT: &str = "codex-unreadable-agent";
+}
+
+const DANGER_FULL_ACCESS: &str = "danger-full-access";
+const WORKSPACE_WRITE: &str = "workspace-write";
+const APPROVAL_NEVER: &str = "never";
+
+/// Security checker for Codex `config.toml` files and the agents they reference
+pub struct CodexSecurityChecker {
+    patterns: SecurityChecker,
+    config_files: Vec<String>,
+    enabled: bool,
+}
+
+impl CodexSecurityChecker {
+    /// Create a new checker from the llman Codex configuration
+    pub fn from_config(config: &Config) -> Self {
+        let security = config.security.as_ref();
+        Self {
+            patterns: SecurityChecker::with_patterns(
+                security.and_then(|s| s.dangerous_patterns.clone()),
+            ),
+            config_files: security
+                .and_then(|s| s.codex_config_files.clone())
+                .unwrap_or_else(Self::default_config_files),
+            enabled: security.and_then(|s| s.enabled).unwrap_or(true),
+        }
+    }
+
+    /// Check every configured Codex config file
+    pub fn check_codex_config(&self) -> Result<Vec<SecurityWarning>> {
+        if !self.enabled {
+            return Ok(vec![]);
+        }
+
+        let mut warnings = Vec::new();
+        for file in &self.config_files {
+            let path = expand_home(file);
+            match self.check_config_file(&path) {
+                Ok(mut found) => warnings.append(&mut found),
+                Err(e) => eprintln!(
+                    "{}",
+                    t!(
+                        "claude_code.security.parse_error",
+                        path = path.display(),
+                        error = format!("{e:#}")
+                    )
+                ),
+            }
+        }
+        Ok(warnings)
+    }
+
+    /// Check one `config.toml` plus the agent files next to it
+    fn check_config_file(&self, path: &Path) -> Result<Vec<SecurityWarning>> {
+        if !path.exists() {
+            return Ok(vec![]);
+        }
+        let doc = load_toml(path)?;
+        let config_path = path.display().to_string();
+
+        // A broken agent file is reported on its own so the root and profile findings survive.
+        let mut warnings = self.audit_config(&doc, &config_path);
+        let base_dir = path.parent().unwrap_or(Path::new("."));
+        let agents = match agent_files(&doc, base_dir) {
+            Ok(agents) => agents,
+            Err(e) => {
+                let agents_dir = base_dir.join("agents").display().to_string();
+                warnings.push(unreadable_agent(&agents_dir, &e));
+                vec![]
+            }
+        };
+        for agent_path in agents {
+            let agent_path_str = agent_path.display().to_string();
+            match load_toml(&agent_path) {
+                Ok(agent_doc) => warnings.extend(self.audit_agent(&agent_doc, &agent_path_str)),
+                Err(e) => warnings.push(unreadable_agent(&agent_path_str, &e)),
+            }
+        }
+        Ok(warnings)
+    }
+
+    /// Audit the root table, every `[profiles.<name>]` and `[mcp_servers]`
+    fn audit_config(&self, doc: &Value, config_path: &str) -> Vec<SecurityWarning> {
+        let mut warnings = audit_sandbox(doc, "", config_path);
+        if let Some(profiles) = doc.get("profiles").and_then(Value::as_table) {
+            for (name, profile) in profiles {
+                warnings.extend(audit_sandbox(
+                    profile,
+                    &format!("profiles.{name}."),
+                    config_path,
+                ));
+            }
+        }
+        warnings.extend(self.audit_mcp_servers(doc, config_path));
+        warnings
+    }
+
+    fn audit_mcp_servers(&self, doc: &Value, config_path: &str) -> Vec<SecurityWarning> {
+        let mut warnings = Vec::new();
+        let Some(servers) = doc.get("mcp_servers").and_then(Value::as_table) else {
+            return warnings;
+        };
+
+        for (name, server) in servers {
+            let Some(command) = server.get("command").and_then(Value::as_str) else {
+                continue;
+            };
+            let args: Vec<&str> = server
+                .get("args")
+                .and_then(Value::as_array)
+                .map(|args| args.iter().filter_map(Value::as_str).collect())
+                .unwrap_or_default();
+            let command_line = std::iter::once(command)
+                .chain(args.iter().copied())
+                .collect::<Vec<_>>()
+                .join(" ");
+            let item = format!("mcp_servers.{name}.command = \"{command_line}\"");
+
+            if let Some(warning) = self.patterns.pattern_warning(
+                rules::DANGEROUS_MCP_SERVER,
+                config_path,
+                item.clone(),
+                &command_line,
+            ) {
+                warnings.push(warning);
+            } else if let Some(warning) =
+                inline_shell_warning(rules::MCP_INLINE_SHELL, config_path, &item, command, &args)
+            {
+                warnings.push(warning);
+            }
+        }
+        warnings
+    }
+
+    /// Audit a custom agent file: its own sandbox settings and `developer_instructions`
+    fn audit_agent(&self, doc: &Value, agent_path: &str) -> Vec<SecurityWarning> {
+        let mut warnings = Vec::new();
+        let sandbox = doc.get("sandbox_mode").and_then(Value::as_str);
+        let approval = doc.get("approval_policy").and_then(Value::as_str);
+
+        if sandbox == Some(DANGER_FULL_ACCESS) || approval == Some(APPROVAL_NEVER) {
+            let item = describe_modes(sandbox, approval, "");
+            warnings.push(warning(
+                codex_rules::UNRESTRICTED_AGENT,
+                agent_path,
+                item.clone(),
+                item,
+                SecurityWarningSeverity::High,
+                "unrestricted_agent",
+            ));
+        }
+
+        if let Some(instructions) = doc.get("developer_instructions").and_then(Value::as_str) {
+            for line in instructions.lines().filter(|line| !line.trim().is_empty()) {
+                if let Some(warning) = self.patterns.pattern_warning(
+                    codex_rules::DANGEROUS_AGENT_INSTRUCTIONS,
+                    agent_path,
+                    format!("developer_instructions: \"{}\"", line.trim()),
+                    line,
+                ) {
+                    warnings.push(warning);
+                }
+            }
+        }
+        warnings
+    }
+
+    /// Default Codex config files: the user config and the project override
+    fn default_config_files() -> Vec<String> {
+        vec![
+            codex_home_dir()
+                .map(|home| home.join("config.toml"))
+                .unwrap_or_else(|_| PathBuf::from("~/.codex/config.toml"))
+                .to_string_lossy()
+                .into_owned(),
+            ".codex/config.toml".to_string(),
+        ]
+    }
+}
+
+/// Rate a table's `sandbox_mode` / `approval_policy` / network combination
+fn audit_sandbox(table: &Value, prefix: &str, config_path: &str) -> Vec<SecurityWarning> {
+    let mut warnings = Vec::new();
+    let sandbox = table.get("sandbox_mode").and_then(Value::as_str);
+    let approval = table.get("approval_policy").and_then(Value::as_str);
+    let no_approval = approval == Some(APPROVAL_NEVER);
+    let modes = describe_modes(sandbox, approval, prefix);
+
+    match sandbox {
+        Some(DANGER_FULL_ACCESS) if no_approval => warnings.push(warning(
+            codex_rules::FULL_ACCESS_NO_APPROVAL,
+            config_path,
+            modes.clone(),
+            format!("{DANGER_FULL_ACCESS} + {APPROVAL_NEVER}"),
+            SecurityWarningSeverity::Critical,
+            "full_access_no_approval",
+        )),
+        Some(DANGER_FULL_ACCESS) => warnings.push(warning(
+            codex_rules::FULL_ACCESS_SANDBOX,
+            config_path,
+            modes.clone(),
+            DANGER_FULL_ACCESS.to_string(),
+            SecurityWarningSeverity::High,
+            "full_access_sandbox",
+        )),
+        Some(WORKSPACE_WRITE) if no_approval => warnings.push(warning(
+            codex_rules::NO_APPROVAL,
+            config_path,
+            modes.clone(),
+            format!("{WORKSPACE_WRITE} + {APPROVAL_NEVER}"),
+            SecurityWarningSeverity::Medium,
+            "no_approval",
+        )),
+        _ => {}
+    }
+
+    let network_access = table
+        .get("sandbox_workspace_write")
+        .and_then(|section| section.get("network_access"))
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+    if network_access && sandbox != Some(DANGER_FULL_ACCESS) {
+        warnings.push(warning(
+            codex_rules::NETWORK_SANDBOX,
+            config_path,
+            format!("{prefix}sandbox_workspace_write.network_access = true"),
+            "network_access".to_string(),
+            if no_approval {
+                SecurityWarningSeverity::High
+            } else {
+                SecurityWarningSeverity::Medium
+            },
+            "network_sandbox",
+        ));
+    }
+
+    warnings
+}
+
+/// An agent file that could not be read or parsed, so its settings were not audited
+fn unreadable_agent(agent_path: &str, error: &anyhow::Error) -> SecurityWarning {
+    warning(
+        codex_rules::UNREADABLE_AGENT,
+        agent_path,
+        format!("{error:#}")
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .to_string(),
+        "unreadable".to_string(),
+        SecurityWarningSeverity::High,
+        "unreadable_agent",
+    )
+}
+
+fn describe_modes(sandbox: Option<&str>, approval: Option<&str>, prefix: &str) -> String {
+    [
+        sandbox.map(|mode| format!("{prefix}sandbox_mode = \"{mode}\"")),
+        approval.map(|policy| format!("{prefix}approval_policy = \"{policy}\"")),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>()
+    .join(", ")
+}
+
+/// Build a warning whose texts live under `codex.security.rule.<key>`
+fn warning(
+    rule_id: &'static str,
+    config_path: &str,
+    config_item: String,
+    matched_pattern: String,
+    severity: SecurityWarningSeverity,
+    key: &str,
+) -> SecurityWarning {
+    let (reason, description, recommendation) = rule_texts(key);
+    SecurityWarning {
+        rule_id,
+        config_path: config_path.to_string(),
+        config_item,
+        reason,
+        severity,
+        matched_pattern,
+        description,
+        recommendation,
+    }
+}
+
+fn rule_texts(key: &str) -> (String, String, String) {
+    let (reason, description, recommendation) = match key {
+        "full_access_no_approval" => (
+            t!("codex.security.rule.full_access_no_approval.reason"),
+            t!("codex.security.rule.full_access_no_approval.description"),
+            t!("codex.security.rule.full_access_no_approval.recommendation"),
+        ),
+        "full_access_sandbox" => (
+            t!("codex.security.rule.full_access_sandbox.reason"),
+            t!("codex.security.rule.full_access_sandbox.description"),
+            t!("codex.security.rule.full_access_sandbox.recommendation"),
+        ),
+        "no_approval" => (
+            t!("codex.security.rule.no_approval.reason"),
+            t!("codex.security.rule.no_approval.description"),
+            t!("codex.security.rule.no_approval.recommendation"),
+        ),
+        "network_sandbox" => (
+            t!("codex.security.rule.network_sandbox.reason"),
+            t!("codex.security.rule.network_sandbox.description"),
+            t!("codex.security.rule.network_sandbox.recommendation"),
+        ),
+        "unreadable_agent" => (
+            t!("codex.security.rule.unreadable_agent.reason"),
+            t!("codex.security.rule.unreadable_agent.description"),
+            t!("codex.security.rule.unreadable_agent.recommendation"),
+        ),
+        _ => (
+            t!("codex.security.rule.unrestricted_agent.reason"),
+            t!("codex.security.rule.unrestricted_agent.description"),
+            t!("codex.security.rule.unrestricted_agent.recommendation"),
+        ),
+    };
+    (
+        reason.to_string(),
+        description.to_string(),
+        recommendation.to_string(),
+    )
+}
+
+/// Agent files referenced by `[agents.<name>] config_file` plus `agents/*.toml` next to the config
+fn agent_files(doc: &Value, base_dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = BTreeSet::new();
+
+    if let Some(agents) = doc.get("agents").and_then(Value::as_table) {
+        for agent in agents.values() {
+            if let Some(file) = agent.get("config_file").and_then(Value::as_str) {
+                let path = expand_home(file);
+                let path = if path.is_absolute() {
+                    path
+                } else {
+                    base_dir.join(path)
+                };
+                if path.is_file() {
+                    files.insert(path);
+                }
+            }
+        }
+    }
+
+    let agents_dir = base_dir.join("agents");
+    if agents_dir.is_dir() {
+        for entry in fs::read_dir(&agents_dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "toml") && path.is_file() {
+                files.insert(path);
+            }
+        }
+    }
+
+    Ok(files.into_iter().collect())
+}
+
+fn load_toml(path: &Path) -> Result<Value> {
+    let content = fs::read_to_string(path).with_context(|| {
+        t!(
+            "claude_code.security.read_settings_failed",
+            path = path.display()
+        )
+    })?;
+    toml::from_str(&content).with_context(|| t!("codex.error.parse_codex_config_failed"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checker() -> CodexSecurityChecker {
+        CodexSecurityChecker {
+            patterns: SecurityChecker::with_patterns(Some(vec!["rm -rf".to_string()])),
+            config_files: vec![],
+            enabled: true,
+        }
+    }
+
+    #[test]
+    fn sandbox_and_approval_combinations_are_rated() {
+        let doc: Value = toml::from_str(
+            r#"
+sandbox_mode = "danger-full-access"
+approval_policy = "never"
+
+[profiles.ci]
+sandbox_mode = "workspace-write"
+approval_policy = "never"
+
+[profiles.review]
+sandbox_mode = "read-only"
+approval_policy = "on-request"
+
+[profiles.net]
+sandbox_mode = "workspace-write"
+
+[profiles.net.sandbox_workspace_write]
+network_access = true
+"#,
+        )
+        .unwrap();
+
+        let warnings = checker().audit_config(&doc, "config.toml");
+        let found: Vec<(&str, SecurityWarningSeverity)> = warnings
+            .iter()
+            .map(|w| (w.rule_id, w.severity.clone()))
+            .collect();
+
+        assert_eq!(
+            found,
+            vec![
+                (
+                    codex_rules::FULL_ACCESS_NO_APPROVAL,
+                    SecurityWarningSeverity::Critical
+                ),
+                (codex_rules::NO_APPROVAL, SecurityWarningSeverity::Medium),
+                (
+                    codex_rules::NETWORK_SANDBOX,
+                    SecurityWarningSeverity::Medium
+                ),
+            ]
+        );
+        assert_eq!(
+            warnings[1].config_item,
+            "profiles.ci.sandbox_mode = \"workspace-write\", profiles.ci.approval_policy = \"never\""
+        );
+    }
+
+    #[test]
+    fn mcp_servers_with_shell_commands_are_flagged() {
+        let doc: Value = toml::from_str(
+            r#"
+[mcp_servers.docs]
+command = "npx"
+args = ["-y", "docs-mcp"]
+
+[mcp_servers.inline]
+command = "bash"
+args = ["-lc", "node server.js"]
+
+[mcp_servers.cleanup]
+command = "sh"
+args = ["-c", "rm -rf /tmp/cache"]
+"#,
+        )
+        .unwrap();
+
+        let warnings = checker().audit_config(&doc, "config.toml");
+        let mut rule_ids: Vec<&str> = warnings.iter().map(|w| w.rule_id).collect();
+        rule_ids.sort_unstable();
+        assert_eq!(
+            rule_ids,
+            vec![rules::DANGEROUS_MCP_SERVER, rules::MCP_INLINE_SHELL]
+        );
+    }
+
+    #[test]
+    fn agents_next_to_config_are_checked() {
+        let temp = tempfile::tempdir().unwrap();
+        let config_path = temp.path().join("config.toml");
+        fs::create_dir_all(temp.path().join("agents")).unwrap();
+        fs::write(
+            &config_path,
+            "[agents.reviewer]\nconfig_file = \"agents/reviewer.toml\"\n",
+        )
+        .unwrap();
+        fs::write(
+            temp.path().join("agents/reviewer.toml"),
+            "sandbox_mode = \"danger-full-access\"\ndeveloper_instructions = \"\"\"\nReview code.\nClean up with rm -rf build\n\"\"\"\n",
+        )
+        .unwrap();
+        fs::write(
+            temp.path().join("agents/planner.toml"),
+            "developer_instructions = \"Plan only.\"\n",
+        )
+        .unwrap();
+
+        let warnings = checker().check_config_file(&config_path).unwrap();
+        let rule_ids: Vec<&str> = warnings.iter().map(|w| w.rule_id).collect();
+        assert_eq!(
+            rule_ids,
+            vec![
+                codex_rules::UNRESTRICTED_AGENT,
+                codex_rules::DANGEROUS_AGENT_INSTRUCTIONS
+            ]
+        );
+        assert!(warnings[0].config_path.ends_with("reviewer.toml"));
+    }
+
+    #[test]
+    fn broken_agent_file_keeps_config_warnings() {
+        let temp = tempfile::tempdir().unwrap();
+        let config_path = temp.path().join("config.toml");
+        fs::create_dir_all(temp.path().join("agents")).unwrap();
+        fs::write(
+            &config_path,
+            "sandbox_mode = \"danger-full-access\"\napproval_policy = \"never\"\n",
+        )
+        .unwrap();
+        fs::write(temp.path().join("agents/broken.toml"), "sandbox_mode = \n").unwrap();
+
+        let warnings = checker().check_config_file(&config_path).unwrap();
+        let rule_ids: Vec<&str> = warnings.iter().map(|w| w.rule_id).collect();
+        assert_eq!(
+            rule_ids,
+            vec![
+                codex_rules::FULL_ACCESS_NO_APPROVAL,
+                codex_rules::UNREADABLE_AGENT
+            ]
+        );
+        assert!(warnings[1].config_path.ends_with("broken.toml"));
+    }
+
+    #[test]
+    fn disabled_checker_reports_nothing() {
+        let config: Config = toml::from_str("[security]\nenabled = false\n").unwrap();
+        let checker = CodexSecurityChecker::from_config(&config);
+        assert!(checker.check_codex_config().unwrap().is_empty());
+    }
+}
@@ -1,16 +1,23 @@
 use crate::config::{CURSOR_APP, Config, TARGET_CURSOR_RULES_DIR};
 use crate::fs_utils::atomic_write_with_mode;
 use crate::path_utils::{safe_parent_for_creation, validate_path_segment};
+use crate::prompts::command::{AppStatusArgs, PromptApp};
 use crate::prompts::confirm::confirm_overwrite;
+use crate::prompts::cursor_rule::{CursorRule, CursorRuleFrontmatter, lint_rule, repo_files};
 use crate::prompts::paths::{cwd, project_root};
+use crate::prompts::render::{PROJECT_SCOPE, PromptRenderer};
 use crate::prompts::store as prompt_store;
 use crate::skills::cli::interactive::is_interactive;
+use crate::x::agents::plan::select_stems;
 use anyhow::{Result, anyhow, bail};
 use clap::{Args, Subcommand, ValueEnum};
 use inquire::{Confirm, MultiSelect};
 use std::fs;
 use std::path::{Path, PathBuf};
 
+const LEGACY_CURSORRULES_FILE: &str = ".cursorrules";
+const LEGACY_TEMPLATE_NAME: &str = "cursorrules";
+
 #[derive(Args, Debug, Clone)]
 #[command(about = "Manage Cursor prompt templates and generate Cursor rules")]
 #[command(subcommand_required = false)]
@@ -34,10 +41,38 @@ pub enum CursorPromptsCommand {
         #[arg(long)]
         name: Option<String>,
 
+        /// Rule description written to the frontmatter (used for agent-requested rules)
+        #[arg(long)]
+        description: Option<String>,
+
+        /// Comma-separated globs written to the frontmatter (e.g. `src/**/*.ts,*.tsx`)
+        #[arg(long)]
+        globs: Option<String>,
+
+        /// `alwaysApply` value written to the frontmatter
+        #[arg(long = "always-apply")]
+        always_apply: Option<bool>,
+
         /// Force generation, skip repository detection / overwrite checks
         #[arg(long)]
         force: bool,
     },
+    /// Import project rules (`.cursor/rules/*.mdc`, legacy `.cursorrules`) into templates
+    Import {
+        /// Only import selected rules (repeatable; matches the rule file stem)
+        #[arg(long, value_delimiter = ',', action = clap::ArgAction::Append)]
+        only: Vec<String>,
+
+        /// Overwrite existing templates without confirmation
+        #[arg(long)]
+        force: bool,
+    },
+    /// Warn about malformed frontmatter and globs that match no files in the project
+    Lint,
+    /// Show whether generated targets still match their templates
+    Status(AppStatusArgs),
+    /// Like `status`, but exit non-zero when any target drifted (for CI)
+    Check(AppStatusArgs),
     /// List available templates
     List,
     /// Create or update a template
@@ -79,14 +114,37 @@ pub fn run(args: &CursorPromptsArgs) -> Result<()> {
             if interactive {
                 return run_wizard();
             }
-            bail!("In non-interactive mode, a subcommand is required (gen/list/upsert/rm).");
+            bail!(
+                "In non-interactive mode, a subcommand is required (gen/status/check/import/lint/list/upsert/rm)."
+            );
         }
         Some(CursorPromptsCommand::Gen {
             template,
             scope,
             name,
+            description,
+            globs,
+            always_apply,
             force,
-        }) => run_gen(template, scope, name.as_deref(), *force, interactive),
+        }) => {
+            let frontmatter = CursorRuleFrontmatter {
+                description: description.clone(),
+                globs: globs.clone(),
+                always_apply: *always_apply,
+            };
+            run_gen(
+                template,
+                scope,
+                name.as_deref(),
+                &frontmatter,
+                *force,
+                interactive,
+            )
+        }
+        Some(CursorPromptsCommand::Import { only, force }) => run_import(only, *force, interactive),
+        Some(CursorPromptsCommand::Lint) => run_lint(interactive),
+        Some(CursorPromptsCommand::Status(status)) => status.run(PromptApp::Cursor, false),
+        Some(CursorPromptsCommand::Check(status)) => status.run(PromptApp::Cursor, true),
         Some(CursorPromptsCommand::List) => run_list(),
         Some(CursorPromptsCommand::Upsert { name, content }) => {
             run_upsert(name, content.content.as_deref(), content.file.as_deref())
@@ -157,9 +215,13 @@ fn run_gen(
     template: &str,
     scopes: &[PromptScopeArg],
     name: Option<&str>,
+    frontmatter: &CursorRuleFrontmatter,
     force: bool,
     interactive: bool,
 ) -> Result<()> {
+    frontmatter
+        .validate()
+        .map_err(|e| anyhow!("invalid rule frontmatter: {e}"))?;
     if scopes.iter().any(|s| *s != PromptScopeArg::Project) {
         return Err(anyhow!(t!(
             "errors.invalid_scope_for_app",
@@ -175,6 +237,17 @@ fn run_gen(
 
     let config = Config::new()?;
     let content = prompt_store::read_template(&config, CURSOR_APP, template)?;
+    let content = PromptRenderer::new(&config, Some(&root))?.render_app_template(
+        template,
+        &content,
+        CURSOR_APP,
+        PROJECT_SCOPE,
+    )?;
+    let content = if *frontmatter == CursorRuleFrontmatter::default() {
+        content
+    } else {
+        CursorRule::parse(&content).with_frontmatter(frontmatter)
+    };
 
     let output_name = name.unwrap_or(template);
     let output_name = validate_path_segment(output_name, "prompt name")
@@ -200,9 +273,123 @@ fn run_gen(
         "{}",
         t!("messages.rule_generated", path = target_path.display())
     );
+    print_lint_warnings(&target_path, &lint_rule(&content, &repo_files(&root)));
     Ok(())
 }
 
+fn run_import(only: &[String], force: bool, interactive: bool) -> Result<()> {
+    let cwd = cwd()?;
+    let Some(root) = project_root(&cwd, force, interactive)? else {
+        return Ok(());
+    };
+
+    let mut candidates: Vec<(String, PathBuf, String)> = Vec::new();
+    for path in project_rule_files(&root)? {
+        let Some(stem) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        let content = fs::read_to_string(&path)?;
+        candidates.push((stem.to_string(), path, content));
+    }
+    let legacy = root.join(LEGACY_CURSORRULES_FILE);
+    if legacy.is_file() {
+        // Legacy rules always applied; keep that explicit once converted to `.mdc`.
+        let content = CursorRule::parse(&fs::read_to_string(&legacy)?)
+            .with_frontmatter(&CursorRuleFrontmatter::default());
+        candidates.push((LEGACY_TEMPLATE_NAME.to_string(), legacy, content));
+    }
+    if candidates.is_empty() {
+        println!("{}", t!("prompt.cursor.import.none", path = root.display()));
+        return Ok(());
+    }
+
+    let available: Vec<String> = candidates.iter().map(|(name, _, _)| name.clone()).collect();
+    let selected = select_stems(&available, only, "rule name")?;
+
+    let config = Config::new()?;
+    for (name, source, content) in candidates {
+        if !selected.contains(&name) {
+            continue;
+        }
+        let name = validate_path_segment(&name, "template name")
+            .map_err(|e| anyhow!("invalid template name: {e}"))?;
+        let template_path = config.rule_file_path(CURSOR_APP, &name);
+        if template_path.exists() {
+            if fs::read_to_string(&template_path).is_ok_and(|existing| existing == content) {
+                println!("{}", t!("prompt.cursor.import.unchanged", name = name));
+                continue;
+            }
+            if !force && !confirm_overwrite(&template_path, interactive)? {
+                println!("{}", t!("messages.operation_cancelled"));
+                continue;
+            }
+        }
+        let path = prompt_store::upsert_template(&config, CURSOR_APP, &name, &content)?;
+        println!(
+            "{}",
+            t!(
+                "prompt.cursor.import.imported",
+                source = source.display(),
+                path = path.display()
+            )
+        );
+    }
+    Ok(())
+}
+
+fn run_lint(interactive: bool) -> Result<()> {
+    let cwd = cwd()?;
+    let Some(root) = project_root(&cwd, false, interactive)? else {
+        return Ok(());
+    };
+
+    let files = repo_files(&root);
+    let mut total = 0;
+    for path in project_rule_files(&root)? {
+        let warnings = lint_rule(&fs::read_to_string(&path)?, &files);
+        total += warnings.len();
+        print_lint_warnings(&path, &warnings);
+    }
+    if root.join(LEGACY_CURSORRULES_FILE).is_file() {
+        total += 1;
+        println!("{}", t!("prompt.cursor.lint.legacy_cursorrules"));
+    }
+
+    if total == 0 {
+        println!("{}", t!("prompt.cursor.lint.clean"));
+    } else {
+        println!("{}", t!("prompt.cursor.lint.summary", count = total));
+    }
+    Ok(())
+}
+
+/// `.mdc` files directly under the project's `.cursor/rules`, sorted.
+fn project_rule_files(root: &Path) -> Result<Vec<PathBuf>> {
+    let rules_dir = root.join(TARGET_CURSOR_RULES_DIR);
+    if !rules_dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut paths: Vec<PathBuf> = fs::read_dir(&rules_dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|p| p.is_file() && p.extension().and_then(|e| e.to_str()) == Some("mdc"))
+        .collect();
+    paths.sort();
+    Ok(paths)
+}
+
+fn print_lint_warnings(path: &Path, warnings: &[String]) {
+    for warning in warnings {
+        println!(
+            "{}",
+            t!(
+                "prompt.cursor.lint.warning",
+                path = path.display(),
+                warning = warning
+            )
+        );
+    }
+}
+
 fn run_wizard() -> Result<()> {
     let cwd = cwd()?;
     let Some(root) = project_root(&cwd, false, true)? else {
@@ -223,8 +410,11 @@ fn run_wizard() -> Result<()> {
         return Ok(());
     }
 
+    let renderer = PromptRenderer::new(&config, Some(&root))?;
     for template in picked {
         let content = prompt_store::read_template(&config, CURSOR_APP, &template)?;
+        let content =
+            renderer.render_app_template(&template, &content, CURSOR_APP, PROJECT_SCOPE)?;
         let name = validate_path_segment(&template, "prompt name")
             .map_err(|e| anyhow!("invalid prompt name: {e}"))?;
         let target_path = root
@@ -0,0 +1,318 @@
+use crate::editor::{parse_editor_command, select_editor_raw};
+use crate::fs_utils::{atomic_write_new_with_mode, atomic_write_with_mode};
+use crate::path_utils::safe_parent_for_creation;
+use crate::skills::cli::interactive::is_interactive;
+use crate::skills::shared::git::find_git_root;
+use crate::x::codex::config::codex_home_dir;
+use crate::x::mcp::registry::{McpRegistry, McpTarget};
+use crate::x::mcp::targets::{McpPaths, McpScope, ServerState, TargetFile, server_state};
+use anyhow::{Context, Result, bail};
+use clap::{Args, Subcommand};
+use inquire::Confirm;
+use similar::TextDiff;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+#[derive(Args, Debug, Clone)]
+#[command(about = "Manage a shared MCP server registry synced to Claude Code, Codex and Cursor")]
+pub struct McpArgs {
+    #[command(subcommand)]
+    pub command: McpCommand,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum McpCommand {
+    /// List servers in the registry
+    List,
+    /// Edit the registry file (<config>/mcp.toml)
+    Edit,
+    /// Show whether each registry server is present and current in each tool (read-only)
+    Status(McpTargetArgs),
+    /// Write registry servers into each tool's MCP configuration
+    Sync {
+        #[command(flatten)]
+        targets: McpTargetArgs,
+
+        /// Only sync these servers (repeatable, or comma-separated)
+        #[arg(long, value_delimiter = ',')]
+        server: Vec<String>,
+
+        /// Show the diff but do not write any files
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Assume "yes" for confirmations (required for non-interactive writes)
+        #[arg(long, short = 'y')]
+        yes: bool,
+    },
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct McpTargetArgs {
+    /// Tool(s) to target (default: all)
+    #[arg(long, short = 't', value_enum, value_delimiter = ',')]
+    pub target: Vec<McpTarget>,
+
+    /// Project files or per-user files
+    #[arg(long, value_enum, default_value = "project")]
+    pub scope: McpScope,
+}
+
+impl McpTargetArgs {
+    fn targets(&self) -> Vec<McpTarget> {
+        if self.target.is_empty() {
+            McpTarget::ALL.to_vec()
+        } else {
+            let mut targets = self.target.clone();
+            targets.sort();
+            targets.dedup();
+            targets
+        }
+    }
+}
+
+pub fn run(args: &McpArgs) -> Result<()> {
+    match &args.command {
+        McpCommand::List => run_list(),
+        McpCommand::Edit => run_edit(),
+        McpCommand::Status(targets) => run_status(targets),
+        McpCommand::Sync {
+            targets,
+            server,
+            dry_run,
+            yes,
+        } => run_sync(targets, server, *dry_run, *yes),
+    }
+}
+
+fn resolve_paths() -> Result<McpPaths> {
+    let cwd = std::env::current_dir().context(t!("x.mcp.current_dir_failed"))?;
+    Ok(McpPaths {
+        project_root: find_git_root(&cwd).unwrap_or(cwd),
+        home: crate::config::home_dir()?,
+        codex_home: codex_home_dir()?,
+    })
+}
+
+fn run_list() -> Result<()> {
+    let registry = McpRegistry::load()?;
+    println!(
+        "{}",
+        t!(
+            "x.mcp.list_header",
+            path = McpRegistry::file_path()?.display()
+        )
+    );
+    if registry.servers.is_empty() {
+        println!("{}", t!("x.mcp.empty"));
+        return Ok(());
+    }
+
+    for (name, server) in &registry.servers {
+        let endpoint = match (&server.command, &server.url) {
+            (Some(command), _) => std::iter::once(command.as_str())
+                .chain(server.args.iter().map(String::as_str))
+                .collect::<Vec<_>>()
+                .join(" "),
+            (None, Some(url)) => url.clone(),
+            (None, None) => String::new(),
+        };
+        let targets = if server.targets.is_empty() {
+            t!("x.mcp.all_targets").to_string()
+        } else {
+            server
+                .targets
+                .iter()
+                .map(|target| target.label())
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+        println!(
+            "{}",
+            t!(
+                "x.mcp.list_item",
+                name = name,
+                endpoint = endpoint,
+                targets = targets
+            )
+        );
+        if let Some(description) = &server.description {
+            println!("    {description}");
+        }
+    }
+    Ok(())
+}
+
+fn run_edit() -> Result<()> {
+    let path = McpRegistry::file_path()?;
+    if let Some(parent) = safe_parent_for_creation(&path) {
+        fs::create_dir_all(parent)
+            .with_context(|| t!("x.mcp.create_dir_failed", path = parent.display()))?;
+    }
+    let template = include_str!("../../../templates/mcp/default.toml");
+    if atomic_write_new_with_mode(&path, template.as_bytes(), Some(0o600))? {
+        println!("{}", t!("x.mcp.created", path = path.display()));
+    }
+
+    let editor_raw = select_editor_raw();
+    let (editor_cmd, editor_args) = parse_editor_command(&editor_raw).map_err(|e| {
+        anyhow::anyhow!(t!(
+            "x.mcp.invalid_editor_command",
+            editor = editor_raw,
+            error = e
+        ))
+    })?;
+    let status = Command::new(&editor_cmd)
+        .args(editor_args)
+        .arg(&path)
+        .status()
+        .with_context(|| t!("x.mcp.open_editor_failed", editor = editor_raw))?;
+    if !status.success() {
+        bail!(t!("x.mcp.editor_exit_status", status = status));
+    }
+
+    // Surface mistakes right away instead of at the next sync.
+    McpRegistry::load()?;
+    Ok(())
+}
+
+fn run_status(args: &McpTargetArgs) -> Result<()> {
+    let registry = McpRegistry::load()?;
+    let paths = resolve_paths()?;
+
+    for target in args.targets() {
+        let path = paths.target_file(target, args.scope);
+        let file = TargetFile::read(target, &path)?;
+        println!("{} ({})", target.label(), path.display());
+
+        let servers = registry.servers_for(target, &[]);
+        if servers.is_empty() && file.servers.is_empty() {
+            println!("{}", t!("x.mcp.status_no_servers"));
+        }
+        for (name, server) in &servers {
+            println!("  - {name}: {}", server_state(&file, name, server).label());
+        }
+        for name in file.servers.keys() {
+            if !servers.iter().any(|(managed, _)| managed == name) {
+                println!("{}", t!("x.mcp.status_unmanaged", name = name));
+            }
+        }
+    }
+    Ok(())
+}
+
+struct FileChange {
+    path: PathBuf,
+    before: String,
+    after: String,
+    created: bool,
+}
+
+fn run_sync(args: &McpTargetArgs, only: &[String], dry_run: bool, yes: bool) -> Result<()> {
+    let registry = McpRegistry::load()?;
+    registry.ensure_known(only)?;
+    let paths = resolve_paths()?;
+
+    let mut changes = Vec::new();
+    for target in args.targets() {
+        let servers = registry.servers_for(target, only);
+        if servers.is_empty() {
+            continue;
+        }
+        let path = paths.target_file(target, args.scope);
+        let file = TargetFile::read(target, &path)?;
+        // Compare parsed servers, not text: the Codex TOML rewrite normalizes formatting.
+        if servers
+            .iter()
+            .all(|(name, server)| server_state(&file, name, server) == ServerState::InSync)
+        {
+            continue;
+        }
+        let after = file.render_with(&servers)?;
+        let before = file.original.clone().unwrap_or_default();
+        changes.push(FileChange {
+            path,
+            created: file.original.is_none(),
+            before,
+            after,
+        });
+    }
+
+    if changes.is_empty() {
+        println!("{}", t!("x.mcp.no_changes"));
+        return Ok(());
+    }
+
+    for change in &changes {
+        let old_label = if change.created {
+            "/dev/null".to_string()
+        } else {
+            change.path.display().to_string()
+        };
+        let diff = TextDiff::from_lines(&change.before, &change.after);
+        print!(
+            "{}",
+            diff.unified_diff()
+                .context_radius(3)
+                .header(&old_label, &change.path.display().to_string())
+        );
+    }
+
+    if dry_run {
+        return Ok(());
+    }
+
+    if !yes {
+        if !is_interactive() {
+            bail!(t!("x.mcp.write_requires_yes"));
+        }
+        let proceed = Confirm::new(&t!("x.mcp.confirm_apply"))
+            .with_default(false)
+            .prompt()
+            .context(t!("x.mcp.confirm_failed"))?;
+        if !proceed {
+            println!("{}", t!("x.mcp.cancelled"));
+            return Ok(());
+        }
+    }
+
+    for change in &changes {
+        if let Some(parent) = safe_parent_for_creation(&change.path) {
+            fs::create_dir_all(parent)
+                .with_context(|| t!("x.mcp.create_dir_failed", path = parent.display()))?;
+        }
+        let mode = if change.created {
+            new_file_mode(args.scope)
+        } else {
+            existing_file_mode(&change.path)
+        };
+        atomic_write_with_mode(&change.path, change.after.as_bytes(), mode)
+            .with_context(|| t!("x.mcp.write_failed", path = change.path.display()))?;
+        println!("{}", t!("x.mcp.updated", path = change.path.display()));
+    }
+    Ok(())
+}
+
+/// Project files are usually committed; per-user files may carry credentials.
+fn new_file_mode(scope: McpScope) -> Option<u32> {
+    match scope {
+        McpScope::Project => Some(0o644),
+        McpScope::User => Some(0o600),
+    }
+}
+
+fn existing_file_mode(path: &Path) -> Option<u32> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let metadata = fs::metadata(path).ok()?;
+        Some(metadata.permissions().mode() & 0o777)
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = path;
+        None
+    }
+}
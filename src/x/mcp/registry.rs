@@ -0,0 +1,186 @@
+use crate::config::resolve_config_dir;
+use crate::fs_utils::atomic_write_with_mode;
+use crate::path_utils::safe_parent_for_creation;
+use anyhow::{Context, Result, bail};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Agent tools that MCP servers can be synced into.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, ValueEnum, Serialize, Deserialize,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum McpTarget {
+    /// `.mcp.json` (project) / `~/.claude.json` (user)
+    ClaudeCode,
+    /// `.codex/config.toml` (project) / `$CODEX_HOME/config.toml` (user)
+    Codex,
+    /// `.cursor/mcp.json` (project) / `~/.cursor/mcp.json` (user)
+    Cursor,
+}
+
+impl McpTarget {
+    pub const ALL: [McpTarget; 3] = [McpTarget::ClaudeCode, McpTarget::Codex, McpTarget::Cursor];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            McpTarget::ClaudeCode => "claude-code",
+            McpTarget::Codex => "codex",
+            McpTarget::Cursor => "cursor",
+        }
+    }
+}
+
+/// One MCP server definition: either a local command (stdio) or a remote URL.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct McpServer {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+    /// Tools this server is synced into (default: all)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub targets: Vec<McpTarget>,
+}
+
+impl McpServer {
+    pub fn applies_to(&self, target: McpTarget) -> bool {
+        self.targets.is_empty() || self.targets.contains(&target)
+    }
+
+    fn validate(&self, name: &str) -> Result<()> {
+        match (&self.command, &self.url) {
+            (Some(_), Some(_)) => bail!(t!("x.mcp.server_both_command_and_url", name = name)),
+            (None, None) => bail!(t!("x.mcp.server_missing_endpoint", name = name)),
+            (Some(command), None) if command.trim().is_empty() => {
+                bail!(t!("x.mcp.server_empty_command", name = name))
+            }
+            (None, Some(url)) if !(url.starts_with("http://") || url.starts_with("https://")) => {
+                bail!(t!("x.mcp.server_invalid_url", name = name, url = url))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// The llman-managed MCP server registry (`<config>/mcp.toml`).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct McpRegistry {
+    #[serde(default)]
+    pub servers: BTreeMap<String, McpServer>,
+}
+
+impl McpRegistry {
+    pub fn file_path() -> Result<PathBuf> {
+        Ok(resolve_config_dir(None)?.join("mcp.toml"))
+    }
+
+    pub fn load() -> Result<Self> {
+        Self::load_from_path(&Self::file_path()?)
+    }
+
+    pub fn load_from_path(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path)
+            .with_context(|| t!("x.mcp.registry_read_failed", path = path.display()))?;
+        let registry: Self = toml::from_str(&content)
+            .with_context(|| t!("x.mcp.registry_parse_failed", path = path.display()))?;
+        for (name, server) in &registry.servers {
+            server.validate(name)?;
+        }
+        Ok(registry)
+    }
+
+    pub fn save_to_path(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = safe_parent_for_creation(path) {
+            fs::create_dir_all(parent)
+                .with_context(|| t!("x.mcp.create_dir_failed", path = parent.display()))?;
+        }
+        let content =
+            toml::to_string_pretty(self).context(t!("x.mcp.registry_serialize_failed"))?;
+        atomic_write_with_mode(path, content.as_bytes(), Some(0o600))
+            .with_context(|| t!("x.mcp.write_failed", path = path.display()))
+    }
+
+    /// Servers for `target`, restricted to `only` when non-empty.
+    pub fn servers_for(&self, target: McpTarget, only: &[String]) -> Vec<(&str, &McpServer)> {
+        self.servers
+            .iter()
+            .filter(|(name, server)| {
+                server.applies_to(target) && (only.is_empty() || only.contains(name))
+            })
+            .map(|(name, server)| (name.as_str(), server))
+            .collect()
+    }
+
+    /// Fail on `--server` names that are not in the registry.
+    pub fn ensure_known(&self, names: &[String]) -> Result<()> {
+        let unknown: Vec<&str> = names
+            .iter()
+            .filter(|name| !self.servers.contains_key(*name))
+            .map(String::as_str)
+            .collect();
+        if !unknown.is_empty() {
+            bail!(t!("x.mcp.unknown_servers", names = unknown.join(", ")));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registry_roundtrips_and_filters_by_target() {
+        let registry: McpRegistry = toml::from_str(
+            r#"
+[servers.docs]
+command = "npx"
+args = ["-y", "docs-mcp"]
+
+[servers.search]
+url = "https://mcp.example.com/sse"
+targets = ["cursor"]
+"#,
+        )
+        .unwrap();
+
+        let names = |target| {
+            registry
+                .servers_for(target, &[])
+                .into_iter()
+                .map(|(name, _)| name.to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(McpTarget::Codex), vec!["docs"]);
+        assert_eq!(names(McpTarget::Cursor), vec!["docs", "search"]);
+
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("mcp.toml");
+        registry.save_to_path(&path).unwrap();
+        assert_eq!(McpRegistry::load_from_path(&path).unwrap(), registry);
+    }
+
+    #[test]
+    fn load_rejects_servers_without_command_or_url() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("mcp.toml");
+        fs::write(&path, "[servers.broken]\ndescription = \"x\"\n").unwrap();
+        let err = McpRegistry::load_from_path(&path).unwrap_err();
+        assert!(err.to_string().contains("broken"), "{err}");
+    }
+}
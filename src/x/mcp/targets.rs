@@ -0,0 +1,318 @@
+//! Per-tool MCP server formats and file locations.
+//!
+//! JSON targets are patched member-by-member so comments and unrelated settings survive;
+//! the Codex TOML target is rewritten the same way `upsert_to_codex_config` does.
+
+use crate::jsonc;
+use crate::x::mcp::registry::{McpServer, McpTarget};
+use anyhow::{Context, Result, anyhow};
+use clap::ValueEnum;
+use llm_json::{RepairOptions, loads};
+use serde_json::{Map, Value, json};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum McpScope {
+    /// Files inside the current project
+    Project,
+    /// Per-user files in the home directory
+    User,
+}
+
+/// Directories the target paths are resolved against.
+#[derive(Debug, Clone)]
+pub struct McpPaths {
+    pub project_root: PathBuf,
+    pub home: PathBuf,
+    pub codex_home: PathBuf,
+}
+
+impl McpPaths {
+    pub fn target_file(&self, target: McpTarget, scope: McpScope) -> PathBuf {
+        match (target, scope) {
+            (McpTarget::ClaudeCode, McpScope::Project) => self.project_root.join(".mcp.json"),
+            (McpTarget::ClaudeCode, McpScope::User) => self.home.join(".claude.json"),
+            (McpTarget::Codex, McpScope::Project) => {
+                self.project_root.join(".codex").join("config.toml")
+            }
+            (McpTarget::Codex, McpScope::User) => self.codex_home.join("config.toml"),
+            (McpTarget::Cursor, McpScope::Project) => {
+                self.project_root.join(".cursor").join("mcp.json")
+            }
+            (McpTarget::Cursor, McpScope::User) => self.home.join(".cursor").join("mcp.json"),
+        }
+    }
+}
+
+/// Key holding the server table in each tool's file.
+fn servers_key(target: McpTarget) -> &'static str {
+    match target {
+        McpTarget::Codex => "mcp_servers",
+        McpTarget::ClaudeCode | McpTarget::Cursor => "mcpServers",
+    }
+}
+
+/// Render a server in the target tool's native shape (as JSON; Codex converts to TOML).
+pub fn render_server(target: McpTarget, server: &McpServer) -> Value {
+    let mut out = Map::new();
+    match (&server.command, &server.url) {
+        (Some(command), _) => {
+            if target == McpTarget::ClaudeCode {
+                out.insert("type".into(), json!("stdio"));
+            }
+            out.insert("command".into(), json!(command));
+            if !server.args.is_empty() || target != McpTarget::Codex {
+                out.insert("args".into(), json!(server.args));
+            }
+            if !server.env.is_empty() {
+                out.insert("env".into(), json!(server.env));
+            }
+        }
+        (None, Some(url)) => {
+            if target == McpTarget::ClaudeCode {
+                let kind = if url.trim_end_matches('/').ends_with("/sse") {
+                    "sse"
+                } else {
+                    "http"
+                };
+                out.insert("type".into(), json!(kind));
+            }
+            out.insert("url".into(), json!(url));
+            if !server.headers.is_empty() {
+                let key = match target {
+                    McpTarget::Codex => "http_headers",
+                    _ => "headers",
+                };
+                out.insert(key.into(), json!(server.headers));
+            }
+        }
+        (None, None) => {}
+    }
+    Value::Object(out)
+}
+
+/// Current contents of a target file and the servers it defines.
+#[derive(Debug, Clone)]
+pub struct TargetFile {
+    pub target: McpTarget,
+    pub path: PathBuf,
+    pub original: Option<String>,
+    pub servers: BTreeMap<String, Value>,
+}
+
+impl TargetFile {
+    pub fn read(target: McpTarget, path: &Path) -> Result<Self> {
+        let original = if path.exists() {
+            Some(
+                fs::read_to_string(path)
+                    .with_context(|| t!("x.mcp.read_failed", path = path.display()))?,
+            )
+        } else {
+            None
+        };
+
+        let doc = match original.as_deref() {
+            Some(content) => parse_document(target, content)
+                .with_context(|| t!("x.mcp.parse_failed", path = path.display()))?,
+            None => json!({}),
+        };
+        let servers = doc
+            .get(servers_key(target))
+            .and_then(Value::as_object)
+            .map(|servers| {
+                servers
+                    .iter()
+                    .map(|(name, value)| (name.clone(), value.clone()))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok(Self {
+            target,
+            path: path.to_path_buf(),
+            original,
+            servers,
+        })
+    }
+
+    /// File content after upserting `servers`; unrelated content is preserved.
+    pub fn render_with(&self, servers: &[(&str, &McpServer)]) -> Result<String> {
+        match self.target {
+            McpTarget::Codex => self.render_toml(servers),
+            McpTarget::ClaudeCode | McpTarget::Cursor => self.render_json(servers),
+        }
+    }
+
+    fn render_json(&self, servers: &[(&str, &McpServer)]) -> Result<String> {
+        let key = servers_key(self.target);
+        let mut content = self.original.clone().unwrap_or_else(|| "{}\n".to_string());
+
+        for (name, server) in servers {
+            let value = render_server(self.target, server);
+            content = match jsonc::set_member(&content, &[key, name], &value) {
+                Some(patched) => patched,
+                None => {
+                    // Shape we cannot patch in place: fall back to a full rewrite.
+                    let mut doc = parse_document(self.target, &content)?;
+                    if !doc.is_object() {
+                        doc = json!({});
+                    }
+                    let root = doc.as_object_mut().expect("object");
+                    let table = root.entry(key).or_insert_with(|| json!({}));
+                    if !table.is_object() {
+                        *table = json!({});
+                    }
+                    table
+                        .as_object_mut()
+                        .expect("object")
+                        .insert((*name).to_string(), value);
+                    let mut pretty = serde_json::to_string_pretty(&doc)?;
+                    pretty.push('\n');
+                    pretty
+                }
+            };
+        }
+        Ok(content)
+    }
+
+    fn render_toml(&self, servers: &[(&str, &McpServer)]) -> Result<String> {
+        let mut doc: toml::Value = match self.original.as_deref() {
+            Some(content) => toml::from_str(content)?,
+            None => toml::Value::Table(toml::map::Map::new()),
+        };
+        let root = doc
+            .as_table_mut()
+            .ok_or_else(|| anyhow!(t!("x.mcp.not_a_table", path = self.path.display())))?;
+        let table = root
+            .entry(servers_key(self.target))
+            .or_insert_with(|| toml::Value::Table(toml::map::Map::new()));
+        let table = table
+            .as_table_mut()
+            .ok_or_else(|| anyhow!(t!("x.mcp.servers_not_a_table", path = self.path.display())))?;
+
+        for (name, server) in servers {
+            let value: toml::Value = serde_json::from_value(render_server(self.target, server))
+                .context(t!("x.mcp.convert_failed"))?;
+            table.insert((*name).to_string(), value);
+        }
+        Ok(toml::to_string_pretty(&doc)?)
+    }
+}
+
+fn parse_document(target: McpTarget, content: &str) -> Result<Value> {
+    match target {
+        McpTarget::Codex => {
+            let doc: toml::Value = toml::from_str(content)?;
+            Ok(serde_json::to_value(doc)?)
+        }
+        McpTarget::ClaudeCode | McpTarget::Cursor => match serde_json::from_str(content) {
+            Ok(value) => Ok(value),
+            Err(_) => loads(content, &RepairOptions::default())
+                .map_err(|e| anyhow!(t!("x.mcp.invalid_json", error = e))),
+        },
+    }
+}
+
+/// Sync state of one registry server inside one target file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServerState {
+    InSync,
+    Missing,
+    Differs,
+}
+
+impl ServerState {
+    pub fn label(self) -> &'static str {
+        match self {
+            ServerState::InSync => "in-sync",
+            ServerState::Missing => "missing",
+            ServerState::Differs => "differs",
+        }
+    }
+}
+
+pub fn server_state(file: &TargetFile, name: &str, server: &McpServer) -> ServerState {
+    match file.servers.get(name) {
+        None => ServerState::Missing,
+        Some(existing) if *existing == render_server(file.target, server) => ServerState::InSync,
+        Some(_) => ServerState::Differs,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stdio() -> McpServer {
+        McpServer {
+            command: Some("npx".to_string()),
+            args: vec!["-y".to_string(), "docs-mcp".to_string()],
+            env: BTreeMap::from([("TOKEN".to_string(), "${DOCS_TOKEN}".to_string())]),
+            ..McpServer::default()
+        }
+    }
+
+    #[test]
+    fn json_targets_keep_comments_and_unrelated_servers() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("mcp.json");
+        fs::write(
+            &path,
+            "{\n  // team servers\n  \"mcpServers\": {\n    \"other\": { \"command\": \"x\" }\n  }\n}\n",
+        )
+        .unwrap();
+
+        let file = TargetFile::read(McpTarget::Cursor, &path).unwrap();
+        let server = stdio();
+        assert_eq!(server_state(&file, "docs", &server), ServerState::Missing);
+
+        let content = file.render_with(&[("docs", &server)]).unwrap();
+        assert!(content.contains("// team servers"));
+        fs::write(&path, &content).unwrap();
+
+        let file = TargetFile::read(McpTarget::Cursor, &path).unwrap();
+        assert_eq!(server_state(&file, "docs", &server), ServerState::InSync);
+        assert!(file.servers.contains_key("other"));
+        assert_eq!(file.render_with(&[("docs", &server)]).unwrap(), content);
+    }
+
+    #[test]
+    fn codex_target_writes_mcp_servers_table() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("config.toml");
+        fs::write(&path, "model = \"o3\"\n").unwrap();
+
+        let file = TargetFile::read(McpTarget::Codex, &path).unwrap();
+        let content = file.render_with(&[("docs", &stdio())]).unwrap();
+        let doc: toml::Value = toml::from_str(&content).unwrap();
+        assert_eq!(doc["model"].as_str(), Some("o3"));
+        assert_eq!(doc["mcp_servers"]["docs"]["command"].as_str(), Some("npx"));
+        assert_eq!(
+            doc["mcp_servers"]["docs"]["env"]["TOKEN"].as_str(),
+            Some("${DOCS_TOKEN}")
+        );
+
+        fs::write(&path, &content).unwrap();
+        let file = TargetFile::read(McpTarget::Codex, &path).unwrap();
+        assert_eq!(server_state(&file, "docs", &stdio()), ServerState::InSync);
+    }
+
+    #[test]
+    fn remote_servers_use_each_tool_shape() {
+        let server = McpServer {
+            url: Some("https://mcp.example.com/sse".to_string()),
+            headers: BTreeMap::from([("X-Team".to_string(), "core".to_string())]),
+            ..McpServer::default()
+        };
+        assert_eq!(
+            render_server(McpTarget::ClaudeCode, &server),
+            json!({"type": "sse", "url": "https://mcp.example.com/sse", "headers": {"X-Team": "core"}})
+        );
+        assert_eq!(
+            render_server(McpTarget::Codex, &server),
+            json!({"url": "https://mcp.example.com/sse", "http_headers": {"X-Team": "core"}})
+        );
+    }
+}
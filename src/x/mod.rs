@@ -1,3 +1,7 @@
+pub mod account_check;
+pub mod agents;
 pub mod claude_code;
 pub mod codex;
 pub mod cursor;
+pub mod mcp;
+pub mod project_accounts;
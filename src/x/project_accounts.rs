@@ -0,0 +1,200 @@
+//! Per-project account pinning.
+//!
+//! A repository declares its account group under `accounts:` in `.llman/config.yaml`.
+//! A per-user `.llman/config.local.yaml` (not meant to be committed) may change the default
+//! group, but only the project file defines the allow-list.
+
+use crate::config_schema::{AccountPin, ProjectAccountsConfig, find_config_root};
+use anyhow::{Result, anyhow, bail};
+use rust_i18n::t;
+use serde::Deserialize;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const PROJECT_CONFIG_FILE: &str = "config.yaml";
+pub const LOCAL_OVERRIDE_FILE: &str = "config.local.yaml";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccountApp {
+    ClaudeCode,
+    Codex,
+}
+
+impl AccountApp {
+    fn pin(self, accounts: ProjectAccountsConfig) -> Option<AccountPin> {
+        match self {
+            Self::ClaudeCode => accounts.claude_code,
+            Self::Codex => accounts.codex,
+        }
+    }
+}
+
+/// Only the `accounts` section matters here; the rest of the project config is left to
+/// the tools that own it.
+#[derive(Debug, Default, Deserialize)]
+struct AccountsSection {
+    #[serde(default)]
+    accounts: Option<ProjectAccountsConfig>,
+}
+
+/// Account policy that applies to the current project.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProjectAccountPolicy {
+    /// Default group and the file that set it.
+    pub default_group: Option<(String, PathBuf)>,
+    /// Allowed groups; empty allows any group.
+    pub allowed: Vec<String>,
+    /// Project config file defining the allow-list.
+    pub allowed_source: Option<PathBuf>,
+}
+
+impl ProjectAccountPolicy {
+    pub fn current(app: AccountApp) -> Result<Self> {
+        Self::discover(&env::current_dir()?, app)
+    }
+
+    pub fn discover(start: &Path, app: AccountApp) -> Result<Self> {
+        let root = find_config_root(start).unwrap_or_else(|| start.to_path_buf());
+        let llman_dir = root.join(".llman");
+        let project_path = llman_dir.join(PROJECT_CONFIG_FILE);
+        let local_path = llman_dir.join(LOCAL_OVERRIDE_FILE);
+
+        let mut policy = Self::default();
+        if let Some(pin) = read_pin(&project_path, app)? {
+            if !pin.allowed.is_empty() {
+                policy.allowed = pin.allowed;
+                policy.allowed_source = Some(project_path.clone());
+            }
+            policy.default_group = non_blank(pin.group).map(|group| (group, project_path));
+        }
+        if let Some(group) = read_pin(&local_path, app)?.and_then(|pin| non_blank(pin.group)) {
+            policy.default_group = Some((group, local_path));
+        }
+        Ok(policy)
+    }
+
+    /// The group to use when the user did not name one.
+    pub fn default_group(&self) -> Option<&str> {
+        self.default_group.as_ref().map(|(group, _)| group.as_str())
+    }
+
+    /// Refuse groups outside the project's allow-list.
+    pub fn ensure_allowed(&self, group: &str) -> Result<()> {
+        if self.allowed.is_empty() || self.allowed.iter().any(|allowed| allowed == group) {
+            return Ok(());
+        }
+        let path = self
+            .allowed_source
+            .as_deref()
+            .map(|path| path.display().to_string())
+            .unwrap_or_default();
+        bail!(t!(
+            "x.project_accounts.not_allowed",
+            group = group,
+            allowed = self.allowed.join(", "),
+            path = path
+        ))
+    }
+
+    /// Print which project file selected the group.
+    pub fn announce_default(&self) {
+        if let Some((group, path)) = &self.default_group {
+            eprintln!(
+                "{}",
+                t!(
+                    "x.project_accounts.using_default",
+                    group = group,
+                    path = path.display()
+                )
+            );
+        }
+    }
+}
+
+fn read_pin(path: &Path, app: AccountApp) -> Result<Option<AccountPin>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(path).map_err(|e| {
+        anyhow!(t!(
+            "x.project_accounts.read_failed",
+            path = path.display(),
+            error = e
+        ))
+    })?;
+    if content.trim().is_empty() {
+        return Ok(None);
+    }
+    let section: AccountsSection = serde_yaml::from_str(&content).map_err(|e| {
+        anyhow!(t!(
+            "x.project_accounts.parse_failed",
+            path = path.display(),
+            error = e
+        ))
+    })?;
+    Ok(section.accounts.and_then(|accounts| app.pin(accounts)))
+}
+
+fn non_blank(value: Option<String>) -> Option<String> {
+    value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write(root: &Path, file: &str, content: &str) {
+        let dir = root.join(".llman");
+        fs::create_dir_all(&dir).expect("llman dir");
+        fs::write(dir.join(file), content).expect("write config");
+    }
+
+    #[test]
+    fn discovers_pin_from_repo_root_and_applies_local_override() {
+        let temp = TempDir::new().expect("temp dir");
+        let root = temp.path();
+        fs::create_dir_all(root.join(".git")).expect("git dir");
+        let nested = root.join("src").join("app");
+        fs::create_dir_all(&nested).expect("nested");
+        write(
+            root,
+            PROJECT_CONFIG_FILE,
+            "version: \"0.1\"\ntools: {}\naccounts:\n  claude-code:\n    group: work\n    allowed: [work, work-backup]\n  codex:\n    group: openai-work\n",
+        );
+
+        let policy =
+            ProjectAccountPolicy::discover(&nested, AccountApp::ClaudeCode).expect("policy");
+        assert_eq!(policy.default_group(), Some("work"));
+        assert!(policy.ensure_allowed("work-backup").is_ok());
+        let err = policy
+            .ensure_allowed("personal")
+            .expect_err("outside allow-list");
+        assert!(err.to_string().contains("personal"), "{err}");
+
+        write(
+            root,
+            LOCAL_OVERRIDE_FILE,
+            "accounts:\n  claude-code:\n    group: work-backup\n    allowed: [personal]\n",
+        );
+        let policy =
+            ProjectAccountPolicy::discover(&nested, AccountApp::ClaudeCode).expect("policy");
+        assert_eq!(policy.default_group(), Some("work-backup"));
+        assert!(policy.ensure_allowed("personal").is_err());
+
+        let codex = ProjectAccountPolicy::discover(&nested, AccountApp::Codex).expect("policy");
+        assert_eq!(codex.default_group(), Some("openai-work"));
+        assert!(codex.ensure_allowed("anything").is_ok());
+    }
+
+    #[test]
+    fn missing_config_yields_permissive_policy() {
+        let temp = TempDir::new().expect("temp dir");
+        let policy =
+            ProjectAccountPolicy::discover(temp.path(), AccountApp::Codex).expect("policy");
+        assert_eq!(policy, ProjectAccountPolicy::default());
+    }
+}
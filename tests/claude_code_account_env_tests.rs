@@ -130,3 +130,78 @@ FOO = "bar"
     assert!(stderr.contains("Configuration group"));
     assert!(stderr.contains("not found"));
 }
+
+#[cfg(unix)]
+#[test]
+fn claude_code_account_env_merges_extends_and_annotates_inherited_vars() {
+    let temp = TempDir::new().expect("temp dir");
+    let work_dir = temp.path();
+    let config_dir = work_dir.join("config");
+
+    write_claude_code_config(
+        &config_dir,
+        r#"
+[groups.base]
+ANTHROPIC_BASE_URL = "https://api.example.com"
+DEBUG = "1"
+
+[groups.fast]
+extends = ["base"]
+unset = ["DEBUG"]
+ANTHROPIC_MODEL = "fast"
+"#,
+    );
+
+    let output = run_llman(
+        &["x", "claude-code", "account", "env", "fast"],
+        work_dir,
+        &config_dir,
+    );
+    assert!(
+        output.status.success(),
+        "stderr:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().skip(1).collect();
+    assert_eq!(
+        lines,
+        vec![
+            "export ANTHROPIC_BASE_URL='https://api.example.com' # from base",
+            "export ANTHROPIC_MODEL='fast'",
+        ]
+    );
+}
+
+#[test]
+fn claude_code_account_env_refuses_group_outside_project_allow_list() {
+    let temp = TempDir::new().expect("temp dir");
+    let config_dir = temp.path().join("config");
+    write_claude_code_config(
+        &config_dir,
+        r#"
+[groups]
+
+[groups.personal]
+ANTHROPIC_AUTH_TOKEN = "sk-personal"
+"#,
+    );
+    let work_dir = temp.path().join("repo");
+    std::fs::create_dir_all(work_dir.join(".llman")).expect("llman dir");
+    std::fs::write(
+        work_dir.join(".llman").join("config.yaml"),
+        "version: \"0.1\"\ntools: {}\naccounts:\n  claude-code:\n    allowed: [work]\n",
+    )
+    .expect("write project config");
+
+    let output = run_llman(
+        &["x", "claude-code", "account", "env", "personal"],
+        &work_dir,
+        &config_dir,
+    );
+    assert!(!output.status.success(), "expected refusal");
+    assert!(output.stdout.is_empty(), "no env statements on refusal");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("not allowed in this project"), "{stderr}");
+}
@@ -0,0 +1,103 @@
+mod common;
+
+use common::{assert_success, llman_command, prepare_work_and_config_dirs};
+use std::path::Path;
+use std::process::Output;
+use tempfile::TempDir;
+
+fn run_hooks(args: &[&str], home: &Path, work_dir: &Path, config_dir: &Path) -> Output {
+    llman_command(config_dir)
+        .args(["x", "cc", "hooks"])
+        .args(args)
+        .env("HOME", home)
+        .current_dir(work_dir)
+        .output()
+        .expect("run llman")
+}
+
+#[test]
+fn claude_code_hooks_add_force_uninstalls_the_replaced_command() {
+    let temp = TempDir::new().expect("temp dir");
+    let (work_dir, config_dir) = prepare_work_and_config_dirs(temp.path());
+    std::fs::create_dir(work_dir.join(".git")).expect("mkdir .git");
+    let home = temp.path().join("home");
+    std::fs::create_dir_all(&home).expect("mkdir home");
+    let run = |args: &[&str]| run_hooks(args, &home, &work_dir, &config_dir);
+
+    let add = |command: &str, extra: &[&str]| {
+        let mut args = vec![
+            "add",
+            "fmt",
+            "--event",
+            "PostToolUse",
+            "--matcher",
+            "Edit",
+            "--command",
+            command,
+        ];
+        args.extend_from_slice(extra);
+        run(&args)
+    };
+    let installed_commands = || {
+        let settings =
+            std::fs::read_to_string(work_dir.join(".claude/settings.json")).expect("settings");
+        let value: serde_json::Value = serde_json::from_str(&settings).expect("json");
+        value["hooks"]["PostToolUse"][0]["hooks"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| entry["command"].as_str().map(str::to_string))
+            .collect::<Vec<_>>()
+    };
+
+    assert_success(&add("cargo fmt", &[]));
+    assert_success(&run(&["sync", "--scope", "project", "--yes"]));
+
+    let output = add(
+        "cargo fmt --all",
+        &["--force", "--scope", "project", "--yes"],
+    );
+    assert_success(&output);
+    assert_eq!(installed_commands(), Vec::<String>::new());
+    assert_success(&run(&["sync", "--scope", "project", "--yes"]));
+    assert_eq!(installed_commands(), vec!["cargo fmt --all"]);
+
+    // Without --scope the old hook stays installed, with a warning.
+    let output = add("cargo fmt --check", &["--force"]);
+    assert_success(&output);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("--scope project"), "{stderr}");
+    assert_eq!(installed_commands(), vec!["cargo fmt --all"]);
+}
+
+#[test]
+fn claude_code_audit_flags_repo_hooks_without_a_hook_library() {
+    let temp = TempDir::new().expect("temp dir");
+    let (work_dir, config_dir) = prepare_work_and_config_dirs(temp.path());
+    let home = temp.path().join("home");
+    std::fs::create_dir_all(&home).expect("mkdir home");
+    std::fs::create_dir_all(work_dir.join(".claude")).expect("mkdir .claude");
+    std::fs::write(
+        work_dir.join(".claude/settings.json"),
+        r#"{"hooks":{"SessionStart":[{"hooks":[{"type":"command","command":"./setup.sh"}]}]}}"#,
+    )
+    .expect("write settings");
+    let audit = |extra: &[&str]| {
+        llman_command(&config_dir)
+            .args(["x", "cc", "audit", "--json"])
+            .args(extra)
+            .env("HOME", &home)
+            .current_dir(&work_dir)
+            .output()
+            .expect("run llman")
+    };
+
+    let output = audit(&[]);
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("\"unvetted-hook\""), "{stdout}");
+
+    let output = audit(&["--allow-unvetted-hooks"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!stdout.contains("\"unvetted-hook\""), "{stdout}");
+}
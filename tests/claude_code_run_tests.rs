@@ -0,0 +1,76 @@
+#![cfg(unix)]
+
+mod common;
+
+use common::{llman_command, write_claude_code_config, write_executable_script};
+use std::fs;
+use std::path::Path;
+use std::process::Output;
+use tempfile::TempDir;
+
+fn run_with_fake_claude(temp: &Path) -> Output {
+    let config_dir = temp.join("config");
+    let home = temp.join("home");
+    let bin = temp.join("bin");
+    fs::create_dir_all(&home).expect("home dir");
+    fs::create_dir_all(&bin).expect("bin dir");
+    write_executable_script(&bin.join("claude"), "#!/bin/sh\nexit 0\n");
+    write_claude_code_config(
+        &config_dir,
+        "[groups.g]\nANTHROPIC_AUTH_TOKEN = \"sk-test\"\n",
+    );
+
+    let path = format!(
+        "{}:{}",
+        bin.display(),
+        std::env::var("PATH").unwrap_or_default()
+    );
+    llman_command(&config_dir)
+        .args(["x", "cc", "run", "--group", "g"])
+        .env("HOME", &home)
+        .env("PATH", path)
+        .current_dir(temp.join("repo"))
+        .output()
+        .expect("run llman")
+}
+
+#[test]
+fn claude_code_run_is_not_blocked_by_audit_only_rules() {
+    let temp = TempDir::new().expect("temp dir");
+    let repo = temp.path().join("repo");
+    fs::create_dir_all(repo.join(".claude")).expect("claude dir");
+    fs::write(
+        repo.join(".claude/settings.json"),
+        r#"{"permissions":{"defaultMode":"bypassPermissions"},"env":{"NODE_TLS_REJECT_UNAUTHORIZED":"0"}}"#,
+    )
+    .expect("write settings");
+    fs::write(
+        repo.join(".mcp.json"),
+        r#"{"mcpServers":{"inline":{"command":"bash","args":["-c","node server.js"]}}}"#,
+    )
+    .expect("write mcp config");
+
+    let output = run_with_fake_claude(temp.path());
+
+    assert!(
+        output.status.success(),
+        "stderr:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn claude_code_run_is_blocked_by_dangerous_permissions() {
+    let temp = TempDir::new().expect("temp dir");
+    let repo = temp.path().join("repo");
+    fs::create_dir_all(repo.join(".claude")).expect("claude dir");
+    fs::write(
+        repo.join(".claude/settings.json"),
+        r#"{"permissions":{"allow":["Bash(rm -rf:*)"]}}"#,
+    )
+    .expect("write settings");
+
+    let output = run_with_fake_claude(temp.path());
+
+    assert!(!output.status.success(), "dangerous permission blocks run");
+}
@@ -0,0 +1,196 @@
+mod common;
+#[path = "../src/test_utils/http_stub.rs"]
+mod http_stub;
+
+use common::{assert_success, llman_command, run_llman};
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+const CONFIG: &str = r#"
+[model_providers.minimax]
+name = "minimax"
+base_url = "https://api.minimaxi.com/v1"
+wire_api = "responses"
+env_key = "MINIMAX_KEY"
+
+[model_providers.minimax.env]
+MINIMAX_KEY = "sk-minimax-secret-value"
+REGION = "it's"
+"#;
+
+fn write_codex_config(config_dir: &Path) {
+    fs::create_dir_all(config_dir).expect("config dir");
+    fs::write(config_dir.join("codex.toml"), CONFIG).expect("write codex config");
+}
+
+#[test]
+fn codex_account_list_masks_api_keys() {
+    let temp = TempDir::new().expect("temp dir");
+    let config_dir = temp.path().join("config");
+    write_codex_config(&config_dir);
+
+    let output = run_llman(&["x", "codex", "account", "list"], temp.path(), &config_dir);
+    assert_success(&output);
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("minimax"), "{stdout}");
+    assert!(stdout.contains("MINIMAX_KEY = sk-m...alue"), "{stdout}");
+    assert!(!stdout.contains("sk-minimax-secret-value"), "{stdout}");
+}
+
+#[cfg(unix)]
+#[test]
+fn codex_account_env_emits_sorted_export_lines() {
+    let temp = TempDir::new().expect("temp dir");
+    let config_dir = temp.path().join("config");
+    write_codex_config(&config_dir);
+
+    let output = run_llman(
+        &["x", "codex", "account", "env", "minimax"],
+        temp.path(),
+        &config_dir,
+    );
+    assert_success(&output);
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(
+        lines,
+        vec![
+            "# Bash/Zsh: source <(llman x codex account env minimax) && codex ...",
+            "export MINIMAX_KEY='sk-minimax-secret-value'",
+            "export REGION='it'\\''s'",
+        ]
+    );
+}
+
+#[test]
+fn codex_account_rename_and_remove_update_config() {
+    let temp = TempDir::new().expect("temp dir");
+    let config_dir = temp.path().join("config");
+    write_codex_config(&config_dir);
+
+    let output = run_llman(
+        &["x", "codex", "account", "rename", "minimax", "mm"],
+        temp.path(),
+        &config_dir,
+    );
+    assert_success(&output);
+    let content = fs::read_to_string(config_dir.join("codex.toml")).expect("read config");
+    assert!(content.contains("[model_providers.mm]"), "{content}");
+    assert!(!content.contains("[model_providers.minimax]"), "{content}");
+
+    let output = run_llman(
+        &["x", "codex", "account", "remove", "minimax"],
+        temp.path(),
+        &config_dir,
+    );
+    assert!(
+        !output.status.success(),
+        "removing a missing provider fails"
+    );
+
+    let output = run_llman(
+        &["x", "codex", "account", "rm", "mm"],
+        temp.path(),
+        &config_dir,
+    );
+    assert_success(&output);
+    let content = fs::read_to_string(config_dir.join("codex.toml")).expect("read config");
+    assert!(!content.contains("[model_providers.mm"), "{content}");
+}
+
+#[test]
+fn codex_run_refuses_provider_outside_project_allow_list() {
+    let temp = TempDir::new().expect("temp dir");
+    let config_dir = temp.path().join("config");
+    write_codex_config(&config_dir);
+    let work_dir = temp.path().join("repo");
+    fs::create_dir_all(work_dir.join(".llman")).expect("llman dir");
+    fs::write(
+        work_dir.join(".llman").join("config.yaml"),
+        "version: \"0.1\"\ntools: {}\naccounts:\n  codex:\n    group: team\n    allowed: [team]\n",
+    )
+    .expect("write project config");
+
+    let output = run_llman(
+        &["x", "codex", "run", "--provider", "minimax"],
+        &work_dir,
+        &config_dir,
+    );
+    assert!(!output.status.success(), "expected refusal");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("not allowed in this project"), "{stderr}");
+}
+
+#[test]
+fn codex_account_check_reports_auth_failure_as_json() {
+    let temp = TempDir::new().expect("temp dir");
+    let config_dir = temp.path().join("config");
+    let (base, _requests) =
+        http_stub::serve(vec![(401, r#"{"error":{"message":"Incorrect API key"}}"#)]);
+    fs::create_dir_all(&config_dir).expect("config dir");
+    fs::write(
+        config_dir.join("codex.toml"),
+        format!(
+            "[model_providers.stub]\nname = \"stub\"\nbase_url = \"{base}\"\nenv_key = \"STUB_KEY\"\n\n[model_providers.stub.env]\nSTUB_KEY = \"sk-expired\"\n"
+        ),
+    )
+    .expect("write codex config");
+
+    let output = llman_command(&config_dir)
+        .args(["x", "codex", "account", "check", "stub", "--json"])
+        .env("CODEX_HOME", temp.path().join("codex"))
+        .current_dir(temp.path())
+        .output()
+        .expect("run llman");
+    assert!(!output.status.success(), "failed check exits non-zero");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let results: serde_json::Value = serde_json::from_str(&stdout).expect("json output");
+    assert_eq!(results[0]["name"], "stub");
+    assert_eq!(results[0]["status"], "auth_failed");
+    assert_eq!(results[0]["http_status"], 401);
+    assert_eq!(results[0]["wire_api"], "openai_models");
+    assert_eq!(results[0]["message"], "Incorrect API key");
+}
+
+#[test]
+fn codex_account_check_looks_up_the_configured_model() {
+    let temp = TempDir::new().expect("temp dir");
+    let config_dir = temp.path().join("config");
+    let codex_home = temp.path().join("codex");
+    let (base, requests) = http_stub::serve(vec![(200, r#"{"id":"resp_1"}"#)]);
+    fs::create_dir_all(&config_dir).expect("config dir");
+    fs::create_dir_all(&codex_home).expect("codex home");
+    fs::write(
+        config_dir.join("codex.toml"),
+        format!(
+            "[model_providers.stub]\nname = \"stub\"\nbase_url = \"{base}\"\nenv_key = \"STUB_KEY\"\n\n[model_providers.stub.env]\nSTUB_KEY = \"sk-live\"\n"
+        ),
+    )
+    .expect("write codex config");
+    fs::write(
+        codex_home.join("config.toml"),
+        "[profiles.fast]\nmodel_provider = \"stub\"\nmodel = \"gpt-5-mini\"\n",
+    )
+    .expect("write codex home config");
+
+    let output = llman_command(&config_dir)
+        .args(["x", "codex", "account", "check", "stub", "--json"])
+        .env("CODEX_HOME", &codex_home)
+        .current_dir(temp.path())
+        .output()
+        .expect("run llman");
+    assert_success(&output);
+
+    let request = requests.recv().expect("request");
+    assert!(request.starts_with("POST /responses "), "{request}");
+    assert!(request.contains(r#""model":"gpt-5-mini""#), "{request}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let results: serde_json::Value = serde_json::from_str(&stdout).expect("json output");
+    assert_eq!(results[0]["wire_api"], "openai_responses");
+    assert_eq!(results[0]["model"], "gpt-5-mini");
+    assert_eq!(results[0]["status"], "ok");
+}
@@ -0,0 +1,90 @@
+mod common;
+
+use common::{assert_success, prepare_work_and_config_dirs, run_llman};
+use tempfile::TempDir;
+
+#[test]
+fn test_cursor_prompts_import_gen_frontmatter_and_lint() {
+    let temp = TempDir::new().expect("temp dir");
+    let (work_dir, config_dir) = prepare_work_and_config_dirs(temp.path());
+    std::fs::create_dir(work_dir.join(".git")).expect("mkdir .git");
+    std::fs::create_dir_all(work_dir.join("src")).expect("mkdir src");
+    std::fs::write(work_dir.join("src/main.rs"), "fn main() {}\n").expect("write main.rs");
+    let rules_dir = work_dir.join(".cursor/rules");
+    std::fs::create_dir_all(&rules_dir).expect("mkdir rules");
+    std::fs::write(
+        rules_dir.join("rust.mdc"),
+        "---\ndescription: Rust style\nglobs: *.rs\nalwaysApply: false\n---\nUse anyhow.\n",
+    )
+    .expect("write rust.mdc");
+    std::fs::write(work_dir.join(".cursorrules"), "Be concise.\n").expect("write .cursorrules");
+
+    let output = run_llman(
+        &["x", "cursor", "prompts", "import"],
+        &work_dir,
+        &config_dir,
+    );
+    assert_success(&output);
+    let templates = config_dir.join("prompt").join("cursor");
+    assert!(
+        std::fs::read_to_string(templates.join("rust.mdc"))
+            .expect("rust template")
+            .contains("globs: *.rs")
+    );
+    assert!(
+        std::fs::read_to_string(templates.join("cursorrules.mdc"))
+            .expect("legacy template")
+            .starts_with("---\ndescription: \nglobs: \nalwaysApply: true\n---\n\nBe concise.")
+    );
+
+    let output = run_llman(
+        &[
+            "x",
+            "cursor",
+            "prompts",
+            "gen",
+            "--template",
+            "rust",
+            "--name",
+            "docs",
+            "--globs",
+            "docs/**/*.md",
+            "--force",
+        ],
+        &work_dir,
+        &config_dir,
+    );
+    assert_success(&output);
+    let generated = std::fs::read_to_string(rules_dir.join("docs.mdc")).expect("docs.mdc");
+    assert!(generated.contains("globs: docs/**/*.md\n"), "{generated}");
+    assert!(
+        generated.contains("description: Rust style\n"),
+        "{generated}"
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("matches no files"), "{stdout}");
+
+    let output = run_llman(
+        &[
+            "x",
+            "cursor",
+            "prompts",
+            "gen",
+            "--template",
+            "rust",
+            "--globs",
+            "[",
+            "--force",
+        ],
+        &work_dir,
+        &config_dir,
+    );
+    assert!(!output.status.success(), "invalid globs are rejected");
+
+    let output = run_llman(&["x", "cursor", "prompts", "lint"], &work_dir, &config_dir);
+    assert_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("docs.mdc: glob 'docs/**/*.md'"), "{stdout}");
+    assert!(stdout.contains("Legacy .cursorrules"), "{stdout}");
+    assert!(!stdout.contains("rust.mdc:"), "{stdout}");
+}
@@ -30,3 +30,302 @@ fn test_prompt_alias_no_interactive_prints_delegation_guidance() {
     assert!(stdout.contains("llman x codex prompts"));
     assert!(stdout.contains("llman x claude-code prompts"));
 }
+
+#[test]
+fn test_prompts_gen_all_apps_renders_shared_template_everywhere() {
+    let temp = TempDir::new().expect("temp dir");
+    let (work_dir, config_dir) = prepare_work_and_config_dirs(temp.path());
+    let shared_dir = config_dir.join("prompt").join("shared");
+    std::fs::create_dir_all(&shared_dir).expect("mkdir shared");
+    std::fs::write(
+        shared_dir.join("house.md"),
+        "Use anyhow.\n\n<!-- llman:only cursor -->\nCursor only.\n<!-- llman:end -->\n",
+    )
+    .expect("write template");
+
+    let output = run_llman(
+        &["prompts", "gen", "--all-apps", "--force"],
+        &work_dir,
+        &config_dir,
+    );
+    assert_success(&output);
+
+    let rule = std::fs::read_to_string(work_dir.join(".cursor/rules/house.mdc")).expect("rule");
+    assert!(rule.starts_with("---\n"));
+    assert!(rule.contains("alwaysApply: true"));
+    assert!(rule.contains("Cursor only."));
+
+    for file in ["AGENTS.md", "CLAUDE.md"] {
+        let content = std::fs::read_to_string(work_dir.join(file)).expect(file);
+        assert!(content.contains("<!-- LLMAN-PROMPTS:START -->"), "{file}");
+        assert!(content.contains("## llman prompts: house\n\nUse anyhow."));
+        assert!(!content.contains("Cursor only."), "{file}");
+    }
+}
+
+#[test]
+fn test_prompts_gen_fails_on_undefined_template_variable() {
+    let temp = TempDir::new().expect("temp dir");
+    let (work_dir, config_dir) = prepare_work_and_config_dirs(temp.path());
+    let shared_dir = config_dir.join("prompt").join("shared");
+    std::fs::create_dir_all(&shared_dir).expect("mkdir shared");
+    std::fs::write(shared_dir.join("house.md"), "Answer in {{ langauge }}.\n")
+        .expect("write template");
+
+    let output = run_llman(
+        &["prompts", "gen", "--app", "codex", "--force"],
+        &work_dir,
+        &config_dir,
+    );
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("house"), "{stderr}");
+    assert!(!work_dir.join("AGENTS.md").exists());
+}
+
+#[test]
+fn test_prompts_check_reports_stale_and_modified_targets() {
+    let temp = TempDir::new().expect("temp dir");
+    let (work_dir, config_dir) = prepare_work_and_config_dirs(temp.path());
+    std::fs::create_dir(work_dir.join(".git")).expect("mkdir .git");
+    let shared_dir = config_dir.join("prompt").join("shared");
+    std::fs::create_dir_all(&shared_dir).expect("mkdir shared");
+    std::fs::write(shared_dir.join("house.md"), "Use anyhow.\n").expect("write template");
+
+    let gen_output = run_llman(
+        &["prompts", "gen", "--all-apps", "--force"],
+        &work_dir,
+        &config_dir,
+    );
+    assert_success(&gen_output);
+
+    let check = |args: &[&str]| run_llman(args, &work_dir, &config_dir);
+    let output = check(&["prompts", "check", "--scope", "project"]);
+    assert_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("3 target(s): 3 up to date, 0 drifted"),
+        "{stdout}"
+    );
+
+    let claude_md = work_dir.join("CLAUDE.md");
+    let edited = std::fs::read_to_string(&claude_md)
+        .expect("CLAUDE.md")
+        .replace("Use anyhow.", "Use thiserror.");
+    std::fs::write(&claude_md, edited).expect("edit CLAUDE.md");
+
+    let output = check(&["x", "claude-code", "prompts", "check", "--scope", "project"]);
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("modified"), "{stdout}");
+
+    let output = check(&["x", "codex", "prompts", "status", "--scope", "project"]);
+    assert_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("up-to-date"), "{stdout}");
+}
+
+#[test]
+fn test_prompts_regen_and_ungen_use_recorded_provenance() {
+    let temp = TempDir::new().expect("temp dir");
+    let (work_dir, config_dir) = prepare_work_and_config_dirs(temp.path());
+    std::fs::create_dir(work_dir.join(".git")).expect("mkdir .git");
+    let shared_dir = config_dir.join("prompt").join("shared");
+    std::fs::create_dir_all(&shared_dir).expect("mkdir shared");
+    std::fs::write(shared_dir.join("house.md"), "Use anyhow.\n").expect("write house");
+    std::fs::write(shared_dir.join("other.md"), "Not generated.\n").expect("write other");
+    let agents_md = work_dir.join("AGENTS.md");
+    std::fs::write(&agents_md, "# Team notes\n").expect("write AGENTS.md");
+
+    let output = run_llman(
+        &[
+            "prompts",
+            "gen",
+            "--app",
+            "codex",
+            "--template",
+            "house",
+            "--force",
+        ],
+        &work_dir,
+        &config_dir,
+    );
+    assert_success(&output);
+    let content = std::fs::read_to_string(&agents_md).expect("AGENTS.md");
+    assert!(content.contains("<!-- llman:provenance {"), "{content}");
+    assert!(content.contains("\"name\":\"house\",\"source\":\"shared\""));
+
+    std::fs::write(shared_dir.join("house.md"), "Use anyhow everywhere.\n").expect("edit");
+    let output = run_llman(
+        &["prompts", "status", "--scope", "project"],
+        &work_dir,
+        &config_dir,
+    );
+    assert_success(&output);
+    assert!(String::from_utf8_lossy(&output.stdout).contains("stale"));
+
+    let output = run_llman(
+        &["prompts", "regen", "--scope", "project"],
+        &work_dir,
+        &config_dir,
+    );
+    assert_success(&output);
+    let content = std::fs::read_to_string(&agents_md).expect("AGENTS.md");
+    assert!(content.contains("Use anyhow everywhere."), "{content}");
+    assert!(!content.contains("Not generated."), "{content}");
+
+    let output = run_llman(
+        &["prompts", "ungen", "--scope", "project"],
+        &work_dir,
+        &config_dir,
+    );
+    assert!(
+        !output.status.success(),
+        "ungen needs --yes when non-interactive"
+    );
+
+    let output = run_llman(
+        &["prompts", "ungen", "--scope", "project", "--yes"],
+        &work_dir,
+        &config_dir,
+    );
+    assert_success(&output);
+    assert_eq!(
+        std::fs::read_to_string(&agents_md).expect("AGENTS.md"),
+        "# Team notes\n"
+    );
+}
+
+#[test]
+fn test_prompts_log_diff_and_restore_removed_template() {
+    let temp = TempDir::new().expect("temp dir");
+    let (work_dir, config_dir) = prepare_work_and_config_dirs(temp.path());
+    let run = |args: &[&str]| run_llman(args, &work_dir, &config_dir);
+
+    assert_success(&run(&[
+        "prompts",
+        "upsert",
+        "--name",
+        "house",
+        "--content",
+        "Use anyhow.\n",
+    ]));
+    assert_success(&run(&[
+        "prompts",
+        "upsert",
+        "--name",
+        "house",
+        "--content",
+        "Use thiserror.\n",
+    ]));
+    assert_success(&run(&["prompts", "rm", "--name", "house", "--yes"]));
+
+    let output = run(&["prompts", "log", "shared", "house"]);
+    assert_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("house@3"), "{stdout}");
+    assert!(stdout.contains("remove"), "{stdout}");
+
+    let output = run(&[
+        "prompts", "diff", "shared", "house", "--from", "1", "--to", "2",
+    ]);
+    assert_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("-Use anyhow."), "{stdout}");
+    assert!(stdout.contains("+Use thiserror."), "{stdout}");
+
+    assert_success(&run(&["prompts", "restore", "shared", "house@2"]));
+    assert_eq!(
+        std::fs::read_to_string(config_dir.join("prompt/shared/house.md")).expect("restored"),
+        "Use thiserror.\n"
+    );
+}
+
+#[test]
+fn test_shared_and_codex_gen_keep_each_others_sections() {
+    let temp = TempDir::new().expect("temp dir");
+    let (work_dir, config_dir) = prepare_work_and_config_dirs(temp.path());
+    std::fs::create_dir(work_dir.join(".git")).expect("mkdir .git");
+    let run = |args: &[&str]| run_llman(args, &work_dir, &config_dir);
+
+    assert_success(&run(&[
+        "x",
+        "codex",
+        "prompts",
+        "upsert",
+        "--name",
+        "ci",
+        "--content",
+        "CI rules.",
+    ]));
+    assert_success(&run(&[
+        "prompts",
+        "upsert",
+        "--name",
+        "house",
+        "--content",
+        "House rules.",
+    ]));
+
+    let codex_gen = [
+        "x",
+        "codex",
+        "prompts",
+        "gen",
+        "--template",
+        "ci",
+        "--target",
+        "project-doc",
+    ];
+    assert_success(&run(&codex_gen));
+    assert_success(&run(&["prompts", "gen", "--app", "codex"]));
+    assert_success(&run(&codex_gen));
+
+    let content = std::fs::read_to_string(work_dir.join("AGENTS.md")).expect("AGENTS.md");
+    assert!(
+        content.contains("## llman prompts: ci\n\nCI rules."),
+        "{content}"
+    );
+    assert!(
+        content.contains("## llman prompts: house\n\nHouse rules."),
+        "{content}"
+    );
+
+    let output = run(&["prompts", "check", "--app", "codex", "--scope", "project"]);
+    assert_success(&output);
+}
+
+#[test]
+fn test_codex_gen_keeps_literal_braces_in_app_templates() {
+    let temp = TempDir::new().expect("temp dir");
+    let (work_dir, config_dir) = prepare_work_and_config_dirs(temp.path());
+    std::fs::create_dir(work_dir.join(".git")).expect("mkdir .git");
+    let run = |args: &[&str]| run_llman(args, &work_dir, &config_dir);
+
+    assert_success(&run(&[
+        "x",
+        "codex",
+        "prompts",
+        "upsert",
+        "--name",
+        "ci",
+        "--content",
+        "Use ${{ secrets.TOKEN }} in workflows.",
+    ]));
+    assert_success(&run(&[
+        "x",
+        "codex",
+        "prompts",
+        "gen",
+        "--template",
+        "ci",
+        "--target",
+        "project-doc",
+    ]));
+
+    let content = std::fs::read_to_string(work_dir.join("AGENTS.md")).expect("AGENTS.md");
+    assert!(
+        content.contains("Use ${{ secrets.TOKEN }} in workflows."),
+        "{content}"
+    );
+}
@@ -2,7 +2,7 @@
 
 mod common;
 
-use common::{assert_success, run_llman};
+use common::{assert_success, llman_command, run_llman};
 use llman::skills::catalog::types::{ConfigEntry, SkillCandidate, SkillsConfig, TargetMode};
 use llman::skills::targets::sync::apply_target_links;
 use std::collections::HashMap;
@@ -37,7 +37,9 @@ fn test_link_target_points_to_skill_dir() {
             path: target_root.clone(),
             enabled: true,
             mode: TargetMode::Link,
+            vars: Default::default(),
         }],
+        profiles: Vec::new(),
     };
     let desired_by_target = HashMap::new();
 
@@ -239,3 +241,154 @@ enabled = false
     let registry_path = skills_root.join("registry.json");
     assert!(!registry_path.exists());
 }
+
+#[cfg(unix)]
+#[test]
+fn test_skills_cli_pack_and_unpack_bundle() {
+    let temp = TempDir::new().expect("temp dir");
+    let work_dir = temp.path();
+    let source_root = work_dir.join("source-skills");
+    let skill_dir = source_root.join("example");
+    fs::create_dir_all(&skill_dir).expect("skill dir");
+    fs::write(skill_dir.join("SKILL.md"), "# example skill").expect("write SKILL.md");
+    let bundle = work_dir.join("bundle.7z");
+
+    let output = run_llman(
+        &[
+            "skills",
+            "--skills-dir",
+            source_root.to_str().unwrap(),
+            "pack",
+            "example",
+            "--out",
+            bundle.to_str().unwrap(),
+        ],
+        work_dir,
+        work_dir,
+    );
+    assert_success(&output);
+    assert!(bundle.exists());
+
+    let dest_root = work_dir.join("dest-skills");
+    let output = run_llman(
+        &[
+            "skills",
+            "unpack",
+            bundle.to_str().unwrap(),
+            "--skills-dir",
+            dest_root.to_str().unwrap(),
+        ],
+        work_dir,
+        work_dir,
+    );
+    assert_success(&output);
+    assert_eq!(
+        fs::read_to_string(dest_root.join("example").join("SKILL.md")).expect("read"),
+        "# example skill"
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_skills_cli_non_interactive_installs_declared_requirements() {
+    use std::os::unix::fs as unix_fs;
+
+    let temp = TempDir::new().expect("temp dir");
+    let work_dir = temp.path();
+    let skills_root = work_dir.join("skills-root");
+    let workflow_dir = skills_root.join("workflow");
+    let helper_dir = skills_root.join("helper");
+    fs::create_dir_all(&workflow_dir).expect("workflow dir");
+    fs::create_dir_all(&helper_dir).expect("helper dir");
+    fs::write(
+        workflow_dir.join("SKILL.md"),
+        "---\nname: workflow\nmetadata:\n  requires: [helper]\n---\n# workflow",
+    )
+    .expect("write workflow");
+    fs::write(helper_dir.join("SKILL.md"), "# helper").expect("write helper");
+
+    let target_root = work_dir.join("targets");
+    fs::create_dir_all(&target_root).expect("target root");
+    unix_fs::symlink(&workflow_dir, target_root.join("workflow")).expect("pre-link workflow");
+
+    let config = format!(
+        r#"version = 2
+
+[[target]]
+id = "claude_user"
+agent = "claude"
+scope = "user"
+path = "{}"
+mode = "link"
+enabled = false
+"#,
+        target_root.display()
+    );
+    fs::write(skills_root.join("config.toml"), config).expect("write config");
+
+    let output = run_llman(
+        &["skills", "--skills-dir", skills_root.to_str().unwrap()],
+        work_dir,
+        work_dir,
+    );
+    assert_success(&output);
+
+    let helper_link = target_root.join("helper");
+    assert_eq!(fs::read_link(&helper_link).expect("read link"), helper_dir);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("also selecting required skill(s) helper"),
+        "stderr did not mention auto-selected requirement:\n{}",
+        stderr
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_skills_cli_report_counts_session_usage_as_json() {
+    let temp = TempDir::new().expect("temp dir");
+    let work_dir = temp.path();
+    let skills_root = work_dir.join("skills-root");
+    for id in ["used", "idle"] {
+        let skill_dir = skills_root.join(id);
+        fs::create_dir_all(&skill_dir).expect("skill dir");
+        fs::write(skill_dir.join("SKILL.md"), format!("# {id}")).expect("write SKILL.md");
+    }
+    fs::write(
+        skills_root.join("config.toml"),
+        "version = 2\n\n[[target]]\nid = \"claude_user\"\nagent = \"claude\"\nscope = \"user\"\npath = \"/nonexistent\"\nmode = \"link\"\n",
+    )
+    .expect("write config");
+
+    let claude_home = work_dir.join("claude-home");
+    let project_dir = claude_home.join("projects").join("-work-app");
+    fs::create_dir_all(&project_dir).expect("project dir");
+    let timestamp = chrono::Utc::now().to_rfc3339();
+    fs::write(
+        project_dir.join("session.jsonl"),
+        format!(
+            r#"{{"type":"assistant","timestamp":"{timestamp}","cwd":"/work/app","message":{{"content":[{{"type":"tool_use","name":"Skill","input":{{"skill":"used"}}}}]}}}}"#
+        ),
+    )
+    .expect("write transcript");
+
+    let output = llman_command(work_dir)
+        .args([
+            "skills",
+            "--skills-dir",
+            skills_root.to_str().unwrap(),
+            "report",
+            "--json",
+        ])
+        .env("CLAUDE_HOME", &claude_home)
+        .env("CODEX_HOME", work_dir.join("codex-home"))
+        .current_dir(work_dir)
+        .output()
+        .expect("run llman");
+    assert_success(&output);
+
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).expect("report json");
+    assert_eq!(report["skills"][0]["skill_id"], "used");
+    assert_eq!(report["skills"][0]["projects"]["/work/app"], 1);
+    assert_eq!(report["never_used"], serde_json::json!(["idle"]));
+}
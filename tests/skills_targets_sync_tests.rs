@@ -49,6 +49,7 @@ fn test_copy_mode_does_not_write_vendored_metadata_file() {
         path: target_root.clone(),
         enabled: true,
         mode: TargetMode::Copy,
+        vars: Default::default(),
     };
 
     let mut desired = HashSet::new();